@@ -54,4 +54,8 @@ pub struct UserTransaction {
     pub entry_function_module_name: Option<String>,
     pub entry_function_function_name: Option<String>,
     pub replay_protection_nonce: Option<BigDecimal>,
+    pub total_charge_gas_units: Option<BigDecimal>,
+    pub execution_gas_units: Option<BigDecimal>,
+    pub io_gas_units: Option<BigDecimal>,
+    pub storage_fee_octas: Option<BigDecimal>,
 }
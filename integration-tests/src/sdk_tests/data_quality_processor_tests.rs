@@ -0,0 +1,230 @@
+#[allow(clippy::needless_return)]
+#[cfg(test)]
+mod sdk_data_quality_processor_tests {
+    use crate::sdk_tests::{
+        fungible_asset_processor_tests::setup_fa_processor_config,
+        test_helpers::{run_processor_test, DEFAULT_OUTPUT_FOLDER},
+        token_v2_processor_tests::setup_token_v2_processor_config,
+    };
+    use anyhow::Result;
+    use aptos_indexer_processor_sdk::testing_framework::{
+        database::{PostgresTestDatabase, TestDatabase},
+        sdk_test_context::SdkTestContext,
+    };
+    use aptos_indexer_test_transactions::json_transactions::generated_transactions::{
+        IMPORTED_MAINNET_TXNS_453498957_TOKEN_V2_MINT_AND_TRANSFER_EVENT_V1,
+        IMPORTED_MAINNET_TXNS_999929475_COIN_AND_FA_TRANSFERS,
+    };
+    use bigdecimal::BigDecimal;
+    use diesel::{
+        pg::PgConnection,
+        sql_types::{BigInt, Text},
+        ExpressionMethods, QueryDsl, RunQueryDsl,
+    };
+    use processor::{
+        config::processor_config::ProcessorConfig,
+        processors::{
+            data_quality::data_quality_checks::{DataQualityCheck, DataQualityMismatch},
+            fungible_asset::fungible_asset_processor::FungibleAssetProcessor,
+            token_v2::token_v2_processor::TokenV2Processor,
+        },
+        schema::{
+            current_fungible_asset_balances::dsl as cfab_dsl,
+            current_token_ownerships_v2::dsl as ctov2_dsl,
+        },
+    };
+    use serde_json::{json, Value};
+    use std::collections::{HashMap, HashSet};
+
+    fn tables_to_write(tables: &[&str]) -> HashSet<String> {
+        tables.iter().map(|table| table.to_string()).collect()
+    }
+
+    fn failing_keys(conn: &mut PgConnection, check: DataQualityCheck) -> Result<Vec<String>> {
+        let mut keys: Vec<String> = diesel::sql_query(check.query(None))
+            .bind::<BigInt, _>(0)
+            .bind::<BigInt, _>(i64::MAX)
+            .bind::<BigInt, _>(100)
+            .load::<DataQualityMismatch>(conn)?
+            .into_iter()
+            .map(|mismatch| mismatch.key)
+            .collect();
+        keys.sort();
+        Ok(keys)
+    }
+
+    /// Runs the checks on the processed balances while they are edited, and returns the failing
+    /// keys of each step.
+    fn run_fungible_asset_checks(conn: &mut PgConnection) -> Result<HashMap<String, Value>> {
+        let mut results = HashMap::new();
+        let check = DataQualityCheck::CurrentFungibleAssetBalances;
+
+        results.insert("processed".to_string(), json!(failing_keys(conn, check)?));
+        results.insert(
+            "non_negative".to_string(),
+            json!(failing_keys(conn, DataQualityCheck::NonNegativeBalances)?),
+        );
+
+        // A v2 amount that doesn't match its history, the combined amount is kept consistent
+        let v2_storage_id: String = cfab_dsl::current_fungible_asset_balances
+            .filter(cfab_dsl::amount_v2.is_not_null())
+            .select(cfab_dsl::storage_id)
+            .order_by(cfab_dsl::storage_id)
+            .first(conn)?;
+        diesel::sql_query(
+            "UPDATE current_fungible_asset_balances \
+             SET amount_v2 = amount_v2 + 1, amount = amount + 1 WHERE storage_id = $1",
+        )
+        .bind::<Text, _>(&v2_storage_id)
+        .execute(conn)?;
+        results.insert("v2_storage_id".to_string(), json!(v2_storage_id));
+        results.insert("v2_mismatch".to_string(), json!(failing_keys(conn, check)?));
+
+        // A v1 version that doesn't match its history
+        let v1_storage_id: String = cfab_dsl::current_fungible_asset_balances
+            .filter(cfab_dsl::amount_v1.is_not_null())
+            .select(cfab_dsl::storage_id)
+            .order_by(cfab_dsl::storage_id)
+            .first(conn)?;
+        diesel::sql_query(
+            "UPDATE current_fungible_asset_balances \
+             SET last_transaction_version_v1 = last_transaction_version_v1 - 1 \
+             WHERE storage_id = $1",
+        )
+        .bind::<Text, _>(&v1_storage_id)
+        .execute(conn)?;
+        results.insert("v1_storage_id".to_string(), json!(v1_storage_id));
+        results.insert("v1_mismatch".to_string(), json!(failing_keys(conn, check)?));
+
+        // Nothing to compare against isn't a pass
+        diesel::sql_query("DELETE FROM fungible_asset_balances").execute(conn)?;
+        results.insert(
+            "missing_history".to_string(),
+            json!(failing_keys(conn, check)?),
+        );
+        Ok(results)
+    }
+
+    /// Runs the checks on the processed ownerships before and after one of them is edited.
+    fn run_token_ownership_checks(conn: &mut PgConnection) -> Result<HashMap<String, Value>> {
+        let mut results = HashMap::new();
+        let check = DataQualityCheck::CurrentTokenOwnershipsV2;
+
+        results.insert("processed".to_string(), json!(failing_keys(conn, check)?));
+
+        let token_data_id: String = ctov2_dsl::current_token_ownerships_v2
+            .filter(ctov2_dsl::amount.gt(BigDecimal::from(0)))
+            .select(ctov2_dsl::token_data_id)
+            .order_by(ctov2_dsl::token_data_id)
+            .first(conn)?;
+        diesel::sql_query(
+            "UPDATE current_token_ownerships_v2 SET amount = amount + 1 \
+             WHERE token_data_id = $1 AND amount > 0",
+        )
+        .bind::<Text, _>(&token_data_id)
+        .execute(conn)?;
+        results.insert("token_data_id".to_string(), json!(token_data_id));
+        results.insert("mismatch".to_string(), json!(failing_keys(conn, check)?));
+        Ok(results)
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_current_fungible_asset_balances_check() {
+        let mut db = PostgresTestDatabase::new();
+        db.setup().await.unwrap();
+        let mut test_context =
+            SdkTestContext::new(&[IMPORTED_MAINNET_TXNS_999929475_COIN_AND_FA_TRANSFERS]);
+        if test_context.init_mock_grpc().await.is_err() {
+            panic!("Failed to initialize mock grpc");
+        };
+
+        let db_url = db.get_db_url();
+        let (mut indexer_processor_config, _) = setup_fa_processor_config(&test_context, &db_url);
+        if let ProcessorConfig::FungibleAssetProcessor(config) =
+            &mut indexer_processor_config.processor_config
+        {
+            config.tables_to_write = tables_to_write(&[
+                "FUNGIBLE_ASSET_BALANCES",
+                "CURRENT_FUNGIBLE_ASSET_BALANCES",
+                "FUNGIBLE_ASSET_ACTIVITIES",
+                "FUNGIBLE_ASSET_METADATA",
+                "FUNGIBLE_ASSET_TO_COIN_MAPPINGS",
+            ]);
+        }
+        let fungible_asset_processor = FungibleAssetProcessor::new(indexer_processor_config)
+            .await
+            .expect("Failed to create FungibleAssetProcessor");
+
+        let results = run_processor_test(
+            &mut test_context,
+            fungible_asset_processor,
+            run_fungible_asset_checks,
+            db_url,
+            false,
+            DEFAULT_OUTPUT_FOLDER.to_string(),
+            None,
+        )
+        .await
+        .expect("Failed to run the data quality checks");
+
+        assert_eq!(results["processed"], json!([]));
+        assert_eq!(results["non_negative"], json!([]));
+        assert_eq!(results["v2_mismatch"], json!([results["v2_storage_id"]]));
+        let mut v1_mismatch = vec![
+            results["v1_storage_id"].as_str().unwrap(),
+            results["v2_storage_id"].as_str().unwrap(),
+        ];
+        v1_mismatch.sort();
+        v1_mismatch.dedup();
+        assert_eq!(results["v1_mismatch"], json!(v1_mismatch));
+        assert_eq!(
+            results["missing_history"],
+            json!(["fungible_asset_balances"])
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_current_token_ownerships_v2_check() {
+        let mut db = PostgresTestDatabase::new();
+        db.setup().await.unwrap();
+        let mut test_context = SdkTestContext::new(&[
+            IMPORTED_MAINNET_TXNS_453498957_TOKEN_V2_MINT_AND_TRANSFER_EVENT_V1,
+        ]);
+        if test_context.init_mock_grpc().await.is_err() {
+            panic!("Failed to initialize mock grpc");
+        };
+
+        let db_url = db.get_db_url();
+        let (mut indexer_processor_config, _) =
+            setup_token_v2_processor_config(&test_context, &db_url);
+        if let ProcessorConfig::TokenV2Processor(config) =
+            &mut indexer_processor_config.processor_config
+        {
+            config.default_config.tables_to_write =
+                tables_to_write(&["TOKEN_OWNERSHIPS_V2", "CURRENT_TOKEN_OWNERSHIPS_V2"]);
+        }
+        let token_v2_processor = TokenV2Processor::new(indexer_processor_config)
+            .await
+            .expect("Failed to create TokenV2Processor");
+
+        let results = run_processor_test(
+            &mut test_context,
+            token_v2_processor,
+            run_token_ownership_checks,
+            db_url,
+            false,
+            DEFAULT_OUTPUT_FOLDER.to_string(),
+            None,
+        )
+        .await
+        .expect("Failed to run the data quality checks");
+
+        assert_eq!(results["processed"], json!([]));
+        let token_data_id = results["token_data_id"].as_str().unwrap();
+        let mismatch = results["mismatch"].as_array().unwrap();
+        assert!(!mismatch.is_empty());
+        assert!(mismatch
+            .iter()
+            .all(|key| key.as_str().unwrap().starts_with(token_data_id)));
+    }
+}
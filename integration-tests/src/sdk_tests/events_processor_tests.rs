@@ -1,10 +1,15 @@
 use ahash::AHashMap;
 use aptos_indexer_processor_sdk::testing_framework::sdk_test_context::SdkTestContext;
-use processor::config::{
-    db_config::{DbConfig, PostgresConfig},
-    indexer_processor_config::IndexerProcessorConfig,
-    processor_config::{DefaultProcessorConfig, ProcessorConfig},
-    processor_mode::{ProcessorMode, TestingConfig},
+use processor::{
+    config::{
+        db_config::{DbConfig, PostgresConfig},
+        indexer_processor_config::IndexerProcessorConfig,
+        processor_config::{DefaultProcessorConfig, ProcessorConfig},
+        processor_mode::{ProcessorMode, TestingConfig},
+    },
+    processors::events::{
+        event_filter::EventFilterConfig, events_processor::EventsProcessorConfig,
+    },
 };
 use std::collections::HashSet;
 
@@ -23,9 +28,16 @@ pub fn setup_events_processor_config(
         per_table_chunk_sizes: AHashMap::new(),
         channel_size: 100,
         tables_to_write: HashSet::new(),
+        copy_tables: HashSet::new(),
+        version_partitioning: None,
+        retention: None,
     };
 
-    let processor_config = ProcessorConfig::EventsProcessor(default_processor_config);
+    let processor_config = ProcessorConfig::EventsProcessor(EventsProcessorConfig {
+        default_config: default_processor_config,
+        event_registry: vec![],
+        event_filter: EventFilterConfig::default(),
+    });
     let processor_name = processor_config.name();
     (
         IndexerProcessorConfig {
@@ -26,6 +26,9 @@ pub fn setup_objects_processor_config(
         per_table_chunk_sizes: AHashMap::new(),
         channel_size: 100,
         tables_to_write: HashSet::new(),
+        copy_tables: HashSet::new(),
+        version_partitioning: None,
+        retention: None,
     };
 
     let objects_processor_config = ObjectsProcessorConfig {
@@ -27,6 +27,9 @@ pub fn setup_stake_processor_config(
             per_table_chunk_sizes: AHashMap::new(),
             channel_size: 100,
             tables_to_write: HashSet::new(),
+            copy_tables: HashSet::new(),
+            version_partitioning: None,
+            retention: None,
         },
         // Avoid doing long lookups in tests
         query_retries: 1,
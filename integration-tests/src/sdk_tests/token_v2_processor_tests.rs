@@ -26,6 +26,9 @@ pub fn setup_token_v2_processor_config(
         per_table_chunk_sizes: AHashMap::new(),
         channel_size: 100,
         tables_to_write: HashSet::new(),
+        copy_tables: HashSet::new(),
+        version_partitioning: None,
+        retention: None,
     };
     let token_v2_processor_config = TokenV2ProcessorConfig {
         default_config: default_processor_config,
@@ -6,11 +6,15 @@ use super::{
 };
 use crate::{
     parquet_processors::{
+        parquet_account_restoration::parquet_account_restoration_processor::ParquetAccountRestorationProcessor,
         parquet_account_transactions::parquet_account_transactions_processor::ParquetAccountTransactionsProcessor,
         parquet_ans::parquet_ans_processor::ParquetAnsProcessor,
+        parquet_compaction::parquet_compaction_processor::ParquetCompactionProcessor,
         parquet_default::parquet_default_processor::ParquetDefaultProcessor,
         parquet_events::parquet_events_processor::ParquetEventsProcessor,
         parquet_fungible_asset::parquet_fungible_asset_processor::ParquetFungibleAssetProcessor,
+        parquet_gas_fee::parquet_gas_fee_processor::ParquetGasFeeProcessor,
+        parquet_loader::parquet_loader_processor::ParquetLoaderProcessor,
         parquet_objects::parquet_objects_processor::ParquetObjectsProcessor,
         parquet_stake::parquet_stake_processor::ParquetStakeProcessor,
         parquet_token_v2::parquet_token_v2_processor::ParquetTokenV2Processor,
@@ -20,12 +24,15 @@ use crate::{
     processors::{
         account_restoration::account_restoration_processor::AccountRestorationProcessor,
         account_transactions::account_transactions_processor::AccountTransactionsProcessor,
-        ans::ans_processor::AnsProcessor, default::default_processor::DefaultProcessor,
+        ans::ans_processor::AnsProcessor,
+        data_quality::data_quality_processor::DataQualityProcessor,
+        default::default_processor::DefaultProcessor,
         events::events_processor::EventsProcessor,
         fungible_asset::fungible_asset_processor::FungibleAssetProcessor,
         gas_fees::gas_fee_processor::GasFeeProcessor,
         monitoring::monitoring_processor::MonitoringProcessor,
         objects::objects_processor::ObjectsProcessor, stake::stake_processor::StakeProcessor,
+        storage_usage::storage_usage_processor::StorageUsageProcessor,
         token_v2::token_v2_processor::TokenV2Processor,
         user_transaction::user_transaction_processor::UserTransactionProcessor,
     },
@@ -101,6 +108,10 @@ impl RunnableConfig for IndexerProcessorConfig {
                 let gas_fee_processor = GasFeeProcessor::new(self.clone()).await?;
                 gas_fee_processor.run_processor().await
             },
+            ProcessorConfig::StorageUsageProcessor(_) => {
+                let storage_usage_processor = StorageUsageProcessor::new(self.clone()).await?;
+                storage_usage_processor.run_processor().await
+            },
             ProcessorConfig::ParquetDefaultProcessor(_) => {
                 let parquet_default_processor = ParquetDefaultProcessor::new(self.clone()).await?;
                 parquet_default_processor.run_processor().await
@@ -133,6 +144,10 @@ impl RunnableConfig for IndexerProcessorConfig {
                 let parquet_token_v2_processor = ParquetTokenV2Processor::new(self.clone()).await?;
                 parquet_token_v2_processor.run_processor().await
             },
+            ProcessorConfig::DataQualityProcessor(_) => {
+                let data_quality_processor = DataQualityProcessor::new(self.clone()).await?;
+                data_quality_processor.run_processor().await
+            },
             ProcessorConfig::ParquetAnsProcessor(_) => {
                 let parquet_ans_processor = ParquetAnsProcessor::new(self.clone()).await?;
                 parquet_ans_processor.run_processor().await
@@ -145,6 +160,24 @@ impl RunnableConfig for IndexerProcessorConfig {
                 let parquet_objects_processor = ParquetObjectsProcessor::new(self.clone()).await?;
                 parquet_objects_processor.run_processor().await
             },
+            ProcessorConfig::ParquetGasFeeProcessor(_) => {
+                let parquet_gas_fee_processor = ParquetGasFeeProcessor::new(self.clone()).await?;
+                parquet_gas_fee_processor.run_processor().await
+            },
+            ProcessorConfig::ParquetAccountRestorationProcessor(_) => {
+                let parquet_account_restoration_processor =
+                    ParquetAccountRestorationProcessor::new(self.clone()).await?;
+                parquet_account_restoration_processor.run_processor().await
+            },
+            ProcessorConfig::ParquetLoaderProcessor(_) => {
+                let parquet_loader_processor = ParquetLoaderProcessor::new(self.clone()).await?;
+                parquet_loader_processor.run_processor().await
+            },
+            ProcessorConfig::ParquetCompactionProcessor(_) => {
+                let parquet_compaction_processor =
+                    ParquetCompactionProcessor::new(self.clone()).await?;
+                parquet_compaction_processor.run_processor().await
+            },
         }
     }
 
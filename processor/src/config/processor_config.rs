@@ -3,66 +3,33 @@
 
 // TODO: add back all models and configs back as we migrate
 use crate::{
+    db::{partitioning::VersionPartitioningConfig, retention::RetentionConfig},
     parquet_processors::{
         parquet_ans::parquet_ans_processor::ParquetAnsProcessorConfig,
-        parquet_transaction_metadata::transaction_metadata_models::write_set_size_info::ParquetWriteSetSize,
-        parquet_utils::util::{format_table_name, NamedTable, VALID_TABLE_NAMES},
+        parquet_compaction::parquet_compaction_processor::ParquetCompactionProcessorConfig,
+        parquet_loader::parquet_loader_processor::ParquetLoaderProcessorConfig,
+        parquet_utils::{
+            gcs_uploader::ParquetFileLayout,
+            parquet_upload_config::ParquetTableUploadConfig,
+            parquet_writer_config::ParquetWriterConfig,
+            util::{format_table_name, VALID_TABLE_NAMES},
+        },
+        ParquetTypeEnum,
     },
     processors::{
-        account_transactions::account_transactions_model::ParquetAccountTransaction,
-        ans::{
-            ans_processor::AnsProcessorConfig,
-            models::{
-                ans_lookup_v2::{ParquetAnsLookupV2, ParquetCurrentAnsLookupV2},
-                ans_primary_name_v2::{ParquetAnsPrimaryNameV2, ParquetCurrentAnsPrimaryNameV2},
-            },
-        },
-        default::models::{
-            block_metadata_transactions::ParquetBlockMetadataTransaction,
-            move_modules::ParquetMoveModule,
-            move_resources::ParquetMoveResource,
-            table_items::{ParquetCurrentTableItem, ParquetTableItem, ParquetTableMetadata},
-            transactions::ParquetTransaction,
-            write_set_changes::ParquetWriteSetChange,
-        },
-        events::events_model::ParquetEvent,
-        fungible_asset::fungible_asset_models::{
-            v2_fungible_asset_activities::ParquetFungibleAssetActivity,
-            v2_fungible_asset_balances::ParquetFungibleAssetBalance,
-            v2_fungible_asset_to_coin_mappings::ParquetFungibleAssetToCoinMapping,
-            v2_fungible_metadata::ParquetFungibleAssetMetadataModel,
-        },
-        objects::{
-            objects_processor::ObjectsProcessorConfig,
-            v2_objects_models::{ParquetCurrentObject, ParquetObject},
-        },
-        stake::{
-            models::{
-                delegator_activities::ParquetDelegatedStakingActivity,
-                delegator_balances::{ParquetCurrentDelegatorBalance, ParquetDelegatorBalance},
-                proposal_votes::ParquetProposalVote,
-            },
-            stake_processor::StakeProcessorConfig,
-        },
-        token_v2::{
-            token_models::{
-                token_claims::ParquetCurrentTokenPendingClaim,
-                token_royalty::ParquetCurrentTokenRoyaltyV1,
-            },
-            token_v2_models::{
-                v2_token_activities::ParquetTokenActivityV2,
-                v2_token_datas::{ParquetCurrentTokenDataV2, ParquetTokenDataV2},
-                v2_token_metadata::ParquetCurrentTokenV2Metadata,
-                v2_token_ownerships::{ParquetCurrentTokenOwnershipV2, ParquetTokenOwnershipV2},
-            },
-            token_v2_processor::TokenV2ProcessorConfig,
-        },
-        user_transaction::models::user_transactions::ParquetUserTransaction,
+        ans::ans_processor::AnsProcessorConfig,
+        data_quality::data_quality_processor::DataQualityProcessorConfig,
+        events::events_processor::EventsProcessorConfig,
+        monitoring::monitoring_processor::MonitoringProcessorConfig,
+        objects::objects_processor::ObjectsProcessorConfig,
+        stake::stake_processor::StakeProcessorConfig,
+        token_v2::token_v2_processor::TokenV2ProcessorConfig,
     },
 };
 use ahash::AHashMap;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use strum::IntoEnumIterator;
 
 /// This enum captures the configs for all the different processors that are defined.
 ///
@@ -102,14 +69,19 @@ pub enum ProcessorConfig {
     AccountTransactionsProcessor(DefaultProcessorConfig),
     AnsProcessor(AnsProcessorConfig),
     DefaultProcessor(DefaultProcessorConfig),
-    EventsProcessor(DefaultProcessorConfig),
+    EventsProcessor(EventsProcessorConfig),
     FungibleAssetProcessor(DefaultProcessorConfig),
     UserTransactionProcessor(DefaultProcessorConfig),
     StakeProcessor(StakeProcessorConfig),
     TokenV2Processor(TokenV2ProcessorConfig),
     ObjectsProcessor(ObjectsProcessorConfig),
-    MonitoringProcessor(DefaultProcessorConfig),
+    MonitoringProcessor(MonitoringProcessorConfig),
     GasFeeProcessor(DefaultProcessorConfig),
+    // Storage used per account and resource type, from the write set size info. Has to start
+    // from version 0, or from a database that already has current_state_item_sizes
+    StorageUsageProcessor(DefaultProcessorConfig),
+    // Checks derived tables against their history tables once
+    DataQualityProcessor(DataQualityProcessorConfig),
     // ParquetProcessor
     ParquetDefaultProcessor(ParquetDefaultProcessorConfig),
     ParquetObjectsProcessor(ParquetDefaultProcessorConfig),
@@ -121,6 +93,12 @@ pub enum ProcessorConfig {
     ParquetAccountTransactionsProcessor(ParquetDefaultProcessorConfig),
     ParquetTokenV2Processor(ParquetDefaultProcessorConfig),
     ParquetStakeProcessor(ParquetDefaultProcessorConfig),
+    ParquetGasFeeProcessor(ParquetDefaultProcessorConfig),
+    ParquetAccountRestorationProcessor(ParquetDefaultProcessorConfig),
+    // Loads parquet files into the Postgres tables
+    ParquetLoaderProcessor(ParquetLoaderProcessorConfig),
+    // Merges small parquet files into target size files
+    ParquetCompactionProcessor(ParquetCompactionProcessorConfig),
 }
 
 impl ProcessorConfig {
@@ -145,7 +123,9 @@ impl ProcessorConfig {
             | ProcessorConfig::ParquetStakeProcessor(config)
             | ProcessorConfig::ParquetObjectsProcessor(config)
             | ProcessorConfig::ParquetFungibleAssetProcessor(config)
-            | ProcessorConfig::ParquetUserTransactionProcessor(config) => config,
+            | ProcessorConfig::ParquetUserTransactionProcessor(config)
+            | ProcessorConfig::ParquetGasFeeProcessor(config)
+            | ProcessorConfig::ParquetAccountRestorationProcessor(config) => config,
             ProcessorConfig::ParquetAnsProcessor(config) => &config.default,
             _ => {
                 return Err(anyhow::anyhow!(
@@ -174,66 +154,15 @@ impl ProcessorConfig {
         }
     }
 
-    // TODO: uncomment this when we migrate the parquet processor
-    // Get the set of table names to process for the given processor.
+    /// Get the set of table names to process for the given processor.
+    ///
+    /// Derived from `ParquetTypeEnum::processor_name` so that every parquet table is valid for
+    /// backfill and tracked in the processor status table without a second list to keep in sync.
     pub fn table_names(processor: &ProcessorName) -> HashSet<String> {
-        match processor {
-            ProcessorName::ParquetDefaultProcessor => HashSet::from([
-                ParquetTransaction::TABLE_NAME.to_string(),
-                ParquetMoveResource::TABLE_NAME.to_string(),
-                ParquetWriteSetChange::TABLE_NAME.to_string(),
-                ParquetTableItem::TABLE_NAME.to_string(),
-                ParquetMoveModule::TABLE_NAME.to_string(),
-                ParquetBlockMetadataTransaction::TABLE_NAME.to_string(),
-                ParquetCurrentTableItem::TABLE_NAME.to_string(),
-                ParquetTableMetadata::TABLE_NAME.to_string(),
-            ]),
-            ProcessorName::ParquetUserTransactionProcessor => {
-                HashSet::from([ParquetUserTransaction::TABLE_NAME.to_string()])
-            },
-            ProcessorName::ParquetEventsProcessor => {
-                HashSet::from([ParquetEvent::TABLE_NAME.to_string()])
-            },
-            ProcessorName::ParquetAnsProcessor => HashSet::from([
-                ParquetAnsLookupV2::TABLE_NAME.to_string(),
-                ParquetAnsPrimaryNameV2::TABLE_NAME.to_string(),
-                ParquetCurrentAnsLookupV2::TABLE_NAME.to_string(),
-                ParquetCurrentAnsPrimaryNameV2::TABLE_NAME.to_string(),
-            ]),
-            ProcessorName::ParquetFungibleAssetProcessor => HashSet::from([
-                ParquetFungibleAssetActivity::TABLE_NAME.to_string(),
-                ParquetFungibleAssetBalance::TABLE_NAME.to_string(),
-                ParquetFungibleAssetMetadataModel::TABLE_NAME.to_string(),
-                ParquetFungibleAssetToCoinMapping::TABLE_NAME.to_string(),
-            ]),
-            ProcessorName::ParquetTransactionMetadataProcessor => {
-                HashSet::from([ParquetWriteSetSize::TABLE_NAME.to_string()])
-            },
-            ProcessorName::ParquetAccountTransactionsProcessor => {
-                HashSet::from([ParquetAccountTransaction::TABLE_NAME.to_string()])
-            },
-            ProcessorName::ParquetTokenV2Processor => HashSet::from([
-                ParquetCurrentTokenPendingClaim::TABLE_NAME.to_string(),
-                ParquetCurrentTokenRoyaltyV1::TABLE_NAME.to_string(),
-                ParquetCurrentTokenV2Metadata::TABLE_NAME.to_string(),
-                ParquetTokenActivityV2::TABLE_NAME.to_string(),
-                ParquetTokenDataV2::TABLE_NAME.to_string(),
-                ParquetCurrentTokenDataV2::TABLE_NAME.to_string(),
-                ParquetTokenOwnershipV2::TABLE_NAME.to_string(),
-                ParquetCurrentTokenOwnershipV2::TABLE_NAME.to_string(),
-            ]),
-            ProcessorName::ParquetObjectsProcessor => HashSet::from([
-                ParquetObject::TABLE_NAME.to_string(),
-                ParquetCurrentObject::TABLE_NAME.to_string(),
-            ]),
-            ProcessorName::ParquetStakeProcessor => HashSet::from([
-                ParquetDelegatedStakingActivity::TABLE_NAME.to_string(),
-                ParquetProposalVote::TABLE_NAME.to_string(),
-                ParquetDelegatorBalance::TABLE_NAME.to_string(),
-                ParquetCurrentDelegatorBalance::TABLE_NAME.to_string(),
-            ]),
-            _ => HashSet::new(), // Default case for unsupported processors
-        }
+        ParquetTypeEnum::iter()
+            .filter(|parquet_type| parquet_type.processor_name() == *processor)
+            .map(|parquet_type| parquet_type.to_string())
+            .collect()
     }
 
     /// This is to validate table_name for the backfill table
@@ -269,6 +198,18 @@ pub struct DefaultProcessorConfig {
     // String vector for tables to write to DB, by default all tables are written
     #[serde(default)]
     pub tables_to_write: HashSet<String>,
+    // Append only tables that are written with a binary COPY into a staging table followed by a
+    // merge instead of chunked inserts. Meant for backfills, see `db::copy_insert`
+    #[serde(default)]
+    pub copy_tables: HashSet<String>,
+    // Range partitioning of the processor's history tables by transaction_version, see
+    // `db::partitioning`
+    #[serde(default)]
+    pub version_partitioning: Option<VersionPartitioningConfig>,
+    // Per table retention of the processor's history tables, pruned in the background, see
+    // `db::retention`
+    #[serde(default)]
+    pub retention: Option<RetentionConfig>,
 }
 
 impl DefaultProcessorConfig {
@@ -283,6 +224,9 @@ impl Default for DefaultProcessorConfig {
             per_table_chunk_sizes: AHashMap::new(),
             channel_size: Self::default_channel_size(),
             tables_to_write: HashSet::new(),
+            copy_tables: HashSet::new(),
+            version_partitioning: None,
+            retention: None,
         }
     }
 }
@@ -292,13 +236,42 @@ impl Default for DefaultProcessorConfig {
 pub struct ParquetDefaultProcessorConfig {
     #[serde(default = "ParquetDefaultProcessorConfig::default_channel_size")]
     pub channel_size: usize,
-    #[serde(default = "ParquetDefaultProcessorConfig::default_max_buffer_size")]
-    pub max_buffer_size: usize,
+    // Encoded size in bytes at which a table's file is uploaded. `max_buffer_size` is the name it
+    // had when the buffer size was estimated from the in-memory rows.
+    #[serde(
+        default = "ParquetDefaultProcessorConfig::default_target_file_size",
+        alias = "max_buffer_size"
+    )]
+    pub target_file_size: usize,
+    // Rows per row group for tables without a `max_row_group_size` in their writer properties.
+    #[serde(default = "ParquetDefaultProcessorConfig::default_max_row_group_size")]
+    pub max_row_group_size: usize,
     #[serde(default = "ParquetDefaultProcessorConfig::default_parquet_upload_interval")]
     pub upload_interval: u64,
+    // Files smaller than this are held back on the upload interval and coalesced with the data of
+    // the following intervals, for at most `max_upload_interval` seconds. Disabled when 0.
+    #[serde(default)]
+    pub min_file_size: usize,
+    #[serde(default = "ParquetDefaultProcessorConfig::default_max_upload_interval")]
+    pub max_upload_interval: u64,
+    // Upload interval and file size overrides keyed by table name.
+    #[serde(default)]
+    pub per_table_upload: HashMap<String, ParquetTableUploadConfig>,
     // Set of table name to backfill. Using HashSet for fast lookups, and for future extensibility.
     #[serde(default)]
     pub backfill_table: HashSet<String>,
+    // Local directory where buffered batches are spilled before upload so they survive a crash.
+    // Spilling is disabled when unset.
+    #[serde(default)]
+    pub spill_dir: Option<String>,
+    // Parquet writer properties keyed by table name, validated against the table schemas on startup.
+    #[serde(default)]
+    pub writer_properties: HashMap<String, ParquetWriterConfig>,
+    #[serde(default)]
+    pub file_layout: ParquetFileLayout,
+    // Appends every upload to a commit log next to the data so readers get atomic snapshots.
+    #[serde(default)]
+    pub table_format: bool,
 }
 
 impl ParquetDefaultProcessorConfig {
@@ -308,15 +281,26 @@ impl ParquetDefaultProcessorConfig {
         100_000
     }
 
-    /// Default maximum buffer size for parquet files in bytes
-    pub const fn default_max_buffer_size() -> usize {
+    /// Default target size for parquet files in bytes
+    pub const fn default_target_file_size() -> usize {
         1024 * 1024 * 100 // 100 MB
     }
 
+    /// Default number of rows per row group, which is also the most rows kept in memory per table
+    /// before they're encoded
+    pub const fn default_max_row_group_size() -> usize {
+        100_000
+    }
+
     /// Default upload interval for parquet files in seconds
     pub const fn default_parquet_upload_interval() -> u64 {
         1800 // 30 minutes
     }
+
+    /// Default longest time in seconds small files are coalesced for
+    pub const fn default_max_upload_interval() -> u64 {
+        86400 // 1 day
+    }
 }
 
 #[cfg(test)]
@@ -328,8 +312,16 @@ mod tests {
         let config = ProcessorConfig::ParquetDefaultProcessor(ParquetDefaultProcessorConfig {
             backfill_table: HashSet::from(["move_resources".to_string()]),
             channel_size: 10,
-            max_buffer_size: 100000,
+            target_file_size: 100000,
+            max_row_group_size: 1000,
             upload_interval: 1800,
+            min_file_size: 0,
+            max_upload_interval: 86400,
+            per_table_upload: HashMap::new(),
+            spill_dir: None,
+            writer_properties: HashMap::new(),
+            file_layout: ParquetFileLayout::default(),
+            table_format: false,
         });
 
         let result = config.get_processor_status_table_names();
@@ -347,8 +339,16 @@ mod tests {
         let config = ProcessorConfig::ParquetDefaultProcessor(ParquetDefaultProcessorConfig {
             backfill_table: HashSet::from(["InvalidTable".to_string(), "transactions".to_string()]),
             channel_size: 10,
-            max_buffer_size: 100000,
+            target_file_size: 100000,
+            max_row_group_size: 1000,
             upload_interval: 1800,
+            min_file_size: 0,
+            max_upload_interval: 86400,
+            per_table_upload: HashMap::new(),
+            spill_dir: None,
+            writer_properties: HashMap::new(),
+            file_layout: ParquetFileLayout::default(),
+            table_format: false,
         });
 
         let result = config.get_processor_status_table_names();
@@ -364,8 +364,16 @@ mod tests {
         let config = ProcessorConfig::ParquetDefaultProcessor(ParquetDefaultProcessorConfig {
             backfill_table: HashSet::new(),
             channel_size: 10,
-            max_buffer_size: 100000,
+            target_file_size: 100000,
+            max_row_group_size: 1000,
             upload_interval: 1800,
+            min_file_size: 0,
+            max_upload_interval: 86400,
+            per_table_upload: HashMap::new(),
+            spill_dir: None,
+            writer_properties: HashMap::new(),
+            file_layout: ParquetFileLayout::default(),
+            table_format: false,
         });
         let result = config.get_processor_status_table_names();
         assert!(result.is_ok());
@@ -380,6 +388,8 @@ mod tests {
             "block_metadata_transactions".to_string(),
             "current_table_items".to_string(),
             "table_metadata".to_string(),
+            "block_epilogue_transactions".to_string(),
+            "validator_transactions".to_string(),
         ]
         .iter()
         .map(|e| format!("parquet_default_processor.{e}"))
@@ -394,8 +404,16 @@ mod tests {
         let config = ProcessorConfig::ParquetDefaultProcessor(ParquetDefaultProcessorConfig {
             backfill_table: HashSet::from(["transactions".to_string(), "transactions".to_string()]),
             channel_size: 10,
-            max_buffer_size: 100000,
+            target_file_size: 100000,
+            max_row_group_size: 1000,
             upload_interval: 1800,
+            min_file_size: 0,
+            max_upload_interval: 86400,
+            per_table_upload: HashMap::new(),
+            spill_dir: None,
+            writer_properties: HashMap::new(),
+            file_layout: ParquetFileLayout::default(),
+            table_format: false,
         });
 
         let result = config.get_processor_status_table_names();
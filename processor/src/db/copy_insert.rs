@@ -0,0 +1,290 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bulk ingestion of append only tables with a binary `COPY FROM STDIN`.
+//!
+//! The rows of a batch are copied into a temporary staging table, which is then merged into the
+//! target table with an `INSERT ... ON CONFLICT` that updates the same columns as the diesel
+//! insert of the table. Replaying a batch therefore has the same result as with chunked inserts.
+
+use crate::config::db_config::DbConfig;
+use anyhow::{bail, Context, Result};
+use aptos_indexer_processor_sdk::{
+    postgres::utils::database::{execute_in_chunks, ArcDbPool},
+    utils::errors::ProcessorError,
+};
+use bigdecimal::BigDecimal;
+use bytes::BytesMut;
+use diesel::{
+    pg::{data_types::PgNumeric as DieselPgNumeric, Pg},
+    query_builder::{QueryFragment, QueryId},
+};
+use postgres_native_tls::MakeTlsConnector;
+use std::{collections::HashSet, error::Error};
+use tokio_postgres::{
+    binary_copy::BinaryCopyInWriter,
+    types::{to_sql_checked, IsNull, ToSql, Type},
+    Client,
+};
+use tracing::error;
+
+/// A column value of a copied row.
+pub type CopyValue<'a> = Box<dyn ToSql + Sync + Send + 'a>;
+
+/// A Postgres model that can be written with a binary COPY.
+pub trait CopyInsertable: Sync {
+    /// Name of the target table.
+    fn table_name() -> &'static str;
+
+    /// Copied columns and their types, in the order of `copy_row`. Columns with a default, like
+    /// `inserted_at`, are left out so they get the default as with the diesel insert.
+    fn columns() -> Vec<(&'static str, Type)>;
+
+    /// Primary key columns the merge conflicts on.
+    fn conflict_columns() -> &'static [&'static str];
+
+    /// Columns set from the staged row when it already exists, same as the `do_update` of the
+    /// diesel insert.
+    fn update_columns() -> &'static [&'static str];
+
+    fn copy_row(&self) -> Vec<CopyValue<'_>>;
+}
+
+/// Binary encoding of a `numeric`, which tokio-postgres has no `ToSql` for.
+#[derive(Debug)]
+pub struct PgNumeric<'a>(pub &'a BigDecimal);
+
+impl ToSql for PgNumeric<'_> {
+    to_sql_checked!();
+
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let (sign, weight, scale, digits) = match DieselPgNumeric::from(self.0) {
+            DieselPgNumeric::Positive {
+                weight,
+                scale,
+                digits,
+            } => (0x0000u16, weight, scale, digits),
+            DieselPgNumeric::Negative {
+                weight,
+                scale,
+                digits,
+            } => (0x4000u16, weight, scale, digits),
+            DieselPgNumeric::NaN => (0xC000u16, 0, 0, vec![]),
+        };
+        out.extend_from_slice(&(digits.len() as i16).to_be_bytes());
+        out.extend_from_slice(&weight.to_be_bytes());
+        out.extend_from_slice(&sign.to_be_bytes());
+        out.extend_from_slice(&scale.to_be_bytes());
+        for digit in digits {
+            out.extend_from_slice(&digit.to_be_bytes());
+        }
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::NUMERIC
+    }
+}
+
+/// Writes the rows of a single table with binary COPY over its own connection, which is opened
+/// on first use and reopened after a failure.
+pub struct CopyInserter {
+    connection_string: String,
+    client: Option<Client>,
+}
+
+impl CopyInserter {
+    pub fn new(connection_string: String) -> Self {
+        Self {
+            connection_string,
+            client: None,
+        }
+    }
+
+    /// Returns the copy inserter of `table` if it's configured in `copy_tables`. `table` is the
+    /// only table with a COPY path in the calling processor, any other configured table is
+    /// rejected.
+    pub fn for_table(
+        db_config: &DbConfig,
+        copy_tables: &HashSet<String>,
+        table: &str,
+    ) -> Result<Option<Self>> {
+        if let Some(unsupported) = copy_tables.iter().find(|copy_table| *copy_table != table) {
+            bail!("Table '{unsupported}' doesn't support COPY in this processor");
+        }
+        if copy_tables.is_empty() {
+            return Ok(None);
+        }
+        match db_config {
+            DbConfig::PostgresConfig(postgres_config) => {
+                Ok(Some(Self::new(postgres_config.connection_string.clone())))
+            },
+            _ => bail!("COPY requires a postgres db config"),
+        }
+    }
+
+    async fn client(&mut self) -> Result<&mut Client> {
+        if self.client.is_none() {
+            let connector = MakeTlsConnector::new(native_tls::TlsConnector::new()?);
+            let (client, connection) = tokio_postgres::connect(&self.connection_string, connector)
+                .await
+                .context("Failed to connect for COPY")?;
+            tokio::spawn(async move {
+                if let Err(e) = connection.await {
+                    error!(error = ?e, "COPY connection failed");
+                }
+            });
+            self.client = Some(client);
+        }
+        Ok(self.client.as_mut().unwrap())
+    }
+
+    /// Copies `items` into a staging table and merges them into the target table, in a single
+    /// transaction.
+    pub async fn insert<T: CopyInsertable>(&mut self, items: &[T]) -> Result<()> {
+        if items.is_empty() {
+            return Ok(());
+        }
+        let res = self.copy_and_merge(items).await;
+        if res.is_err() {
+            // The connection may be broken, start over with a new one
+            self.client = None;
+        }
+        res
+    }
+
+    async fn copy_and_merge<T: CopyInsertable>(&mut self, items: &[T]) -> Result<()> {
+        let (column_names, column_types): (Vec<&str>, Vec<Type>) = T::columns().into_iter().unzip();
+        let staging_table = staging_table_name::<T>();
+
+        let client = self.client().await?;
+        let transaction = client.transaction().await?;
+        transaction
+            .batch_execute(&format!(
+                "CREATE TEMP TABLE {staging_table} (LIKE {} INCLUDING DEFAULTS) ON COMMIT DROP",
+                T::table_name()
+            ))
+            .await?;
+        let sink = transaction
+            .copy_in(&format!(
+                "COPY {staging_table} ({}) FROM STDIN (FORMAT binary)",
+                quoted_list(&column_names)
+            ))
+            .await?;
+        let writer = BinaryCopyInWriter::new(sink, &column_types);
+        futures::pin_mut!(writer);
+        for item in items {
+            let row = item.copy_row();
+            let values: Vec<&(dyn ToSql + Sync)> = row
+                .iter()
+                .map(|value| &**value as &(dyn ToSql + Sync))
+                .collect();
+            writer.as_mut().write(&values).await?;
+        }
+        writer.finish().await?;
+        transaction.execute(&merge_statement::<T>(), &[]).await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+}
+
+fn staging_table_name<T: CopyInsertable>() -> String {
+    format!("copy_staging_{}", T::table_name())
+}
+
+fn quoted_list(columns: &[&str]) -> String {
+    columns
+        .iter()
+        .map(|column| format!("\"{column}\""))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn merge_statement<T: CopyInsertable>() -> String {
+    let column_names: Vec<&str> = T::columns().into_iter().map(|(name, _)| name).collect();
+    let columns = quoted_list(&column_names);
+    let updates = T::update_columns()
+        .iter()
+        .map(|column| format!("\"{column}\" = EXCLUDED.\"{column}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "INSERT INTO {} ({columns}) SELECT {columns} FROM {} ON CONFLICT ({}) DO UPDATE SET {updates}",
+        T::table_name(),
+        staging_table_name::<T>(),
+        quoted_list(T::conflict_columns()),
+    )
+}
+
+/// Writes `items` with the copy inserter if the table has one, and with `execute_in_chunks`
+/// otherwise.
+pub async fn execute_copy_or_in_chunks<U, T>(
+    copy_inserter: Option<&mut CopyInserter>,
+    conn: ArcDbPool,
+    build_query: fn(Vec<T>) -> U,
+    items_to_insert: &[T],
+    chunk_size: usize,
+) -> Result<(), ProcessorError>
+where
+    U: QueryFragment<Pg> + QueryId + Send + 'static,
+    T: CopyInsertable
+        + serde::Serialize
+        + for<'de> serde::Deserialize<'de>
+        + Clone
+        + Send
+        + 'static,
+{
+    match copy_inserter {
+        Some(copy_inserter) => {
+            copy_inserter
+                .insert(items_to_insert)
+                .await
+                .map_err(|e| ProcessorError::DBStoreError {
+                    message: format!("Failed to copy into {}: {:?}", T::table_name(), e),
+                    query: None,
+                })
+        },
+        None => execute_in_chunks(conn, build_query, items_to_insert, chunk_size).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processors::events::events_model::PostgresEvent;
+    use std::str::FromStr;
+
+    fn encode_numeric(value: &str) -> Vec<u8> {
+        let value = BigDecimal::from_str(value).unwrap();
+        let mut out = BytesMut::new();
+        PgNumeric(&value).to_sql(&Type::NUMERIC, &mut out).unwrap();
+        out.to_vec()
+    }
+
+    #[test]
+    fn test_numeric_binary_encoding() {
+        // 1 2345 . 6789 in base 10000 digits
+        assert_eq!(encode_numeric("12345.6789"), vec![
+            0, 3, 0, 1, 0, 0, 0, 4, 0, 1, 0x09, 0x29, 0x1A, 0x85
+        ]);
+        assert_eq!(encode_numeric("-7"), vec![0, 1, 0, 0, 0x40, 0, 0, 0, 0, 7]);
+    }
+
+    #[test]
+    fn test_merge_statement() {
+        assert_eq!(
+            merge_statement::<PostgresEvent>(),
+            "INSERT INTO events (\"sequence_number\", \"creation_number\", \"account_address\", \
+             \"transaction_version\", \"transaction_block_height\", \"type\", \"data\", \
+             \"event_index\", \"indexed_type\") SELECT \"sequence_number\", \"creation_number\", \
+             \"account_address\", \"transaction_version\", \"transaction_block_height\", \
+             \"type\", \"data\", \"event_index\", \"indexed_type\" FROM copy_staging_events ON \
+             CONFLICT (\"transaction_version\", \"event_index\") DO UPDATE SET \"inserted_at\" = \
+             EXCLUDED.\"inserted_at\", \"indexed_type\" = EXCLUDED.\"indexed_type\""
+        );
+    }
+}
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS move_module_functions;
+DROP TABLE IF EXISTS move_module_structs;
+DROP TABLE IF EXISTS move_module_upgrades;
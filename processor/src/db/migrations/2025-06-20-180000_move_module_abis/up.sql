@@ -0,0 +1,48 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS move_module_functions (
+  transaction_version BIGINT NOT NULL,
+  write_set_change_index BIGINT NOT NULL,
+  address VARCHAR(66) NOT NULL,
+  module_name TEXT NOT NULL,
+  name TEXT NOT NULL,
+  visibility VARCHAR(20) NOT NULL,
+  is_entry BOOLEAN NOT NULL,
+  generic_type_params JSONB NOT NULL,
+  params JSONB NOT NULL,
+  return_types JSONB NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (transaction_version, write_set_change_index, name)
+);
+CREATE INDEX IF NOT EXISTS mmf_addr_module_name_index ON move_module_functions (address, module_name, name);
+CREATE INDEX IF NOT EXISTS mmf_insat_index ON move_module_functions (inserted_at);
+
+CREATE TABLE IF NOT EXISTS move_module_structs (
+  transaction_version BIGINT NOT NULL,
+  write_set_change_index BIGINT NOT NULL,
+  address VARCHAR(66) NOT NULL,
+  module_name TEXT NOT NULL,
+  name TEXT NOT NULL,
+  is_native BOOLEAN NOT NULL,
+  abilities JSONB NOT NULL,
+  generic_type_params JSONB NOT NULL,
+  fields JSONB NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (transaction_version, write_set_change_index, name)
+);
+CREATE INDEX IF NOT EXISTS mms_addr_module_name_index ON move_module_structs (address, module_name, name);
+CREATE INDEX IF NOT EXISTS mms_insat_index ON move_module_structs (inserted_at);
+
+-- One row per change of a module's bytecode, i.e. the initial publish and every upgrade
+CREATE TABLE IF NOT EXISTS move_module_upgrades (
+  address VARCHAR(66) NOT NULL,
+  module_name TEXT NOT NULL,
+  bytecode_hash VARCHAR(66) NOT NULL,
+  transaction_version BIGINT NOT NULL,
+  write_set_change_index BIGINT NOT NULL,
+  transaction_block_height BIGINT NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (address, module_name, transaction_version)
+);
+CREATE INDEX IF NOT EXISTS mmu_addr_module_hash_index ON move_module_upgrades (address, module_name, bytecode_hash);
+CREATE INDEX IF NOT EXISTS mmu_version_index ON move_module_upgrades (transaction_version);
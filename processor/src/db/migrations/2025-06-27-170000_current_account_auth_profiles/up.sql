@@ -0,0 +1,17 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS current_account_auth_profiles (
+  address VARCHAR(66) NOT NULL,
+  auth_scheme VARCHAR(50) NOT NULL,
+  keyless_issuer TEXT,
+  keyless_audience TEXT,
+  multikey_threshold BIGINT,
+  multikey_key_count BIGINT,
+  first_transaction_version BIGINT NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (address, auth_scheme)
+);
+CREATE INDEX IF NOT EXISTS caap_auth_scheme_index ON current_account_auth_profiles (auth_scheme);
+CREATE INDEX IF NOT EXISTS caap_keyless_issuer_index ON current_account_auth_profiles (keyless_issuer);
+CREATE INDEX IF NOT EXISTS caap_insat_index ON current_account_auth_profiles (inserted_at);
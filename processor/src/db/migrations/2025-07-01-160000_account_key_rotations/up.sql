@@ -0,0 +1,17 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS account_key_rotations (
+  transaction_version BIGINT NOT NULL,
+  account_address VARCHAR(66) NOT NULL,
+  old_auth_key VARCHAR(66),
+  new_auth_key VARCHAR(66) NOT NULL,
+  public_key TEXT,
+  public_key_type VARCHAR(50),
+  verified_public_key_indices JSONB,
+  is_verified BOOLEAN NOT NULL,
+  entry_function_id_str TEXT,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (transaction_version, account_address)
+);
+CREATE INDEX IF NOT EXISTS akr_account_address_index ON account_key_rotations (account_address, transaction_version);
+CREATE INDEX IF NOT EXISTS akr_old_auth_key_index ON account_key_rotations (old_auth_key);
+CREATE INDEX IF NOT EXISTS akr_new_auth_key_index ON account_key_rotations (new_auth_key);
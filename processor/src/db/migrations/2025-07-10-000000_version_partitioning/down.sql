@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+-- Converted tables stay partitioned, converting them back would rewrite all their rows
+DROP FUNCTION IF EXISTS convert_to_version_partitioned(TEXT, BIGINT);
+DROP FUNCTION IF EXISTS version_partitioning_legacy_name(TEXT);
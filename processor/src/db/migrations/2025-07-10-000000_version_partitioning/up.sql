@@ -0,0 +1,150 @@
+-- Your SQL goes here
+-- Converts a table keyed by transaction_version into a table range partitioned by
+-- transaction_version. Processors call it on startup for the tables in their `version_partitioning`
+-- config, converted tables are left untouched. The existing rows are kept as the `<table>_legacy`
+-- partition covering every version below the first multiple of `partition_size` above them, an
+-- empty table is dropped instead. Constraints, indexes, foreign keys of other tables and dependent
+-- views keep their names so migrations apply the same way to partitioned and unpartitioned
+-- installs.
+
+-- Name of the legacy copy of a table, constraint or index. Long names are shortened with a hash
+-- instead of letting Postgres truncate them to 63 bytes, which could make two names collide.
+CREATE OR REPLACE FUNCTION version_partitioning_legacy_name(name TEXT)
+RETURNS TEXT AS $$
+  SELECT CASE
+    WHEN octet_length(name) <= 56 THEN name || '_legacy'
+    ELSE left(name, 47) || '_' || left(md5(name), 8) || '_legacy'
+  END;
+$$ LANGUAGE sql IMMUTABLE;
+
+CREATE OR REPLACE FUNCTION convert_to_version_partitioned(tbl TEXT, partition_size BIGINT)
+RETURNS VOID AS $$
+DECLARE
+  legacy TEXT := version_partitioning_legacy_name(tbl);
+  legacy_check TEXT := version_partitioning_legacy_name(tbl || '_bound');
+  max_version BIGINT;
+  upper_bound BIGINT;
+  constraint_names TEXT[];
+  constraint_defs TEXT[];
+  foreign_key_tables TEXT[];
+  foreign_key_names TEXT[];
+  foreign_key_defs TEXT[];
+  index_names TEXT[];
+  index_defs TEXT[];
+  view_names TEXT[];
+  view_defs TEXT[];
+  i INT;
+BEGIN
+  IF (SELECT relkind FROM pg_class WHERE oid = tbl::regclass) = 'p' THEN
+    RETURN;
+  END IF;
+  EXECUTE format('LOCK TABLE %I IN ACCESS EXCLUSIVE MODE', tbl);
+  EXECUTE format('SELECT max(transaction_version) FROM %I', tbl) INTO max_version;
+
+  -- Definitions are read before the rename so they refer to the table by its name
+  SELECT
+    coalesce(array_agg(conname::TEXT ORDER BY oid), '{}'),
+    coalesce(array_agg(pg_get_constraintdef(oid) ORDER BY oid), '{}')
+  INTO constraint_names, constraint_defs
+  FROM pg_constraint
+  WHERE conrelid = tbl::regclass AND contype IN ('p', 'u', 'f', 'c');
+
+  -- Foreign keys of other tables would keep pointing at the legacy table
+  SELECT
+    coalesce(array_agg(conrelid::regclass::TEXT ORDER BY oid), '{}'),
+    coalesce(array_agg(conname::TEXT ORDER BY oid), '{}'),
+    coalesce(array_agg(pg_get_constraintdef(oid) ORDER BY oid), '{}')
+  INTO foreign_key_tables, foreign_key_names, foreign_key_defs
+  FROM pg_constraint
+  WHERE confrelid = tbl::regclass AND conrelid <> tbl::regclass AND contype = 'f';
+
+  SELECT
+    coalesce(array_agg(c.relname::TEXT ORDER BY c.oid), '{}'),
+    coalesce(array_agg(pg_get_indexdef(c.oid) ORDER BY c.oid), '{}')
+  INTO index_names, index_defs
+  FROM pg_index x
+  JOIN pg_class c ON c.oid = x.indexrelid
+  WHERE x.indrelid = tbl::regclass
+    AND NOT EXISTS (
+      SELECT 1 FROM pg_constraint
+      WHERE conrelid = tbl::regclass AND conindid = x.indexrelid
+    );
+
+  SELECT
+    coalesce(array_agg(v.oid::regclass::TEXT ORDER BY v.oid), '{}'),
+    coalesce(array_agg(pg_get_viewdef(v.oid) ORDER BY v.oid), '{}')
+  INTO view_names, view_defs
+  FROM pg_class v
+  WHERE v.relkind = 'v' AND v.oid IN (
+    SELECT r.ev_class
+    FROM pg_depend d
+    JOIN pg_rewrite r ON r.oid = d.objid
+    WHERE d.classid = 'pg_rewrite'::regclass
+      AND d.refclassid = 'pg_class'::regclass
+      AND d.refobjid = tbl::regclass
+  );
+
+  FOR i IN 1 .. coalesce(array_length(foreign_key_names, 1), 0) LOOP
+    EXECUTE format(
+      'ALTER TABLE %s DROP CONSTRAINT %I', foreign_key_tables[i], foreign_key_names[i]
+    );
+  END LOOP;
+
+  EXECUTE format('ALTER TABLE %I RENAME TO %I', tbl, legacy);
+  FOR i IN 1 .. coalesce(array_length(constraint_names, 1), 0) LOOP
+    EXECUTE format(
+      'ALTER TABLE %I RENAME CONSTRAINT %I TO %I',
+      legacy, constraint_names[i], version_partitioning_legacy_name(constraint_names[i])
+    );
+  END LOOP;
+  FOR i IN 1 .. coalesce(array_length(index_names, 1), 0) LOOP
+    EXECUTE format(
+      'ALTER INDEX %I RENAME TO %I',
+      index_names[i], version_partitioning_legacy_name(index_names[i])
+    );
+  END LOOP;
+
+  EXECUTE format(
+    'CREATE TABLE %I (LIKE %I INCLUDING DEFAULTS INCLUDING STORAGE INCLUDING COMMENTS) '
+    'PARTITION BY RANGE (transaction_version)',
+    tbl, legacy
+  );
+  FOR i IN 1 .. coalesce(array_length(constraint_names, 1), 0) LOOP
+    EXECUTE format(
+      'ALTER TABLE %I ADD CONSTRAINT %I %s', tbl, constraint_names[i], constraint_defs[i]
+    );
+  END LOOP;
+  FOR i IN 1 .. coalesce(array_length(index_defs, 1), 0) LOOP
+    EXECUTE index_defs[i];
+  END LOOP;
+  FOR i IN 1 .. coalesce(array_length(view_names, 1), 0) LOOP
+    EXECUTE format('CREATE OR REPLACE VIEW %s AS %s', view_names[i], view_defs[i]);
+  END LOOP;
+
+  IF max_version IS NULL THEN
+    EXECUTE format('DROP TABLE %I', legacy);
+  ELSE
+    -- A validated check implying the partition bound lets the attach skip its own scan of the
+    -- legacy rows. Attaching reuses the matching indexes of the legacy table.
+    upper_bound := (max_version / partition_size + 1) * partition_size;
+    EXECUTE format(
+      'ALTER TABLE %I ADD CONSTRAINT %I CHECK (transaction_version < %s) NOT VALID',
+      legacy, legacy_check, upper_bound
+    );
+    EXECUTE format('ALTER TABLE %I VALIDATE CONSTRAINT %I', legacy, legacy_check);
+    EXECUTE format(
+      'ALTER TABLE %I ATTACH PARTITION %I FOR VALUES FROM (MINVALUE) TO (%s)',
+      tbl, legacy, upper_bound
+    );
+    EXECUTE format('ALTER TABLE %I DROP CONSTRAINT %I', legacy, legacy_check);
+  END IF;
+
+  -- Added once the legacy rows are attached, since they are validated against them
+  FOR i IN 1 .. coalesce(array_length(foreign_key_names, 1), 0) LOOP
+    EXECUTE format(
+      'ALTER TABLE %s ADD CONSTRAINT %I %s',
+      foreign_key_tables[i], foreign_key_names[i], foreign_key_defs[i]
+    );
+  END LOOP;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS gas_fee_entry_function_hourly_summaries;
+DROP TABLE IF EXISTS gas_fee_hourly_summaries;
+DROP TABLE IF EXISTS gas_fee_block_summaries;
+ALTER TABLE gas_fees DROP COLUMN IF EXISTS gas_unit_price;
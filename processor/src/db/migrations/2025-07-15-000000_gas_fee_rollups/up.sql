@@ -0,0 +1,44 @@
+-- Your SQL goes here
+ALTER TABLE gas_fees
+ADD COLUMN IF NOT EXISTS gas_unit_price BIGINT;
+CREATE TABLE IF NOT EXISTS gas_fee_block_summaries (
+  block_height BIGINT NOT NULL PRIMARY KEY,
+  block_timestamp TIMESTAMP NOT NULL,
+  transaction_count BIGINT NOT NULL,
+  total_fee NUMERIC NOT NULL,
+  total_storage_refund NUMERIC NOT NULL,
+  fee_payer_transaction_count BIGINT NOT NULL,
+  median_gas_unit_price BIGINT NOT NULL,
+  p95_gas_unit_price BIGINT NOT NULL,
+  gas_unit_price_histogram JSONB NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS gfbs_block_timestamp_index ON gas_fee_block_summaries (block_timestamp);
+CREATE TABLE IF NOT EXISTS gas_fee_hourly_summaries (
+  bucket_start TIMESTAMP NOT NULL PRIMARY KEY,
+  transaction_count BIGINT NOT NULL,
+  total_fee NUMERIC NOT NULL,
+  total_storage_refund NUMERIC NOT NULL,
+  fee_payer_transaction_count BIGINT NOT NULL,
+  median_gas_unit_price BIGINT NOT NULL,
+  p95_gas_unit_price BIGINT NOT NULL,
+  gas_unit_price_histogram JSONB NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE TABLE IF NOT EXISTS gas_fee_entry_function_hourly_summaries (
+  bucket_start TIMESTAMP NOT NULL,
+  entry_function_id_str VARCHAR(1000) NOT NULL,
+  transaction_count BIGINT NOT NULL,
+  total_fee NUMERIC NOT NULL,
+  total_storage_refund NUMERIC NOT NULL,
+  fee_payer_transaction_count BIGINT NOT NULL,
+  median_gas_unit_price BIGINT NOT NULL,
+  p95_gas_unit_price BIGINT NOT NULL,
+  gas_unit_price_histogram JSONB NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (bucket_start, entry_function_id_str)
+);
+CREATE INDEX IF NOT EXISTS gfefhs_entry_function_index ON gas_fee_entry_function_hourly_summaries (entry_function_id_str, bucket_start);
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE user_transactions DROP COLUMN IF EXISTS total_charge_gas_units,
+  DROP COLUMN IF EXISTS execution_gas_units,
+  DROP COLUMN IF EXISTS io_gas_units,
+  DROP COLUMN IF EXISTS storage_fee_octas;
+ALTER TABLE gas_fees DROP COLUMN IF EXISTS total_charge_gas_units,
+  DROP COLUMN IF EXISTS execution_gas_units,
+  DROP COLUMN IF EXISTS io_gas_units,
+  DROP COLUMN IF EXISTS storage_fee_octas;
@@ -0,0 +1,11 @@
+-- Your SQL goes here
+ALTER TABLE gas_fees
+ADD COLUMN IF NOT EXISTS total_charge_gas_units NUMERIC,
+  ADD COLUMN IF NOT EXISTS execution_gas_units NUMERIC,
+  ADD COLUMN IF NOT EXISTS io_gas_units NUMERIC,
+  ADD COLUMN IF NOT EXISTS storage_fee_octas NUMERIC;
+ALTER TABLE user_transactions
+ADD COLUMN IF NOT EXISTS total_charge_gas_units NUMERIC,
+  ADD COLUMN IF NOT EXISTS execution_gas_units NUMERIC,
+  ADD COLUMN IF NOT EXISTS io_gas_units NUMERIC,
+  ADD COLUMN IF NOT EXISTS storage_fee_octas NUMERIC;
@@ -0,0 +1,24 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS chain_health_snapshots (
+  start_version BIGINT NOT NULL PRIMARY KEY,
+  end_version BIGINT NOT NULL,
+  start_block_height BIGINT NOT NULL,
+  end_block_height BIGINT NOT NULL,
+  start_timestamp TIMESTAMP NOT NULL,
+  end_timestamp TIMESTAMP NOT NULL,
+  epoch BIGINT NOT NULL,
+  epoch_changes BIGINT NOT NULL,
+  block_count BIGINT NOT NULL,
+  avg_block_interval_ms BIGINT,
+  max_block_interval_ms BIGINT,
+  transaction_count BIGINT NOT NULL,
+  user_transaction_count BIGINT NOT NULL,
+  failed_transaction_count BIGINT NOT NULL,
+  tps NUMERIC NOT NULL,
+  failed_transaction_rate NUMERIC NOT NULL,
+  failed_proposal_count BIGINT NOT NULL,
+  proposer_counts JSONB NOT NULL,
+  stream_lag_ms BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS chs_end_timestamp_index ON chain_health_snapshots (end_timestamp);
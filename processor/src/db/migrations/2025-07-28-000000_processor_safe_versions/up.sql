@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS processor_safe_versions (
+  set_name VARCHAR(100) NOT NULL PRIMARY KEY,
+  safe_version BIGINT NOT NULL,
+  safe_transaction_timestamp TIMESTAMP,
+  lagging_processor VARCHAR(100) NOT NULL,
+  last_updated TIMESTAMP NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS validator_transactions;
+DROP TABLE IF EXISTS block_epilogue_transactions;
@@ -0,0 +1,30 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS block_epilogue_transactions (
+  version BIGINT NOT NULL PRIMARY KEY,
+  block_height BIGINT NOT NULL,
+  epoch BIGINT NOT NULL,
+  -- Block end info, null if the transaction doesn't have any
+  block_gas_limit_reached BOOLEAN,
+  block_output_limit_reached BOOLEAN,
+  block_effective_block_gas_units BIGINT,
+  block_approx_output_size BIGINT,
+  "timestamp" TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS bet_block_height_index ON block_epilogue_transactions (block_height);
+CREATE TABLE IF NOT EXISTS validator_transactions (
+  version BIGINT NOT NULL PRIMARY KEY,
+  block_height BIGINT NOT NULL,
+  epoch BIGINT NOT NULL,
+  validator_transaction_type VARCHAR(50) NOT NULL,
+  -- Set for observed JWK updates
+  jwk_issuer VARCHAR(1000),
+  jwk_version BIGINT,
+  num_jwks BIGINT,
+  -- Set for DKG results
+  dkg_epoch BIGINT,
+  dkg_author VARCHAR(66),
+  "timestamp" TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS vt_type_index ON validator_transactions (validator_transaction_type);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS current_storage_usage;
+DROP TABLE IF EXISTS storage_usage_changes;
+DROP TABLE IF EXISTS current_state_item_sizes;
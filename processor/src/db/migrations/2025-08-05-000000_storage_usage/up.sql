@@ -0,0 +1,37 @@
+-- Your SQL goes here
+-- Stored size of every state item, used to compute the size change of its next write
+CREATE TABLE IF NOT EXISTS current_state_item_sizes (
+  state_key_hash VARCHAR(66) NOT NULL PRIMARY KEY,
+  address VARCHAR(66) NOT NULL,
+  resource_type VARCHAR(1000) NOT NULL,
+  key_bytes BIGINT NOT NULL,
+  value_bytes BIGINT NOT NULL,
+  is_deleted BOOLEAN NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+-- Net change of the stored bytes and items of each account and resource type per transaction
+CREATE TABLE IF NOT EXISTS storage_usage_changes (
+  transaction_version BIGINT NOT NULL,
+  address VARCHAR(66) NOT NULL,
+  resource_type VARCHAR(1000) NOT NULL,
+  bytes_delta BIGINT NOT NULL,
+  items_delta BIGINT NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (transaction_version, address, resource_type)
+);
+CREATE INDEX IF NOT EXISTS suc_address_resource_type_index ON storage_usage_changes (address, resource_type);
+CREATE INDEX IF NOT EXISTS suc_transaction_timestamp_index ON storage_usage_changes (transaction_timestamp);
+-- Bytes and items currently stored by each account and resource type
+CREATE TABLE IF NOT EXISTS current_storage_usage (
+  address VARCHAR(66) NOT NULL,
+  resource_type VARCHAR(1000) NOT NULL,
+  bytes BIGINT NOT NULL,
+  items BIGINT NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (address, resource_type)
+);
+CREATE INDEX IF NOT EXISTS csu_bytes_index ON current_storage_usage (bytes);
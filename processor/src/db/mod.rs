@@ -1,2 +1,5 @@
 pub mod backfill_processor_status;
+pub mod copy_insert;
+pub mod partitioning;
 pub mod resources;
+pub mod retention;
@@ -0,0 +1,334 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Range partitioning of history tables by `transaction_version`.
+//!
+//! On startup the processor converts each configured table with the
+//! `convert_to_version_partitioned` migration function, and while processing it creates the
+//! partitions of the upcoming versions ahead of the stream. Migrations touching a table that can
+//! be partitioned must work on a partitioned table too: no `CREATE INDEX CONCURRENTLY` and no
+//! unique constraint without `transaction_version`.
+
+use anyhow::{bail, Context, Result};
+use aptos_indexer_processor_sdk::{
+    postgres::utils::database::{execute_with_better_error, ArcDbPool},
+    traits::{async_step::AsyncRunType, AsyncStep, NamedStep, Processable},
+    types::transaction_context::TransactionContext,
+    utils::errors::ProcessorError,
+};
+use async_trait::async_trait;
+use diesel::{
+    sql_types::{BigInt, Text},
+    QueryableByName,
+};
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
+};
+use tracing::info;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct VersionPartitioningConfig {
+    // Tables to partition, only tables written by the processor are accepted
+    pub tables: HashSet<String>,
+    // Number of versions per partition
+    #[serde(default = "VersionPartitioningConfig::default_partition_size")]
+    pub partition_size: u64,
+    // Number of partitions created ahead of the partition of the latest processed version
+    #[serde(default = "VersionPartitioningConfig::default_premake_partitions")]
+    pub premake_partitions: u64,
+}
+
+impl VersionPartitioningConfig {
+    pub const fn default_partition_size() -> u64 {
+        100_000_000
+    }
+
+    pub const fn default_premake_partitions() -> u64 {
+        2
+    }
+}
+
+/// Half open version range `[start, end)` of a partition. `MINVALUE` and `MAXVALUE` bounds are
+/// `i64::MIN` and `i64::MAX`.
+pub type VersionRange = (i64, i64);
+
+#[derive(QueryableByName)]
+struct Partition {
+    #[diesel(sql_type = Text)]
+    name: String,
+    #[diesel(sql_type = Text)]
+    bound: String,
+}
+
+/// Lists the partitions of `table` by name with their version ranges, none if the table isn't
+/// partitioned.
+pub async fn list_partitions(
+    conn_pool: ArcDbPool,
+    table: &str,
+) -> Result<Vec<(String, VersionRange)>> {
+    let mut conn = conn_pool.get().await?;
+    let partitions: Vec<Partition> = diesel::sql_query(
+        "SELECT c.relname::TEXT AS name, pg_get_expr(c.relpartbound, c.oid) AS bound \
+         FROM pg_inherits i JOIN pg_class c ON c.oid = i.inhrelid \
+         WHERE i.inhparent = $1::regclass",
+    )
+    .bind::<Text, _>(table)
+    .load(&mut conn)
+    .await?;
+    partitions
+        .into_iter()
+        .map(|partition| Ok((partition.name, parse_partition_bound(&partition.bound)?)))
+        .collect()
+}
+
+/// Creates the partitions of the configured tables.
+pub struct VersionPartitionManager {
+    conn_pool: ArcDbPool,
+    partition_size: i64,
+    premake_partitions: i64,
+    // Version ranges of the existing partitions of each table
+    partitions: HashMap<String, Vec<VersionRange>>,
+}
+
+impl VersionPartitionManager {
+    /// Converts the configured tables and loads their partitions. `owned_tables` are the tables
+    /// the processor writes, only those can be partitioned by it.
+    pub async fn new(
+        conn_pool: ArcDbPool,
+        config: Option<&VersionPartitioningConfig>,
+        owned_tables: &[&str],
+    ) -> Result<Self> {
+        let Some(config) = config else {
+            return Ok(Self {
+                conn_pool,
+                partition_size: 1,
+                premake_partitions: 0,
+                partitions: HashMap::new(),
+            });
+        };
+        if config.partition_size == 0 {
+            bail!("partition_size must be positive");
+        }
+        if let Some(table) = config
+            .tables
+            .iter()
+            .find(|table| !owned_tables.contains(&table.as_str()))
+        {
+            bail!("Table '{table}' can't be partitioned by this processor");
+        }
+
+        let mut manager = Self {
+            conn_pool,
+            partition_size: config.partition_size as i64,
+            premake_partitions: config.premake_partitions as i64,
+            partitions: HashMap::new(),
+        };
+        for table in &config.tables {
+            execute_with_better_error(
+                manager.conn_pool.clone(),
+                diesel::sql_query("SELECT convert_to_version_partitioned($1, $2)")
+                    .bind::<Text, _>(table)
+                    .bind::<BigInt, _>(manager.partition_size),
+            )
+            .await
+            .with_context(|| format!("Failed to partition table '{table}'"))?;
+            manager.load_partitions(table).await?;
+        }
+        Ok(manager)
+    }
+
+    async fn load_partitions(&mut self, table: &str) -> Result<()> {
+        let mut ranges: Vec<VersionRange> = list_partitions(self.conn_pool.clone(), table)
+            .await?
+            .into_iter()
+            .map(|(_, range)| range)
+            .collect();
+        ranges.sort();
+        self.partitions.insert(table.to_string(), ranges);
+        Ok(())
+    }
+
+    /// Makes sure every table has partitions for `[start_version, end_version]` and the
+    /// `premake_partitions` partitions after it.
+    pub async fn ensure_partitions(&mut self, start_version: u64, end_version: u64) -> Result<()> {
+        let from = start_version as i64 / self.partition_size * self.partition_size;
+        let to = (end_version as i64 / self.partition_size + 1 + self.premake_partitions)
+            * self.partition_size;
+        let tables: Vec<String> = self.partitions.keys().cloned().collect();
+        for table in tables {
+            let missing = missing_ranges(&self.partitions[&table], from, to, self.partition_size);
+            for (start, end) in missing {
+                if let Err(e) = self.create_partition(&table, start, end).await {
+                    // Another processor may have created it in the meantime
+                    self.load_partitions(&table).await?;
+                    let ranges = &self.partitions[&table];
+                    if !missing_ranges(ranges, start, end, self.partition_size).is_empty() {
+                        return Err(e);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn create_partition(&mut self, table: &str, start: i64, end: i64) -> Result<()> {
+        execute_with_better_error(
+            self.conn_pool.clone(),
+            diesel::sql_query(format!(
+                "CREATE TABLE IF NOT EXISTS {table}_v{start} PARTITION OF {table} \
+                 FOR VALUES FROM ({start}) TO ({end})"
+            )),
+        )
+        .await
+        .with_context(|| format!("Failed to create partition [{start}, {end}) of '{table}'"))?;
+        info!(table, start, end, "Created partition");
+
+        let ranges = self.partitions.entry(table.to_string()).or_default();
+        ranges.push((start, end));
+        ranges.sort();
+        Ok(())
+    }
+}
+
+/// Parses a range partition bound like `FOR VALUES FROM ('0') TO ('100')`.
+fn parse_partition_bound(bound: &str) -> Result<VersionRange> {
+    let parse_value = |value: &str| -> Result<i64> {
+        let value = value.trim().trim_matches('\'');
+        match value {
+            "MINVALUE" => Ok(i64::MIN),
+            "MAXVALUE" => Ok(i64::MAX),
+            _ => value
+                .parse()
+                .with_context(|| format!("Invalid partition bound '{bound}'")),
+        }
+    };
+    let Some((from, to)) = bound
+        .strip_prefix("FOR VALUES FROM (")
+        .and_then(|rest| rest.strip_suffix(')'))
+        .and_then(|rest| rest.split_once(") TO ("))
+    else {
+        bail!("Unsupported partition bound '{bound}'");
+    };
+    Ok((parse_value(from)?, parse_value(to)?))
+}
+
+/// Returns the ranges of `[from, to)` that no partition covers, split at multiples of
+/// `partition_size`.
+fn missing_ranges(
+    partitions: &[VersionRange],
+    from: i64,
+    to: i64,
+    partition_size: i64,
+) -> Vec<VersionRange> {
+    let mut missing = vec![];
+    let mut start = from;
+    while start < to {
+        if let Some((_, end)) = partitions.iter().find(|(partition_start, partition_end)| {
+            *partition_start <= start && start < *partition_end
+        }) {
+            start = *end;
+            continue;
+        }
+        // Up to the next multiple of the partition size or the next partition
+        let mut end = (start.div_euclid(partition_size) + 1) * partition_size;
+        end = end.min(to);
+        if let Some((next_start, _)) = partitions
+            .iter()
+            .find(|(partition_start, _)| start < *partition_start && *partition_start < end)
+        {
+            end = *next_start;
+        }
+        missing.push((start, end));
+        start = end;
+    }
+    missing
+}
+
+/// Passes batches through after making sure the partitions of their versions exist.
+pub struct VersionPartitionStep<T>
+where
+    Self: Sized + Send + 'static,
+    T: Send + 'static,
+{
+    manager: VersionPartitionManager,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Send + 'static> VersionPartitionStep<T> {
+    pub fn new(manager: VersionPartitionManager) -> Self {
+        Self {
+            manager,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<T: Send + 'static> Processable for VersionPartitionStep<T> {
+    type Input = T;
+    type Output = T;
+    type RunType = AsyncRunType;
+
+    async fn process(
+        &mut self,
+        input: TransactionContext<T>,
+    ) -> Result<Option<TransactionContext<T>>, ProcessorError> {
+        self.manager
+            .ensure_partitions(input.metadata.start_version, input.metadata.end_version)
+            .await
+            .map_err(|e| ProcessorError::DBStoreError {
+                message: format!(
+                    "Failed to create partitions for versions {} to {}: {:?}",
+                    input.metadata.start_version, input.metadata.end_version, e
+                ),
+                query: None,
+            })?;
+        Ok(Some(input))
+    }
+}
+
+impl<T: Send + 'static> AsyncStep for VersionPartitionStep<T> {}
+
+impl<T: Send + 'static> NamedStep for VersionPartitionStep<T> {
+    fn name(&self) -> String {
+        "VersionPartitionStep".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_partition_bound() {
+        assert_eq!(
+            parse_partition_bound("FOR VALUES FROM ('100') TO ('200')").unwrap(),
+            (100, 200)
+        );
+        assert_eq!(
+            parse_partition_bound("FOR VALUES FROM (MINVALUE) TO ('300')").unwrap(),
+            (i64::MIN, 300)
+        );
+        assert!(parse_partition_bound("DEFAULT").is_err());
+    }
+
+    #[test]
+    fn test_missing_ranges() {
+        // Nothing partitioned yet
+        assert_eq!(missing_ranges(&[], 100, 400, 100), vec![
+            (100, 200),
+            (200, 300),
+            (300, 400)
+        ]);
+        // Legacy partition below 200 and a partition created with another partition size
+        assert_eq!(
+            missing_ranges(&[(i64::MIN, 200), (250, 300)], 100, 500, 100),
+            vec![(200, 250), (300, 400), (400, 500)]
+        );
+        assert!(missing_ranges(&[(0, 100), (100, 200)], 0, 200, 100).is_empty());
+    }
+}
@@ -0,0 +1,366 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Retention of history tables.
+//!
+//! Processors prune the rows of their history tables that are older than the configured horizon
+//! in a background task. Partitions entirely past the horizon are dropped or detached, the rest
+//! is deleted in version windows. `current_*` tables are never pruned.
+
+use crate::{
+    config::{indexer_processor_config::IndexerProcessorConfig, processor_mode::ProcessorMode},
+    db::partitioning::{list_partitions, VersionRange},
+    utils::counters::{PRUNED_PARTITIONS_COUNT, PRUNED_ROWS_COUNT, RETENTION_HORIZON_VERSION},
+};
+use anyhow::{bail, Context, Result};
+use aptos_indexer_processor_sdk::postgres::{
+    models::processor_status::ProcessorStatusQuery,
+    utils::database::{execute_with_better_error, ArcDbPool},
+};
+use chrono::{Duration as ChronoDuration, NaiveDateTime, Utc};
+use diesel::{
+    sql_types::{BigInt, Nullable, Timestamp},
+    OptionalExtension, QueryableByName,
+};
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, time::Duration};
+use tracing::{error, info};
+
+/// History tables that can be pruned, with their block timestamp column if they have one. The
+/// horizon of tables without one is looked up in `block_metadata_transactions`, which only the
+/// default processor writes, so their days retention needs it to run on the same database.
+const HISTORY_TABLES: &[(&str, Option<&str>)] = &[
+    ("account_transactions", None),
+    ("events", None),
+    ("fungible_asset_activities", Some("transaction_timestamp")),
+    ("token_activities_v2", Some("transaction_timestamp")),
+];
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RetentionConfig {
+    // Retention of each history table, keyed by table name
+    pub tables: HashMap<String, TableRetention>,
+    #[serde(default = "RetentionConfig::default_prune_interval_secs")]
+    pub prune_interval_secs: u64,
+    // Number of versions deleted per statement
+    #[serde(default = "RetentionConfig::default_delete_batch_versions")]
+    pub delete_batch_versions: u64,
+    // Detach the partitions past the horizon instead of dropping them, e.g. to archive them
+    #[serde(default)]
+    pub detach_expired_partitions: bool,
+}
+
+impl RetentionConfig {
+    pub const fn default_prune_interval_secs() -> u64 {
+        3600
+    }
+
+    pub const fn default_delete_batch_versions() -> u64 {
+        100_000
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum TableRetention {
+    /// Keeps the rows of the latest number of versions.
+    Versions(u64),
+    /// Keeps the rows of the latest number of days, by block timestamp.
+    Days(u64),
+}
+
+#[derive(QueryableByName)]
+struct BlockTimestamp {
+    #[diesel(sql_type = Timestamp)]
+    timestamp: NaiveDateTime,
+}
+
+#[derive(QueryableByName)]
+struct MinVersion {
+    #[diesel(sql_type = Nullable<BigInt>)]
+    min_version: Option<i64>,
+}
+
+/// Validates the retention config of a processor and starts pruning in the background.
+/// `owned_tables` are the history tables the processor writes, only those can be pruned by it.
+/// Pruning only runs in the default processor mode, it would race with backfills.
+pub fn start_history_pruning(
+    indexer_config: &IndexerProcessorConfig,
+    conn_pool: ArcDbPool,
+    config: Option<&RetentionConfig>,
+    owned_tables: &[&str],
+) -> Result<()> {
+    let Some(config) = config else {
+        return Ok(());
+    };
+    validate_retention_config(config, owned_tables)?;
+    if !matches!(indexer_config.processor_mode, ProcessorMode::Default(_)) {
+        info!("Retention pruning only runs in the default processor mode");
+        return Ok(());
+    }
+
+    let pruner = HistoryPruner {
+        conn_pool,
+        processor_name: indexer_config.processor_config.name().to_string(),
+        config: config.clone(),
+    };
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(pruner.config.prune_interval_secs));
+        loop {
+            interval.tick().await;
+            if let Err(e) = pruner.prune().await {
+                error!(
+                    processor_name = pruner.processor_name,
+                    error = ?e,
+                    "Failed to prune history tables"
+                );
+            }
+        }
+    });
+    Ok(())
+}
+
+fn validate_retention_config(config: &RetentionConfig, owned_tables: &[&str]) -> Result<()> {
+    for table in config.tables.keys() {
+        if !owned_tables.contains(&table.as_str())
+            || !HISTORY_TABLES.iter().any(|(name, _)| name == table)
+        {
+            bail!("Table '{table}' can't be pruned by this processor");
+        }
+    }
+    if config.prune_interval_secs == 0 || config.delete_batch_versions == 0 {
+        bail!("prune_interval_secs and delete_batch_versions must be positive");
+    }
+    Ok(())
+}
+
+struct HistoryPruner {
+    conn_pool: ArcDbPool,
+    processor_name: String,
+    config: RetentionConfig,
+}
+
+impl HistoryPruner {
+    async fn prune(&self) -> Result<()> {
+        let mut conn = self.conn_pool.get().await?;
+        let Some(status) = ProcessorStatusQuery::get_by_processor(&self.processor_name, &mut conn)
+            .await
+            .context("Failed to query processor_status")?
+        else {
+            // Nothing processed yet
+            return Ok(());
+        };
+        drop(conn);
+        let latest_version = status.last_success_version;
+
+        for (table, retention) in &self.config.tables {
+            // A table that can't be pruned doesn't hold back the others
+            if let Err(e) = self
+                .prune_to_retention(table, *retention, latest_version)
+                .await
+            {
+                error!(
+                    processor_name = self.processor_name,
+                    table,
+                    error = ?e,
+                    "Failed to prune history table"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn prune_to_retention(
+        &self,
+        table: &str,
+        retention: TableRetention,
+        latest_version: i64,
+    ) -> Result<()> {
+        let horizon = match retention {
+            TableRetention::Versions(versions) => version_horizon(latest_version, versions),
+            TableRetention::Days(days) => {
+                let horizon_timestamp = Utc::now().naive_utc() - ChronoDuration::days(days as i64);
+                self.first_version_at(table, horizon_timestamp, latest_version)
+                    .await?
+            },
+        };
+        self.prune_table(table, horizon).await?;
+        RETENTION_HORIZON_VERSION
+            .with_label_values(&[&self.processor_name, table])
+            .set(horizon);
+        Ok(())
+    }
+
+    /// Binary searches the first version at or after `timestamp`, every version before it is
+    /// older than `timestamp`. Fails if the horizon has to be looked up in an empty
+    /// `block_metadata_transactions`, instead of silently keeping every row.
+    async fn first_version_at(
+        &self,
+        table: &str,
+        timestamp: NaiveDateTime,
+        latest_version: i64,
+    ) -> Result<i64> {
+        let (source_table, version_column, timestamp_column) = match HISTORY_TABLES
+            .iter()
+            .find(|(name, _)| *name == table)
+            .and_then(|(_, timestamp_column)| *timestamp_column)
+        {
+            Some(timestamp_column) => (table, "transaction_version", timestamp_column),
+            None => ("block_metadata_transactions", "version", "timestamp"),
+        };
+        let query = format!(
+            "SELECT {timestamp_column} AS timestamp FROM {source_table} \
+             WHERE {version_column} >= $1 ORDER BY {version_column} LIMIT 1"
+        );
+
+        let mut conn = self.conn_pool.get().await?;
+        if source_table != table
+            && diesel::sql_query(&query)
+                .bind::<BigInt, _>(0)
+                .get_result::<BlockTimestamp>(&mut conn)
+                .await
+                .optional()?
+                .is_none()
+        {
+            bail!(
+                "Days retention of '{table}' looks up timestamps in {source_table}, which is \
+                 empty. Run the default processor on the same database or use a versions \
+                 retention"
+            );
+        }
+        let (mut low, mut high) = (0, latest_version + 1);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let first_timestamp: Option<BlockTimestamp> = diesel::sql_query(&query)
+                .bind::<BigInt, _>(mid)
+                .get_result(&mut conn)
+                .await
+                .optional()?;
+            match first_timestamp {
+                Some(first) if first.timestamp < timestamp => low = mid + 1,
+                _ => high = mid,
+            }
+        }
+        Ok(low)
+    }
+
+    async fn prune_table(&self, table: &str, horizon: i64) -> Result<()> {
+        let labels = [self.processor_name.as_str(), table];
+
+        for (partition, _) in expired_partitions(
+            list_partitions(self.conn_pool.clone(), table).await?,
+            horizon,
+        ) {
+            let statement = if self.config.detach_expired_partitions {
+                format!("ALTER TABLE {table} DETACH PARTITION {partition}")
+            } else {
+                format!("DROP TABLE {partition}")
+            };
+            execute_with_better_error(self.conn_pool.clone(), diesel::sql_query(statement))
+                .await
+                .with_context(|| format!("Failed to prune partition '{partition}'"))?;
+            PRUNED_PARTITIONS_COUNT.with_label_values(&labels).inc();
+            info!(table, partition, horizon, "Pruned partition");
+        }
+
+        let mut conn = self.conn_pool.get().await?;
+        let min_version = diesel::sql_query(format!(
+            "SELECT min(transaction_version) AS min_version FROM {table}"
+        ))
+        .get_result::<MinVersion>(&mut conn)
+        .await?
+        .min_version;
+        drop(conn);
+        let Some(mut start) = min_version else {
+            return Ok(());
+        };
+        while start < horizon {
+            let end = horizon.min(start + self.config.delete_batch_versions as i64);
+            let deleted = execute_with_better_error(
+                self.conn_pool.clone(),
+                diesel::sql_query(format!(
+                    "DELETE FROM {table} WHERE transaction_version >= $1 AND transaction_version < $2"
+                ))
+                .bind::<BigInt, _>(start)
+                .bind::<BigInt, _>(end),
+            )
+            .await
+            .with_context(|| format!("Failed to prune '{table}' versions [{start}, {end})"))?;
+            PRUNED_ROWS_COUNT
+                .with_label_values(&labels)
+                .inc_by(deleted as u64);
+            start = end;
+        }
+        Ok(())
+    }
+}
+
+/// First version kept when keeping `versions` versions up to `latest_version`.
+fn version_horizon(latest_version: i64, versions: u64) -> i64 {
+    (latest_version + 1 - versions.min(i64::MAX as u64) as i64).max(0)
+}
+
+/// Partitions whose versions are all before `horizon`.
+fn expired_partitions(
+    partitions: Vec<(String, VersionRange)>,
+    horizon: i64,
+) -> Vec<(String, VersionRange)> {
+    partitions
+        .into_iter()
+        .filter(|(_, (_, end))| *end <= horizon)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retention_config() {
+        let config: RetentionConfig = serde_json::from_value(serde_json::json!({
+            "tables": {
+                "events": { "days": 90 },
+                "account_transactions": { "versions": 1000 }
+            }
+        }))
+        .unwrap();
+        assert_eq!(config.tables["events"], TableRetention::Days(90));
+        assert_eq!(
+            config.tables["account_transactions"],
+            TableRetention::Versions(1000)
+        );
+        assert_eq!(config.prune_interval_secs, 3600);
+
+        assert!(validate_retention_config(&config, &["events", "account_transactions"]).is_ok());
+        // Only the processor's own history tables can be pruned
+        assert!(validate_retention_config(&config, &["events"]).is_err());
+        let config = RetentionConfig {
+            tables: HashMap::from([(
+                "current_fungible_asset_balances".to_string(),
+                TableRetention::Days(1),
+            )]),
+            ..config
+        };
+        assert!(validate_retention_config(&config, &["current_fungible_asset_balances"]).is_err());
+    }
+
+    #[test]
+    fn test_horizons() {
+        assert_eq!(version_horizon(999, 100), 900);
+        assert_eq!(version_horizon(50, 100), 0);
+
+        let partitions = vec![
+            ("events_legacy".to_string(), (i64::MIN, 100)),
+            ("events_v100".to_string(), (100, 200)),
+            ("events_v200".to_string(), (200, 300)),
+        ];
+        let expired: Vec<String> = expired_partitions(partitions, 250)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(expired, vec!["events_legacy", "events_v100"]);
+    }
+}
@@ -1,5 +1,24 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    account_key_rotations (transaction_version, account_address) {
+        transaction_version -> Int8,
+        #[max_length = 66]
+        account_address -> Varchar,
+        #[max_length = 66]
+        old_auth_key -> Nullable<Varchar>,
+        #[max_length = 66]
+        new_auth_key -> Varchar,
+        public_key -> Nullable<Text>,
+        #[max_length = 50]
+        public_key_type -> Nullable<Varchar>,
+        verified_public_key_indices -> Nullable<Jsonb>,
+        is_verified -> Bool,
+        entry_function_id_str -> Nullable<Text>,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     account_transactions (account_address, transaction_version) {
         transaction_version -> Int8,
@@ -109,6 +128,20 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    block_epilogue_transactions (version) {
+        version -> Int8,
+        block_height -> Int8,
+        epoch -> Int8,
+        block_gas_limit_reached -> Nullable<Bool>,
+        block_output_limit_reached -> Nullable<Bool>,
+        block_effective_block_gas_units -> Nullable<Int8>,
+        block_approx_output_size -> Nullable<Int8>,
+        timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     block_metadata_transactions (version) {
         version -> Int8,
@@ -126,6 +159,31 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    chain_health_snapshots (start_version) {
+        start_version -> Int8,
+        end_version -> Int8,
+        start_block_height -> Int8,
+        end_block_height -> Int8,
+        start_timestamp -> Timestamp,
+        end_timestamp -> Timestamp,
+        epoch -> Int8,
+        epoch_changes -> Int8,
+        block_count -> Int8,
+        avg_block_interval_ms -> Nullable<Int8>,
+        max_block_interval_ms -> Nullable<Int8>,
+        transaction_count -> Int8,
+        user_transaction_count -> Int8,
+        failed_transaction_count -> Int8,
+        tps -> Numeric,
+        failed_transaction_rate -> Numeric,
+        failed_proposal_count -> Int8,
+        proposer_counts -> Jsonb,
+        stream_lag_ms -> Int8,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     coin_activities (transaction_version, event_account_address, event_creation_number, event_sequence_number) {
         transaction_version -> Int8,
@@ -257,6 +315,23 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    current_account_auth_profiles (address, auth_scheme) {
+        #[max_length = 66]
+        address -> Varchar,
+        #[max_length = 50]
+        auth_scheme -> Varchar,
+        keyless_issuer -> Nullable<Text>,
+        keyless_audience -> Nullable<Text>,
+        multikey_threshold -> Nullable<Int8>,
+        multikey_key_count -> Nullable<Int8>,
+        first_transaction_version -> Int8,
+        last_transaction_version -> Int8,
+        last_transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     current_ans_lookup (domain, subdomain) {
         #[max_length = 64]
@@ -524,6 +599,36 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    current_state_item_sizes (state_key_hash) {
+        #[max_length = 66]
+        state_key_hash -> Varchar,
+        #[max_length = 66]
+        address -> Varchar,
+        #[max_length = 1000]
+        resource_type -> Varchar,
+        key_bytes -> Int8,
+        value_bytes -> Int8,
+        is_deleted -> Bool,
+        last_transaction_version -> Int8,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    current_storage_usage (address, resource_type) {
+        #[max_length = 66]
+        address -> Varchar,
+        #[max_length = 1000]
+        resource_type -> Varchar,
+        bytes -> Int8,
+        items -> Int8,
+        last_transaction_version -> Int8,
+        last_transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     current_table_items (table_handle, key_hash) {
         #[max_length = 66]
@@ -879,6 +984,54 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    gas_fee_block_summaries (block_height) {
+        block_height -> Int8,
+        block_timestamp -> Timestamp,
+        transaction_count -> Int8,
+        total_fee -> Numeric,
+        total_storage_refund -> Numeric,
+        fee_payer_transaction_count -> Int8,
+        median_gas_unit_price -> Int8,
+        p95_gas_unit_price -> Int8,
+        gas_unit_price_histogram -> Jsonb,
+        last_transaction_version -> Int8,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    gas_fee_entry_function_hourly_summaries (bucket_start, entry_function_id_str) {
+        bucket_start -> Timestamp,
+        #[max_length = 1000]
+        entry_function_id_str -> Varchar,
+        transaction_count -> Int8,
+        total_fee -> Numeric,
+        total_storage_refund -> Numeric,
+        fee_payer_transaction_count -> Int8,
+        median_gas_unit_price -> Int8,
+        p95_gas_unit_price -> Int8,
+        gas_unit_price_histogram -> Jsonb,
+        last_transaction_version -> Int8,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    gas_fee_hourly_summaries (bucket_start) {
+        bucket_start -> Timestamp,
+        transaction_count -> Int8,
+        total_fee -> Numeric,
+        total_storage_refund -> Numeric,
+        fee_payer_transaction_count -> Int8,
+        median_gas_unit_price -> Int8,
+        p95_gas_unit_price -> Int8,
+        gas_unit_price_histogram -> Jsonb,
+        last_transaction_version -> Int8,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     gas_fees (transaction_version) {
         transaction_version -> Int8,
@@ -893,6 +1046,11 @@ diesel::table! {
         block_height -> Int8,
         transaction_timestamp -> Timestamp,
         storage_refund_amount -> Numeric,
+        gas_unit_price -> Nullable<Int8>,
+        total_charge_gas_units -> Nullable<Numeric>,
+        execution_gas_units -> Nullable<Numeric>,
+        io_gas_units -> Nullable<Numeric>,
+        storage_fee_octas -> Nullable<Numeric>,
     }
 }
 
@@ -911,6 +1069,55 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    move_module_functions (transaction_version, write_set_change_index, name) {
+        transaction_version -> Int8,
+        write_set_change_index -> Int8,
+        #[max_length = 66]
+        address -> Varchar,
+        module_name -> Text,
+        name -> Text,
+        #[max_length = 20]
+        visibility -> Varchar,
+        is_entry -> Bool,
+        generic_type_params -> Jsonb,
+        params -> Jsonb,
+        return_types -> Jsonb,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    move_module_structs (transaction_version, write_set_change_index, name) {
+        transaction_version -> Int8,
+        write_set_change_index -> Int8,
+        #[max_length = 66]
+        address -> Varchar,
+        module_name -> Text,
+        name -> Text,
+        is_native -> Bool,
+        abilities -> Jsonb,
+        generic_type_params -> Jsonb,
+        fields -> Jsonb,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    move_module_upgrades (address, module_name, transaction_version) {
+        #[max_length = 66]
+        address -> Varchar,
+        module_name -> Text,
+        #[max_length = 66]
+        bytecode_hash -> Varchar,
+        transaction_version -> Int8,
+        write_set_change_index -> Int8,
+        transaction_block_height -> Int8,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     move_modules (transaction_version, write_set_change_index) {
         transaction_version -> Int8,
@@ -979,6 +1186,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    processor_safe_versions (set_name) {
+        #[max_length = 100]
+        set_name -> Varchar,
+        safe_version -> Int8,
+        safe_transaction_timestamp -> Nullable<Timestamp>,
+        #[max_length = 100]
+        lagging_processor -> Varchar,
+        last_updated -> Timestamp,
+    }
+}
+
 diesel::table! {
     processor_status (processor, processor) {
         #[max_length = 100]
@@ -1051,6 +1270,20 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    storage_usage_changes (transaction_version, address, resource_type) {
+        transaction_version -> Int8,
+        #[max_length = 66]
+        address -> Varchar,
+        #[max_length = 1000]
+        resource_type -> Varchar,
+        bytes_delta -> Int8,
+        items_delta -> Int8,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     table_items (transaction_version, write_set_change_index) {
         key -> Text,
@@ -1329,6 +1562,29 @@ diesel::table! {
         #[max_length = 255]
         entry_function_function_name -> Nullable<Varchar>,
         replay_protection_nonce -> Nullable<Numeric>,
+        total_charge_gas_units -> Nullable<Numeric>,
+        execution_gas_units -> Nullable<Numeric>,
+        io_gas_units -> Nullable<Numeric>,
+        storage_fee_octas -> Nullable<Numeric>,
+    }
+}
+
+diesel::table! {
+    validator_transactions (version) {
+        version -> Int8,
+        block_height -> Int8,
+        epoch -> Int8,
+        #[max_length = 50]
+        validator_transaction_type -> Varchar,
+        #[max_length = 1000]
+        jwk_issuer -> Nullable<Varchar>,
+        jwk_version -> Nullable<Int8>,
+        num_jwks -> Nullable<Int8>,
+        dkg_epoch -> Nullable<Int8>,
+        #[max_length = 66]
+        dkg_author -> Nullable<Varchar>,
+        timestamp -> Timestamp,
+        inserted_at -> Timestamp,
     }
 }
 
@@ -1358,6 +1614,7 @@ diesel::table! {
 }
 
 diesel::allow_tables_to_appear_in_same_query!(
+    account_key_rotations,
     account_transactions,
     ans_lookup,
     ans_lookup_v2,
@@ -1365,13 +1622,16 @@ diesel::allow_tables_to_appear_in_same_query!(
     ans_primary_name_v2,
     auth_key_account_addresses,
     backfill_processor_status,
+    block_epilogue_transactions,
     block_metadata_transactions,
+    chain_health_snapshots,
     coin_activities,
     coin_balances,
     coin_infos,
     coin_supply,
     collection_datas,
     collections_v2,
+    current_account_auth_profiles,
     current_ans_lookup,
     current_ans_lookup_v2,
     current_ans_primary_name,
@@ -1386,6 +1646,8 @@ diesel::allow_tables_to_appear_in_same_query!(
     current_fungible_asset_balances_legacy,
     current_objects,
     current_staking_pool_voter,
+    current_state_item_sizes,
+    current_storage_usage,
     current_table_items,
     current_token_datas,
     current_token_datas_v2,
@@ -1404,18 +1666,26 @@ diesel::allow_tables_to_appear_in_same_query!(
     fungible_asset_balances,
     fungible_asset_metadata,
     fungible_asset_to_coin_mappings,
+    gas_fee_block_summaries,
+    gas_fee_entry_function_hourly_summaries,
+    gas_fee_hourly_summaries,
     gas_fees,
     indexer_status,
     ledger_infos,
+    move_module_functions,
+    move_module_structs,
+    move_module_upgrades,
     move_modules,
     move_resources,
     nft_points,
     objects,
+    processor_safe_versions,
     processor_status,
     proposal_votes,
     public_key_auth_keys,
     signatures,
     spam_assets,
+    storage_usage_changes,
     table_items,
     table_metadatas,
     token_activities,
@@ -1428,6 +1698,99 @@ diesel::allow_tables_to_appear_in_same_query!(
     transaction_size_info,
     transactions,
     user_transactions,
+    validator_transactions,
     write_set_changes,
     write_set_size_info,
 );
+
+/// Names of all the tables above, kept next to `allow_tables_to_appear_in_same_query!` when the
+/// schema is regenerated.
+pub const TABLE_NAMES: [&str; 87] = [
+    "account_key_rotations",
+    "account_transactions",
+    "ans_lookup",
+    "ans_lookup_v2",
+    "ans_primary_name",
+    "ans_primary_name_v2",
+    "auth_key_account_addresses",
+    "backfill_processor_status",
+    "block_epilogue_transactions",
+    "block_metadata_transactions",
+    "chain_health_snapshots",
+    "coin_activities",
+    "coin_balances",
+    "coin_infos",
+    "coin_supply",
+    "collection_datas",
+    "collections_v2",
+    "current_account_auth_profiles",
+    "current_ans_lookup",
+    "current_ans_lookup_v2",
+    "current_ans_primary_name",
+    "current_ans_primary_name_v2",
+    "current_coin_balances",
+    "current_collection_datas",
+    "current_collections_v2",
+    "current_delegated_staking_pool_balances",
+    "current_delegated_voter",
+    "current_delegator_balances",
+    "current_fungible_asset_balances",
+    "current_fungible_asset_balances_legacy",
+    "current_objects",
+    "current_staking_pool_voter",
+    "current_state_item_sizes",
+    "current_storage_usage",
+    "current_table_items",
+    "current_token_datas",
+    "current_token_datas_v2",
+    "current_token_ownerships",
+    "current_token_ownerships_v2",
+    "current_token_pending_claims",
+    "current_token_royalty_v1",
+    "current_token_v2_metadata",
+    "delegated_staking_activities",
+    "delegated_staking_pool_balances",
+    "delegated_staking_pools",
+    "delegator_balances",
+    "event_size_info",
+    "events",
+    "fungible_asset_activities",
+    "fungible_asset_balances",
+    "fungible_asset_metadata",
+    "fungible_asset_to_coin_mappings",
+    "gas_fee_block_summaries",
+    "gas_fee_entry_function_hourly_summaries",
+    "gas_fee_hourly_summaries",
+    "gas_fees",
+    "indexer_status",
+    "ledger_infos",
+    "move_module_functions",
+    "move_module_structs",
+    "move_module_upgrades",
+    "move_modules",
+    "move_resources",
+    "nft_points",
+    "objects",
+    "processor_safe_versions",
+    "processor_status",
+    "proposal_votes",
+    "public_key_auth_keys",
+    "signatures",
+    "spam_assets",
+    "storage_usage_changes",
+    "table_items",
+    "table_metadatas",
+    "token_activities",
+    "token_activities_v2",
+    "token_datas",
+    "token_datas_v2",
+    "token_ownerships",
+    "token_ownerships_v2",
+    "tokens",
+    "transaction_size_info",
+    "transactions",
+    "user_transactions",
+    "validator_transactions",
+    "write_set_changes",
+    "write_set_size_info",
+];
@@ -1,38 +1,74 @@
 use crate::{
-    config::db_config::DbConfig,
+    config::{
+        db_config::{DbConfig, ParquetConfig},
+        indexer_processor_config::IndexerProcessorConfig,
+        processor_config::{ParquetDefaultProcessorConfig, ProcessorName},
+    },
     parquet_processors::{
-        parquet_transaction_metadata::transaction_metadata_models::write_set_size_info::ParquetWriteSetSize,
+        parquet_compaction::parquet_compaction_processor::{encode_compacted_files, CompactedFile},
+        parquet_loader::parquet_row_reader::read_parquet_rows,
+        parquet_processor_status_saver::ParquetProcessorStatusSaver,
+        parquet_transaction_metadata::transaction_metadata_models::{
+            event_size_info::ParquetEventSize, write_set_size_info::ParquetWriteSetSize,
+        },
         parquet_utils::{
-            gcs_uploader::{create_new_writer, GCSUploader},
+            gcs_uploader::GCSUploader,
+            object_store::GcsObjectStore,
             parquet_buffer_step::ParquetBufferStep,
+            parquet_spill_store::ParquetSpillStore,
+            parquet_upload_config::build_upload_schedules,
+            parquet_version_tracker_step::{
+                ParquetProcessorStatusSaverTrait, ParquetVersionTrackerStep,
+            },
+            parquet_writer_config::build_writer_properties,
+            table_commit_log::TableCommitLog,
+            util::{HasParquetSchema, HasTimestamp, HasVersion, NamedTable},
         },
     },
     processors::{
+        account_restoration::account_restoration_models::{
+            auth_key_account_addresses::ParquetAuthKeyAccountAddress,
+            public_key_auth_keys::ParquetPublicKeyAuthKey,
+        },
         account_transactions::account_transactions_model::ParquetAccountTransaction,
         ans::models::{
             ans_lookup_v2::{ParquetAnsLookupV2, ParquetCurrentAnsLookupV2},
             ans_primary_name_v2::{ParquetAnsPrimaryNameV2, ParquetCurrentAnsPrimaryNameV2},
         },
         default::models::{
+            block_epilogue_transactions::ParquetBlockEpilogueTransaction,
             block_metadata_transactions::ParquetBlockMetadataTransaction,
             move_modules::ParquetMoveModule,
             move_resources::ParquetMoveResource,
             table_items::{ParquetCurrentTableItem, ParquetTableItem, ParquetTableMetadata},
             transactions::ParquetTransaction,
+            validator_transactions::ParquetValidatorTransaction,
             write_set_changes::ParquetWriteSetChange,
         },
         events::events_model::ParquetEvent,
         fungible_asset::fungible_asset_models::{
             v2_fungible_asset_activities::ParquetFungibleAssetActivity,
-            v2_fungible_asset_balances::ParquetFungibleAssetBalance,
+            v2_fungible_asset_balances::{
+                ParquetCurrentUnifiedFungibleAssetBalance, ParquetFungibleAssetBalance,
+            },
             v2_fungible_asset_to_coin_mappings::ParquetFungibleAssetToCoinMapping,
             v2_fungible_metadata::ParquetFungibleAssetMetadataModel,
         },
+        gas_fees::{
+            gas_fee_rollups::{
+                ParquetGasFeeBlockSummary, ParquetGasFeeEntryFunctionHourlySummary,
+                ParquetGasFeeHourlySummary,
+            },
+            models::ParquetGasFee,
+        },
         objects::v2_objects_models::{ParquetCurrentObject, ParquetObject},
         stake::models::{
+            current_delegated_voter::ParquetCurrentDelegatedVoter,
             delegator_activities::ParquetDelegatedStakingActivity,
             delegator_balances::{ParquetCurrentDelegatorBalance, ParquetDelegatorBalance},
+            delegator_pools::ParquetDelegatorPool,
             proposal_votes::ParquetProposalVote,
+            staking_pool_voter::ParquetCurrentStakingPoolVoter,
         },
         token_v2::{
             token_models::{
@@ -40,7 +76,7 @@ use crate::{
                 token_royalty::ParquetCurrentTokenRoyaltyV1,
             },
             token_v2_models::{
-                v2_collections::ParquetCollectionV2,
+                v2_collections::{ParquetCollectionV2, ParquetCurrentCollectionV2},
                 v2_token_activities::ParquetTokenActivityV2,
                 v2_token_datas::{ParquetCurrentTokenDataV2, ParquetTokenDataV2},
                 v2_token_metadata::ParquetCurrentTokenV2Metadata,
@@ -54,13 +90,17 @@ use crate::{
     utils::table_flags::TableFlags,
 };
 use aptos_indexer_processor_sdk::{
+    common_steps::DEFAULT_UPDATE_PROCESSOR_STATUS_SECS,
     postgres::utils::database::{new_db_pool, ArcDbPool},
     utils::errors::ProcessorError,
 };
-use async_trait::async_trait;
 use enum_dispatch::enum_dispatch;
 use google_cloud_storage::client::{Client as GCSClient, ClientConfig as GcsClientConfig};
-use parquet::schema::types::Type;
+use parquet::{
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    record::RecordWriter,
+    schema::types::Type,
+};
 #[allow(unused_imports)]
 use serde::{Deserialize, Serialize};
 use std::{
@@ -68,13 +108,17 @@ use std::{
     sync::Arc,
     time::Duration,
 };
-use strum::{Display, EnumIter};
+use strum::{Display, EnumIter, IntoEnumIterator};
 
+pub mod parquet_account_restoration;
 pub mod parquet_account_transactions;
 pub mod parquet_ans;
+pub mod parquet_compaction;
 pub mod parquet_default;
 pub mod parquet_events;
 pub mod parquet_fungible_asset;
+pub mod parquet_gas_fee;
+pub mod parquet_loader;
 pub mod parquet_objects;
 pub mod parquet_processor_status_saver;
 pub mod parquet_stake;
@@ -116,6 +160,8 @@ pub enum ParquetTypeEnum {
     CurrentTableItems,
     BlockMetadataTransactions,
     TableMetadata,
+    BlockEpilogueTransactions,
+    ValidatorTransactions,
     // events
     Events,
     // user transactions
@@ -131,8 +177,10 @@ pub enum ParquetTypeEnum {
     FungibleAssetMetadata,
     FungibleAssetBalances,
     FungibleAssetToCoinMappings,
+    CurrentFungibleAssetBalances,
     // txn metadata,
     WriteSetSize,
+    EventSize,
     // account transactions
     AccountTransactions,
     // token v2
@@ -145,53 +193,191 @@ pub enum ParquetTypeEnum {
     TokenOwnershipsV2,
     CurrentTokenOwnershipsV2,
     CollectionsV2,
+    CurrentCollectionsV2,
     // stake
     DelegatedStakingActivities,
     CurrentDelegatorBalances,
     DelegatorBalances,
     ProposalVotes,
+    DelegatedStakingPools,
+    CurrentDelegatedVoter,
+    CurrentStakingPoolVoter,
     // Objects
     Objects,
     CurrentObjects,
+    // gas fees
+    GasFees,
+    GasFeeBlockSummaries,
+    GasFeeHourlySummaries,
+    GasFeeEntryFunctionHourlySummaries,
+    // account restoration
+    AuthKeyAccountAddresses,
+    PublicKeyAuthKeys,
+}
+
+impl ParquetTypeEnum {
+    /// The parquet processor that writes the table. The match is exhaustive on purpose so that a
+    /// new table can't be added without registering it with a processor, which in turn makes it
+    /// part of `ProcessorConfig::table_names` and `VALID_TABLE_NAMES`.
+    pub const fn processor_name(&self) -> ProcessorName {
+        match self {
+            ParquetTypeEnum::MoveResources
+            | ParquetTypeEnum::WriteSetChanges
+            | ParquetTypeEnum::Transactions
+            | ParquetTypeEnum::TableItems
+            | ParquetTypeEnum::MoveModules
+            | ParquetTypeEnum::CurrentTableItems
+            | ParquetTypeEnum::BlockMetadataTransactions
+            | ParquetTypeEnum::TableMetadata
+            | ParquetTypeEnum::BlockEpilogueTransactions
+            | ParquetTypeEnum::ValidatorTransactions => ProcessorName::ParquetDefaultProcessor,
+            ParquetTypeEnum::Events => ProcessorName::ParquetEventsProcessor,
+            ParquetTypeEnum::UserTransactions | ParquetTypeEnum::Signatures => {
+                ProcessorName::ParquetUserTransactionProcessor
+            },
+            ParquetTypeEnum::AnsPrimaryNameV2
+            | ParquetTypeEnum::CurrentAnsPrimaryNameV2
+            | ParquetTypeEnum::AnsLookupV2
+            | ParquetTypeEnum::CurrentAnsLookupV2 => ProcessorName::ParquetAnsProcessor,
+            ParquetTypeEnum::FungibleAssetActivities
+            | ParquetTypeEnum::FungibleAssetMetadata
+            | ParquetTypeEnum::FungibleAssetBalances
+            | ParquetTypeEnum::FungibleAssetToCoinMappings
+            | ParquetTypeEnum::CurrentFungibleAssetBalances => {
+                ProcessorName::ParquetFungibleAssetProcessor
+            },
+            ParquetTypeEnum::WriteSetSize | ParquetTypeEnum::EventSize => {
+                ProcessorName::ParquetTransactionMetadataProcessor
+            },
+            ParquetTypeEnum::AccountTransactions => {
+                ProcessorName::ParquetAccountTransactionsProcessor
+            },
+            ParquetTypeEnum::CurrentTokenPendingClaims
+            | ParquetTypeEnum::CurrentTokenRoyaltiesV1
+            | ParquetTypeEnum::CurrentTokenV2Metadata
+            | ParquetTypeEnum::TokenActivitiesV2
+            | ParquetTypeEnum::TokenDatasV2
+            | ParquetTypeEnum::CurrentTokenDatasV2
+            | ParquetTypeEnum::TokenOwnershipsV2
+            | ParquetTypeEnum::CurrentTokenOwnershipsV2
+            | ParquetTypeEnum::CollectionsV2
+            | ParquetTypeEnum::CurrentCollectionsV2 => ProcessorName::ParquetTokenV2Processor,
+            ParquetTypeEnum::DelegatedStakingActivities
+            | ParquetTypeEnum::CurrentDelegatorBalances
+            | ParquetTypeEnum::DelegatorBalances
+            | ParquetTypeEnum::ProposalVotes
+            | ParquetTypeEnum::DelegatedStakingPools
+            | ParquetTypeEnum::CurrentDelegatedVoter
+            | ParquetTypeEnum::CurrentStakingPoolVoter => ProcessorName::ParquetStakeProcessor,
+            ParquetTypeEnum::Objects | ParquetTypeEnum::CurrentObjects => {
+                ProcessorName::ParquetObjectsProcessor
+            },
+            ParquetTypeEnum::GasFees
+            | ParquetTypeEnum::GasFeeBlockSummaries
+            | ParquetTypeEnum::GasFeeHourlySummaries
+            | ParquetTypeEnum::GasFeeEntryFunctionHourlySummaries => {
+                ProcessorName::ParquetGasFeeProcessor
+            },
+            ParquetTypeEnum::AuthKeyAccountAddresses | ParquetTypeEnum::PublicKeyAuthKeys => {
+                ProcessorName::ParquetAccountRestorationProcessor
+            },
+        }
+    }
 }
 
 /// Trait for handling various Parquet types.
-#[async_trait]
 #[enum_dispatch]
 pub trait ParquetTypeTrait: std::fmt::Debug + Send + Sync {
     fn parquet_type(&self) -> ParquetTypeEnum;
+    fn table_name(&self) -> &'static str;
+    fn parquet_schema(&self) -> Arc<Type>;
     fn calculate_size(&self) -> usize;
+    fn num_rows(&self) -> usize;
+
+    /// Serializes the rows into a standalone parquet file with a single row group.
+    fn to_parquet_bytes(&self, props: Arc<WriterProperties>) -> anyhow::Result<Vec<u8>>;
 
-    async fn upload_to_gcs(
-        &self,
-        uploader: &mut GCSUploader,
-        parquet_type: ParquetTypeEnum,
-        table_name: &str,
-    ) -> anyhow::Result<()>;
+    /// Encodes the rows into full row groups of the table's open files in the uploader and
+    /// removes them from the buffer. Rows that don't fill a row group are kept unless `flush` is set.
+    fn write_row_groups(&mut self, uploader: &mut GCSUploader, flush: bool) -> anyhow::Result<()>;
+
+    /// Appends the rows of a parquet file written for this table.
+    fn extend_from_parquet(&mut self, data: Vec<u8>) -> anyhow::Result<()>;
+
+    /// Lowest and highest transaction version of the rows.
+    fn version_range(&self) -> Option<(i64, i64)>;
+
+    /// Sorts the rows by version and encodes them into files of about `target_file_size` bytes.
+    /// With `partition_by_date`, a file only holds rows of a single date.
+    fn to_compacted_files(
+        &mut self,
+        props: Arc<WriterProperties>,
+        target_file_size: usize,
+        partition_by_date: bool,
+    ) -> anyhow::Result<Vec<CompactedFile>>;
 }
 
 /// Macro for implementing ParquetTypeTrait for multiple types.
 macro_rules! impl_parquet_trait {
     ($type:ty, $enum_variant:expr) => {
-        #[async_trait]
         impl ParquetTypeTrait for Vec<$type> {
             fn parquet_type(&self) -> ParquetTypeEnum {
                 $enum_variant
             }
 
+            fn table_name(&self) -> &'static str {
+                <$type>::TABLE_NAME
+            }
+
+            fn parquet_schema(&self) -> Arc<Type> {
+                <$type>::schema()
+            }
+
             fn calculate_size(&self) -> usize {
                 allocative::size_of_unique(self)
             }
 
-            async fn upload_to_gcs(
-                &self,
+            fn num_rows(&self) -> usize {
+                self.len()
+            }
+
+            fn to_parquet_bytes(&self, props: Arc<WriterProperties>) -> anyhow::Result<Vec<u8>> {
+                let mut writer = SerializedFileWriter::new(Vec::new(), <$type>::schema(), props)?;
+                // An empty buffer is encoded as a file without row groups
+                if !self.is_empty() {
+                    let mut row_group_writer = writer.next_row_group()?;
+                    self.as_slice().write_to_row_group(&mut row_group_writer)?;
+                    row_group_writer.close()?;
+                }
+                Ok(writer.into_inner()?)
+            }
+
+            fn write_row_groups(
+                &mut self,
                 uploader: &mut GCSUploader,
-                parquet_type: ParquetTypeEnum,
-                table_name: &str,
+                flush: bool,
             ) -> anyhow::Result<()> {
-                uploader
-                    .upload_generic(self, parquet_type, table_name)
-                    .await
+                uploader.write_row_groups(self, $enum_variant, flush)
+            }
+
+            fn extend_from_parquet(&mut self, data: Vec<u8>) -> anyhow::Result<()> {
+                self.extend(read_parquet_rows::<$type>(data, $enum_variant)?);
+                Ok(())
+            }
+
+            fn version_range(&self) -> Option<(i64, i64)> {
+                let min_version = self.iter().map(HasVersion::version).min()?;
+                let max_version = self.iter().map(HasVersion::version).max()?;
+                Some((min_version, max_version))
+            }
+
+            fn to_compacted_files(
+                &mut self,
+                props: Arc<WriterProperties>,
+                target_file_size: usize,
+                partition_by_date: bool,
+            ) -> anyhow::Result<Vec<CompactedFile>> {
+                encode_compacted_files(self, props, target_file_size, partition_by_date)
             }
         }
     };
@@ -209,6 +395,14 @@ impl_parquet_trait!(
     ParquetTypeEnum::BlockMetadataTransactions
 );
 impl_parquet_trait!(ParquetTableMetadata, ParquetTypeEnum::TableMetadata);
+impl_parquet_trait!(
+    ParquetBlockEpilogueTransaction,
+    ParquetTypeEnum::BlockEpilogueTransactions
+);
+impl_parquet_trait!(
+    ParquetValidatorTransaction,
+    ParquetTypeEnum::ValidatorTransactions
+);
 impl_parquet_trait!(ParquetEvent, ParquetTypeEnum::Events);
 impl_parquet_trait!(ParquetUserTransaction, ParquetTypeEnum::UserTransactions);
 impl_parquet_trait!(ParquetSignature, ParquetTypeEnum::Signatures);
@@ -280,6 +474,111 @@ impl_parquet_trait!(ParquetProposalVote, ParquetTypeEnum::ProposalVotes);
 impl_parquet_trait!(ParquetObject, ParquetTypeEnum::Objects);
 impl_parquet_trait!(ParquetCurrentObject, ParquetTypeEnum::CurrentObjects);
 impl_parquet_trait!(ParquetCollectionV2, ParquetTypeEnum::CollectionsV2);
+impl_parquet_trait!(
+    ParquetCurrentCollectionV2,
+    ParquetTypeEnum::CurrentCollectionsV2
+);
+impl_parquet_trait!(
+    ParquetCurrentUnifiedFungibleAssetBalance,
+    ParquetTypeEnum::CurrentFungibleAssetBalances
+);
+impl_parquet_trait!(ParquetEventSize, ParquetTypeEnum::EventSize);
+impl_parquet_trait!(ParquetDelegatorPool, ParquetTypeEnum::DelegatedStakingPools);
+impl_parquet_trait!(
+    ParquetCurrentDelegatedVoter,
+    ParquetTypeEnum::CurrentDelegatedVoter
+);
+impl_parquet_trait!(
+    ParquetCurrentStakingPoolVoter,
+    ParquetTypeEnum::CurrentStakingPoolVoter
+);
+impl_parquet_trait!(ParquetGasFee, ParquetTypeEnum::GasFees);
+impl_parquet_trait!(
+    ParquetGasFeeBlockSummary,
+    ParquetTypeEnum::GasFeeBlockSummaries
+);
+impl_parquet_trait!(
+    ParquetGasFeeHourlySummary,
+    ParquetTypeEnum::GasFeeHourlySummaries
+);
+impl_parquet_trait!(
+    ParquetGasFeeEntryFunctionHourlySummary,
+    ParquetTypeEnum::GasFeeEntryFunctionHourlySummaries
+);
+impl_parquet_trait!(
+    ParquetAuthKeyAccountAddress,
+    ParquetTypeEnum::AuthKeyAccountAddresses
+);
+impl_parquet_trait!(ParquetPublicKeyAuthKey, ParquetTypeEnum::PublicKeyAuthKeys);
+
+/// Macro for implementing HasTimestamp from a timestamp column. Types listed without a column
+/// have no timestamp.
+macro_rules! impl_has_timestamp {
+    ($type:ty, $field:ident) => {
+        impl HasTimestamp for $type {
+            fn timestamp(&self) -> Option<chrono::NaiveDateTime> {
+                Some(self.$field)
+            }
+        }
+    };
+    ($type:ty) => {
+        impl HasTimestamp for $type {}
+    };
+}
+
+// ParquetCurrentUnifiedFungibleAssetBalance implements HasTimestamp itself
+impl_has_timestamp!(ParquetMoveResource, block_timestamp);
+impl_has_timestamp!(ParquetWriteSetChange, block_timestamp);
+impl_has_timestamp!(ParquetTransaction, block_timestamp);
+impl_has_timestamp!(ParquetTableItem, block_timestamp);
+impl_has_timestamp!(ParquetMoveModule, block_timestamp);
+impl_has_timestamp!(ParquetCurrentTableItem, block_timestamp);
+impl_has_timestamp!(ParquetBlockMetadataTransaction, block_timestamp);
+impl_has_timestamp!(ParquetTableMetadata);
+impl_has_timestamp!(ParquetBlockEpilogueTransaction, block_timestamp);
+impl_has_timestamp!(ParquetValidatorTransaction, block_timestamp);
+impl_has_timestamp!(ParquetEvent, block_timestamp);
+impl_has_timestamp!(ParquetUserTransaction, block_timestamp);
+impl_has_timestamp!(ParquetSignature, block_timestamp);
+impl_has_timestamp!(ParquetAnsPrimaryNameV2, block_timestamp);
+impl_has_timestamp!(ParquetCurrentAnsPrimaryNameV2);
+impl_has_timestamp!(ParquetAnsLookupV2, block_timestamp);
+impl_has_timestamp!(ParquetCurrentAnsLookupV2);
+impl_has_timestamp!(ParquetFungibleAssetActivity, block_timestamp);
+impl_has_timestamp!(
+    ParquetFungibleAssetMetadataModel,
+    last_transaction_timestamp
+);
+impl_has_timestamp!(ParquetFungibleAssetBalance, block_timestamp);
+impl_has_timestamp!(ParquetFungibleAssetToCoinMapping);
+impl_has_timestamp!(ParquetWriteSetSize, block_timestamp);
+impl_has_timestamp!(ParquetAccountTransaction, block_timestamp);
+impl_has_timestamp!(ParquetCurrentTokenPendingClaim, last_transaction_timestamp);
+impl_has_timestamp!(ParquetCurrentTokenRoyaltyV1, last_transaction_timestamp);
+impl_has_timestamp!(ParquetCurrentTokenV2Metadata, last_transaction_timestamp);
+impl_has_timestamp!(ParquetTokenActivityV2, block_timestamp);
+impl_has_timestamp!(ParquetTokenDataV2, block_timestamp);
+impl_has_timestamp!(ParquetCurrentTokenDataV2, last_transaction_timestamp);
+impl_has_timestamp!(ParquetTokenOwnershipV2, block_timestamp);
+impl_has_timestamp!(ParquetCurrentTokenOwnershipV2, last_transaction_timestamp);
+impl_has_timestamp!(ParquetDelegatedStakingActivity, block_timestamp);
+impl_has_timestamp!(ParquetCurrentDelegatorBalance, block_timestamp);
+impl_has_timestamp!(ParquetDelegatorBalance, block_timestamp);
+impl_has_timestamp!(ParquetProposalVote, transaction_timestamp);
+impl_has_timestamp!(ParquetObject, block_timestamp);
+impl_has_timestamp!(ParquetCurrentObject, block_timestamp);
+impl_has_timestamp!(ParquetCollectionV2, block_timestamp);
+impl_has_timestamp!(ParquetCurrentCollectionV2, last_transaction_timestamp);
+impl_has_timestamp!(ParquetEventSize, block_timestamp);
+impl_has_timestamp!(ParquetDelegatorPool);
+impl_has_timestamp!(ParquetCurrentDelegatedVoter, last_transaction_timestamp);
+impl_has_timestamp!(ParquetCurrentStakingPoolVoter, block_timestamp);
+impl_has_timestamp!(ParquetGasFee, block_timestamp);
+impl_has_timestamp!(ParquetGasFeeBlockSummary, block_timestamp);
+impl_has_timestamp!(ParquetGasFeeHourlySummary, bucket_start);
+impl_has_timestamp!(ParquetGasFeeEntryFunctionHourlySummary, bucket_start);
+impl_has_timestamp!(ParquetAuthKeyAccountAddress);
+impl_has_timestamp!(ParquetPublicKeyAuthKey);
 
 #[derive(Debug, Clone)]
 #[enum_dispatch(ParquetTypeTrait)]
@@ -293,6 +592,8 @@ pub enum ParquetTypeStructs {
     CurrentTableItem(Vec<ParquetCurrentTableItem>),
     BlockMetadataTransaction(Vec<ParquetBlockMetadataTransaction>),
     TableMetadata(Vec<ParquetTableMetadata>),
+    BlockEpilogueTransaction(Vec<ParquetBlockEpilogueTransaction>),
+    ValidatorTransaction(Vec<ParquetValidatorTransaction>),
     // User txn
     UserTransaction(Vec<ParquetUserTransaction>),
     Signature(Vec<ParquetSignature>),
@@ -308,8 +609,10 @@ pub enum ParquetTypeStructs {
     FungibleAssetMetadata(Vec<ParquetFungibleAssetMetadataModel>),
     FungibleAssetBalance(Vec<ParquetFungibleAssetBalance>),
     FungibleAssetToCoinMappings(Vec<ParquetFungibleAssetToCoinMapping>),
+    CurrentFungibleAssetBalance(Vec<ParquetCurrentUnifiedFungibleAssetBalance>),
     // Txn metadata
     WriteSetSize(Vec<ParquetWriteSetSize>),
+    EventSize(Vec<ParquetEventSize>),
     // account txn
     AccountTransaction(Vec<ParquetAccountTransaction>),
     // Token V2
@@ -322,14 +625,26 @@ pub enum ParquetTypeStructs {
     TokenOwnershipV2(Vec<ParquetTokenOwnershipV2>),
     CurrentTokenOwnershipV2(Vec<ParquetCurrentTokenOwnershipV2>),
     CollectionV2(Vec<ParquetCollectionV2>),
+    CurrentCollectionV2(Vec<ParquetCurrentCollectionV2>),
     // Stake
     DelegatedStakingActivity(Vec<ParquetDelegatedStakingActivity>),
     CurrentDelegatorBalance(Vec<ParquetCurrentDelegatorBalance>),
     DelegatorBalance(Vec<ParquetDelegatorBalance>),
     ProposalVote(Vec<ParquetProposalVote>),
+    DelegatorPool(Vec<ParquetDelegatorPool>),
+    CurrentDelegatedVoter(Vec<ParquetCurrentDelegatedVoter>),
+    CurrentStakingPoolVoter(Vec<ParquetCurrentStakingPoolVoter>),
     // Objects
     Object(Vec<ParquetObject>),
     CurrentObject(Vec<ParquetCurrentObject>),
+    // Gas fees
+    GasFee(Vec<ParquetGasFee>),
+    GasFeeBlockSummary(Vec<ParquetGasFeeBlockSummary>),
+    GasFeeHourlySummary(Vec<ParquetGasFeeHourlySummary>),
+    GasFeeEntryFunctionHourlySummary(Vec<ParquetGasFeeEntryFunctionHourlySummary>),
+    // Account restoration
+    AuthKeyAccountAddress(Vec<ParquetAuthKeyAccountAddress>),
+    PublicKeyAuthKey(Vec<ParquetPublicKeyAuthKey>),
 }
 
 impl ParquetTypeStructs {
@@ -345,6 +660,12 @@ impl ParquetTypeStructs {
                 ParquetTypeStructs::BlockMetadataTransaction(Vec::new())
             },
             ParquetTypeEnum::TableMetadata => ParquetTypeStructs::TableMetadata(Vec::new()),
+            ParquetTypeEnum::BlockEpilogueTransactions => {
+                ParquetTypeStructs::BlockEpilogueTransaction(Vec::new())
+            },
+            ParquetTypeEnum::ValidatorTransactions => {
+                ParquetTypeStructs::ValidatorTransaction(Vec::new())
+            },
             ParquetTypeEnum::UserTransactions => ParquetTypeStructs::UserTransaction(Vec::new()),
             ParquetTypeEnum::Signatures => ParquetTypeStructs::Signature(Vec::new()),
             ParquetTypeEnum::Events => ParquetTypeStructs::Event(Vec::new()),
@@ -401,6 +722,34 @@ impl ParquetTypeStructs {
             ParquetTypeEnum::Objects => ParquetTypeStructs::Object(Vec::new()),
             ParquetTypeEnum::CurrentObjects => ParquetTypeStructs::CurrentObject(Vec::new()),
             ParquetTypeEnum::CollectionsV2 => ParquetTypeStructs::CollectionV2(Vec::new()),
+            ParquetTypeEnum::CurrentCollectionsV2 => {
+                ParquetTypeStructs::CurrentCollectionV2(Vec::new())
+            },
+            ParquetTypeEnum::CurrentFungibleAssetBalances => {
+                ParquetTypeStructs::CurrentFungibleAssetBalance(Vec::new())
+            },
+            ParquetTypeEnum::EventSize => ParquetTypeStructs::EventSize(Vec::new()),
+            ParquetTypeEnum::DelegatedStakingPools => ParquetTypeStructs::DelegatorPool(Vec::new()),
+            ParquetTypeEnum::CurrentDelegatedVoter => {
+                ParquetTypeStructs::CurrentDelegatedVoter(Vec::new())
+            },
+            ParquetTypeEnum::CurrentStakingPoolVoter => {
+                ParquetTypeStructs::CurrentStakingPoolVoter(Vec::new())
+            },
+            ParquetTypeEnum::GasFees => ParquetTypeStructs::GasFee(Vec::new()),
+            ParquetTypeEnum::GasFeeBlockSummaries => {
+                ParquetTypeStructs::GasFeeBlockSummary(Vec::new())
+            },
+            ParquetTypeEnum::GasFeeHourlySummaries => {
+                ParquetTypeStructs::GasFeeHourlySummary(Vec::new())
+            },
+            ParquetTypeEnum::GasFeeEntryFunctionHourlySummaries => {
+                ParquetTypeStructs::GasFeeEntryFunctionHourlySummary(Vec::new())
+            },
+            ParquetTypeEnum::AuthKeyAccountAddresses => {
+                ParquetTypeStructs::AuthKeyAccountAddress(Vec::new())
+            },
+            ParquetTypeEnum::PublicKeyAuthKeys => ParquetTypeStructs::PublicKeyAuthKey(Vec::new()),
         }
     }
 
@@ -465,6 +814,18 @@ impl ParquetTypeStructs {
             ) => {
                 handle_append!(self_data, other_data)
             },
+            (
+                ParquetTypeStructs::BlockEpilogueTransaction(self_data),
+                ParquetTypeStructs::BlockEpilogueTransaction(other_data),
+            ) => {
+                handle_append!(self_data, other_data)
+            },
+            (
+                ParquetTypeStructs::ValidatorTransaction(self_data),
+                ParquetTypeStructs::ValidatorTransaction(other_data),
+            ) => {
+                handle_append!(self_data, other_data)
+            },
             (
                 ParquetTypeStructs::UserTransaction(self_data),
                 ParquetTypeStructs::UserTransaction(other_data),
@@ -625,6 +986,75 @@ impl ParquetTypeStructs {
             ) => {
                 handle_append!(self_data, other_data)
             },
+            (
+                ParquetTypeStructs::CurrentCollectionV2(self_data),
+                ParquetTypeStructs::CurrentCollectionV2(other_data),
+            ) => {
+                handle_append!(self_data, other_data)
+            },
+            (
+                ParquetTypeStructs::CurrentFungibleAssetBalance(self_data),
+                ParquetTypeStructs::CurrentFungibleAssetBalance(other_data),
+            ) => {
+                handle_append!(self_data, other_data)
+            },
+            (
+                ParquetTypeStructs::EventSize(self_data),
+                ParquetTypeStructs::EventSize(other_data),
+            ) => {
+                handle_append!(self_data, other_data)
+            },
+            (
+                ParquetTypeStructs::DelegatorPool(self_data),
+                ParquetTypeStructs::DelegatorPool(other_data),
+            ) => {
+                handle_append!(self_data, other_data)
+            },
+            (
+                ParquetTypeStructs::CurrentDelegatedVoter(self_data),
+                ParquetTypeStructs::CurrentDelegatedVoter(other_data),
+            ) => {
+                handle_append!(self_data, other_data)
+            },
+            (
+                ParquetTypeStructs::CurrentStakingPoolVoter(self_data),
+                ParquetTypeStructs::CurrentStakingPoolVoter(other_data),
+            ) => {
+                handle_append!(self_data, other_data)
+            },
+            (ParquetTypeStructs::GasFee(self_data), ParquetTypeStructs::GasFee(other_data)) => {
+                handle_append!(self_data, other_data)
+            },
+            (
+                ParquetTypeStructs::GasFeeBlockSummary(self_data),
+                ParquetTypeStructs::GasFeeBlockSummary(other_data),
+            ) => {
+                handle_append!(self_data, other_data)
+            },
+            (
+                ParquetTypeStructs::GasFeeHourlySummary(self_data),
+                ParquetTypeStructs::GasFeeHourlySummary(other_data),
+            ) => {
+                handle_append!(self_data, other_data)
+            },
+            (
+                ParquetTypeStructs::GasFeeEntryFunctionHourlySummary(self_data),
+                ParquetTypeStructs::GasFeeEntryFunctionHourlySummary(other_data),
+            ) => {
+                handle_append!(self_data, other_data)
+            },
+            (
+                ParquetTypeStructs::AuthKeyAccountAddress(self_data),
+                ParquetTypeStructs::AuthKeyAccountAddress(other_data),
+            ) => {
+                handle_append!(self_data, other_data)
+            },
+            (
+                ParquetTypeStructs::PublicKeyAuthKey(self_data),
+                ParquetTypeStructs::PublicKeyAuthKey(other_data),
+            ) => {
+                handle_append!(self_data, other_data)
+            },
             _ => Err(ProcessorError::ProcessError {
                 message: "Mismatched buffer types in append operation".to_string(),
             }),
@@ -671,38 +1101,181 @@ async fn initialize_database_pool(config: &DbConfig) -> anyhow::Result<ArcDbPool
 async fn initialize_parquet_buffer_step(
     gcs_client: Arc<GCSClient>,
     parquet_type_to_schemas: HashMap<ParquetTypeEnum, Arc<Type>>,
-    upload_interval: u64,
-    max_buffer_size: usize,
+    parquet_processor_config: &ParquetDefaultProcessorConfig,
     bucket_name: String,
     bucket_root: String,
     processor_name: String,
+    spill_store: Option<ParquetSpillStore>,
 ) -> anyhow::Result<ParquetBufferStep> {
-    let parquet_type_to_writer = parquet_type_to_schemas
-        .iter()
-        .map(|(key, schema)| {
-            let writer = create_new_writer(schema.clone()).expect("Failed to create writer");
-            (*key, writer)
-        })
-        .collect();
+    let parquet_type_to_properties = build_writer_properties(
+        &parquet_processor_config.writer_properties,
+        &parquet_type_to_schemas,
+        parquet_processor_config.max_row_group_size,
+    )?;
+    let upload_schedules =
+        build_upload_schedules(parquet_processor_config, parquet_type_to_schemas.keys())?;
 
+    let commit_log = initialize_table_commit_log(
+        parquet_processor_config,
+        gcs_client.clone(),
+        &bucket_name,
+        &bucket_root,
+        &processor_name,
+    );
     let buffer_uploader = GCSUploader::new(
         gcs_client,
         parquet_type_to_schemas,
-        parquet_type_to_writer,
         bucket_name,
         bucket_root,
         processor_name,
-    )?;
+    )?
+    .with_writer_properties(parquet_type_to_properties)
+    .with_file_layout(parquet_processor_config.file_layout)
+    .with_commit_log(commit_log);
 
     let default_size_buffer_step = ParquetBufferStep::new(
-        Duration::from_secs(upload_interval),
+        Duration::from_secs(parquet_processor_config.upload_interval),
         buffer_uploader,
-        max_buffer_size,
-    );
+        parquet_processor_config.target_file_size,
+    )
+    .with_upload_schedules(upload_schedules)
+    .with_spill_store(spill_store);
 
     Ok(default_size_buffer_step)
 }
 
+/// Initializes the spill store when a spill directory is configured and recovers the data spilled
+/// by a previous run. This must happen before the starting version is read from the checkpoints.
+async fn initialize_spill_store<S: ParquetProcessorStatusSaverTrait>(
+    parquet_processor_config: &ParquetDefaultProcessorConfig,
+    gcs_client: &Arc<GCSClient>,
+    parquet_db_config: &ParquetConfig,
+    processor_name: &str,
+    processor_status_saver: &S,
+) -> anyhow::Result<Option<ParquetSpillStore>> {
+    let Some(spill_dir) = &parquet_processor_config.spill_dir else {
+        return Ok(None);
+    };
+    let spill_store = ParquetSpillStore::new(spill_dir, processor_name)?;
+    // The spilled tables are only known once the spill directory is read
+    let parquet_type_to_schemas: HashMap<ParquetTypeEnum, Arc<Type>> = ParquetTypeEnum::iter()
+        .map(|parquet_type| {
+            let schema = ParquetTypeStructs::default_for_type(&parquet_type).parquet_schema();
+            (parquet_type, schema)
+        })
+        .collect();
+    let parquet_type_to_properties = build_writer_properties(
+        &parquet_processor_config.writer_properties,
+        &parquet_type_to_schemas,
+        parquet_processor_config.max_row_group_size,
+    )?;
+    let commit_log = initialize_table_commit_log(
+        parquet_processor_config,
+        gcs_client.clone(),
+        &parquet_db_config.bucket_name,
+        &parquet_db_config.bucket_root,
+        processor_name,
+    );
+    let mut uploader = GCSUploader::new(
+        gcs_client.clone(),
+        parquet_type_to_schemas,
+        parquet_db_config.bucket_name.clone(),
+        parquet_db_config.bucket_root.clone(),
+        processor_name.to_string(),
+    )?
+    .with_writer_properties(parquet_type_to_properties)
+    .with_file_layout(parquet_processor_config.file_layout)
+    .with_commit_log(commit_log);
+    spill_store
+        .recover(&mut uploader, processor_status_saver)
+        .await?;
+    Ok(Some(spill_store))
+}
+
+/// Builds the steps every parquet processor runs after its extractor: the buffer step uploading
+/// the tables and the version tracker checkpointing them, both sharing the spill store.
+pub struct ParquetStepsBuilder {
+    config: IndexerProcessorConfig,
+    db_pool: ArcDbPool,
+    gcs_client: Arc<GCSClient>,
+    parquet_db_config: ParquetConfig,
+    parquet_processor_config: ParquetDefaultProcessorConfig,
+    spill_store: Option<ParquetSpillStore>,
+}
+
+impl ParquetStepsBuilder {
+    /// Recovers the data spilled by a previous run, so it must be created before the starting
+    /// version is read from the checkpoints.
+    pub async fn new(
+        config: &IndexerProcessorConfig,
+        db_pool: ArcDbPool,
+        parquet_db_config: &ParquetConfig,
+        parquet_processor_config: &ParquetDefaultProcessorConfig,
+    ) -> anyhow::Result<Self> {
+        let gcs_client =
+            initialize_gcs_client(parquet_db_config.google_application_credentials.clone()).await;
+        let spill_store = initialize_spill_store(
+            parquet_processor_config,
+            &gcs_client,
+            parquet_db_config,
+            config.processor_config.name(),
+            &ParquetProcessorStatusSaver::new(config.clone(), db_pool.clone()),
+        )
+        .await?;
+        Ok(Self {
+            config: config.clone(),
+            db_pool,
+            gcs_client,
+            parquet_db_config: parquet_db_config.clone(),
+            parquet_processor_config: parquet_processor_config.clone(),
+            spill_store,
+        })
+    }
+
+    pub async fn build(
+        self,
+        parquet_type_to_schemas: HashMap<ParquetTypeEnum, Arc<Type>>,
+    ) -> anyhow::Result<(
+        ParquetBufferStep,
+        ParquetVersionTrackerStep<ParquetProcessorStatusSaver>,
+    )> {
+        let buffer_step = initialize_parquet_buffer_step(
+            self.gcs_client,
+            parquet_type_to_schemas,
+            &self.parquet_processor_config,
+            self.parquet_db_config.bucket_name,
+            self.parquet_db_config.bucket_root,
+            self.config.processor_config.name().to_string(),
+            self.spill_store.clone(),
+        )
+        .await?;
+        let version_tracker_step = ParquetVersionTrackerStep::new(
+            ParquetProcessorStatusSaver::new(self.config, self.db_pool),
+            DEFAULT_UPDATE_PROCESSOR_STATUS_SECS,
+        )
+        .with_spill_store(self.spill_store);
+        Ok((buffer_step, version_tracker_step))
+    }
+}
+
+/// Initializes the table commit log, stored in the same bucket as the data, when the table
+/// format is enabled.
+fn initialize_table_commit_log(
+    parquet_processor_config: &ParquetDefaultProcessorConfig,
+    gcs_client: Arc<GCSClient>,
+    bucket_name: &str,
+    bucket_root: &str,
+    processor_name: &str,
+) -> Option<TableCommitLog> {
+    parquet_processor_config.table_format.then(|| {
+        TableCommitLog::new(
+            Box::new(GcsObjectStore::new(gcs_client, bucket_name.to_string())),
+            bucket_root,
+            processor_name,
+        )
+    })
+}
+
 /// Sets the backfill table flag.
 fn set_backfill_table_flag(table_names: HashSet<String>) -> TableFlags {
     let mut backfill_table = TableFlags::empty();
@@ -749,4 +1322,26 @@ mod tests {
             assert_eq!(default.parquet_type(), t);
         }
     }
+
+    #[test]
+    fn test_every_parquet_type_is_registered() {
+        use crate::parquet_processors::parquet_utils::util::VALID_TABLE_NAMES;
+
+        for t in ParquetTypeEnum::iter() {
+            let default = ParquetTypeStructs::default_for_type(&t);
+            assert_eq!(default.parquet_type(), t);
+            // The enum string is what ends up in the bucket path and processor status, so the
+            // model's table name has to agree with it.
+            assert_eq!(default.table_name(), t.to_string());
+
+            let processor_name = t.processor_name().to_string();
+            let table_names = VALID_TABLE_NAMES
+                .get(&processor_name)
+                .unwrap_or_else(|| panic!("{processor_name} has no valid table names"));
+            assert!(
+                table_names.contains(&t.to_string()),
+                "{t} is not a valid table for {processor_name}"
+            );
+        }
+    }
 }
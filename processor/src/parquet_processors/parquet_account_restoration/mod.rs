@@ -0,0 +1,2 @@
+pub mod parquet_account_restoration_extractor;
+pub mod parquet_account_restoration_processor;
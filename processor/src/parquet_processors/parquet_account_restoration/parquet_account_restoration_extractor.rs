@@ -0,0 +1,89 @@
+use crate::{
+    parquet_processors::{
+        parquet_utils::util::add_to_map_if_opted_in_for_backfill, ParquetTypeEnum,
+        ParquetTypeStructs,
+    },
+    processors::account_restoration::{
+        account_restoration_models::{
+            auth_key_account_addresses::ParquetAuthKeyAccountAddress,
+            public_key_auth_keys::ParquetPublicKeyAuthKey,
+        },
+        account_restoration_processor_helpers::parse_account_restoration_models,
+    },
+    utils::table_flags::TableFlags,
+};
+use aptos_indexer_processor_sdk::{
+    aptos_protos::transaction::v1::Transaction,
+    traits::{async_step::AsyncRunType, AsyncStep, NamedStep, Processable},
+    types::transaction_context::TransactionContext,
+    utils::errors::ProcessorError,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+/// Extracts parquet data from transactions, allowing optional selection of specific tables.
+pub struct ParquetAccountRestorationExtractor
+where
+    Self: Processable + Send + Sized + 'static,
+{
+    pub opt_in_tables: TableFlags,
+}
+
+type ParquetTypeMap = HashMap<ParquetTypeEnum, ParquetTypeStructs>;
+
+#[async_trait]
+impl Processable for ParquetAccountRestorationExtractor {
+    type Input = Vec<Transaction>;
+    type Output = ParquetTypeMap;
+    type RunType = AsyncRunType;
+
+    async fn process(
+        &mut self,
+        transactions: TransactionContext<Self::Input>,
+    ) -> anyhow::Result<Option<TransactionContext<ParquetTypeMap>>, ProcessorError> {
+        // Key rotation history is Postgres only for now
+        let (auth_key_account_addresses, public_key_auth_keys, _account_key_rotations) =
+            parse_account_restoration_models(&transactions.data);
+
+        let parquet_auth_key_account_addresses: Vec<ParquetAuthKeyAccountAddress> =
+            auth_key_account_addresses
+                .into_iter()
+                .map(ParquetAuthKeyAccountAddress::from)
+                .collect();
+        let parquet_public_key_auth_keys: Vec<ParquetPublicKeyAuthKey> = public_key_auth_keys
+            .into_iter()
+            .map(ParquetPublicKeyAuthKey::from)
+            .collect();
+
+        let mut map: HashMap<ParquetTypeEnum, ParquetTypeStructs> = HashMap::new();
+
+        let data_types = [
+            (
+                TableFlags::AUTH_KEY_ACCOUNT_ADDRESSES,
+                ParquetTypeEnum::AuthKeyAccountAddresses,
+                ParquetTypeStructs::AuthKeyAccountAddress(parquet_auth_key_account_addresses),
+            ),
+            (
+                TableFlags::PUBLIC_KEY_AUTH_KEYS,
+                ParquetTypeEnum::PublicKeyAuthKeys,
+                ParquetTypeStructs::PublicKeyAuthKey(parquet_public_key_auth_keys),
+            ),
+        ];
+
+        // Populate the map based on opt-in tables
+        add_to_map_if_opted_in_for_backfill(self.opt_in_tables, &mut map, data_types.to_vec());
+
+        Ok(Some(TransactionContext {
+            data: map,
+            metadata: transactions.metadata,
+        }))
+    }
+}
+
+impl AsyncStep for ParquetAccountRestorationExtractor {}
+
+impl NamedStep for ParquetAccountRestorationExtractor {
+    fn name(&self) -> String {
+        "ParquetAccountRestorationExtractor".to_string()
+    }
+}
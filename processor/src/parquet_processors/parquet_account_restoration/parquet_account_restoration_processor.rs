@@ -0,0 +1,169 @@
+use crate::{
+    config::{
+        db_config::DbConfig, indexer_processor_config::IndexerProcessorConfig,
+        processor_config::ProcessorConfig,
+    },
+    parquet_processors::{
+        initialize_database_pool,
+        parquet_account_restoration::parquet_account_restoration_extractor::ParquetAccountRestorationExtractor,
+        parquet_processor_status_saver::{get_parquet_end_version, get_parquet_starting_version},
+        parquet_utils::util::HasParquetSchema,
+        set_backfill_table_flag, ParquetStepsBuilder, ParquetTypeEnum,
+    },
+    processors::account_restoration::account_restoration_models::{
+        auth_key_account_addresses::ParquetAuthKeyAccountAddress,
+        public_key_auth_keys::ParquetPublicKeyAuthKey,
+    },
+    MIGRATIONS,
+};
+use aptos_indexer_processor_sdk::{
+    aptos_indexer_transaction_stream::TransactionStreamConfig,
+    builder::ProcessorBuilder,
+    common_steps::TransactionStreamStep,
+    postgres::utils::{
+        checkpoint::PostgresChainIdChecker,
+        database::{run_migrations, ArcDbPool},
+    },
+    traits::{processor_trait::ProcessorTrait, IntoRunnableStep},
+    utils::chain_id_check::check_or_update_chain_id,
+};
+use parquet::schema::types::Type;
+use std::{collections::HashMap, sync::Arc};
+use tracing::{debug, info};
+
+pub struct ParquetAccountRestorationProcessor {
+    pub config: IndexerProcessorConfig,
+    pub db_pool: ArcDbPool, // for processor status
+}
+
+impl ParquetAccountRestorationProcessor {
+    pub async fn new(config: IndexerProcessorConfig) -> anyhow::Result<Self> {
+        let db_pool = initialize_database_pool(&config.db_config).await?;
+        Ok(Self { config, db_pool })
+    }
+}
+#[async_trait::async_trait]
+impl ProcessorTrait for ParquetAccountRestorationProcessor {
+    fn name(&self) -> &'static str {
+        self.config.processor_config.name()
+    }
+
+    async fn run_processor(&self) -> anyhow::Result<()> {
+        // Run Migrations
+        let parquet_db_config = match self.config.db_config {
+            DbConfig::ParquetConfig(ref parquet_config) => {
+                run_migrations(
+                    parquet_config.connection_string.clone(),
+                    self.db_pool.clone(),
+                    MIGRATIONS,
+                )
+                .await;
+                parquet_config
+            },
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Invalid db config for ParquetAccountRestorationProcessor {:?}",
+                    self.config.db_config
+                ));
+            },
+        };
+
+        // Check and update the ledger chain id to ensure we're indexing the correct chain
+        check_or_update_chain_id(
+            &self.config.transaction_stream_config,
+            &PostgresChainIdChecker::new(self.db_pool.clone()),
+        )
+        .await?;
+
+        let parquet_processor_config = match self.config.processor_config.clone() {
+            ProcessorConfig::ParquetAccountRestorationProcessor(parquet_processor_config) => {
+                parquet_processor_config
+            },
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Invalid processor configuration for ParquetAccountRestorationProcessor {:?}",
+                    self.config.processor_config
+                ));
+            },
+        };
+
+        // Upload anything a previous run spilled but didn't checkpoint before picking the starting version
+        let parquet_steps_builder = ParquetStepsBuilder::new(
+            &self.config,
+            self.db_pool.clone(),
+            parquet_db_config,
+            &parquet_processor_config,
+        )
+        .await?;
+
+        let (starting_version, ending_version) = (
+            get_parquet_starting_version(&self.config, self.db_pool.clone()).await?,
+            get_parquet_end_version(&self.config, self.db_pool.clone()).await?,
+        );
+
+        // Define processor transaction stream config
+        let transaction_stream = TransactionStreamStep::new(TransactionStreamConfig {
+            starting_version,
+            request_ending_version: ending_version,
+            ..self.config.transaction_stream_config.clone()
+        })
+        .await?;
+
+        let backfill_table = set_backfill_table_flag(parquet_processor_config.backfill_table);
+        let parquet_account_restoration_extractor = ParquetAccountRestorationExtractor {
+            opt_in_tables: backfill_table,
+        };
+
+        let parquet_type_to_schemas: HashMap<ParquetTypeEnum, Arc<Type>> = [
+            (
+                ParquetTypeEnum::AuthKeyAccountAddresses,
+                ParquetAuthKeyAccountAddress::schema(),
+            ),
+            (
+                ParquetTypeEnum::PublicKeyAuthKeys,
+                ParquetPublicKeyAuthKey::schema(),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let (default_size_buffer_step, parquet_version_tracker_step) = parquet_steps_builder
+            .build(parquet_type_to_schemas)
+            .await
+            .unwrap_or_else(|e| {
+                panic!("Failed to initialize parquet buffer step: {e:?}");
+            });
+
+        let channel_size = parquet_processor_config.channel_size;
+
+        // Connect processor steps together
+        let (_, buffer_receiver) = ProcessorBuilder::new_with_inputless_first_step(
+            transaction_stream.into_runnable_step(),
+        )
+        .connect_to(
+            parquet_account_restoration_extractor.into_runnable_step(),
+            channel_size,
+        )
+        .connect_to(default_size_buffer_step.into_runnable_step(), channel_size)
+        .connect_to(
+            parquet_version_tracker_step.into_runnable_step(),
+            channel_size,
+        )
+        .end_and_return_output_receiver(channel_size);
+
+        loop {
+            match buffer_receiver.recv().await {
+                Ok(txn_context) => {
+                    debug!(
+                        "Finished processing versions [{:?}, {:?}]",
+                        txn_context.metadata.start_version, txn_context.metadata.end_version,
+                    );
+                },
+                Err(e) => {
+                    info!("No more transactions in channel: {:?}", e);
+                    break Ok(());
+                },
+            }
+        }
+    }
+}
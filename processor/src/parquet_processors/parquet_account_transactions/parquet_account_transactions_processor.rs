@@ -4,15 +4,11 @@ use crate::{
         processor_config::ProcessorConfig,
     },
     parquet_processors::{
-        initialize_database_pool, initialize_gcs_client, initialize_parquet_buffer_step,
+        initialize_database_pool,
         parquet_account_transactions::parquet_account_transactions_extractor::ParquetAccountTransactionsExtractor,
-        parquet_processor_status_saver::{
-            get_parquet_end_version, get_parquet_starting_version, ParquetProcessorStatusSaver,
-        },
-        parquet_utils::{
-            parquet_version_tracker_step::ParquetVersionTrackerStep, util::HasParquetSchema,
-        },
-        set_backfill_table_flag, ParquetTypeEnum,
+        parquet_processor_status_saver::{get_parquet_end_version, get_parquet_starting_version},
+        parquet_utils::util::HasParquetSchema,
+        set_backfill_table_flag, ParquetStepsBuilder, ParquetTypeEnum,
     },
     processors::account_transactions::account_transactions_model::ParquetAccountTransaction,
     MIGRATIONS,
@@ -20,7 +16,7 @@ use crate::{
 use aptos_indexer_processor_sdk::{
     aptos_indexer_transaction_stream::TransactionStreamConfig,
     builder::ProcessorBuilder,
-    common_steps::{TransactionStreamStep, DEFAULT_UPDATE_PROCESSOR_STATUS_SECS},
+    common_steps::TransactionStreamStep,
     postgres::utils::{
         checkpoint::PostgresChainIdChecker,
         database::{run_migrations, ArcDbPool},
@@ -89,6 +85,15 @@ impl ProcessorTrait for ParquetAccountTransactionsProcessor {
             },
         };
 
+        // Upload anything a previous run spilled but didn't checkpoint before picking the starting version
+        let parquet_steps_builder = ParquetStepsBuilder::new(
+            &self.config,
+            self.db_pool.clone(),
+            parquet_db_config,
+            &parquet_processor_config,
+        )
+        .await?;
+
         let (starting_version, ending_version) = (
             get_parquet_starting_version(&self.config, self.db_pool.clone()).await?,
             get_parquet_end_version(&self.config, self.db_pool.clone()).await?,
@@ -109,9 +114,6 @@ impl ProcessorTrait for ParquetAccountTransactionsProcessor {
             opt_in_tables: backfill_table,
         };
 
-        let gcs_client =
-            initialize_gcs_client(parquet_db_config.google_application_credentials.clone()).await;
-
         let parquet_type_to_schemas: HashMap<ParquetTypeEnum, Arc<Type>> = [(
             ParquetTypeEnum::AccountTransactions,
             ParquetAccountTransaction::schema(),
@@ -119,24 +121,12 @@ impl ProcessorTrait for ParquetAccountTransactionsProcessor {
         .into_iter()
         .collect();
 
-        let default_size_buffer_step = initialize_parquet_buffer_step(
-            gcs_client.clone(),
-            parquet_type_to_schemas,
-            parquet_processor_config.upload_interval,
-            parquet_processor_config.max_buffer_size,
-            parquet_db_config.bucket_name.clone(),
-            parquet_db_config.bucket_root.clone(),
-            self.name().to_string(),
-        )
-        .await
-        .unwrap_or_else(|e| {
-            panic!("Failed to initialize parquet buffer step: {e:?}");
-        });
-
-        let parquet_version_tracker_step = ParquetVersionTrackerStep::new(
-            ParquetProcessorStatusSaver::new(self.config.clone(), self.db_pool.clone()),
-            DEFAULT_UPDATE_PROCESSOR_STATUS_SECS,
-        );
+        let (default_size_buffer_step, parquet_version_tracker_step) = parquet_steps_builder
+            .build(parquet_type_to_schemas)
+            .await
+            .unwrap_or_else(|e| {
+                panic!("Failed to initialize parquet buffer step: {e:?}");
+            });
 
         let channel_size = parquet_processor_config.channel_size;
 
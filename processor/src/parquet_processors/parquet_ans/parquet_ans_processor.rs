@@ -5,15 +5,11 @@ use crate::{
         processor_config::{ParquetDefaultProcessorConfig, ProcessorConfig},
     },
     parquet_processors::{
-        initialize_database_pool, initialize_gcs_client, initialize_parquet_buffer_step,
+        initialize_database_pool,
         parquet_ans::parquet_ans_extractor::ParquetAnsExtractor,
-        parquet_processor_status_saver::{
-            get_parquet_end_version, get_parquet_starting_version, ParquetProcessorStatusSaver,
-        },
-        parquet_utils::{
-            parquet_version_tracker_step::ParquetVersionTrackerStep, util::HasParquetSchema,
-        },
-        set_backfill_table_flag, ParquetTypeEnum,
+        parquet_processor_status_saver::{get_parquet_end_version, get_parquet_starting_version},
+        parquet_utils::util::HasParquetSchema,
+        set_backfill_table_flag, ParquetStepsBuilder, ParquetTypeEnum,
     },
     processors::ans::models::{
         ans_lookup_v2::{ParquetAnsLookupV2, ParquetCurrentAnsLookupV2},
@@ -24,7 +20,7 @@ use crate::{
 use aptos_indexer_processor_sdk::{
     aptos_indexer_transaction_stream::TransactionStreamConfig,
     builder::ProcessorBuilder,
-    common_steps::{TransactionStreamStep, DEFAULT_UPDATE_PROCESSOR_STATUS_SECS},
+    common_steps::TransactionStreamStep,
     postgres::utils::{
         checkpoint::PostgresChainIdChecker,
         database::{run_migrations, ArcDbPool},
@@ -102,6 +98,15 @@ impl ProcessorTrait for ParquetAnsProcessor {
             },
         };
 
+        // Upload anything a previous run spilled but didn't checkpoint before picking the starting version
+        let parquet_steps_builder = ParquetStepsBuilder::new(
+            &self.config,
+            self.db_pool.clone(),
+            parquet_db_config,
+            &parquet_processor_config.default,
+        )
+        .await?;
+
         let (starting_version, ending_version) = (
             get_parquet_starting_version(&self.config, self.db_pool.clone()).await?,
             get_parquet_end_version(&self.config, self.db_pool.clone()).await?,
@@ -123,9 +128,6 @@ impl ProcessorTrait for ParquetAnsProcessor {
             opt_in_tables: backfill_table,
         };
 
-        let gcs_client =
-            initialize_gcs_client(parquet_db_config.google_application_credentials.clone()).await;
-
         let parquet_type_to_schemas: HashMap<ParquetTypeEnum, Arc<Type>> = [
             (
                 ParquetTypeEnum::AnsPrimaryNameV2,
@@ -144,24 +146,12 @@ impl ProcessorTrait for ParquetAnsProcessor {
         .into_iter()
         .collect();
 
-        let default_size_buffer_step = initialize_parquet_buffer_step(
-            gcs_client.clone(),
-            parquet_type_to_schemas,
-            parquet_processor_config.default.upload_interval,
-            parquet_processor_config.default.max_buffer_size,
-            parquet_db_config.bucket_name.clone(),
-            parquet_db_config.bucket_root.clone(),
-            self.name().to_string(),
-        )
-        .await
-        .unwrap_or_else(|e| {
-            panic!("Failed to initialize parquet buffer step: {e:?}");
-        });
-
-        let parquet_version_tracker_step = ParquetVersionTrackerStep::new(
-            ParquetProcessorStatusSaver::new(self.config.clone(), self.db_pool.clone()),
-            DEFAULT_UPDATE_PROCESSOR_STATUS_SECS,
-        );
+        let (default_size_buffer_step, parquet_version_tracker_step) = parquet_steps_builder
+            .build(parquet_type_to_schemas)
+            .await
+            .unwrap_or_else(|e| {
+                panic!("Failed to initialize parquet buffer step: {e:?}");
+            });
 
         let channel_size = parquet_processor_config.default.channel_size;
 
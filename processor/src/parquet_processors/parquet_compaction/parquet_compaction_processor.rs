@@ -0,0 +1,863 @@
+use crate::{
+    config::{
+        indexer_processor_config::IndexerProcessorConfig,
+        processor_config::{ParquetDefaultProcessorConfig, ProcessorConfig},
+    },
+    parquet_processors::{
+        parquet_loader::parquet_loader_processor::ParquetLoaderSource,
+        parquet_utils::{
+            gcs_uploader::ParquetFileLayout,
+            object_store::ObjectStore,
+            parquet_writer_config::ParquetWriterConfig,
+            table_commit_log::{AddedFile, TableCommitLog},
+            util::{HasParquetSchema, HasTimestamp, HasVersion},
+        },
+        ParquetTypeEnum, ParquetTypeStructs, ParquetTypeTrait,
+    },
+};
+use anyhow::{bail, Context, Result};
+use aptos_indexer_processor_sdk::traits::processor_trait::ProcessorTrait;
+use chrono::NaiveDate;
+use parquet::{
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    record::RecordWriter,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, path::Path, sync::Arc, time::Duration};
+use strum::IntoEnumIterator;
+use tracing::{info, warn};
+
+const PARQUET_FILE_EXTENSION: &str = ".parquet";
+const COMPACTION_JOURNAL_DIR: &str = "_compaction";
+
+/// Merges the small parquet files of a table, e.g. left behind by time based uploads and
+/// restarts, into files of the target size sorted by transaction version.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ParquetCompactionProcessorConfig {
+    // Parquet table name to compact
+    pub table: String,
+    // Only files whose rows are all within the version range are compacted
+    #[serde(default)]
+    pub start_version: i64,
+    #[serde(default)]
+    pub end_version: Option<i64>,
+    pub source: ParquetLoaderSource,
+    #[serde(default)]
+    pub bucket_root: String,
+    // Layout of the files to compact, the compacted files are written with the same layout
+    #[serde(default)]
+    pub file_layout: ParquetFileLayout,
+    // Replaces the compacted files in the table commit log. The originals are only removed from
+    // the snapshots and kept for older snapshots unless `delete_originals` is set.
+    #[serde(default)]
+    pub table_format: bool,
+    // Without `table_format` the originals are always deleted, since readers would otherwise see
+    // their rows twice. A journal written before the swap lets a rerun finish or undo it.
+    #[serde(default)]
+    pub delete_originals: bool,
+    #[serde(default = "ParquetDefaultProcessorConfig::default_target_file_size")]
+    pub target_file_size: usize,
+    #[serde(default)]
+    pub writer_properties: ParquetWriterConfig,
+}
+
+impl ParquetCompactionProcessorConfig {
+    fn parquet_type(&self) -> Result<ParquetTypeEnum> {
+        ParquetTypeEnum::iter()
+            .find(|parquet_type| parquet_type.to_string() == self.table)
+            .with_context(|| format!("Unknown parquet table '{}'", self.table))
+    }
+
+    fn in_version_range(&self, (min_version, max_version): (i64, i64)) -> bool {
+        min_version >= self.start_version
+            && self
+                .end_version
+                .map_or(true, |end_version| max_version <= end_version)
+    }
+
+    fn journal_prefix(&self, table_name: &str) -> String {
+        format!(
+            "{}/",
+            Path::new(&self.bucket_root)
+                .join(COMPACTION_JOURNAL_DIR)
+                .join(table_name)
+                .display()
+        )
+    }
+
+    /// Date partition of a file, taken from the `date=` directory of the Hive style layout.
+    fn partition_date(&self, path: &str) -> Result<Option<NaiveDate>> {
+        if self.file_layout != ParquetFileLayout::HiveDate {
+            return Ok(None);
+        }
+        let date = path
+            .split('/')
+            .find_map(|segment| segment.strip_prefix("date="))
+            .with_context(|| format!("Parquet file {path} is not in a date partition"))?;
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map(Some)
+            .with_context(|| format!("Invalid date partition of parquet file {path}"))
+    }
+}
+
+/// Written before a compaction without the commit log swaps any file, so that a rerun after a
+/// crash can tell the compacted files from the originals. If every compacted file was written,
+/// the rerun deletes the originals left, otherwise it deletes the compacted files.
+#[derive(Debug, Deserialize, Serialize)]
+struct CompactionJournal {
+    originals: Vec<String>,
+    compacted: Vec<String>,
+}
+
+/// Finishes or undoes the compactions interrupted in between writing their journal and deleting
+/// it. Every step is idempotent, so this can itself be interrupted and rerun.
+async fn recover_interrupted_compactions(
+    config: &ParquetCompactionProcessorConfig,
+    table_name: &str,
+    object_store: &dyn ObjectStore,
+) -> Result<()> {
+    for journal_path in object_store
+        .list(&config.journal_prefix(table_name))
+        .await?
+    {
+        let Some(data) = object_store.get(&journal_path).await? else {
+            continue;
+        };
+        let journal: CompactionJournal = serde_json::from_slice(&data)
+            .with_context(|| format!("Failed to parse compaction journal {journal_path}"))?;
+        let mut all_written = true;
+        for path in &journal.compacted {
+            if object_store.get(path).await?.is_none() {
+                all_written = false;
+                break;
+            }
+        }
+        let to_delete = if all_written {
+            &journal.originals
+        } else {
+            &journal.compacted
+        };
+        for path in to_delete {
+            object_store.delete(path).await?;
+        }
+        object_store.delete(&journal_path).await?;
+        warn!(
+            table_name = table_name,
+            journal_path = journal_path,
+            rolled_forward = all_written,
+            "Recovered interrupted parquet compaction"
+        );
+    }
+    Ok(())
+}
+
+/// A parquet file written by the compaction.
+pub struct CompactedFile {
+    pub data: Vec<u8>,
+    pub row_count: i64,
+    pub min_version: i64,
+    pub max_version: i64,
+    pub partition_date: Option<NaiveDate>,
+}
+
+/// Sorts the rows by version and encodes them into files that are closed once their encoded size
+/// reaches `target_file_size`. With `partition_by_date`, a file only holds rows of a single date.
+pub fn encode_compacted_files<ParquetType>(
+    rows: &mut [ParquetType],
+    props: Arc<WriterProperties>,
+    target_file_size: usize,
+    partition_by_date: bool,
+) -> Result<Vec<CompactedFile>>
+where
+    ParquetType: HasVersion + HasTimestamp + HasParquetSchema,
+    for<'a> &'a [ParquetType]: RecordWriter<ParquetType>,
+{
+    rows.sort_by_key(HasVersion::version);
+    let row_date = |row: &ParquetType| row.timestamp().map(|timestamp| timestamp.date());
+    let max_row_group_size = props.max_row_group_size();
+
+    let mut files = vec![];
+    let mut start = 0;
+    while start < rows.len() {
+        let partition_date = row_date(&rows[start]);
+        let mut writer =
+            SerializedFileWriter::new(Vec::new(), ParquetType::schema(), props.clone())?;
+        let mut end = start;
+        // A file gets at least one row group, even if the target size is tiny
+        while end < rows.len() && (end == start || writer.bytes_written() < target_file_size) {
+            let mut row_group_end = rows.len().min(end + max_row_group_size);
+            if partition_by_date {
+                row_group_end = (end..row_group_end)
+                    .find(|index| row_date(&rows[*index]) != partition_date)
+                    .unwrap_or(row_group_end);
+                if row_group_end == end {
+                    break;
+                }
+            }
+            let mut row_group_writer = writer.next_row_group()?;
+            rows[end..row_group_end].write_to_row_group(&mut row_group_writer)?;
+            row_group_writer.close()?;
+            end = row_group_end;
+        }
+
+        files.push(CompactedFile {
+            data: writer.into_inner()?,
+            row_count: (end - start) as i64,
+            min_version: rows[start].version(),
+            max_version: rows[end - 1].version(),
+            partition_date,
+        });
+        start = end;
+    }
+    Ok(files)
+}
+
+/// Compacts the files of a table. Files are merged in path order, in groups of about the target
+/// file size to bound memory use, and each group is swapped in one commit. A group never mixes
+/// date partitions. Returns the number of files that were replaced.
+pub async fn compact_table(
+    config: &ParquetCompactionProcessorConfig,
+    object_store: &dyn ObjectStore,
+    commit_log: Option<&TableCommitLog>,
+) -> Result<usize> {
+    let parquet_type = config.parquet_type()?;
+    let table_name = parquet_type.to_string();
+    let schema = ParquetTypeStructs::default_for_type(&parquet_type).parquet_schema();
+    let props = Arc::new(
+        config
+            .writer_properties
+            .table_writer_properties(parquet_type, &schema)?,
+    );
+    // Files of other schema versions can't be merged with the current model
+    let prefix = config.file_layout.table_prefix(
+        Path::new(&config.bucket_root),
+        &table_name,
+        parquet_type.schema_version(),
+    );
+
+    if commit_log.is_none() {
+        recover_interrupted_compactions(config, &table_name, object_store).await?;
+    }
+
+    // Files from the commit log have their size and versions, listed files are checked once read
+    let mut candidates: Vec<String> = match commit_log {
+        Some(commit_log) => commit_log
+            .snapshot(&table_name)
+            .await?
+            .into_iter()
+            .filter(|file| {
+                file.path.starts_with(&prefix)
+                    && (file.size_bytes as usize) < config.target_file_size
+                    && config.in_version_range((file.min_version, file.max_version))
+            })
+            .map(|file| file.path)
+            .collect(),
+        None => object_store
+            .list(&prefix)
+            .await?
+            .into_iter()
+            .filter(|name| name.ends_with(PARQUET_FILE_EXTENSION))
+            .collect(),
+    };
+    // Keeps the files of a date partition next to each other
+    candidates.sort();
+
+    let mut num_compacted = 0;
+    let mut group = CompactionGroup::new(parquet_type, None);
+    for path in candidates {
+        let partition_date = config.partition_date(&path)?;
+        if partition_date != group.partition_date {
+            num_compacted += group
+                .compact(config, &props, object_store, commit_log)
+                .await?;
+            group = CompactionGroup::new(parquet_type, partition_date);
+        }
+        let data = object_store
+            .get(&path)
+            .await?
+            .with_context(|| format!("Parquet file {path} disappeared"))?;
+        if data.len() >= config.target_file_size {
+            continue;
+        }
+        let size_bytes = data.len();
+        let mut rows = ParquetTypeStructs::default_for_type(&parquet_type);
+        rows.extend_from_parquet(data)
+            .with_context(|| format!("Failed to read parquet file {path}"))?;
+        if !rows
+            .version_range()
+            .map_or(true, |version_range| config.in_version_range(version_range))
+        {
+            continue;
+        }
+
+        group
+            .rows
+            .append(rows)
+            .map_err(|e| anyhow::anyhow!("Failed to merge parquet file {path}: {e:?}"))?;
+        group.paths.push(path);
+        group.size_bytes += size_bytes;
+        if group.size_bytes >= config.target_file_size {
+            num_compacted += group
+                .compact(config, &props, object_store, commit_log)
+                .await?;
+            group = CompactionGroup::new(parquet_type, partition_date);
+        }
+    }
+    num_compacted += group
+        .compact(config, &props, object_store, commit_log)
+        .await?;
+    Ok(num_compacted)
+}
+
+/// Small files of a date partition that are merged together.
+struct CompactionGroup {
+    parquet_type: ParquetTypeEnum,
+    partition_date: Option<NaiveDate>,
+    rows: ParquetTypeStructs,
+    paths: Vec<String>,
+    size_bytes: usize,
+}
+
+impl CompactionGroup {
+    fn new(parquet_type: ParquetTypeEnum, partition_date: Option<NaiveDate>) -> Self {
+        Self {
+            parquet_type,
+            partition_date,
+            rows: ParquetTypeStructs::default_for_type(&parquet_type),
+            paths: vec![],
+            size_bytes: 0,
+        }
+    }
+
+    /// Writes the merged files, swaps them for the originals in the commit log and deletes the
+    /// originals. Without the commit log, a journal is written first so that an interrupted
+    /// swap can be recovered. Returns the number of files that were replaced.
+    async fn compact(
+        mut self,
+        config: &ParquetCompactionProcessorConfig,
+        props: &Arc<WriterProperties>,
+        object_store: &dyn ObjectStore,
+        commit_log: Option<&TableCommitLog>,
+    ) -> Result<usize> {
+        // A single file has nothing to be merged with
+        if self.paths.len() < 2 {
+            return Ok(0);
+        }
+        let table_name = self.parquet_type.to_string();
+        let compacted_files = self.rows.to_compacted_files(
+            props.clone(),
+            config.target_file_size,
+            config.file_layout == ParquetFileLayout::HiveDate,
+        )?;
+
+        // The names are picked before anything is written, so that the journal can list them
+        let mut paths = HashSet::new();
+        let mut added_files = vec![];
+        for compacted_file in &compacted_files {
+            // Rows without a timestamp stay in the partition of the files they came from. The
+            // upload time layout doesn't partition by date.
+            let partition_date = compacted_file
+                .partition_date
+                .or(self.partition_date)
+                .unwrap_or_default();
+            let path = loop {
+                let path = config.file_layout.file_path(
+                    Path::new(&config.bucket_root),
+                    &table_name,
+                    self.parquet_type.schema_version(),
+                    partition_date,
+                    compacted_file.min_version,
+                    compacted_file.max_version,
+                );
+                let path = path.to_string_lossy().to_string();
+                if paths.insert(path.clone()) {
+                    break path;
+                }
+                // Names embed the upload time in milliseconds, retry with a later one
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            };
+            added_files.push(AddedFile {
+                path,
+                size_bytes: compacted_file.data.len() as u64,
+                row_count: compacted_file.row_count,
+                min_version: compacted_file.min_version,
+                max_version: compacted_file.max_version,
+            });
+        }
+
+        let journal_path = match commit_log {
+            Some(_) => None,
+            None => Some(
+                self.write_journal(config, object_store, &added_files)
+                    .await?,
+            ),
+        };
+        // Every file is written under a new name, so readers never see a partial file
+        for (index, (compacted_file, added_file)) in
+            compacted_files.into_iter().zip(&added_files).enumerate()
+        {
+            if !object_store
+                .put_if_absent(&added_file.path, compacted_file.data)
+                .await?
+            {
+                // The name was taken in the meantime, undo the files written so far
+                for written_file in &added_files[..index] {
+                    object_store.delete(&written_file.path).await?;
+                }
+                if let Some(journal_path) = &journal_path {
+                    object_store.delete(journal_path).await?;
+                }
+                bail!("Compacted parquet file {} already exists", added_file.path);
+            }
+        }
+
+        if let Some(commit_log) = commit_log {
+            if let Err(e) = commit_log
+                .commit_changes(
+                    &table_name,
+                    added_files.clone(),
+                    self.paths.clone(),
+                    &self.rows.parquet_schema(),
+                )
+                .await
+            {
+                // E.g. a concurrent compaction already replaced the originals, the compacted
+                // files are not part of any snapshot
+                for added_file in &added_files {
+                    object_store.delete(&added_file.path).await?;
+                }
+                return Err(e);
+            }
+        }
+        if commit_log.is_none() || config.delete_originals {
+            for path in &self.paths {
+                object_store.delete(path).await?;
+            }
+        }
+        if let Some(journal_path) = &journal_path {
+            object_store.delete(journal_path).await?;
+        }
+
+        info!(
+            table_name = table_name,
+            num_files = self.paths.len(),
+            num_compacted_files = added_files.len(),
+            "Compacted parquet files"
+        );
+        Ok(self.paths.len())
+    }
+
+    /// Writes the journal of the swap and returns its path.
+    async fn write_journal(
+        &self,
+        config: &ParquetCompactionProcessorConfig,
+        object_store: &dyn ObjectStore,
+        added_files: &[AddedFile],
+    ) -> Result<String> {
+        let journal = serde_json::to_vec(&CompactionJournal {
+            originals: self.paths.clone(),
+            compacted: added_files.iter().map(|file| file.path.clone()).collect(),
+        })?;
+        let journal_prefix = config.journal_prefix(&self.parquet_type.to_string());
+        loop {
+            let journal_path = format!(
+                "{journal_prefix}{}.json",
+                chrono::Utc::now().timestamp_millis()
+            );
+            if object_store
+                .put_if_absent(&journal_path, journal.clone())
+                .await?
+            {
+                return Ok(journal_path);
+            }
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+    }
+}
+
+pub struct ParquetCompactionProcessor {
+    pub config: IndexerProcessorConfig,
+}
+
+impl ParquetCompactionProcessor {
+    pub async fn new(config: IndexerProcessorConfig) -> Result<Self> {
+        Ok(Self { config })
+    }
+}
+
+#[async_trait::async_trait]
+impl ProcessorTrait for ParquetCompactionProcessor {
+    fn name(&self) -> &'static str {
+        self.config.processor_config.name()
+    }
+
+    async fn run_processor(&self) -> Result<()> {
+        let compaction_config = match &self.config.processor_config {
+            ProcessorConfig::ParquetCompactionProcessor(compaction_config) => compaction_config,
+            _ => bail!(
+                "Invalid processor config for ParquetCompactionProcessor: {:?}",
+                self.config.processor_config
+            ),
+        };
+        let object_store = compaction_config.source.object_store().await;
+        let commit_log = if compaction_config.table_format {
+            Some(TableCommitLog::new(
+                compaction_config.source.object_store().await,
+                &compaction_config.bucket_root,
+                self.name(),
+            ))
+        } else {
+            None
+        };
+
+        let num_compacted = compact_table(
+            compaction_config,
+            object_store.as_ref(),
+            commit_log.as_ref(),
+        )
+        .await?;
+        info!(
+            table_name = compaction_config.table,
+            num_compacted = num_compacted,
+            "Parquet compaction finished"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parquet_processors::{
+            parquet_loader::parquet_row_reader::read_parquet_rows,
+            parquet_utils::object_store::LocalFileSystemObjectStore,
+        },
+        processors::default::models::{
+            move_resources::ParquetMoveResource, table_items::ParquetTableMetadata,
+        },
+    };
+    use chrono::NaiveDateTime;
+    use std::path::PathBuf;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("parquet_compaction_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn move_resource(txn_version: i64, block_timestamp: &str) -> ParquetMoveResource {
+        ParquetMoveResource {
+            txn_version,
+            block_timestamp: NaiveDateTime::parse_from_str(block_timestamp, "%Y-%m-%d %H:%M:%S")
+                .unwrap(),
+            ..ParquetMoveResource::default()
+        }
+    }
+
+    fn props() -> Arc<WriterProperties> {
+        Arc::new(
+            ParquetWriterConfig {
+                max_row_group_size: Some(1),
+                ..ParquetWriterConfig::default()
+            }
+            .table_writer_properties(
+                ParquetTypeEnum::MoveResources,
+                &ParquetMoveResource::schema(),
+            )
+            .unwrap(),
+        )
+    }
+
+    fn compaction_config(dir: &Path, table_format: bool) -> ParquetCompactionProcessorConfig {
+        ParquetCompactionProcessorConfig {
+            table: "move_resources".to_string(),
+            start_version: 0,
+            end_version: None,
+            source: ParquetLoaderSource::LocalFileSystem {
+                path: dir.to_string_lossy().to_string(),
+            },
+            bucket_root: "root".to_string(),
+            file_layout: ParquetFileLayout::UploadTime,
+            table_format,
+            delete_originals: false,
+            target_file_size: 1024 * 1024,
+            writer_properties: ParquetWriterConfig::default(),
+        }
+    }
+
+    #[test]
+    fn test_encode_compacted_files() {
+        let mut rows = vec![
+            move_resource(3, "2024-03-10 00:00:01"),
+            move_resource(1, "2024-03-09 23:59:59"),
+            move_resource(2, "2024-03-09 23:59:59"),
+        ];
+
+        // Every row group fills a tiny file, and the files are in version order
+        let files = encode_compacted_files(&mut rows.clone(), props(), 1, false).unwrap();
+        let ranges: Vec<_> = files
+            .iter()
+            .map(|file| (file.min_version, file.max_version, file.row_count))
+            .collect();
+        assert_eq!(ranges, vec![(1, 1, 1), (2, 2, 1), (3, 3, 1)]);
+
+        // Date partitions are never mixed in a file
+        let files = encode_compacted_files(&mut rows, props(), 1024 * 1024, true).unwrap();
+        let ranges: Vec<_> = files
+            .iter()
+            .map(|file| (file.min_version, file.max_version, file.partition_date))
+            .collect();
+        assert_eq!(ranges, vec![
+            (1, 2, NaiveDate::from_ymd_opt(2024, 3, 9)),
+            (3, 3, NaiveDate::from_ymd_opt(2024, 3, 10)),
+        ]);
+        let rows: Vec<ParquetMoveResource> =
+            read_parquet_rows(files[0].data.clone(), ParquetTypeEnum::MoveResources).unwrap();
+        assert_eq!(
+            rows.iter().map(|row| row.txn_version).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+    }
+
+    async fn write_small_files(
+        object_store: &dyn ObjectStore,
+        commit_log: Option<&TableCommitLog>,
+    ) -> Vec<String> {
+        let mut paths = vec![];
+        for (index, versions) in [vec![5, 6], vec![1, 2], vec![3, 4]].into_iter().enumerate() {
+            let rows: Vec<_> = versions
+                .iter()
+                .map(|version| move_resource(*version, "2024-03-09 12:00:00"))
+                .collect();
+            let data = rows.to_parquet_bytes(props()).unwrap();
+            let path = format!(
+                "root/move_resources/v{}/0/{index}_0.parquet",
+                ParquetTypeEnum::MoveResources.schema_version()
+            );
+            let added_file = AddedFile {
+                path: path.clone(),
+                size_bytes: data.len() as u64,
+                row_count: rows.len() as i64,
+                min_version: versions[0],
+                max_version: versions[1],
+            };
+            assert!(object_store.put_if_absent(&path, data).await.unwrap());
+            if let Some(commit_log) = commit_log {
+                commit_log
+                    .commit(
+                        "move_resources",
+                        vec![added_file],
+                        &ParquetMoveResource::schema(),
+                    )
+                    .await
+                    .unwrap();
+            }
+            paths.push(path);
+        }
+        paths
+    }
+
+    #[tokio::test]
+    async fn test_compact_table_without_commit_log() {
+        let dir = test_dir("listing");
+        let object_store = LocalFileSystemObjectStore::new(&dir);
+        let originals = write_small_files(&object_store, None).await;
+
+        let config = compaction_config(&dir, false);
+        let num_compacted = compact_table(&config, &object_store, None).await.unwrap();
+        assert_eq!(num_compacted, 3);
+
+        // The originals are replaced by a single file sorted by version
+        let files = object_store.list("root/").await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(!originals.contains(&files[0]));
+        let data = object_store.get(&files[0]).await.unwrap().unwrap();
+        let rows: Vec<ParquetMoveResource> =
+            read_parquet_rows(data, ParquetTypeEnum::MoveResources).unwrap();
+        assert_eq!(
+            rows.iter().map(|row| row.txn_version).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    async fn write_journal(
+        object_store: &dyn ObjectStore,
+        name: &str,
+        originals: &[String],
+        compacted: Vec<String>,
+    ) {
+        let journal = serde_json::to_vec(&CompactionJournal {
+            originals: originals.to_vec(),
+            compacted,
+        })
+        .unwrap();
+        let path = format!("root/_compaction/move_resources/{name}.json");
+        assert!(object_store.put_if_absent(&path, journal).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_compactions_are_recovered() {
+        let dir = test_dir("journal");
+        let object_store = LocalFileSystemObjectStore::new(&dir);
+        let originals = write_small_files(&object_store, None).await;
+        let config = compaction_config(&dir, false);
+        let compacted = |name: &str| {
+            format!(
+                "root/move_resources/v{}/0/{name}.parquet",
+                ParquetTypeEnum::MoveResources.schema_version()
+            )
+        };
+
+        // Not every compacted file was written, so the ones written are deleted
+        assert!(object_store
+            .put_if_absent(&compacted("a"), vec![1])
+            .await
+            .unwrap());
+        write_journal(&object_store, "1", &originals, vec![
+            compacted("a"),
+            compacted("b"),
+        ])
+        .await;
+        recover_interrupted_compactions(&config, "move_resources", &object_store)
+            .await
+            .unwrap();
+        assert_eq!(object_store.list("root/").await.unwrap(), {
+            let mut originals = originals.clone();
+            originals.sort();
+            originals
+        });
+
+        // Every compacted file was written, so the originals left are deleted
+        assert!(object_store
+            .put_if_absent(&compacted("c"), vec![1])
+            .await
+            .unwrap());
+        write_journal(&object_store, "2", &originals[..2], vec![compacted("c")]).await;
+        recover_interrupted_compactions(&config, "move_resources", &object_store)
+            .await
+            .unwrap();
+        assert_eq!(object_store.list("root/").await.unwrap(), vec![
+            originals[2].clone(),
+            compacted("c"),
+        ]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rows_without_timestamp_keep_partition_date() {
+        let dir = test_dir("partition_date");
+        let object_store = LocalFileSystemObjectStore::new(&dir);
+        let schema_version = ParquetTypeEnum::TableMetadata.schema_version();
+        let props = Arc::new(
+            ParquetWriterConfig::default()
+                .table_writer_properties(
+                    ParquetTypeEnum::TableMetadata,
+                    &ParquetTableMetadata::schema(),
+                )
+                .unwrap(),
+        );
+        for index in 0..2 {
+            let rows = vec![ParquetTableMetadata {
+                handle: format!("0x{index}"),
+                ..ParquetTableMetadata::default()
+            }];
+            let path = format!(
+                "root/table=table_metadata/schema_version={schema_version}/date=2024-03-09/{index}_0.parquet"
+            );
+            let data = rows.to_parquet_bytes(props.clone()).unwrap();
+            assert!(object_store.put_if_absent(&path, data).await.unwrap());
+        }
+
+        let config = ParquetCompactionProcessorConfig {
+            table: "table_metadata".to_string(),
+            file_layout: ParquetFileLayout::HiveDate,
+            ..compaction_config(&dir, false)
+        };
+        let num_compacted = compact_table(&config, &object_store, None).await.unwrap();
+        assert_eq!(num_compacted, 2);
+
+        let files = object_store.list("root/").await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].starts_with(&format!(
+            "root/table=table_metadata/schema_version={schema_version}/date=2024-03-09/"
+        )));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_compact_table_with_commit_log() {
+        let dir = test_dir("commit_log");
+        let object_store = LocalFileSystemObjectStore::new(&dir);
+        let commit_log = TableCommitLog::new(
+            Box::new(LocalFileSystemObjectStore::new(&dir)),
+            "root",
+            "test_processor",
+        );
+        let originals = write_small_files(&object_store, Some(&commit_log)).await;
+
+        // Only the files within the version range are compacted
+        let config = ParquetCompactionProcessorConfig {
+            end_version: Some(4),
+            ..compaction_config(&dir, true)
+        };
+        let num_compacted = compact_table(&config, &object_store, Some(&commit_log))
+            .await
+            .unwrap();
+        assert_eq!(num_compacted, 2);
+
+        let snapshot = commit_log.snapshot("move_resources").await.unwrap();
+        let ranges: Vec<_> = snapshot
+            .iter()
+            .map(|file| (file.min_version, file.max_version))
+            .collect();
+        assert_eq!(ranges, vec![(5, 6), (1, 4)]);
+        assert_eq!(snapshot[0].path, originals[0]);
+
+        // The compacted originals are only tombstoned in the commit log
+        for path in &originals {
+            assert!(object_store.get(path).await.unwrap().is_some());
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_compactions_with_commit_log() {
+        let dir = test_dir("concurrent_compactions");
+        let object_store = LocalFileSystemObjectStore::new(&dir);
+        let commit_log = TableCommitLog::new(
+            Box::new(LocalFileSystemObjectStore::new(&dir)),
+            "root",
+            "test_processor",
+        );
+        write_small_files(&object_store, Some(&commit_log)).await;
+
+        let config = compaction_config(&dir, true);
+        let (first, second) = tokio::join!(
+            compact_table(&config, &object_store, Some(&commit_log)),
+            compact_table(&config, &object_store, Some(&commit_log)),
+        );
+        assert!(first.is_ok() || second.is_ok());
+
+        // Whichever compaction lost, every row is live exactly once
+        let mut ranges: Vec<_> = commit_log
+            .snapshot("move_resources")
+            .await
+            .unwrap()
+            .iter()
+            .map(|file| (file.min_version, file.max_version, file.row_count))
+            .collect();
+        ranges.sort();
+        assert_eq!(ranges.iter().map(|range| range.2).sum::<i64>(), 6);
+        assert!(ranges.windows(2).all(|pair| pair[0].1 < pair[1].0));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -5,8 +5,10 @@ use crate::{
     },
     processors::default::{
         models::{
+            block_epilogue_transactions::ParquetBlockEpilogueTransaction,
             block_metadata_transactions::ParquetBlockMetadataTransaction,
             table_items::{ParquetCurrentTableItem, ParquetTableItem, ParquetTableMetadata},
+            validator_transactions::ParquetValidatorTransaction,
         },
         process_transactions, process_transactions_parquet,
     },
@@ -48,6 +50,11 @@ impl Processable for ParquetDefaultExtractor {
             raw_current_table_items,
             raw_table_metadata,
             _move_modules,
+            _move_module_functions,
+            _move_module_structs,
+            _move_module_upgrades,
+            raw_block_epilogue_transactions,
+            raw_validator_transactions,
         ) = process_transactions(transactions.data.clone());
 
         let parquet_table_items: Vec<ParquetTableItem> = raw_table_items
@@ -67,6 +74,16 @@ impl Processable for ParquetDefaultExtractor {
             .into_iter()
             .map(ParquetTableMetadata::from)
             .collect();
+        let parquet_block_epilogue_transactions: Vec<ParquetBlockEpilogueTransaction> =
+            raw_block_epilogue_transactions
+                .into_iter()
+                .map(ParquetBlockEpilogueTransaction::from)
+                .collect();
+        let parquet_validator_transactions: Vec<ParquetValidatorTransaction> =
+            raw_validator_transactions
+                .into_iter()
+                .map(ParquetValidatorTransaction::from)
+                .collect();
 
         let (
             parquet_move_resources,
@@ -90,6 +107,14 @@ impl Processable for ParquetDefaultExtractor {
             parquet_block_metadata_transactions.len()
         );
         debug!(" - TableMetadata: {}", parquet_table_metadata.len());
+        debug!(
+            " - BlockEpilogueTransactions: {}",
+            parquet_block_epilogue_transactions.len()
+        );
+        debug!(
+            " - ValidatorTransactions: {}",
+            parquet_validator_transactions.len()
+        );
 
         let mut map: HashMap<ParquetTypeEnum, ParquetTypeStructs> = HashMap::new();
 
@@ -135,6 +160,16 @@ impl Processable for ParquetDefaultExtractor {
                 ParquetTypeEnum::TableMetadata,
                 ParquetTypeStructs::TableMetadata(parquet_table_metadata),
             ),
+            (
+                TableFlags::BLOCK_EPILOGUE_TRANSACTIONS,
+                ParquetTypeEnum::BlockEpilogueTransactions,
+                ParquetTypeStructs::BlockEpilogueTransaction(parquet_block_epilogue_transactions),
+            ),
+            (
+                TableFlags::VALIDATOR_TRANSACTIONS,
+                ParquetTypeEnum::ValidatorTransactions,
+                ParquetTypeStructs::ValidatorTransaction(parquet_validator_transactions),
+            ),
         ];
 
         // Populate the map based on opt-in tables
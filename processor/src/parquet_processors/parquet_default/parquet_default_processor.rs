@@ -4,22 +4,20 @@ use crate::{
         processor_config::ProcessorConfig,
     },
     parquet_processors::{
-        initialize_database_pool, initialize_gcs_client, initialize_parquet_buffer_step,
+        initialize_database_pool,
         parquet_default::parquet_default_extractor::ParquetDefaultExtractor,
-        parquet_processor_status_saver::{
-            get_parquet_end_version, get_parquet_starting_version, ParquetProcessorStatusSaver,
-        },
-        parquet_utils::{
-            parquet_version_tracker_step::ParquetVersionTrackerStep, util::HasParquetSchema,
-        },
-        set_backfill_table_flag, ParquetTypeEnum,
+        parquet_processor_status_saver::{get_parquet_end_version, get_parquet_starting_version},
+        parquet_utils::util::HasParquetSchema,
+        set_backfill_table_flag, ParquetStepsBuilder, ParquetTypeEnum,
     },
     processors::default::models::{
+        block_epilogue_transactions::ParquetBlockEpilogueTransaction,
         block_metadata_transactions::ParquetBlockMetadataTransaction,
         move_modules::ParquetMoveModule,
         move_resources::ParquetMoveResource,
         table_items::{ParquetCurrentTableItem, ParquetTableItem, ParquetTableMetadata},
         transactions::ParquetTransaction,
+        validator_transactions::ParquetValidatorTransaction,
         write_set_changes::ParquetWriteSetChange,
     },
     MIGRATIONS,
@@ -27,7 +25,7 @@ use crate::{
 use aptos_indexer_processor_sdk::{
     aptos_indexer_transaction_stream::TransactionStreamConfig,
     builder::ProcessorBuilder,
-    common_steps::{TransactionStreamStep, DEFAULT_UPDATE_PROCESSOR_STATUS_SECS},
+    common_steps::TransactionStreamStep,
     postgres::utils::{
         checkpoint::PostgresChainIdChecker,
         database::{run_migrations, ArcDbPool},
@@ -96,6 +94,15 @@ impl ProcessorTrait for ParquetDefaultProcessor {
             },
         };
 
+        // Upload anything a previous run spilled but didn't checkpoint before picking the starting version
+        let parquet_steps_builder = ParquetStepsBuilder::new(
+            &self.config,
+            self.db_pool.clone(),
+            parquet_db_config,
+            &parquet_processor_config,
+        )
+        .await?;
+
         let (starting_version, ending_version) = (
             get_parquet_starting_version(&self.config, self.db_pool.clone()).await?,
             get_parquet_end_version(&self.config, self.db_pool.clone()).await?,
@@ -114,9 +121,6 @@ impl ProcessorTrait for ParquetDefaultProcessor {
             opt_in_tables: backfill_table,
         };
 
-        let gcs_client =
-            initialize_gcs_client(parquet_db_config.google_application_credentials.clone()).await;
-
         let parquet_type_to_schemas: HashMap<ParquetTypeEnum, Arc<Type>> = [
             (
                 ParquetTypeEnum::MoveResources,
@@ -141,28 +145,24 @@ impl ProcessorTrait for ParquetDefaultProcessor {
                 ParquetTypeEnum::TableMetadata,
                 ParquetTableMetadata::schema(),
             ),
+            (
+                ParquetTypeEnum::BlockEpilogueTransactions,
+                ParquetBlockEpilogueTransaction::schema(),
+            ),
+            (
+                ParquetTypeEnum::ValidatorTransactions,
+                ParquetValidatorTransaction::schema(),
+            ),
         ]
         .into_iter()
         .collect();
 
-        let default_size_buffer_step = initialize_parquet_buffer_step(
-            gcs_client.clone(),
-            parquet_type_to_schemas,
-            parquet_processor_config.upload_interval,
-            parquet_processor_config.max_buffer_size,
-            parquet_db_config.bucket_name.clone(),
-            parquet_db_config.bucket_root.clone(),
-            self.name().to_string(),
-        )
-        .await
-        .unwrap_or_else(|e| {
-            panic!("Failed to initialize parquet buffer step: {e:?}");
-        });
-
-        let parquet_version_tracker_step = ParquetVersionTrackerStep::new(
-            ParquetProcessorStatusSaver::new(self.config.clone(), self.db_pool.clone()),
-            DEFAULT_UPDATE_PROCESSOR_STATUS_SECS,
-        );
+        let (default_size_buffer_step, parquet_version_tracker_step) = parquet_steps_builder
+            .build(parquet_type_to_schemas)
+            .await
+            .unwrap_or_else(|e| {
+                panic!("Failed to initialize parquet buffer step: {e:?}");
+            });
 
         let channel_size = parquet_processor_config.channel_size;
 
@@ -6,7 +6,9 @@ use crate::{
     processors::fungible_asset::{
         fungible_asset_models::{
             v2_fungible_asset_activities::ParquetFungibleAssetActivity,
-            v2_fungible_asset_balances::ParquetFungibleAssetBalance,
+            v2_fungible_asset_balances::{
+                ParquetCurrentUnifiedFungibleAssetBalance, ParquetFungibleAssetBalance,
+            },
             v2_fungible_asset_to_coin_mappings::{
                 FungibleAssetToCoinMapping, FungibleAssetToCoinMappings,
                 ParquetFungibleAssetToCoinMapping,
@@ -74,7 +76,7 @@ impl Processable for ParquetFungibleAssetExtractor {
             raw_fungible_asset_activities,
             raw_fungible_asset_metadata,
             raw_fungible_asset_balances,
-            _,
+            (raw_current_unified_fab_v1, raw_current_unified_fab_v2),
             _raw_coin_supply,
             raw_fa_to_coin_mappings,
         ) = parse_v2_coin(&transactions.data, Some(&self.fa_to_coin_mapping)).await;
@@ -97,6 +99,15 @@ impl Processable for ParquetFungibleAssetExtractor {
                 .map(ParquetFungibleAssetBalance::from)
                 .collect();
 
+        // v1 and v2 rows for the same storage id are kept as separate rows, each with only its own
+        // half of the columns populated; readers merge them the same way the Postgres upsert does.
+        let parquet_current_unified_fab: Vec<ParquetCurrentUnifiedFungibleAssetBalance> =
+            raw_current_unified_fab_v1
+                .into_iter()
+                .chain(raw_current_unified_fab_v2)
+                .map(ParquetCurrentUnifiedFungibleAssetBalance::from)
+                .collect();
+
         let parquet_fa_to_coin_mappings: Vec<ParquetFungibleAssetToCoinMapping> =
             raw_fa_to_coin_mappings
                 .into_iter()
@@ -126,6 +137,11 @@ impl Processable for ParquetFungibleAssetExtractor {
                 ParquetTypeEnum::FungibleAssetToCoinMappings,
                 ParquetTypeStructs::FungibleAssetToCoinMappings(parquet_fa_to_coin_mappings),
             ),
+            (
+                TableFlags::CURRENT_FUNGIBLE_ASSET_BALANCES,
+                ParquetTypeEnum::CurrentFungibleAssetBalances,
+                ParquetTypeStructs::CurrentFungibleAssetBalance(parquet_current_unified_fab),
+            ),
         ];
 
         // Populate the map based on opt-in tables
@@ -0,0 +1,2 @@
+pub mod parquet_gas_fee_extractor;
+pub mod parquet_gas_fee_processor;
@@ -0,0 +1,113 @@
+use crate::{
+    parquet_processors::{
+        parquet_utils::util::add_to_map_if_opted_in_for_backfill, ParquetTypeEnum,
+        ParquetTypeStructs,
+    },
+    processors::gas_fees::{
+        gas_fee_rollups::{
+            roll_up, GasFeeBlockSummary, GasFeeEntryFunctionHourlySummary, GasFeeHourlySummary,
+            ParquetGasFeeBlockSummary, ParquetGasFeeEntryFunctionHourlySummary,
+            ParquetGasFeeHourlySummary,
+        },
+        models::{GasFee, ParquetGasFee},
+    },
+    utils::table_flags::TableFlags,
+};
+use aptos_indexer_processor_sdk::{
+    aptos_protos::transaction::v1::Transaction,
+    traits::{async_step::AsyncRunType, AsyncStep, NamedStep, Processable},
+    types::transaction_context::TransactionContext,
+    utils::errors::ProcessorError,
+};
+use async_trait::async_trait;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// Extracts parquet data from transactions, allowing optional selection of specific tables.
+pub struct ParquetGasFeeExtractor
+where
+    Self: Processable + Send + Sized + 'static,
+{
+    pub opt_in_tables: TableFlags,
+}
+
+type ParquetTypeMap = HashMap<ParquetTypeEnum, ParquetTypeStructs>;
+
+#[async_trait]
+impl Processable for ParquetGasFeeExtractor {
+    type Input = Vec<Transaction>;
+    type Output = ParquetTypeMap;
+    type RunType = AsyncRunType;
+
+    async fn process(
+        &mut self,
+        transactions: TransactionContext<Self::Input>,
+    ) -> anyhow::Result<Option<TransactionContext<ParquetTypeMap>>, ProcessorError> {
+        let gas_fees: Vec<GasFee> = transactions
+            .data
+            .par_iter()
+            .filter_map(GasFee::from_transaction)
+            .collect();
+
+        // Rolled up per batch, see `gas_fee_rollups`
+        let block_summaries: Vec<ParquetGasFeeBlockSummary> =
+            roll_up::<GasFeeBlockSummary>(&gas_fees, vec![])
+                .into_iter()
+                .map(ParquetGasFeeBlockSummary::from)
+                .collect();
+        let hourly_summaries: Vec<ParquetGasFeeHourlySummary> =
+            roll_up::<GasFeeHourlySummary>(&gas_fees, vec![])
+                .into_iter()
+                .map(ParquetGasFeeHourlySummary::from)
+                .collect();
+        let entry_function_hourly_summaries: Vec<ParquetGasFeeEntryFunctionHourlySummary> =
+            roll_up::<GasFeeEntryFunctionHourlySummary>(&gas_fees, vec![])
+                .into_iter()
+                .map(ParquetGasFeeEntryFunctionHourlySummary::from)
+                .collect();
+        let gas_fees: Vec<ParquetGasFee> = gas_fees.into_iter().map(ParquetGasFee::from).collect();
+
+        let mut map: HashMap<ParquetTypeEnum, ParquetTypeStructs> = HashMap::new();
+
+        let data_types = [
+            (
+                TableFlags::GAS_FEES,
+                ParquetTypeEnum::GasFees,
+                ParquetTypeStructs::GasFee(gas_fees),
+            ),
+            (
+                TableFlags::GAS_FEE_BLOCK_SUMMARIES,
+                ParquetTypeEnum::GasFeeBlockSummaries,
+                ParquetTypeStructs::GasFeeBlockSummary(block_summaries),
+            ),
+            (
+                TableFlags::GAS_FEE_HOURLY_SUMMARIES,
+                ParquetTypeEnum::GasFeeHourlySummaries,
+                ParquetTypeStructs::GasFeeHourlySummary(hourly_summaries),
+            ),
+            (
+                TableFlags::GAS_FEE_ENTRY_FUNCTION_HOURLY_SUMMARIES,
+                ParquetTypeEnum::GasFeeEntryFunctionHourlySummaries,
+                ParquetTypeStructs::GasFeeEntryFunctionHourlySummary(
+                    entry_function_hourly_summaries,
+                ),
+            ),
+        ];
+
+        // Populate the map based on opt-in tables
+        add_to_map_if_opted_in_for_backfill(self.opt_in_tables, &mut map, data_types.to_vec());
+
+        Ok(Some(TransactionContext {
+            data: map,
+            metadata: transactions.metadata,
+        }))
+    }
+}
+
+impl AsyncStep for ParquetGasFeeExtractor {}
+
+impl NamedStep for ParquetGasFeeExtractor {
+    fn name(&self) -> String {
+        "ParquetGasFeeExtractor".to_string()
+    }
+}
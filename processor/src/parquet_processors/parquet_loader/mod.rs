@@ -0,0 +1,2 @@
+pub mod parquet_loader_processor;
+pub mod parquet_row_reader;
@@ -0,0 +1,644 @@
+use crate::{
+    config::{
+        db_config::DbConfig,
+        indexer_processor_config::IndexerProcessorConfig,
+        processor_config::{ProcessorConfig, ProcessorName},
+    },
+    parquet_processors::{
+        initialize_gcs_client,
+        parquet_loader::parquet_row_reader::read_parquet_rows,
+        parquet_utils::{
+            gcs_uploader::ParquetFileLayout,
+            object_store::{GcsObjectStore, LocalFileSystemObjectStore, ObjectStore},
+            table_commit_log::TableCommitLog,
+            util::{HasTimestamp, HasVersion},
+        },
+        ParquetTypeEnum,
+    },
+    processors::{
+        account_transactions::{
+            account_transactions_model::ParquetAccountTransaction,
+            account_transactions_storer::insert_account_transactions_query,
+        },
+        default::{
+            default_storer::insert_transactions_query, models::transactions::ParquetTransaction,
+        },
+        events::{events_model::ParquetEvent, events_storer::insert_events_query},
+        fungible_asset::{
+            fungible_asset_models::{
+                v2_fungible_asset_activities::ParquetFungibleAssetActivity,
+                v2_fungible_asset_balances::ParquetCurrentUnifiedFungibleAssetBalance,
+            },
+            fungible_asset_storer::{
+                insert_current_unified_fungible_asset_balances_v1_query,
+                insert_current_unified_fungible_asset_balances_v2_query,
+                insert_fungible_asset_activities_query,
+            },
+        },
+        objects::{
+            objects_storer::{insert_current_objects_query, insert_objects_query},
+            v2_objects_models::{ParquetCurrentObject, ParquetObject},
+        },
+        processor_status_saver::save_processor_status,
+        token_v2::{
+            token_v2_models::{
+                v2_token_activities::ParquetTokenActivityV2,
+                v2_token_ownerships::{ParquetCurrentTokenOwnershipV2, ParquetTokenOwnershipV2},
+            },
+            token_v2_processor_queries::{
+                insert_current_token_ownerships_v2_query, insert_token_activities_v2_query,
+                insert_token_ownerships_v2_query,
+            },
+        },
+    },
+    MIGRATIONS,
+};
+use ahash::AHashMap;
+use anyhow::{bail, Context, Result};
+use aptos_indexer_processor_sdk::{
+    aptos_protos::util::timestamp::Timestamp,
+    postgres::utils::database::{
+        execute_in_chunks, get_config_table_chunk_size, new_db_pool, run_migrations, ArcDbPool,
+    },
+    traits::processor_trait::ProcessorTrait,
+    types::transaction_context::{TransactionContext, TransactionMetadata},
+};
+use chrono::NaiveDateTime;
+use diesel::{pg::Pg, query_builder::QueryFragment};
+use field_count::FieldCount;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::{hash_map::Entry, HashSet},
+    hash::Hash,
+    path::Path,
+};
+use strum::IntoEnumIterator;
+use tracing::{info, warn};
+
+const PARQUET_FILE_EXTENSION: &str = ".parquet";
+
+/// Loads the parquet files written by the parquet processors into the Postgres tables of a
+/// Postgres processor, e.g. to hydrate a fresh database without replaying the stream from genesis.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ParquetLoaderProcessorConfig {
+    // Postgres processor that owns the loaded tables. Its processor_status is set to the loaded
+    // version so it resumes right after the loaded data.
+    pub target_processor: ProcessorName,
+    // Parquet table names to load
+    pub tables: HashSet<String>,
+    pub source: ParquetLoaderSource,
+    #[serde(default)]
+    pub bucket_root: String,
+    // Read the files from the table commit log instead of listing them, which skips files that
+    // were uploaded but never committed.
+    #[serde(default)]
+    pub table_format: bool,
+    // Number of rows to insert, per chunk, keyed by parquet table name
+    #[serde(default = "AHashMap::new")]
+    pub per_table_chunk_sizes: AHashMap<String, usize>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ParquetLoaderSource {
+    Gcs {
+        bucket_name: String,
+        #[serde(default)]
+        google_application_credentials: Option<String>,
+    },
+    LocalFileSystem {
+        path: String,
+    },
+}
+
+impl ParquetLoaderSource {
+    pub async fn object_store(&self) -> Box<dyn ObjectStore> {
+        match self {
+            ParquetLoaderSource::Gcs {
+                bucket_name,
+                google_application_credentials,
+            } => Box::new(GcsObjectStore::new(
+                initialize_gcs_client(google_application_credentials.clone()).await,
+                bucket_name.clone(),
+            )),
+            ParquetLoaderSource::LocalFileSystem { path } => {
+                Box::new(LocalFileSystemObjectStore::new(path))
+            },
+        }
+    }
+}
+
+impl ParquetLoaderProcessorConfig {
+    /// Parses the configured tables, rejecting the ones that can't be loaded.
+    fn parquet_types(&self) -> Result<Vec<ParquetTypeEnum>> {
+        let mut parquet_types = vec![];
+        for table_name in &self.tables {
+            let Some(parquet_type) = ParquetTypeEnum::iter()
+                .find(|parquet_type| parquet_type.to_string() == *table_name)
+            else {
+                bail!("Unknown parquet table '{table_name}'");
+            };
+            if !is_loadable(parquet_type) {
+                bail!("Loading parquet table '{table_name}' into Postgres is not supported");
+            }
+            parquet_types.push(parquet_type);
+        }
+        parquet_types.sort_by_key(|parquet_type| parquet_type.to_string());
+        Ok(parquet_types)
+    }
+}
+
+/// Tables with a Postgres model and insert query to load into.
+const fn is_loadable(parquet_type: ParquetTypeEnum) -> bool {
+    matches!(
+        parquet_type,
+        ParquetTypeEnum::Transactions
+            | ParquetTypeEnum::Events
+            | ParquetTypeEnum::AccountTransactions
+            | ParquetTypeEnum::Objects
+            | ParquetTypeEnum::CurrentObjects
+            | ParquetTypeEnum::FungibleAssetActivities
+            | ParquetTypeEnum::CurrentFungibleAssetBalances
+            | ParquetTypeEnum::TokenActivitiesV2
+            | ParquetTypeEnum::TokenOwnershipsV2
+            | ParquetTypeEnum::CurrentTokenOwnershipsV2
+    )
+}
+
+/// Latest transaction loaded into a table.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+struct LoadedVersion {
+    version: i64,
+    timestamp: Option<NaiveDateTime>,
+}
+
+fn latest_loaded_version<P: HasVersion + HasTimestamp>(rows: &[P]) -> Option<LoadedVersion> {
+    rows.iter()
+        .map(|row| LoadedVersion {
+            version: row.version(),
+            timestamp: row.timestamp(),
+        })
+        .max()
+}
+
+/// Keeps the latest row of each key. A file of a current table can hold several versions of the
+/// same row, and Postgres can't upsert the same key twice in one statement.
+fn latest_by_key<P: HasVersion, K: Eq + Hash>(rows: Vec<P>, key: impl Fn(&P) -> K) -> Vec<P> {
+    let mut latest: AHashMap<K, P> = AHashMap::new();
+    for row in rows {
+        match latest.entry(key(&row)) {
+            Entry::Occupied(mut entry) => {
+                if row.version() >= entry.get().version() {
+                    entry.insert(row);
+                }
+            },
+            Entry::Vacant(entry) => {
+                entry.insert(row);
+            },
+        }
+    }
+    latest.into_iter().map(|(_, row)| row).collect()
+}
+
+/// Prefixes of the current schema version of a table, for both the upload time and the Hive style
+/// layouts. Files of other schema versions can't be read into the current model.
+fn table_prefixes(bucket_root: &str, parquet_type: ParquetTypeEnum) -> Vec<String> {
+    [ParquetFileLayout::UploadTime, ParquetFileLayout::HiveDate]
+        .into_iter()
+        .map(|file_layout| {
+            file_layout.table_prefix(
+                Path::new(bucket_root),
+                &parquet_type.to_string(),
+                parquet_type.schema_version(),
+            )
+        })
+        .collect()
+}
+
+/// Lists the parquet files of the current schema version of a table.
+async fn list_table_files(
+    object_store: &dyn ObjectStore,
+    bucket_root: &str,
+    parquet_type: ParquetTypeEnum,
+) -> Result<Vec<String>> {
+    let mut files = vec![];
+    for prefix in table_prefixes(bucket_root, parquet_type) {
+        files.extend(
+            object_store
+                .list(&prefix)
+                .await?
+                .into_iter()
+                .filter(|name| name.ends_with(PARQUET_FILE_EXTENSION)),
+        );
+    }
+    Ok(files)
+}
+
+pub struct ParquetLoaderProcessor {
+    pub config: IndexerProcessorConfig,
+    pub db_pool: ArcDbPool,
+}
+
+impl ParquetLoaderProcessor {
+    pub async fn new(config: IndexerProcessorConfig) -> Result<Self> {
+        match config.db_config {
+            DbConfig::PostgresConfig(ref postgres_config) => {
+                let conn_pool = new_db_pool(
+                    &postgres_config.connection_string,
+                    Some(postgres_config.db_pool_size),
+                )
+                .await
+                .map_err(|e| {
+                    anyhow::anyhow!(
+                        "Failed to create connection pool for PostgresConfig: {:?}",
+                        e
+                    )
+                })?;
+
+                Ok(Self {
+                    config,
+                    db_pool: conn_pool,
+                })
+            },
+            _ => Err(anyhow::anyhow!(
+                "Invalid db config for ParquetLoaderProcessor {:?}",
+                config.db_config
+            )),
+        }
+    }
+
+    /// Converts the rows to the Postgres model and upserts them with the storer's insert query.
+    async fn insert_rows<P, G, U>(
+        &self,
+        loader_config: &ParquetLoaderProcessorConfig,
+        parquet_type: ParquetTypeEnum,
+        rows: Vec<P>,
+        build_query: fn(Vec<G>) -> U,
+    ) -> Result<Option<LoadedVersion>>
+    where
+        P: HasVersion + HasTimestamp,
+        G: TryFrom<P> + FieldCount + Serialize + DeserializeOwned + Clone + Send + 'static,
+        <G as TryFrom<P>>::Error: Into<anyhow::Error>,
+        U: QueryFragment<Pg> + diesel::query_builder::QueryId + Send + 'static,
+    {
+        let loaded_version = latest_loaded_version(&rows);
+        let items = rows
+            .into_iter()
+            .map(|row| G::try_from(row).map_err(Into::into))
+            .collect::<Result<Vec<G>>>()?;
+        execute_in_chunks(
+            self.db_pool.clone(),
+            build_query,
+            &items,
+            get_config_table_chunk_size::<G>(
+                &parquet_type.to_string(),
+                &loader_config.per_table_chunk_sizes,
+            ),
+        )
+        .await?;
+        Ok(loaded_version)
+    }
+
+    /// Loads a parquet file and returns the latest version it contained.
+    async fn load_file(
+        &self,
+        loader_config: &ParquetLoaderProcessorConfig,
+        parquet_type: ParquetTypeEnum,
+        data: Vec<u8>,
+    ) -> Result<Option<LoadedVersion>> {
+        match parquet_type {
+            ParquetTypeEnum::Transactions => {
+                let rows: Vec<ParquetTransaction> = read_parquet_rows(data, parquet_type)?;
+                self.insert_rows(loader_config, parquet_type, rows, insert_transactions_query)
+                    .await
+            },
+            ParquetTypeEnum::Events => {
+                let rows: Vec<ParquetEvent> = read_parquet_rows(data, parquet_type)?;
+                self.insert_rows(loader_config, parquet_type, rows, insert_events_query)
+                    .await
+            },
+            ParquetTypeEnum::AccountTransactions => {
+                let rows: Vec<ParquetAccountTransaction> = read_parquet_rows(data, parquet_type)?;
+                self.insert_rows(
+                    loader_config,
+                    parquet_type,
+                    rows,
+                    insert_account_transactions_query,
+                )
+                .await
+            },
+            ParquetTypeEnum::Objects => {
+                let rows: Vec<ParquetObject> = read_parquet_rows(data, parquet_type)?;
+                self.insert_rows(loader_config, parquet_type, rows, insert_objects_query)
+                    .await
+            },
+            ParquetTypeEnum::CurrentObjects => {
+                let rows: Vec<ParquetCurrentObject> = read_parquet_rows(data, parquet_type)?;
+                let rows = latest_by_key(rows, |row| row.object_address.clone());
+                self.insert_rows(
+                    loader_config,
+                    parquet_type,
+                    rows,
+                    insert_current_objects_query,
+                )
+                .await
+            },
+            ParquetTypeEnum::FungibleAssetActivities => {
+                let rows: Vec<ParquetFungibleAssetActivity> =
+                    read_parquet_rows(data, parquet_type)?;
+                self.insert_rows(
+                    loader_config,
+                    parquet_type,
+                    rows,
+                    insert_fungible_asset_activities_query,
+                )
+                .await
+            },
+            ParquetTypeEnum::CurrentFungibleAssetBalances => {
+                let rows: Vec<ParquetCurrentUnifiedFungibleAssetBalance> =
+                    read_parquet_rows(data, parquet_type)?;
+                // v1 and v2 balances update different columns of the same row
+                let (v1_rows, v2_rows): (Vec<_>, Vec<_>) = rows
+                    .into_iter()
+                    .partition(|row| row.last_transaction_version_v1.is_some());
+                let v1_loaded_version = self
+                    .insert_rows(
+                        loader_config,
+                        parquet_type,
+                        latest_by_key(v1_rows, |row| row.storage_id.clone()),
+                        insert_current_unified_fungible_asset_balances_v1_query,
+                    )
+                    .await?;
+                let v2_loaded_version = self
+                    .insert_rows(
+                        loader_config,
+                        parquet_type,
+                        latest_by_key(v2_rows, |row| row.storage_id.clone()),
+                        insert_current_unified_fungible_asset_balances_v2_query,
+                    )
+                    .await?;
+                Ok(v1_loaded_version.max(v2_loaded_version))
+            },
+            ParquetTypeEnum::TokenActivitiesV2 => {
+                let rows: Vec<ParquetTokenActivityV2> = read_parquet_rows(data, parquet_type)?;
+                self.insert_rows(
+                    loader_config,
+                    parquet_type,
+                    rows,
+                    insert_token_activities_v2_query,
+                )
+                .await
+            },
+            ParquetTypeEnum::TokenOwnershipsV2 => {
+                let rows: Vec<ParquetTokenOwnershipV2> = read_parquet_rows(data, parquet_type)?;
+                self.insert_rows(
+                    loader_config,
+                    parquet_type,
+                    rows,
+                    insert_token_ownerships_v2_query,
+                )
+                .await
+            },
+            ParquetTypeEnum::CurrentTokenOwnershipsV2 => {
+                let rows: Vec<ParquetCurrentTokenOwnershipV2> =
+                    read_parquet_rows(data, parquet_type)?;
+                let rows = latest_by_key(rows, |row| {
+                    (
+                        row.token_data_id.clone(),
+                        row.property_version_v1,
+                        row.owner_address.clone(),
+                        row.storage_id.clone(),
+                    )
+                });
+                self.insert_rows(
+                    loader_config,
+                    parquet_type,
+                    rows,
+                    insert_current_token_ownerships_v2_query,
+                )
+                .await
+            },
+            _ => bail!("Loading parquet table '{parquet_type}' into Postgres is not supported"),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ProcessorTrait for ParquetLoaderProcessor {
+    fn name(&self) -> &'static str {
+        self.config.processor_config.name()
+    }
+
+    async fn run_processor(&self) -> Result<()> {
+        // Run migrations
+        if let DbConfig::PostgresConfig(ref postgres_config) = self.config.db_config {
+            run_migrations(
+                postgres_config.connection_string.clone(),
+                self.db_pool.clone(),
+                MIGRATIONS,
+            )
+            .await;
+        }
+
+        let loader_config = match &self.config.processor_config {
+            ProcessorConfig::ParquetLoaderProcessor(loader_config) => loader_config,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Invalid processor config for ParquetLoaderProcessor: {:?}",
+                    self.config.processor_config
+                ))
+            },
+        };
+        let parquet_types = loader_config.parquet_types()?;
+        let object_store = loader_config.source.object_store().await;
+        let commit_log = if loader_config.table_format {
+            Some(TableCommitLog::new(
+                loader_config.source.object_store().await,
+                &loader_config.bucket_root,
+                self.name(),
+            ))
+        } else {
+            None
+        };
+
+        let mut table_loaded_versions = vec![];
+        for parquet_type in parquet_types {
+            let table_name = parquet_type.to_string();
+            let files = match &commit_log {
+                Some(commit_log) => {
+                    let prefixes = table_prefixes(&loader_config.bucket_root, parquet_type);
+                    commit_log
+                        .snapshot(&table_name)
+                        .await?
+                        .into_iter()
+                        .filter(|file| prefixes.iter().any(|prefix| file.path.starts_with(prefix)))
+                        .map(|file| file.path)
+                        .collect()
+                },
+                None => {
+                    list_table_files(
+                        object_store.as_ref(),
+                        &loader_config.bucket_root,
+                        parquet_type,
+                    )
+                    .await?
+                },
+            };
+            info!(
+                table_name = table_name,
+                num_files = files.len(),
+                "Loading parquet table into Postgres"
+            );
+
+            let mut table_loaded_version = None;
+            for file in files {
+                let data = object_store
+                    .get(&file)
+                    .await?
+                    .with_context(|| format!("Parquet file {file} disappeared"))?;
+                let file_loaded_version =
+                    self.load_file(loader_config, parquet_type, data)
+                        .await
+                        .with_context(|| format!("Failed to load parquet file {file}"))?;
+                table_loaded_version = table_loaded_version.max(file_loaded_version);
+            }
+            match table_loaded_version {
+                Some(loaded_version) => {
+                    info!(
+                        table_name = table_name,
+                        version = loaded_version.version,
+                        "Loaded parquet table into Postgres"
+                    );
+                    table_loaded_versions.push(loaded_version);
+                },
+                None => warn!(table_name = table_name, "No parquet rows found for table"),
+            }
+        }
+
+        // Every table is complete up to the lowest version loaded across the tables, so the
+        // target processor resumes from there and reprocesses the rest idempotently.
+        let Some(loaded_version) = table_loaded_versions.into_iter().min() else {
+            warn!("No parquet rows loaded, processor status is left unchanged");
+            return Ok(());
+        };
+        let end_transaction_timestamp = loaded_version.timestamp.map(|timestamp| {
+            let timestamp = timestamp.and_utc();
+            Timestamp {
+                seconds: timestamp.timestamp(),
+                nanos: timestamp.timestamp_subsec_nanos() as i32,
+            }
+        });
+        save_processor_status(
+            &loader_config.target_processor.to_string(),
+            self.config.processor_mode.clone(),
+            &TransactionContext {
+                data: (),
+                metadata: TransactionMetadata {
+                    end_version: loaded_version.version as u64,
+                    end_transaction_timestamp,
+                    ..TransactionMetadata::default()
+                },
+            },
+            self.db_pool.clone(),
+        )
+        .await?;
+        info!(
+            target_processor = loader_config.target_processor.to_string(),
+            version = loaded_version.version,
+            "Set processor status to the loaded version"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_by_key() {
+        let object = |object_address: &str, last_transaction_version| ParquetCurrentObject {
+            object_address: object_address.to_string(),
+            last_transaction_version,
+            ..ParquetCurrentObject::default()
+        };
+        let mut rows = latest_by_key(
+            vec![
+                object("0x1", 5),
+                object("0x2", 3),
+                object("0x1", 7),
+                object("0x1", 6),
+            ],
+            |row| row.object_address.clone(),
+        );
+        rows.sort_by_key(|row| row.last_transaction_version);
+        let rows: Vec<_> = rows
+            .iter()
+            .map(|row| (row.object_address.as_str(), row.last_transaction_version))
+            .collect();
+        assert_eq!(rows, vec![("0x2", 3), ("0x1", 7)]);
+    }
+
+    #[test]
+    fn test_unsupported_tables_are_rejected() {
+        let config = |table: &str| ParquetLoaderProcessorConfig {
+            target_processor: ProcessorName::DefaultProcessor,
+            tables: HashSet::from([table.to_string()]),
+            source: ParquetLoaderSource::LocalFileSystem {
+                path: "/tmp".to_string(),
+            },
+            bucket_root: String::new(),
+            table_format: false,
+            per_table_chunk_sizes: AHashMap::new(),
+        };
+        assert!(config("events").parquet_types().is_ok());
+        assert!(config("transactions").parquet_types().is_ok());
+        assert!(config("token_ownerships_v2").parquet_types().is_ok());
+        assert!(config("table_items").parquet_types().is_err());
+        assert!(config("unknown_table").parquet_types().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_table_files_skips_other_schema_versions() {
+        let dir = std::env::temp_dir().join(format!("parquet_loader_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let object_store = LocalFileSystemObjectStore::new(&dir);
+        let parquet_type = ParquetTypeEnum::Events;
+        let schema_version = parquet_type.schema_version();
+
+        let mut current = vec![];
+        for file_layout in [ParquetFileLayout::UploadTime, ParquetFileLayout::HiveDate] {
+            let path = file_layout.file_path(
+                Path::new("root"),
+                "events",
+                schema_version,
+                chrono::NaiveDate::default(),
+                1,
+                2,
+            );
+            current.push(path.display().to_string());
+            let other_path = file_layout.file_path(
+                Path::new("root"),
+                "events",
+                schema_version + 1,
+                chrono::NaiveDate::default(),
+                1,
+                2,
+            );
+            for path in [path, other_path] {
+                let path = path.display().to_string();
+                assert!(object_store.put_if_absent(&path, vec![]).await.unwrap());
+            }
+        }
+
+        let mut files = list_table_files(&object_store, "root", parquet_type)
+            .await
+            .unwrap();
+        files.sort();
+        current.sort();
+        assert_eq!(files, current);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
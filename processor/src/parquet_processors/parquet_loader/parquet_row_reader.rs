@@ -0,0 +1,164 @@
+use crate::parquet_processors::{
+    parquet_utils::schema_registry::read_schema_version, ParquetTypeEnum,
+};
+use anyhow::{bail, Context, Result};
+use chrono::DateTime;
+use hyper::body::Bytes;
+use parquet::{
+    file::reader::{FileReader, SerializedFileReader},
+    record::Field,
+};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+/// Reads the rows of a parquet file written by a parquet processor back into its parquet model.
+///
+/// Rows go through serde, so the model field names have to match the column names, which is the
+/// case for everything written with `ParquetRecordWriter`. Files written with another schema
+/// version than the current one are rejected since the model can't represent them.
+pub fn read_parquet_rows<T: DeserializeOwned>(
+    data: Vec<u8>,
+    parquet_type: ParquetTypeEnum,
+) -> Result<Vec<T>> {
+    let reader = SerializedFileReader::new(Bytes::from(data))?;
+    let schema_version = read_schema_version(reader.metadata().file_metadata());
+    if schema_version != parquet_type.schema_version() {
+        bail!(
+            "Can't load {parquet_type} schema version {schema_version}, the current schema version is {}",
+            parquet_type.schema_version()
+        );
+    }
+
+    reader
+        .get_row_iter(None)?
+        .map(|row| {
+            let row = row?;
+            let mut columns = Map::new();
+            for (name, field) in row.get_column_iter() {
+                columns.insert(
+                    name.clone(),
+                    field_to_json(field).with_context(|| format!("Invalid column {name}"))?,
+                );
+            }
+            Ok(serde_json::from_value(Value::Object(columns))?)
+        })
+        .collect()
+}
+
+/// Converts a parquet field into the json value the model field deserializes from. Only the
+/// physical types produced by `ParquetRecordWriter` for the parquet models are supported.
+fn field_to_json(field: &Field) -> Result<Value> {
+    Ok(match field {
+        Field::Null => Value::Null,
+        Field::Bool(value) => Value::from(*value),
+        Field::Int(value) => Value::from(*value),
+        Field::Long(value) => Value::from(*value),
+        Field::UInt(value) => Value::from(*value),
+        Field::ULong(value) => Value::from(*value),
+        Field::Double(value) => Value::from(*value),
+        Field::Str(value) => Value::from(value.as_str()),
+        Field::TimestampMillis(value) => serde_json::to_value(
+            DateTime::from_timestamp_millis(*value)
+                .context("Timestamp out of range")?
+                .naive_utc(),
+        )?,
+        Field::TimestampMicros(value) => serde_json::to_value(
+            DateTime::from_timestamp_micros(*value)
+                .context("Timestamp out of range")?
+                .naive_utc(),
+        )?,
+        _ => bail!("Unsupported parquet field {field:?}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parquet_processors::{
+            parquet_utils::{parquet_writer_config::ParquetWriterConfig, util::HasParquetSchema},
+            ParquetTypeTrait,
+        },
+        processors::{
+            events::events_model::ParquetEvent, objects::v2_objects_models::ParquetCurrentObject,
+        },
+    };
+    use chrono::NaiveDateTime;
+    use parquet::file::properties::WriterProperties;
+    use serde::Serialize;
+    use std::sync::Arc;
+
+    fn timestamp() -> NaiveDateTime {
+        DateTime::from_timestamp_millis(1_700_000_000_123)
+            .unwrap()
+            .naive_utc()
+    }
+
+    fn writer_properties<T: HasParquetSchema>(
+        parquet_type: ParquetTypeEnum,
+    ) -> Arc<WriterProperties> {
+        Arc::new(
+            ParquetWriterConfig::default()
+                .table_writer_properties(parquet_type, &T::schema())
+                .unwrap(),
+        )
+    }
+
+    fn assert_same_rows<T: Serialize>(expected: &[T], actual: &[T]) {
+        assert_eq!(
+            serde_json::to_value(expected).unwrap(),
+            serde_json::to_value(actual).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_read_parquet_rows_round_trip() {
+        let events = vec![
+            ParquetEvent {
+                txn_version: 10,
+                account_address: "0x1".to_string(),
+                sequence_number: 3,
+                creation_number: 4,
+                block_height: 2,
+                event_type: "0x1::coin::DepositEvent".to_string(),
+                data: "{\"amount\":\"100\"}".to_string(),
+                event_index: 0,
+                indexed_type: "0x1::coin::DepositEvent".to_string(),
+                type_tag_bytes: 23,
+                total_bytes: 40,
+                block_timestamp: timestamp(),
+            },
+            ParquetEvent {
+                txn_version: 11,
+                event_index: 1,
+                block_timestamp: timestamp(),
+                ..ParquetEvent::default()
+            },
+        ];
+        let data = events
+            .to_parquet_bytes(writer_properties::<ParquetEvent>(ParquetTypeEnum::Events))
+            .unwrap();
+        let rows: Vec<ParquetEvent> = read_parquet_rows(data, ParquetTypeEnum::Events).unwrap();
+        assert_same_rows(&events, &rows);
+
+        let current_objects = vec![ParquetCurrentObject {
+            object_address: "0xa".to_string(),
+            owner_address: "0xb".to_string(),
+            state_key_hash: "0xc".to_string(),
+            allow_ungated_transfer: true,
+            last_guid_creation_num: u64::MAX,
+            last_transaction_version: 12,
+            is_deleted: false,
+            untransferrable: true,
+            block_timestamp: timestamp(),
+        }];
+        let data = current_objects
+            .to_parquet_bytes(writer_properties::<ParquetCurrentObject>(
+                ParquetTypeEnum::CurrentObjects,
+            ))
+            .unwrap();
+        let rows: Vec<ParquetCurrentObject> =
+            read_parquet_rows(data, ParquetTypeEnum::CurrentObjects).unwrap();
+        assert_same_rows(&current_objects, &rows);
+    }
+}
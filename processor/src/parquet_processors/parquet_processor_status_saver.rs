@@ -52,6 +52,44 @@ impl ParquetProcessorStatusSaverTrait for ParquetProcessorStatusSaver {
         )
         .await
     }
+
+    async fn get_parquet_processor_status(
+        &self,
+        table_name: &str,
+    ) -> Result<Option<u64>, ProcessorError> {
+        let processor_id = format_table_name(self.config.processor_config.name(), table_name);
+        let mut conn = self
+            .db_pool
+            .get()
+            .await
+            .map_err(|err| ProcessorError::ProcessError {
+                message: format!("Failed to get database connection. {err:?}"),
+            })?;
+        match &self.config.processor_mode {
+            ProcessorMode::Default(_) => {
+                ProcessorStatusQuery::get_by_processor(&processor_id, &mut conn)
+                    .await
+                    .map(|status| status.map(|status| status.last_success_version as u64))
+                    .map_err(|e| ProcessorError::ProcessError {
+                        message: format!("Failed to query processor_status table. {e:?}"),
+                    })
+            },
+            ProcessorMode::Backfill(BackfillConfig { backfill_id, .. }) => {
+                BackfillProcessorStatusQuery::get_by_processor(
+                    &processor_id,
+                    backfill_id,
+                    &mut conn,
+                )
+                .await
+                .map(|status| status.map(|status| status.last_success_version as u64))
+                .map_err(|e| ProcessorError::ProcessError {
+                    message: format!("Failed to query backfill_processor_status table. {e:?}"),
+                })
+            },
+            // Testing mode doesn't store checkpoints
+            ProcessorMode::Testing(_) => Ok(None),
+        }
+    }
 }
 
 /// Get the appropriate minimum last success version for the parquet processors.
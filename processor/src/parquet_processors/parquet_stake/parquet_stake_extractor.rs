@@ -5,9 +5,12 @@ use crate::{
     },
     processors::stake::{
         models::{
+            current_delegated_voter::ParquetCurrentDelegatedVoter,
             delegator_activities::ParquetDelegatedStakingActivity,
             delegator_balances::{ParquetCurrentDelegatorBalance, ParquetDelegatorBalance},
+            delegator_pools::ParquetDelegatorPool,
             proposal_votes::ParquetProposalVote,
+            staking_pool_voter::ParquetCurrentStakingPoolVoter,
         },
         parse_stake_data,
     },
@@ -44,15 +47,15 @@ impl Processable for ParquetStakeExtractor {
         transactions: TransactionContext<Self::Input>,
     ) -> anyhow::Result<Option<TransactionContext<ParquetTypeMap>>, ProcessorError> {
         let (
-            _,
+            raw_all_current_stake_pool_voters,
             raw_all_proposal_votes,
             raw_all_delegator_activities,
             raw_all_delegator_balances,
             raw_all_current_delegator_balances,
+            raw_all_delegator_pools,
             _,
             _,
-            _,
-            _,
+            raw_all_current_delegated_voter,
         ) = match parse_stake_data(&transactions.data, None, 0, 0).await {
             Ok(data) => data,
             Err(e) => {
@@ -85,6 +88,18 @@ impl Processable for ParquetStakeExtractor {
             .into_iter()
             .map(ParquetProposalVote::from)
             .collect::<Vec<_>>();
+        let all_current_stake_pool_voters = raw_all_current_stake_pool_voters
+            .into_iter()
+            .map(ParquetCurrentStakingPoolVoter::from)
+            .collect::<Vec<_>>();
+        let all_delegator_pools = raw_all_delegator_pools
+            .into_iter()
+            .map(ParquetDelegatorPool::from)
+            .collect::<Vec<_>>();
+        let all_current_delegated_voter = raw_all_current_delegated_voter
+            .into_iter()
+            .map(ParquetCurrentDelegatedVoter::from)
+            .collect::<Vec<_>>();
 
         // Print the size of each extracted data type
         debug!("Processed data sizes:");
@@ -98,6 +113,15 @@ impl Processable for ParquetStakeExtractor {
             " - CurrentDelegatorBalance: {}",
             all_current_delegator_balances.len()
         );
+        debug!(
+            " - CurrentStakingPoolVoter: {}",
+            all_current_stake_pool_voters.len()
+        );
+        debug!(" - DelegatorPool: {}", all_delegator_pools.len());
+        debug!(
+            " - CurrentDelegatedVoter: {}",
+            all_current_delegated_voter.len()
+        );
 
         let mut map: HashMap<ParquetTypeEnum, ParquetTypeStructs> = HashMap::new();
 
@@ -122,6 +146,21 @@ impl Processable for ParquetStakeExtractor {
                 ParquetTypeEnum::CurrentDelegatorBalances,
                 ParquetTypeStructs::CurrentDelegatorBalance(all_current_delegator_balances),
             ),
+            (
+                TableFlags::CURRENT_STAKING_POOL_VOTER,
+                ParquetTypeEnum::CurrentStakingPoolVoter,
+                ParquetTypeStructs::CurrentStakingPoolVoter(all_current_stake_pool_voters),
+            ),
+            (
+                TableFlags::DELEGATED_STAKING_POOLS,
+                ParquetTypeEnum::DelegatedStakingPools,
+                ParquetTypeStructs::DelegatorPool(all_delegator_pools),
+            ),
+            (
+                TableFlags::CURRENT_DELEGATED_VOTER,
+                ParquetTypeEnum::CurrentDelegatedVoter,
+                ParquetTypeStructs::CurrentDelegatedVoter(all_current_delegated_voter),
+            ),
         ];
 
         // Populate the map based on opt-in tables
@@ -4,27 +4,26 @@ use crate::{
         processor_config::ProcessorConfig,
     },
     parquet_processors::{
-        initialize_database_pool, initialize_gcs_client, initialize_parquet_buffer_step,
-        parquet_processor_status_saver::{
-            get_parquet_end_version, get_parquet_starting_version, ParquetProcessorStatusSaver,
-        },
+        initialize_database_pool,
+        parquet_processor_status_saver::{get_parquet_end_version, get_parquet_starting_version},
         parquet_stake::parquet_stake_extractor::ParquetStakeExtractor,
-        parquet_utils::{
-            parquet_version_tracker_step::ParquetVersionTrackerStep, util::HasParquetSchema,
-        },
-        set_backfill_table_flag, ParquetTypeEnum,
+        parquet_utils::util::HasParquetSchema,
+        set_backfill_table_flag, ParquetStepsBuilder, ParquetTypeEnum,
     },
     processors::stake::models::{
+        current_delegated_voter::ParquetCurrentDelegatedVoter,
         delegator_activities::ParquetDelegatedStakingActivity,
         delegator_balances::{ParquetCurrentDelegatorBalance, ParquetDelegatorBalance},
+        delegator_pools::ParquetDelegatorPool,
         proposal_votes::ParquetProposalVote,
+        staking_pool_voter::ParquetCurrentStakingPoolVoter,
     },
     MIGRATIONS,
 };
 use aptos_indexer_processor_sdk::{
     aptos_indexer_transaction_stream::TransactionStreamConfig,
     builder::ProcessorBuilder,
-    common_steps::{TransactionStreamStep, DEFAULT_UPDATE_PROCESSOR_STATUS_SECS},
+    common_steps::TransactionStreamStep,
     postgres::utils::{
         checkpoint::PostgresChainIdChecker,
         database::{run_migrations, ArcDbPool},
@@ -93,6 +92,15 @@ impl ProcessorTrait for ParquetStakeProcessor {
             },
         };
 
+        // Upload anything a previous run spilled but didn't checkpoint before picking the starting version
+        let parquet_steps_builder = ParquetStepsBuilder::new(
+            &self.config,
+            self.db_pool.clone(),
+            parquet_db_config,
+            &parquet_processor_config,
+        )
+        .await?;
+
         let (starting_version, ending_version) = (
             get_parquet_starting_version(&self.config, self.db_pool.clone()).await?,
             get_parquet_end_version(&self.config, self.db_pool.clone()).await?,
@@ -111,9 +119,6 @@ impl ProcessorTrait for ParquetStakeProcessor {
             opt_in_tables: backfill_table,
         };
 
-        let gcs_client =
-            initialize_gcs_client(parquet_db_config.google_application_credentials.clone()).await;
-
         let parquet_type_to_schemas: HashMap<ParquetTypeEnum, Arc<Type>> = [
             (
                 ParquetTypeEnum::DelegatedStakingActivities,
@@ -131,28 +136,28 @@ impl ProcessorTrait for ParquetStakeProcessor {
                 ParquetTypeEnum::CurrentDelegatorBalances,
                 ParquetCurrentDelegatorBalance::schema(),
             ),
+            (
+                ParquetTypeEnum::CurrentStakingPoolVoter,
+                ParquetCurrentStakingPoolVoter::schema(),
+            ),
+            (
+                ParquetTypeEnum::DelegatedStakingPools,
+                ParquetDelegatorPool::schema(),
+            ),
+            (
+                ParquetTypeEnum::CurrentDelegatedVoter,
+                ParquetCurrentDelegatedVoter::schema(),
+            ),
         ]
         .into_iter()
         .collect();
 
-        let default_size_buffer_step = initialize_parquet_buffer_step(
-            gcs_client.clone(),
-            parquet_type_to_schemas,
-            parquet_processor_config.upload_interval,
-            parquet_processor_config.max_buffer_size,
-            parquet_db_config.bucket_name.clone(),
-            parquet_db_config.bucket_root.clone(),
-            self.name().to_string(),
-        )
-        .await
-        .unwrap_or_else(|e| {
-            panic!("Failed to initialize parquet buffer step: {e:?}");
-        });
-
-        let parquet_version_tracker_step = ParquetVersionTrackerStep::new(
-            ParquetProcessorStatusSaver::new(self.config.clone(), self.db_pool.clone()),
-            DEFAULT_UPDATE_PROCESSOR_STATUS_SECS,
-        );
+        let (default_size_buffer_step, parquet_version_tracker_step) = parquet_steps_builder
+            .build(parquet_type_to_schemas)
+            .await
+            .unwrap_or_else(|e| {
+                panic!("Failed to initialize parquet buffer step: {e:?}");
+            });
 
         let channel_size = parquet_processor_config.channel_size;
 
@@ -9,7 +9,7 @@ use crate::{
             token_royalty::ParquetCurrentTokenRoyaltyV1, tokens::TableMetadataForToken,
         },
         token_v2_models::{
-            v2_collections::ParquetCollectionV2,
+            v2_collections::{ParquetCollectionV2, ParquetCurrentCollectionV2},
             v2_token_activities::ParquetTokenActivityV2,
             v2_token_datas::{ParquetCurrentTokenDataV2, ParquetTokenDataV2},
             v2_token_metadata::ParquetCurrentTokenV2Metadata,
@@ -57,7 +57,7 @@ impl Processable for ParquetTokenV2Extractor {
             collections_v2,
             raw_token_datas_v2,
             raw_token_ownerships_v2,
-            _current_collections_v2,
+            raw_current_collections_v2,
             raw_current_token_datas_v2,
             raw_current_deleted_token_datas_v2,
             raw_current_token_ownerships_v2,
@@ -130,6 +130,12 @@ impl Processable for ParquetTokenV2Extractor {
             .map(ParquetCollectionV2::from)
             .collect();
 
+        let parquet_current_collections_v2: Vec<ParquetCurrentCollectionV2> =
+            raw_current_collections_v2
+                .into_iter()
+                .map(ParquetCurrentCollectionV2::from)
+                .collect();
+
         // We are merging these two tables, b/c they are essentially the same table
         let mut combined_current_token_datas_v2: Vec<ParquetCurrentTokenDataV2> = Vec::new();
         parquet_current_token_datas_v2
@@ -197,6 +203,11 @@ impl Processable for ParquetTokenV2Extractor {
                 ParquetTypeEnum::CollectionsV2,
                 ParquetTypeStructs::CollectionV2(parquet_collections_v2),
             ),
+            (
+                TableFlags::CURRENT_COLLECTIONS_V2,
+                ParquetTypeEnum::CurrentCollectionsV2,
+                ParquetTypeStructs::CurrentCollectionV2(parquet_current_collections_v2),
+            ),
         ];
 
         // Populate the map based on opt-in tables
@@ -4,15 +4,11 @@ use crate::{
         processor_config::ProcessorConfig,
     },
     parquet_processors::{
-        initialize_database_pool, initialize_gcs_client, initialize_parquet_buffer_step,
-        parquet_processor_status_saver::{
-            get_parquet_end_version, get_parquet_starting_version, ParquetProcessorStatusSaver,
-        },
+        initialize_database_pool,
+        parquet_processor_status_saver::{get_parquet_end_version, get_parquet_starting_version},
         parquet_token_v2::parquet_token_v2_extractor::ParquetTokenV2Extractor,
-        parquet_utils::{
-            parquet_version_tracker_step::ParquetVersionTrackerStep, util::HasParquetSchema,
-        },
-        set_backfill_table_flag, ParquetTypeEnum,
+        parquet_utils::util::HasParquetSchema,
+        set_backfill_table_flag, ParquetStepsBuilder, ParquetTypeEnum,
     },
     processors::token_v2::{
         token_models::{
@@ -20,7 +16,7 @@ use crate::{
             token_royalty::ParquetCurrentTokenRoyaltyV1,
         },
         token_v2_models::{
-            v2_collections::ParquetCollectionV2,
+            v2_collections::{ParquetCollectionV2, ParquetCurrentCollectionV2},
             v2_token_activities::ParquetTokenActivityV2,
             v2_token_datas::{ParquetCurrentTokenDataV2, ParquetTokenDataV2},
             v2_token_metadata::ParquetCurrentTokenV2Metadata,
@@ -32,7 +28,7 @@ use crate::{
 use aptos_indexer_processor_sdk::{
     aptos_indexer_transaction_stream::TransactionStreamConfig,
     builder::ProcessorBuilder,
-    common_steps::{TransactionStreamStep, DEFAULT_UPDATE_PROCESSOR_STATUS_SECS},
+    common_steps::TransactionStreamStep,
     postgres::utils::{
         checkpoint::PostgresChainIdChecker,
         database::{run_migrations, ArcDbPool},
@@ -101,6 +97,15 @@ impl ProcessorTrait for ParquetTokenV2Processor {
             },
         };
 
+        // Upload anything a previous run spilled but didn't checkpoint before picking the starting version
+        let parquet_steps_builder = ParquetStepsBuilder::new(
+            &self.config,
+            self.db_pool.clone(),
+            parquet_db_config,
+            &parquet_processor_config,
+        )
+        .await?;
+
         let (starting_version, ending_version) = (
             get_parquet_starting_version(&self.config, self.db_pool.clone()).await?,
             get_parquet_end_version(&self.config, self.db_pool.clone()).await?,
@@ -121,9 +126,6 @@ impl ProcessorTrait for ParquetTokenV2Processor {
             opt_in_tables: backfill_table,
         };
 
-        let gcs_client =
-            initialize_gcs_client(parquet_db_config.google_application_credentials.clone()).await;
-
         // TODO: Update this
         let parquet_type_to_schemas: HashMap<ParquetTypeEnum, Arc<Type>> = [
             (
@@ -159,28 +161,20 @@ impl ProcessorTrait for ParquetTokenV2Processor {
                 ParquetTypeEnum::CollectionsV2,
                 ParquetCollectionV2::schema(),
             ),
+            (
+                ParquetTypeEnum::CurrentCollectionsV2,
+                ParquetCurrentCollectionV2::schema(),
+            ),
         ]
         .into_iter()
         .collect();
 
-        let default_size_buffer_step = initialize_parquet_buffer_step(
-            gcs_client.clone(),
-            parquet_type_to_schemas,
-            parquet_processor_config.upload_interval,
-            parquet_processor_config.max_buffer_size,
-            parquet_db_config.bucket_name.clone(),
-            parquet_db_config.bucket_root.clone(),
-            self.name().to_string(),
-        )
-        .await
-        .unwrap_or_else(|e| {
-            panic!("Failed to initialize parquet buffer step: {e:?}");
-        });
-
-        let parquet_version_tracker_step = ParquetVersionTrackerStep::new(
-            ParquetProcessorStatusSaver::new(self.config.clone(), self.db_pool.clone()),
-            DEFAULT_UPDATE_PROCESSOR_STATUS_SECS,
-        );
+        let (default_size_buffer_step, parquet_version_tracker_step) = parquet_steps_builder
+            .build(parquet_type_to_schemas)
+            .await
+            .unwrap_or_else(|e| {
+                panic!("Failed to initialize parquet buffer step: {e:?}");
+            });
 
         let channel_size = parquet_processor_config.channel_size;
 
@@ -36,18 +36,26 @@ impl Processable for ParquetTransactionMetadataExtractor {
         &mut self,
         transactions: TransactionContext<Self::Input>,
     ) -> anyhow::Result<Option<TransactionContext<ParquetTypeMap>>, ProcessorError> {
-        let write_set_size = process_transactions(transactions.data);
+        let (write_set_size, event_size) = process_transactions(transactions.data);
 
         debug!("Processed data sizes:");
         debug!(" - WriteSetSize: {}", write_set_size.len());
+        debug!(" - EventSize: {}", event_size.len());
 
         let mut map: HashMap<ParquetTypeEnum, ParquetTypeStructs> = HashMap::new();
 
-        let data_types = [(
-            TableFlags::WRITE_SET_SIZE,
-            ParquetTypeEnum::WriteSetSize,
-            ParquetTypeStructs::WriteSetSize(write_set_size),
-        )];
+        let data_types = [
+            (
+                TableFlags::WRITE_SET_SIZE,
+                ParquetTypeEnum::WriteSetSize,
+                ParquetTypeStructs::WriteSetSize(write_set_size),
+            ),
+            (
+                TableFlags::EVENT_SIZE,
+                ParquetTypeEnum::EventSize,
+                ParquetTypeStructs::EventSize(event_size),
+            ),
+        ];
 
         // Populate the map based on opt-in tables
         add_to_map_if_opted_in_for_backfill(self.opt_in_tables, &mut map, data_types.to_vec());
@@ -1,13 +1,20 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::parquet_processors::parquet_transaction_metadata::transaction_metadata_models::write_set_size_info::ParquetWriteSetSize;
-use aptos_indexer_processor_sdk::aptos_indexer_transaction_stream::utils::time::parse_timestamp;
-use aptos_indexer_processor_sdk::aptos_protos::transaction::v1::Transaction;
+use crate::parquet_processors::parquet_transaction_metadata::transaction_metadata_models::{
+    event_size_info::ParquetEventSize, write_set_size_info::ParquetWriteSetSize,
+};
+use aptos_indexer_processor_sdk::{
+    aptos_indexer_transaction_stream::utils::time::parse_timestamp,
+    aptos_protos::transaction::v1::Transaction,
+};
 use tracing::warn;
 
-pub fn process_transactions(transactions: Vec<Transaction>) -> Vec<ParquetWriteSetSize> {
+pub fn process_transactions(
+    transactions: Vec<Transaction>,
+) -> (Vec<ParquetWriteSetSize>, Vec<ParquetEventSize>) {
     let mut write_set_sizes = vec![];
+    let mut event_sizes = vec![];
 
     for txn in transactions {
         let txn_version = txn.version as i64;
@@ -28,6 +35,14 @@ pub fn process_transactions(transactions: Vec<Transaction>) -> Vec<ParquetWriteS
                 block_timestamp,
             ));
         }
+        for (index, event_size_info) in size_info.event_size_info.iter().enumerate() {
+            event_sizes.push(ParquetEventSize::from_event_size_info(
+                event_size_info,
+                txn_version,
+                index as i64,
+                block_timestamp,
+            ));
+        }
     }
-    write_set_sizes
+    (write_set_sizes, event_sizes)
 }
@@ -39,7 +39,8 @@ impl Processable for ParquetUserTransactionExtractor {
         &mut self,
         transactions: TransactionContext<Self::Input>,
     ) -> anyhow::Result<Option<TransactionContext<ParquetTypeMap>>, ProcessorError> {
-        let (user_txns, signatures) = user_transaction_parse(transactions.data.clone());
+        let (user_txns, signatures, _auth_profiles) =
+            user_transaction_parse(transactions.data.clone());
 
         let parquet_user_txns: Vec<ParquetUserTransaction> = user_txns
             .into_iter()
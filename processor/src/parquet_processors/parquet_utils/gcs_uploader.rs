@@ -1,14 +1,21 @@
 use crate::{
     parquet_processors::{
-        parquet_utils::util::{HasParquetSchema, HasVersion, ParquetProcessorError},
+        parquet_utils::{
+            parquet_writer_config::ParquetWriterConfig,
+            table_commit_log::{AddedFile, TableCommitLog},
+            util::{HasTimestamp, HasVersion, ParquetProcessorError},
+        },
         ParquetTypeEnum, ParquetTypeStructs, ParquetTypeTrait,
     },
     utils::counters::PARQUET_BUFFER_SIZE,
 };
 use anyhow::{Context, Result};
-use aptos_indexer_processor_sdk::utils::errors::ProcessorError;
+use aptos_indexer_processor_sdk::{
+    aptos_indexer_transaction_stream::utils::time::parse_timestamp,
+    types::transaction_context::TransactionMetadata, utils::errors::ProcessorError,
+};
 use async_trait::async_trait;
-use chrono::{Datelike, Timelike};
+use chrono::{Datelike, NaiveDate, Timelike};
 use google_cloud_storage::{
     client::Client as GCSClient,
     http::objects::upload::{Media, UploadObjectRequest, UploadType},
@@ -19,8 +26,9 @@ use parquet::{
     record::RecordWriter,
     schema::types::Type,
 };
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -33,12 +41,61 @@ const TIMEOUT_SECONDS: u64 = 300;
 pub struct GCSUploader {
     gcs_client: Arc<GCSClient>,
     parquet_type_to_schemas: HashMap<ParquetTypeEnum, Arc<Type>>,
-    parquet_type_to_writer: HashMap<ParquetTypeEnum, SerializedFileWriter<Vec<u8>>>,
+    parquet_type_to_properties: HashMap<ParquetTypeEnum, Arc<WriterProperties>>,
+    // Files row groups are written to until they're uploaded, keyed by date partition with the
+    // `HiveDate` layout and by `None` otherwise
+    parquet_type_to_open_files: HashMap<ParquetTypeEnum, BTreeMap<Option<NaiveDate>, OpenFile>>,
+    // Date of the latest batch of each table, the partition of rows without a timestamp
+    parquet_type_to_batch_date: HashMap<ParquetTypeEnum, NaiveDate>,
+    file_layout: ParquetFileLayout,
+    commit_log: Option<TableCommitLog>,
     pub bucket_name: String,
     pub bucket_root: String,
     pub processor_name: String,
 }
 
+/// A parquet file that is being written. The version range and the partition date are tracked as
+/// row groups are written since the rows themselves aren't kept around.
+struct OpenFile {
+    writer: SerializedFileWriter<Vec<u8>>,
+    row_count: i64,
+    min_version: i64,
+    max_version: i64,
+    partition_date: Option<NaiveDate>,
+}
+
+impl OpenFile {
+    fn new(writer: SerializedFileWriter<Vec<u8>>, partition_date: Option<NaiveDate>) -> Self {
+        Self {
+            writer,
+            row_count: 0,
+            min_version: i64::MAX,
+            max_version: i64::MIN,
+            partition_date,
+        }
+    }
+
+    fn write_row_group<ParquetType>(&mut self, rows: &[ParquetType]) -> anyhow::Result<()>
+    where
+        ParquetType: HasVersion + HasTimestamp,
+        for<'a> &'a [ParquetType]: RecordWriter<ParquetType>,
+    {
+        write_row_group(&mut self.writer, rows)?;
+        self.row_count += rows.len() as i64;
+        for row in rows {
+            self.min_version = self.min_version.min(row.version());
+            self.max_version = self.max_version.max(row.version());
+        }
+        if self.partition_date.is_none() {
+            self.partition_date = rows
+                .iter()
+                .find_map(HasTimestamp::timestamp)
+                .map(|timestamp| timestamp.date());
+        }
+        Ok(())
+    }
+}
+
 #[async_trait]
 pub trait Uploadable {
     async fn upload_buffer(
@@ -49,14 +106,17 @@ pub trait Uploadable {
 
 #[async_trait]
 impl Uploadable for GCSUploader {
+    /// Writes the remaining rows of the buffer and uploads all the open files of its table.
     async fn upload_buffer(
         &mut self,
-        buffer: ParquetTypeStructs,
+        mut buffer: ParquetTypeStructs,
     ) -> anyhow::Result<(), ProcessorError> {
         let parquet_type = buffer.parquet_type();
-        let table_name = parquet_type.to_string();
 
-        let result = buffer.upload_to_gcs(self, parquet_type, &table_name).await;
+        let result = match buffer.write_row_groups(self, true) {
+            Ok(()) => self.upload_open_files(parquet_type).await,
+            Err(e) => Err(e),
+        };
         if let Err(e) = result {
             error!("Failed to upload buffer: {}", e);
             return Err(ProcessorError::ProcessError {
@@ -73,14 +133,20 @@ pub fn create_new_writer(schema: Arc<Type>) -> anyhow::Result<SerializedFileWrit
         .build();
     let props_arc = Arc::new(props);
 
-    SerializedFileWriter::new(Vec::new(), schema, props_arc).context("Failed to create new writer")
+    create_new_writer_with_properties(schema, props_arc)
+}
+
+pub fn create_new_writer_with_properties(
+    schema: Arc<Type>,
+    props: Arc<WriterProperties>,
+) -> anyhow::Result<SerializedFileWriter<Vec<u8>>> {
+    SerializedFileWriter::new(Vec::new(), schema, props).context("Failed to create new writer")
 }
 
 impl GCSUploader {
     pub fn new(
         gcs_client: Arc<GCSClient>,
         parquet_type_to_schemas: HashMap<ParquetTypeEnum, Arc<Type>>,
-        parquet_type_to_writer: HashMap<ParquetTypeEnum, SerializedFileWriter<Vec<u8>>>,
         bucket_name: String,
         bucket_root: String,
         processor_name: String,
@@ -88,113 +154,283 @@ impl GCSUploader {
         Ok(Self {
             gcs_client,
             parquet_type_to_schemas,
-            parquet_type_to_writer,
+            parquet_type_to_properties: HashMap::new(),
+            parquet_type_to_open_files: HashMap::new(),
+            parquet_type_to_batch_date: HashMap::new(),
+            file_layout: ParquetFileLayout::default(),
+            commit_log: None,
             bucket_name,
             bucket_root,
             processor_name,
         })
     }
 
-    fn create_new_writer(
+    /// Writer properties to use per table, tables without properties use the LZ4 defaults.
+    pub fn with_writer_properties(
+        mut self,
+        parquet_type_to_properties: HashMap<ParquetTypeEnum, Arc<WriterProperties>>,
+    ) -> Self {
+        self.parquet_type_to_properties = parquet_type_to_properties;
+        self
+    }
+
+    pub fn with_file_layout(mut self, file_layout: ParquetFileLayout) -> Self {
+        self.file_layout = file_layout;
+        self
+    }
+
+    /// Appends a commit to the table commit log after every upload.
+    pub fn with_commit_log(mut self, commit_log: Option<TableCommitLog>) -> Self {
+        self.commit_log = commit_log;
+        self
+    }
+
+    /// Records the transaction timestamp of the latest batch of a table. Rows without a timestamp
+    /// are partitioned by it.
+    pub fn set_batch_timestamp(
+        &mut self,
+        parquet_type: ParquetTypeEnum,
+        batch_metadata: &TransactionMetadata,
+    ) {
+        if let Some(timestamp) = batch_metadata.end_transaction_timestamp.as_ref() {
+            let date = parse_timestamp(timestamp, batch_metadata.end_version as i64).date_naive();
+            self.parquet_type_to_batch_date.insert(parquet_type, date);
+        }
+    }
+
+    fn batch_date(&self, parquet_type: ParquetTypeEnum) -> anyhow::Result<NaiveDate> {
+        self.parquet_type_to_batch_date
+            .get(&parquet_type)
+            .copied()
+            .with_context(|| format!("No batch timestamp to partition {parquet_type} rows by"))
+    }
+
+    fn writer_properties(
         &self,
         parquet_type: ParquetTypeEnum,
-    ) -> anyhow::Result<SerializedFileWriter<Vec<u8>>> {
+    ) -> anyhow::Result<(Arc<Type>, Arc<WriterProperties>)> {
         let schema = self
             .parquet_type_to_schemas
             .get(&parquet_type)
             .context("Parquet type not found in schemas")?
             .clone();
 
-        create_new_writer(schema)
+        let props = match self.parquet_type_to_properties.get(&parquet_type) {
+            Some(props) => props.clone(),
+            None => Arc::new(
+                ParquetWriterConfig::default().table_writer_properties(parquet_type, &schema)?,
+            ),
+        };
+        Ok((schema, props))
     }
 
-    /// # Context: Why we replace our writer
-    ///
-    /// Once we’re ready to upload (either because the buffer is full or enough time has passed),
-    /// we don’t want to keep adding new data to that same writer. we want a clean slate for the next batch.
-    /// So, we replace the old writer with a new one to empty the writer buffer without losing any data.
-    fn get_and_replace_writer(
-        &mut self,
+    fn create_new_writer(
+        &self,
         parquet_type: ParquetTypeEnum,
     ) -> anyhow::Result<SerializedFileWriter<Vec<u8>>> {
-        let old_writer = self
-            .parquet_type_to_writer
-            .remove(&parquet_type)
-            .context("Writer for specified Parquet type not found")?;
+        let (schema, props) = self.writer_properties(parquet_type)?;
+        create_new_writer_with_properties(schema, props)
+    }
+
+    /// Number of bytes encoded so far in the open files of a table. The footer is only written on
+    /// upload, so this is slightly less than the size of the uploaded files.
+    pub fn encoded_size(&self, parquet_type: ParquetTypeEnum) -> usize {
+        self.parquet_type_to_open_files
+            .get(&parquet_type)
+            .map_or(0, |open_files| {
+                open_files
+                    .values()
+                    .map(|open_file| open_file.writer.bytes_written())
+                    .sum()
+            })
+    }
 
-        // Create a new writer and replace the old writer with it
-        let new_writer = self.create_new_writer(parquet_type)?;
-        self.parquet_type_to_writer.insert(parquet_type, new_writer);
+    /// Writes the rows in row groups of at most the table's max row group size and drains them
+    /// from `rows`. The rows that don't fill a row group are left in `rows` unless `flush` is set.
+    pub fn write_row_groups<ParquetType>(
+        &mut self,
+        rows: &mut Vec<ParquetType>,
+        parquet_type: ParquetTypeEnum,
+        flush: bool,
+    ) -> anyhow::Result<()>
+    where
+        ParquetType: Clone + HasVersion + HasTimestamp,
+        for<'a> &'a [ParquetType]: RecordWriter<ParquetType>,
+    {
+        let max_row_group_size = self.writer_properties(parquet_type)?.1.max_row_group_size();
+        let mut written = 0;
+        while rows.len() - written >= max_row_group_size || (flush && written < rows.len()) {
+            let end = rows.len().min(written + max_row_group_size);
+            self.write_rows(&rows[written..end], parquet_type)?;
+            written = end;
+        }
+        rows.drain(..written);
+        Ok(())
+    }
 
-        // Return the old writer so its contents can be used
-        Ok(old_writer)
+    /// Writes the rows as a new row group of the table's open file, or of the open file of each
+    /// date partition with the `HiveDate` layout.
+    fn write_rows<ParquetType>(
+        &mut self,
+        rows: &[ParquetType],
+        parquet_type: ParquetTypeEnum,
+    ) -> anyhow::Result<()>
+    where
+        ParquetType: Clone + HasVersion + HasTimestamp,
+        for<'a> &'a [ParquetType]: RecordWriter<ParquetType>,
+    {
+        match self.file_layout {
+            ParquetFileLayout::UploadTime => self.write_partition(parquet_type, None, rows),
+            ParquetFileLayout::HiveDate => {
+                // Rows that cross a day boundary go to one file per date partition
+                let mut partitions: BTreeMap<NaiveDate, Vec<ParquetType>> = BTreeMap::new();
+                for row in rows {
+                    let date = match row.timestamp() {
+                        Some(timestamp) => timestamp.date(),
+                        None => self.batch_date(parquet_type)?,
+                    };
+                    partitions.entry(date).or_default().push(row.clone());
+                }
+                for (date, rows) in partitions {
+                    self.write_partition(parquet_type, Some(date), &rows)?;
+                }
+                Ok(())
+            },
+        }
     }
 
-    // Generic upload function to handle any data type
-    pub async fn upload_generic<ParquetType>(
+    fn write_partition<ParquetType>(
         &mut self,
-        data: &[ParquetType],
         parquet_type: ParquetTypeEnum,
-        table_name: &str,
+        partition: Option<NaiveDate>,
+        rows: &[ParquetType],
     ) -> anyhow::Result<()>
     where
-        ParquetType: HasVersion + HasParquetSchema,
+        ParquetType: HasVersion + HasTimestamp,
         for<'a> &'a [ParquetType]: RecordWriter<ParquetType>,
     {
-        if data.is_empty() {
-            println!("Buffer is empty, skipping upload.");
-            return Ok(());
+        let is_open = self
+            .parquet_type_to_open_files
+            .get(&parquet_type)
+            .is_some_and(|open_files| open_files.contains_key(&partition));
+        if !is_open {
+            let writer = self.create_new_writer(parquet_type)?;
+            self.parquet_type_to_open_files
+                .entry(parquet_type)
+                .or_default()
+                .insert(partition, OpenFile::new(writer, partition));
         }
 
-        let writer = self
-            .parquet_type_to_writer
+        self.parquet_type_to_open_files
             .get_mut(&parquet_type)
-            .context("Writer not found for specified parquet type")?;
+            .and_then(|open_files| open_files.get_mut(&partition))
+            .context("Open file not found for specified parquet type")?
+            .write_row_group(rows)
+    }
 
-        let mut row_group_writer = writer.next_row_group().context("Failed to get row group")?;
+    /// Closes and uploads all the open files of a table.
+    pub async fn upload_open_files(&mut self, parquet_type: ParquetTypeEnum) -> anyhow::Result<()> {
+        let Some(open_files) = self.parquet_type_to_open_files.remove(&parquet_type) else {
+            println!("Buffer is empty, skipping upload.");
+            return Ok(());
+        };
+        let table_name = parquet_type.to_string();
 
-        data.write_to_row_group(&mut row_group_writer)
-            .context("Failed to write to row group")?;
+        let mut added_files = vec![];
+        for open_file in open_files.into_values() {
+            added_files.push(
+                self.upload_file(open_file, parquet_type, &table_name)
+                    .await?,
+            );
+        }
 
-        row_group_writer
-            .close()
-            .context("Failed to close row group")?;
+        // All the files of a flush are committed together so readers never see part of it
+        if let Some(commit_log) = &self.commit_log {
+            let schema = self
+                .parquet_type_to_schemas
+                .get(&parquet_type)
+                .context("Parquet type not found in schemas")?;
+            commit_log.commit(&table_name, added_files, schema).await?;
+        }
+        Ok(())
+    }
 
-        let old_writer = self
-            .get_and_replace_writer(parquet_type)
-            .context("Failed to close writer")?;
-        let upload_buffer = old_writer
-            .into_inner()
-            .context("Failed to get inner buffer")?;
+    async fn upload_file(
+        &self,
+        open_file: OpenFile,
+        parquet_type: ParquetTypeEnum,
+        table_name: &str,
+    ) -> anyhow::Result<AddedFile> {
+        let OpenFile {
+            writer,
+            row_count,
+            min_version: start_version,
+            max_version: end_version,
+            partition_date,
+        } = open_file;
+        let upload_buffer = writer.into_inner().context("Failed to get inner buffer")?;
+        let partition_date = match partition_date {
+            Some(partition_date) => partition_date,
+            None => self.batch_date(parquet_type)?,
+        };
 
-        let bucket_root = PathBuf::from(&self.bucket_root);
+        let object_name = self.file_layout.file_path(
+            Path::new(&self.bucket_root),
+            table_name,
+            parquet_type.schema_version(),
+            partition_date,
+            start_version,
+            end_version,
+        );
+        let size_bytes = upload_buffer.len() as u64;
         upload_parquet_to_gcs(
             &self.gcs_client,
             upload_buffer,
             table_name,
             &self.bucket_name,
-            &bucket_root,
+            &object_name,
             self.processor_name.clone(),
         )
         .await?;
 
         debug!(
             "Uploaded parquet to GCS for table: {}, start_version: {}, end_version: {}",
-            table_name,
-            data[0].version(),
-            data[data.len() - 1].version()
+            table_name, start_version, end_version
         );
-
-        Ok(())
+        Ok(AddedFile {
+            path: object_name.to_string_lossy().to_string(),
+            size_bytes,
+            row_count,
+            min_version: start_version,
+            max_version: end_version,
+        })
     }
 }
 
+fn write_row_group<ParquetType>(
+    writer: &mut SerializedFileWriter<Vec<u8>>,
+    rows: &[ParquetType],
+) -> anyhow::Result<()>
+where
+    for<'a> &'a [ParquetType]: RecordWriter<ParquetType>,
+{
+    let mut row_group_writer = writer.next_row_group().context("Failed to get row group")?;
+
+    rows.write_to_row_group(&mut row_group_writer)
+        .context("Failed to write to row group")?;
+
+    row_group_writer
+        .close()
+        .context("Failed to close row group")?;
+    Ok(())
+}
+
 pub async fn upload_parquet_to_gcs(
     client: &GCSClient,
     buffer: Vec<u8>,
     table_name: &str,
     bucket_name: &str,
-    bucket_root: &Path,
+    object_name: &Path,
     processor_name: String,
 ) -> Result<(), ParquetProcessorError> {
     if buffer.is_empty() {
@@ -204,24 +440,6 @@ pub async fn upload_parquet_to_gcs(
         ));
     }
 
-    let now = chrono::Utc::now();
-    let start_of_month = now
-        .with_day(1)
-        .unwrap()
-        .with_hour(0)
-        .unwrap()
-        .with_minute(0)
-        .unwrap()
-        .with_second(0)
-        .unwrap()
-        .with_nanosecond(0)
-        .unwrap();
-    let highwater_s = start_of_month.timestamp_millis();
-    let highwater_ms = now.timestamp_millis();
-    let counter = 0; // THIS NEED TO BE REPLACED OR REIMPLEMENTED WITH AN ACTUAL LOGIC TO ENSURE FILE UNIQUENESS.
-    let object_name: PathBuf =
-        generate_parquet_file_path(bucket_root, table_name, highwater_s, highwater_ms, counter);
-
     let file_name = object_name.to_str().unwrap().to_owned();
     let upload_type: UploadType = UploadType::Simple(Media::new(file_name.clone()));
 
@@ -277,14 +495,212 @@ pub async fn upload_parquet_to_gcs(
     }
 }
 
+/// How uploaded files are laid out under the bucket root. Files of each schema version of a table
+/// get their own prefix.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetFileLayout {
+    /// `{table}/v{schema_version}/{start_of_month_ms}/{upload_ms}_{counter}.parquet`, partitioned
+    /// by upload time.
+    #[default]
+    UploadTime,
+    /// `table={table}/schema_version={schema_version}/date={YYYY-MM-DD}/{start_version}_{end_version}_{upload_ms}.parquet`,
+    /// partitioned by the transaction timestamp of the rows.
+    HiveDate,
+}
+
+impl ParquetFileLayout {
+    /// Prefix of all the files of a schema version of a table, ending with a `/`.
+    pub fn table_prefix(
+        &self,
+        bucket_root: &Path,
+        table_name: &str,
+        schema_version: u32,
+    ) -> String {
+        let table_dir = match self {
+            ParquetFileLayout::UploadTime => format!("{table_name}/v{schema_version}"),
+            ParquetFileLayout::HiveDate => {
+                format!("table={table_name}/schema_version={schema_version}")
+            },
+        };
+        format!("{}/", bucket_root.join(table_dir).display())
+    }
+
+    pub fn file_path(
+        &self,
+        bucket_root: &Path,
+        table_name: &str,
+        schema_version: u32,
+        partition_date: NaiveDate,
+        start_version: i64,
+        end_version: i64,
+    ) -> PathBuf {
+        let now = chrono::Utc::now();
+        match self {
+            ParquetFileLayout::UploadTime => {
+                let start_of_month = now
+                    .with_day(1)
+                    .unwrap()
+                    .with_hour(0)
+                    .unwrap()
+                    .with_minute(0)
+                    .unwrap()
+                    .with_second(0)
+                    .unwrap()
+                    .with_nanosecond(0)
+                    .unwrap();
+                let highwater_s = start_of_month.timestamp_millis();
+                let highwater_ms = now.timestamp_millis();
+                let counter = 0; // THIS NEED TO BE REPLACED OR REIMPLEMENTED WITH AN ACTUAL LOGIC TO ENSURE FILE UNIQUENESS.
+                generate_parquet_file_path(
+                    bucket_root,
+                    table_name,
+                    schema_version,
+                    highwater_s,
+                    highwater_ms,
+                    counter,
+                )
+            },
+            ParquetFileLayout::HiveDate => bucket_root.join(format!(
+                "table={table_name}/schema_version={schema_version}/date={}/{start_version}_{end_version}_{}.parquet",
+                partition_date.format("%Y-%m-%d"),
+                now.timestamp_millis()
+            )),
+        }
+    }
+}
+
 fn generate_parquet_file_path(
     gcs_bucket_root: &Path,
     table: &str,
+    schema_version: u32,
     highwater_s: i64,
     highwater_ms: i64,
     counter: u32,
 ) -> PathBuf {
     gcs_bucket_root.join(format!(
-        "{table}/{highwater_s}/{highwater_ms}_{counter}.parquet"
+        "{table}/v{schema_version}/{highwater_s}/{highwater_ms}_{counter}.parquet"
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hive_date_file_path() {
+        let path = ParquetFileLayout::HiveDate.file_path(
+            Path::new("root"),
+            "events",
+            2,
+            NaiveDate::from_ymd_opt(2024, 3, 9).unwrap(),
+            100,
+            250,
+        );
+        let path = path.to_str().unwrap();
+        assert!(path.starts_with("root/table=events/schema_version=2/date=2024-03-09/100_250_"));
+        assert!(path.ends_with(".parquet"));
+        assert!(path.starts_with(&ParquetFileLayout::HiveDate.table_prefix(
+            Path::new("root"),
+            "events",
+            2
+        )));
+    }
+
+    #[test]
+    fn test_upload_time_file_path() {
+        let path = ParquetFileLayout::UploadTime.file_path(
+            Path::new("root"),
+            "events",
+            2,
+            NaiveDate::from_ymd_opt(2024, 3, 9).unwrap(),
+            100,
+            250,
+        );
+        assert!(path.to_str().unwrap().starts_with("root/events/v2/"));
+        assert_eq!(
+            ParquetFileLayout::UploadTime.table_prefix(Path::new("root"), "events", 2),
+            "root/events/v2/"
+        );
+        assert!(path.to_str().unwrap().ends_with("_0.parquet"));
+    }
+
+    #[test]
+    fn test_rows_without_timestamp_use_batch_date() {
+        use crate::processors::default::models::{
+            move_resources::ParquetMoveResource, table_items::ParquetTableMetadata,
+        };
+        use aptos_indexer_processor_sdk::aptos_protos::util::timestamp::Timestamp;
+
+        let gcs_client = Arc::new(GCSClient::new(
+            google_cloud_storage::client::ClientConfig::default(),
+        ));
+        let parquet_type_to_schemas = [
+            ParquetTypeEnum::MoveResources,
+            ParquetTypeEnum::TableMetadata,
+        ]
+        .into_iter()
+        .map(|parquet_type| {
+            let schema = ParquetTypeStructs::default_for_type(&parquet_type).parquet_schema();
+            (parquet_type, schema)
+        })
+        .collect();
+        let mut uploader = GCSUploader::new(
+            gcs_client,
+            parquet_type_to_schemas,
+            "bucket".to_string(),
+            "root".to_string(),
+            "test_processor".to_string(),
+        )
+        .unwrap()
+        .with_file_layout(ParquetFileLayout::HiveDate);
+        let date = |day| NaiveDate::from_ymd_opt(2024, 3, day).unwrap();
+
+        // Rows are split by their own timestamp
+        let mut move_resources = [9, 10]
+            .into_iter()
+            .map(|day| ParquetMoveResource {
+                block_timestamp: date(day).and_hms_opt(23, 0, 0).unwrap(),
+                ..ParquetMoveResource::default()
+            })
+            .collect::<Vec<_>>();
+        uploader
+            .write_row_groups(&mut move_resources, ParquetTypeEnum::MoveResources, true)
+            .unwrap();
+        assert_eq!(
+            uploader.parquet_type_to_open_files[&ParquetTypeEnum::MoveResources]
+                .keys()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![Some(date(9)), Some(date(10))]
+        );
+
+        // Rows without a timestamp need the date of their batch
+        let mut table_metadata = vec![ParquetTableMetadata::default()];
+        assert!(uploader
+            .write_row_groups(&mut table_metadata, ParquetTypeEnum::TableMetadata, true)
+            .is_err());
+        let batch_timestamp = date(11).and_hms_opt(1, 0, 0).unwrap().and_utc();
+        uploader.set_batch_timestamp(
+            ParquetTypeEnum::TableMetadata,
+            &TransactionMetadata {
+                end_transaction_timestamp: Some(Timestamp {
+                    seconds: batch_timestamp.timestamp(),
+                    nanos: 0,
+                }),
+                ..TransactionMetadata::default()
+            },
+        );
+        let mut table_metadata = vec![ParquetTableMetadata::default()];
+        uploader
+            .write_row_groups(&mut table_metadata, ParquetTypeEnum::TableMetadata, true)
+            .unwrap();
+        assert_eq!(
+            uploader.parquet_type_to_open_files[&ParquetTypeEnum::TableMetadata]
+                .keys()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![Some(date(11))]
+        );
+    }
+}
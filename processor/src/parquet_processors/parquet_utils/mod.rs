@@ -1,4 +1,10 @@
 pub mod gcs_uploader;
+pub mod object_store;
 pub mod parquet_buffer_step;
+pub mod parquet_spill_store;
+pub mod parquet_upload_config;
 pub mod parquet_version_tracker_step;
+pub mod parquet_writer_config;
+pub mod schema_registry;
+pub mod table_commit_log;
 pub mod util;
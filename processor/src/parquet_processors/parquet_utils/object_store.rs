@@ -0,0 +1,235 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use google_cloud_storage::{
+    client::Client as GCSClient,
+    http::{
+        objects::{
+            delete::DeleteObjectRequest,
+            download::Range,
+            get::GetObjectRequest,
+            list::ListObjectsRequest,
+            upload::{Media, UploadObjectRequest, UploadType},
+        },
+        Error as StorageError,
+    },
+};
+use std::{
+    fs::{self, File},
+    io::{ErrorKind, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+const PRECONDITION_FAILED: u16 = 412;
+const NOT_FOUND: u16 = 404;
+
+/// Minimal object store used by the table commit log and the compaction of parquet files. Paths
+/// are `/` separated object names.
+#[async_trait]
+pub trait ObjectStore: Send + Sync {
+    /// Writes the object only if it doesn't exist yet. Returns false when it already exists.
+    async fn put_if_absent(&self, path: &str, data: Vec<u8>) -> Result<bool>;
+
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Lists the object names starting with `prefix`, sorted.
+    async fn list(&self, prefix: &str) -> Result<Vec<String>>;
+
+    /// Deletes the object. Deleting an object that doesn't exist is not an error.
+    async fn delete(&self, path: &str) -> Result<()>;
+}
+
+pub struct GcsObjectStore {
+    gcs_client: Arc<GCSClient>,
+    bucket_name: String,
+}
+
+impl GcsObjectStore {
+    pub fn new(gcs_client: Arc<GCSClient>, bucket_name: String) -> Self {
+        Self {
+            gcs_client,
+            bucket_name,
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for GcsObjectStore {
+    async fn put_if_absent(&self, path: &str, data: Vec<u8>) -> Result<bool> {
+        let upload_request = UploadObjectRequest {
+            bucket: self.bucket_name.clone(),
+            // Generation 0 only matches when there is no live object
+            if_generation_match: Some(0),
+            ..Default::default()
+        };
+        let upload_type = UploadType::Simple(Media::new(path.to_string()));
+        match self
+            .gcs_client
+            .upload_object(&upload_request, data, &upload_type)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(StorageError::Response(e)) if e.code == PRECONDITION_FAILED => Ok(false),
+            Err(e) => Err(e).context(format!("Failed to write object {path}")),
+        }
+    }
+
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        let request = GetObjectRequest {
+            bucket: self.bucket_name.clone(),
+            object: path.to_string(),
+            ..Default::default()
+        };
+        match self
+            .gcs_client
+            .download_object(&request, &Range::default())
+            .await
+        {
+            Ok(data) => Ok(Some(data)),
+            Err(StorageError::Response(e)) if e.code == NOT_FOUND => Ok(None),
+            Err(e) => Err(e).context(format!("Failed to read object {path}")),
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut names = vec![];
+        let mut page_token = None;
+        loop {
+            let response = self
+                .gcs_client
+                .list_objects(&ListObjectsRequest {
+                    bucket: self.bucket_name.clone(),
+                    prefix: Some(prefix.to_string()),
+                    page_token,
+                    ..Default::default()
+                })
+                .await
+                .context(format!("Failed to list objects under {prefix}"))?;
+            names.extend(
+                response
+                    .items
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|o| o.name),
+            );
+            page_token = response.next_page_token;
+            if page_token.is_none() {
+                break;
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        let request = DeleteObjectRequest {
+            bucket: self.bucket_name.clone(),
+            object: path.to_string(),
+            ..Default::default()
+        };
+        match self.gcs_client.delete_object(&request).await {
+            Ok(()) => Ok(()),
+            Err(StorageError::Response(e)) if e.code == NOT_FOUND => Ok(()),
+            Err(e) => Err(e).context(format!("Failed to delete object {path}")),
+        }
+    }
+}
+
+/// Object store backed by a local directory, for tests and local runs. Objects are written to a
+/// hidden temporary file first, so readers never see a partially written object.
+pub struct LocalFileSystemObjectStore {
+    root: PathBuf,
+    next_tmp_file: AtomicU64,
+}
+
+impl LocalFileSystemObjectStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            next_tmp_file: AtomicU64::new(0),
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for LocalFileSystemObjectStore {
+    async fn put_if_absent(&self, path: &str, data: Vec<u8>) -> Result<bool> {
+        let path = self.root.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .context("Invalid object name")?;
+        let tmp_path = path.with_file_name(format!(
+            ".{file_name}.{}.{}.tmp",
+            std::process::id(),
+            self.next_tmp_file.fetch_add(1, Ordering::Relaxed)
+        ));
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(&data)?;
+        file.sync_all()?;
+        // Unlike a rename, linking fails if the object exists, which makes the put conditional
+        let result = match fs::hard_link(&tmp_path, &path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+            Err(e) => Err(e).context(format!("Failed to write object {path:?}")),
+        };
+        fs::remove_file(&tmp_path)?;
+        result
+    }
+
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        match fs::read(self.root.join(path)) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).context(format!("Failed to read object {path}")),
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        // Prefixes used by the commit log and the loader always end at a directory boundary.
+        // Subdirectories are listed too, like the nested object names of a GCS listing.
+        let mut names = vec![];
+        let mut dirs = vec![prefix.trim_end_matches('/').to_string()];
+        while let Some(dir) = dirs.pop() {
+            let dir_path = self.root.join(&dir);
+            if !dir_path.is_dir() {
+                continue;
+            }
+            for entry in fs::read_dir(&dir_path)? {
+                let entry = entry?;
+                let file_name = entry.file_name();
+                let file_name = file_name.to_str().context("Invalid object name")?;
+                // Temporary files of writes in progress
+                if file_name.starts_with('.') {
+                    continue;
+                }
+                let name = if dir.is_empty() {
+                    file_name.to_string()
+                } else {
+                    format!("{dir}/{file_name}")
+                };
+                if entry.file_type()?.is_dir() {
+                    dirs.push(name);
+                } else {
+                    names.push(name);
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        match fs::remove_file(self.root.join(path)) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e).context(format!("Failed to delete object {path}")),
+        }
+    }
+}
@@ -1,7 +1,10 @@
 use crate::parquet_processors::ParquetTypeTrait;
 #[allow(unused_imports)]
 use crate::{
-    parquet_processors::parquet_utils::{gcs_uploader::GCSUploader, gcs_uploader::Uploadable},
+    parquet_processors::parquet_utils::{
+        gcs_uploader::GCSUploader, gcs_uploader::Uploadable,
+        parquet_spill_store::ParquetSpillStore, parquet_upload_config::UploadSchedule,
+    },
     parquet_processors::{ParquetTypeEnum, ParquetTypeStructs},
 };
 use anyhow::Result;
@@ -13,23 +16,26 @@ use aptos_indexer_processor_sdk::{
     utils::errors::ProcessorError,
 };
 use async_trait::async_trait;
-use std::{collections::HashMap, time::Duration};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
 use tracing::debug;
 
-/// `ParquetBuffer` is a struct that holds `ParquetTypeStructs` data
-/// and tracks the buffer size in bytes, along with metadata about the data in the buffer.
+/// `ParquetBuffer` is a struct that holds the `ParquetTypeStructs` rows that haven't been encoded
+/// into a row group yet, along with metadata about the data in the buffer.
 struct ParquetBuffer {
     pub buffer: ParquetTypeStructs,
-    pub buffer_size_bytes: usize,
     current_batch_metadata: Option<TransactionMetadata>,
+    last_upload_time: Instant,
 }
 
 impl ParquetBuffer {
     fn new(parquet_type: &ParquetTypeEnum) -> Self {
         Self {
             buffer: ParquetTypeStructs::default_for_type(parquet_type),
-            buffer_size_bytes: 0,
             current_batch_metadata: None,
+            last_upload_time: Instant::now(),
         }
     }
 
@@ -63,50 +69,73 @@ impl ParquetBuffer {
     }
 }
 
-/// `ParquetBufferStep` is a step that accumulates data in buffers until they reach a specified size limit.
+/// `ParquetBufferStep` is a step that accumulates data in buffers until their files reach a target size.
+///
+/// Incoming rows are encoded into the uploader's open files one row group at a time, so the size
+/// compared against `target_file_size` is the actual encoded size of the file rather than an
+/// estimate of the in-memory rows. Once a file reaches the target size, it's uploaded to Google
+/// Cloud Storage (GCS) through the uploader.
 ///
-/// It then uploads the buffered data to Google Cloud Storage (GCS) through an uploader.
-/// This step is typically used to manage large data volumes efficiently by buffering and uploading
-/// only when necessary.
+/// Every table has its own `UploadSchedule`, so small tables can be uploaded less often than big
+/// ones and small files can be coalesced across upload intervals. Each table's upload metadata is
+/// returned on its own, so its checkpoint only advances once its data is uploaded.
 ///
 ///
 /// # Type Parameters
 /// - `U`: A type that implements the `Uploadable` trait, providing the uploading functionality.
 pub struct ParquetBufferStep {
     internal_buffers: HashMap<ParquetTypeEnum, ParquetBuffer>,
-    pub poll_interval: Duration,
     pub buffer_uploader: GCSUploader,
-    pub buffer_max_size: usize,
+    // Schedule of the tables that don't have one in `upload_schedules`
+    default_schedule: UploadSchedule,
+    upload_schedules: HashMap<ParquetTypeEnum, UploadSchedule>,
+    spill_store: Option<ParquetSpillStore>,
 }
 
 impl ParquetBufferStep {
     pub fn new(
         poll_interval: Duration,
         buffer_uploader: GCSUploader,
-        buffer_max_size: usize,
+        target_file_size: usize,
     ) -> Self {
         Self {
             internal_buffers: HashMap::new(),
-            poll_interval,
             buffer_uploader,
-            buffer_max_size,
+            default_schedule: UploadSchedule::new(poll_interval, target_file_size),
+            upload_schedules: HashMap::new(),
+            spill_store: None,
         }
     }
 
-    fn append_to_buffer(
-        buffer: &mut ParquetBuffer,
-        parquet_data: ParquetTypeStructs,
-    ) -> Result<(), ProcessorError> {
-        buffer.buffer_size_bytes += parquet_data.calculate_size();
-        buffer.buffer.append(parquet_data)?;
-        Ok(())
+    /// Upload schedules per table, tables without a schedule use the interval and target file size
+    /// the step was created with.
+    pub fn with_upload_schedules(
+        mut self,
+        upload_schedules: HashMap<ParquetTypeEnum, UploadSchedule>,
+    ) -> Self {
+        self.upload_schedules = upload_schedules;
+        self
+    }
+
+    fn upload_schedule(&self, parquet_type: ParquetTypeEnum) -> UploadSchedule {
+        self.upload_schedules
+            .get(&parquet_type)
+            .copied()
+            .unwrap_or(self.default_schedule)
+    }
+
+    /// Spills every incoming batch to local part files before buffering it, so buffered data
+    /// that hasn't been uploaded yet survives a crash.
+    pub fn with_spill_store(mut self, spill_store: Option<ParquetSpillStore>) -> Self {
+        self.spill_store = spill_store;
+        self
     }
 
     /// Handles the addition of `parquet_data` to the buffer for a specified `ParquetTypeEnum`.
     ///
-    /// We check the size of the buffer + the size of the incoming data before appending it.
-    /// If the sum of the two exceeds the maximum limit size, it uploads the buffer content to GCS to avoid
-    /// spliting the batch data, allowing for more efficient and simpler version tracking.
+    /// The batch is appended as a whole and every full row group is encoded right away. If the
+    /// encoded size then reaches the target file size, the file is uploaded to GCS. Batches are
+    /// never split across files, allowing for more efficient and simpler version tracking.
     async fn upload_buffer_append(
         &mut self,
         parquet_type: ParquetTypeEnum,
@@ -114,6 +143,17 @@ impl ParquetBufferStep {
         cur_batch_metadata: &TransactionMetadata,
         upload_metadata_map: &mut HashMap<ParquetTypeEnum, TransactionMetadata>,
     ) -> Result<(), ProcessorError> {
+        let target_file_size = self.upload_schedule(parquet_type).target_file_size;
+
+        if let Some(spill_store) = &self.spill_store {
+            spill_store
+                .spill(&parquet_data, cur_batch_metadata)
+                .await
+                .map_err(|e| ProcessorError::ProcessError {
+                    message: format!("Failed to spill batch for {parquet_type:?}: {e:?}"),
+                })?;
+        }
+
         // Get or initialize the buffer for the specific ParquetTypeEnum
         let buffer = self
             .internal_buffers
@@ -126,18 +166,31 @@ impl ParquetBufferStep {
                 ParquetBuffer::new(&parquet_type)
             });
 
-        let curr_batch_size_bytes = parquet_data.calculate_size();
-
+        // Append new data to the buffer and encode the full row groups
+        buffer.buffer.append(parquet_data)?;
+        buffer.update_current_batch_metadata(cur_batch_metadata)?;
+        self.buffer_uploader
+            .set_batch_timestamp(parquet_type, cur_batch_metadata);
+        buffer
+            .buffer
+            .write_row_groups(&mut self.buffer_uploader, false)
+            .map_err(|e| ProcessorError::ProcessError {
+                message: format!("Failed to write row groups for {parquet_type:?}: {e:?}"),
+            })?;
+
+        let encoded_size = self.buffer_uploader.encoded_size(parquet_type);
         debug!(
-            "Current batch size for {:?}: {} bytes, buffer size before append: {} bytes",
-            parquet_type, curr_batch_size_bytes, buffer.buffer_size_bytes,
+            "Updated buffer for {:?}: {} bytes encoded, {} rows pending",
+            parquet_type,
+            encoded_size,
+            buffer.buffer.num_rows(),
         );
 
-        // If the current buffer size + new batch exceeds max size, upload the buffer
-        if buffer.buffer_size_bytes + curr_batch_size_bytes > self.buffer_max_size {
+        // If the encoded file reached the target size, upload it with the rest of the buffer
+        if encoded_size >= target_file_size {
             println!(
-                "Buffer size {} + batch size {} exceeds max size {}. Uploading buffer for {:?}.",
-                buffer.buffer_size_bytes, curr_batch_size_bytes, self.buffer_max_size, parquet_type
+                "Encoded size {} reached target file size {}. Uploading buffer for {:?}.",
+                encoded_size, target_file_size, parquet_type
             );
 
             // Take the current buffer to upload and reset the buffer in place
@@ -147,21 +200,9 @@ impl ParquetBufferStep {
             );
             self.buffer_uploader.upload_buffer(struct_buffer).await?;
 
-            // update this metadata before insert
-            upload_metadata_map
-                .insert(parquet_type, buffer.current_batch_metadata.clone().unwrap());
-            buffer.buffer_size_bytes = 0;
-            buffer.current_batch_metadata = None;
+            upload_metadata_map.insert(parquet_type, buffer.current_batch_metadata.take().unwrap());
+            buffer.last_upload_time = Instant::now();
         }
-
-        // Append new data to the buffer
-        Self::append_to_buffer(buffer, parquet_data)?;
-        buffer.update_current_batch_metadata(cur_batch_metadata)?;
-
-        debug!(
-            "Updated buffer size for {:?}: {} bytes",
-            parquet_type, buffer.buffer_size_bytes,
-        );
         Ok(())
     }
 }
@@ -208,16 +249,17 @@ impl Processable for ParquetBufferStep {
         let mut metadata_map = HashMap::new();
         debug!("Starting cleanup: uploading all remaining buffers.");
         for (parquet_type, mut buffer) in self.internal_buffers.drain() {
-            if buffer.buffer_size_bytes > 0 {
+            if buffer.current_batch_metadata.is_some() {
                 let struct_buffer = std::mem::replace(
                     &mut buffer.buffer,
                     ParquetTypeStructs::default_for_type(&parquet_type),
                 );
+                let buffer_size_bytes = self.buffer_uploader.encoded_size(parquet_type);
 
                 self.buffer_uploader.upload_buffer(struct_buffer).await?;
 
                 if let Some(buffer_metadata) = &mut buffer.current_batch_metadata {
-                    buffer_metadata.total_size_in_bytes = buffer.buffer_size_bytes as u64;
+                    buffer_metadata.total_size_in_bytes = buffer_size_bytes as u64;
                     metadata_map.insert(parquet_type, buffer_metadata.clone());
                 } else {
                     // This should never happen
@@ -240,33 +282,65 @@ impl Processable for ParquetBufferStep {
 
 #[async_trait]
 impl PollableAsyncStep for ParquetBufferStep {
+    /// Polls as often as the table with the shortest upload interval needs.
     fn poll_interval(&self) -> Duration {
-        self.poll_interval
+        self.upload_schedules
+            .values()
+            .map(|schedule| schedule.upload_interval)
+            .min()
+            .unwrap_or(self.default_schedule.upload_interval)
     }
 
-    /// Polls all buffers to check if any should be uploaded based on the current size.
-    /// Uploads data and clears the buffer if necessary, and returns upload metadata.
+    /// Uploads the buffers of the tables whose upload interval has passed and returns their
+    /// upload metadata.
+    ///
+    /// Files smaller than the table's `min_file_size` are held back and coalesced with the data of
+    /// the following intervals, until `max_upload_interval` has passed. Tables without buffered
+    /// rows return their metadata without uploading anything, so their checkpoints keep advancing.
     async fn poll(
         &mut self,
     ) -> Result<Option<Vec<TransactionContext<Self::Output>>>, ProcessorError> {
         let mut metadata_map = HashMap::new();
         debug!("Polling to check if any buffers need uploading.");
 
-        for (parquet_type, mut buffer) in self.internal_buffers.drain() {
-            if buffer.buffer_size_bytes > 0 {
-                let struct_buffer = std::mem::replace(
-                    &mut buffer.buffer,
-                    ParquetTypeStructs::default_for_type(&parquet_type),
-                );
+        for (parquet_type, buffer) in self.internal_buffers.iter_mut() {
+            let schedule = self
+                .upload_schedules
+                .get(parquet_type)
+                .copied()
+                .unwrap_or(self.default_schedule);
+            let elapsed = buffer.last_upload_time.elapsed();
+            if buffer.current_batch_metadata.is_none() || elapsed < schedule.upload_interval {
+                continue;
+            }
 
-                self.buffer_uploader.upload_buffer(struct_buffer).await?;
+            // Rows that don't fill a row group are encoded too so the size of small files is known
+            buffer
+                .buffer
+                .write_row_groups(&mut self.buffer_uploader, true)
+                .map_err(|e| ProcessorError::ProcessError {
+                    message: format!("Failed to write row groups for {parquet_type:?}: {e:?}"),
+                })?;
+            let encoded_size = self.buffer_uploader.encoded_size(*parquet_type);
+            if encoded_size > 0
+                && encoded_size < schedule.min_file_size
+                && elapsed < schedule.max_upload_interval
+            {
+                debug!(
+                    "Coalescing {:?}: {} bytes encoded is below min file size {}",
+                    parquet_type, encoded_size, schedule.min_file_size,
+                );
+                continue;
+            }
 
-                let metadata = buffer.current_batch_metadata.clone().unwrap();
-                metadata_map.insert(parquet_type, metadata);
+            let struct_buffer = std::mem::replace(
+                &mut buffer.buffer,
+                ParquetTypeStructs::default_for_type(parquet_type),
+            );
+            self.buffer_uploader.upload_buffer(struct_buffer).await?;
 
-                buffer.buffer_size_bytes = 0;
-                buffer.current_batch_metadata = None;
-            }
+            metadata_map.insert(*parquet_type, buffer.current_batch_metadata.take().unwrap());
+            buffer.last_upload_time = Instant::now();
         }
 
         if !metadata_map.is_empty() {
@@ -288,15 +362,20 @@ impl NamedStep for ParquetBufferStep {
 mod tests {
     use crate::{
         config::db_config::ParquetConfig,
-        parquet_processors::parquet_utils::{
-            gcs_uploader::{create_new_writer, GCSUploader},
-            parquet_buffer_step::{ParquetBufferStep, ParquetTypeEnum, ParquetTypeStructs},
-            util::HasParquetSchema,
+        parquet_processors::{
+            parquet_utils::{
+                gcs_uploader::GCSUploader,
+                parquet_buffer_step::{ParquetBufferStep, ParquetTypeEnum, ParquetTypeStructs},
+                parquet_upload_config::UploadSchedule,
+                parquet_writer_config::ParquetWriterConfig,
+                util::HasParquetSchema,
+            },
+            ParquetTypeTrait,
         },
         processors::default::models::move_resources::ParquetMoveResource,
     };
     use aptos_indexer_processor_sdk::{
-        traits::Processable,
+        traits::{PollableAsyncStep, Processable},
         types::transaction_context::{TransactionContext, TransactionMetadata},
     };
     use google_cloud_storage::client::{Client as GCSClient, ClientConfig as GcsClientConfig};
@@ -324,7 +403,7 @@ mod tests {
 
         assert!(
             result.is_none(),
-            "Expected no upload for data below buffer_max_size"
+            "Expected no upload for data below target_file_size"
         );
 
         Ok(())
@@ -332,50 +411,135 @@ mod tests {
 
     #[tokio::test]
     #[allow(clippy::needless_return)]
-    async fn test_parquet_buffer_step_trigger_upload() -> anyhow::Result<()> {
-        let buffer_max_size = 25; // Default ParquetTypeStructs for MoveResource is 24 bytes
+    async fn test_parquet_buffer_step_encodes_full_row_groups() -> anyhow::Result<()> {
         let db_config = create_parquet_db_config();
-
-        let buffer_uploader = create_parquet_uploader(&db_config).await?;
+        let writer_properties = ParquetWriterConfig {
+            max_row_group_size: Some(2),
+            ..ParquetWriterConfig::default()
+        }
+        .table_writer_properties(
+            ParquetTypeEnum::MoveResources,
+            &ParquetMoveResource::schema(),
+        )?;
+        let buffer_uploader = create_parquet_uploader(&db_config)
+            .await?
+            .with_writer_properties(HashMap::from([(
+                ParquetTypeEnum::MoveResources,
+                Arc::new(writer_properties),
+            )]));
         let mut parquet_step =
-            ParquetBufferStep::new(Duration::from_secs(10), buffer_uploader, buffer_max_size);
+            ParquetBufferStep::new(Duration::from_secs(10), buffer_uploader, 1024 * 1024);
 
-        // Test data below `buffer_max_size`
-        let data = HashMap::from([(
-            ParquetTypeEnum::MoveResources,
-            ParquetTypeStructs::default_for_type(&ParquetTypeEnum::MoveResources),
-        )]);
-        let metadata = TransactionMetadata::default();
+        // A single row doesn't fill a row group so nothing is encoded yet
+        let result = parquet_step
+            .process(move_resources_batch(0, 1))
+            .await
+            .unwrap();
+        assert!(result.is_none());
+        assert_eq!(
+            parquet_step
+                .buffer_uploader
+                .encoded_size(ParquetTypeEnum::MoveResources),
+            0
+        );
 
+        // Three buffered rows make one full row group and leave one row pending
         let result = parquet_step
-            .process(TransactionContext { data, metadata })
+            .process(move_resources_batch(1, 2))
             .await
             .unwrap();
         assert!(
             result.is_none(),
-            "Expected no upload for data below buffer_max_size"
+            "Expected no upload for files below target_file_size"
+        );
+        assert!(
+            parquet_step
+                .buffer_uploader
+                .encoded_size(ParquetTypeEnum::MoveResources)
+                > 0
+        );
+        assert_eq!(
+            parquet_step.internal_buffers[&ParquetTypeEnum::MoveResources]
+                .buffer
+                .num_rows(),
+            1
         );
 
-        // Test buffer + data > `buffer_max_size`
-        let data = HashMap::from([(
-            ParquetTypeEnum::MoveResources,
-            ParquetTypeStructs::default_for_type(&ParquetTypeEnum::MoveResources),
-        )]);
-        let metadata = TransactionMetadata::default();
+        Ok(())
+    }
 
-        let result = parquet_step
-            .process(TransactionContext { data, metadata })
+    #[tokio::test]
+    #[allow(clippy::needless_return)]
+    async fn test_parquet_buffer_step_coalesces_small_files() -> anyhow::Result<()> {
+        let db_config = create_parquet_db_config();
+        let buffer_uploader = create_parquet_uploader(&db_config).await?;
+        let mut parquet_step =
+            ParquetBufferStep::new(Duration::from_secs(10), buffer_uploader, 1024 * 1024)
+                .with_upload_schedules(HashMap::from([(
+                    ParquetTypeEnum::MoveResources,
+                    UploadSchedule {
+                        upload_interval: Duration::ZERO,
+                        target_file_size: 1024 * 1024,
+                        min_file_size: 1024 * 1024,
+                        max_upload_interval: Duration::from_secs(3600),
+                    },
+                )]));
+        assert_eq!(parquet_step.poll_interval(), Duration::ZERO);
+
+        // A table without rows still returns its metadata so its checkpoint advances
+        parquet_step
+            .process(move_resources_batch(0, 0))
             .await
             .unwrap();
+        let result = parquet_step.poll().await.unwrap().unwrap();
+        assert_eq!(
+            result[0].data[&ParquetTypeEnum::MoveResources].end_version,
+            0
+        );
 
+        // A file below min_file_size is encoded but held back
+        parquet_step
+            .process(move_resources_batch(1, 1))
+            .await
+            .unwrap();
+        let result = parquet_step.poll().await.unwrap();
+        assert!(result.is_none(), "Expected small file to be coalesced");
         assert!(
-            result.is_some(),
-            "Expected upload when data exceeds buffer_max_size"
+            parquet_step
+                .buffer_uploader
+                .encoded_size(ParquetTypeEnum::MoveResources)
+                > 0
         );
 
         Ok(())
     }
 
+    fn move_resources_batch(
+        start_version: u64,
+        num_rows: u64,
+    ) -> TransactionContext<HashMap<ParquetTypeEnum, ParquetTypeStructs>> {
+        let end_version = (start_version + num_rows).max(1) - 1;
+        let rows = (start_version..start_version + num_rows)
+            .map(|version| ParquetMoveResource {
+                txn_version: version as i64,
+                resource_address: "0x1".to_string(),
+                resource_type: "0x1::account::Account".to_string(),
+                ..ParquetMoveResource::default()
+            })
+            .collect();
+        TransactionContext {
+            data: HashMap::from([(
+                ParquetTypeEnum::MoveResources,
+                ParquetTypeStructs::MoveResource(rows),
+            )]),
+            metadata: TransactionMetadata {
+                start_version,
+                end_version,
+                ..TransactionMetadata::default()
+            },
+        }
+    }
+
     async fn create_parquet_uploader(db_config: &ParquetConfig) -> anyhow::Result<GCSUploader> {
         let gcs_config = GcsClientConfig::default()
             .with_auth()
@@ -390,18 +554,9 @@ mod tests {
         .into_iter()
         .collect();
 
-        let parquet_type_to_writer = parquet_type_to_schemas
-            .iter()
-            .map(|(key, schema)| {
-                let writer = create_new_writer(schema.clone()).expect("Failed to create writer");
-                (*key, writer)
-            })
-            .collect();
-
         GCSUploader::new(
             gcs_client,
             parquet_type_to_schemas,
-            parquet_type_to_writer,
             db_config.bucket_name.clone(),
             db_config.bucket_root.clone(),
             "processor_name".to_string(),
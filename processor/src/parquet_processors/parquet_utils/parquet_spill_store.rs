@@ -0,0 +1,490 @@
+use crate::parquet_processors::{
+    parquet_utils::{
+        gcs_uploader::{GCSUploader, Uploadable},
+        parquet_version_tracker_step::ParquetProcessorStatusSaverTrait,
+    },
+    ParquetTypeEnum, ParquetTypeStructs, ParquetTypeTrait,
+};
+use anyhow::{Context, Result};
+use aptos_indexer_processor_sdk::{
+    aptos_protos::util::timestamp::Timestamp,
+    types::transaction_context::{TransactionContext, TransactionMetadata},
+};
+use hyper::body::Bytes;
+use parquet::file::{
+    metadata::{KeyValue, ParquetMetaData},
+    properties::WriterProperties,
+    reader::{FileReader, SerializedFileReader},
+};
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use strum::IntoEnumIterator;
+use tracing::{info, warn};
+
+const SPILL_FILE_EXTENSION: &str = "parquet";
+const TMP_FILE_EXTENSION: &str = "tmp";
+const END_TIMESTAMP_SECONDS_KEY: &str = "end_transaction_timestamp_seconds";
+const END_TIMESTAMP_NANOS_KEY: &str = "end_transaction_timestamp_nanos";
+
+/// A part file holding the rows of a single batch for one table.
+#[derive(Debug)]
+struct SpillFile {
+    path: PathBuf,
+    start_version: u64,
+    end_version: u64,
+}
+
+/// Write-ahead store for the parquet buffers.
+///
+/// Every batch appended to a buffer is first written to a local part file named after its version
+/// range, `{spill_dir}/{processor_name}/{table_name}/{start_version}_{end_version}.parquet`.
+/// Part files are written to a temporary file, fsynced and renamed, so a part file is either
+/// complete or absent. Once a checkpoint covering a part file has been saved the file is pruned.
+/// On startup, the part files left behind by a crash that aren't covered by a checkpoint yet are
+/// merged per table, uploaded and checkpointed before the stream resumes.
+#[derive(Clone, Debug)]
+pub struct ParquetSpillStore {
+    root: PathBuf,
+}
+
+impl ParquetSpillStore {
+    pub fn new(spill_dir: impl AsRef<Path>, processor_name: &str) -> Result<Self> {
+        let root = spill_dir.as_ref().join(processor_name);
+        fs::create_dir_all(&root)
+            .with_context(|| format!("Failed to create spill directory {root:?}"))?;
+        Ok(Self { root })
+    }
+
+    fn table_dir(&self, table_name: &str) -> PathBuf {
+        self.root.join(table_name)
+    }
+
+    /// Durably writes the rows of a batch before they are appended to the in-memory buffer.
+    ///
+    /// Empty batches are written as well, so the part files of every table cover the same
+    /// version ranges and recovery can advance the checkpoints of tables without rows.
+    pub async fn spill(
+        &self,
+        parquet_data: &ParquetTypeStructs,
+        metadata: &TransactionMetadata,
+    ) -> Result<()> {
+        let table_dir = self.table_dir(&parquet_data.parquet_type().to_string());
+        let bytes = parquet_data.to_parquet_bytes(Arc::new(spill_writer_properties(
+            parquet_data.parquet_type(),
+            metadata,
+        )))?;
+        let file_name = format!(
+            "{:020}_{:020}",
+            metadata.start_version, metadata.end_version
+        );
+        tokio::task::spawn_blocking(move || write_spill_file(&table_dir, &file_name, &bytes))
+            .await
+            .context("Spill write task panicked")?
+    }
+
+    /// Removes the part files of a table that are covered by a saved checkpoint.
+    pub fn prune(&self, parquet_type: ParquetTypeEnum, checkpoint_version: u64) -> Result<()> {
+        let table_dir = self.table_dir(&parquet_type.to_string());
+        for spill_file in list_spill_files(&table_dir)? {
+            if spill_file.end_version <= checkpoint_version {
+                fs::remove_file(&spill_file.path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Uploads the part files left behind by a previous run and saves their checkpoints.
+    ///
+    /// Must run before the starting version is computed so the stream resumes after the recovered
+    /// data.
+    pub async fn recover<S: ParquetProcessorStatusSaverTrait>(
+        &self,
+        uploader: &mut GCSUploader,
+        processor_status_saver: &S,
+    ) -> Result<()> {
+        // Part files covered by a checkpoint were uploaded before the crash and are dropped
+        let mut tables = vec![];
+        for entry in fs::read_dir(&self.root)? {
+            let table_dir = entry?.path();
+            if !table_dir.is_dir() {
+                continue;
+            }
+            let table_name = table_dir
+                .file_name()
+                .and_then(|name| name.to_str())
+                .context("Invalid spill table directory")?
+                .to_string();
+            let parquet_type = ParquetTypeEnum::iter()
+                .find(|parquet_type| parquet_type.to_string() == table_name)
+                .with_context(|| format!("Unknown table in spill directory {table_name}"))?;
+            let checkpoint = processor_status_saver
+                .get_parquet_processor_status(&table_name)
+                .await?;
+            let mut spill_files = vec![];
+            for spill_file in list_spill_files(&table_dir)? {
+                if checkpoint.is_some_and(|checkpoint| spill_file.end_version <= checkpoint) {
+                    fs::remove_file(&spill_file.path)?;
+                } else {
+                    spill_files.push(spill_file);
+                }
+            }
+            tables.push((parquet_type, checkpoint, spill_files));
+        }
+
+        // A crash in the middle of a batch leaves the part file of that batch for some tables
+        // only. Recovery stops at the last version spilled by every table, the stream resumes
+        // from there and writes the remaining part files again.
+        let Some(recovered_version) = tables
+            .iter()
+            .filter_map(|(_, checkpoint, spill_files)| {
+                spill_files
+                    .last()
+                    .map(|spill_file| spill_file.end_version)
+                    .max(*checkpoint)
+            })
+            .min()
+        else {
+            return Ok(());
+        };
+
+        for (parquet_type, _, spill_files) in tables {
+            let table_name = parquet_type.to_string();
+            let (spill_files, unfinished): (Vec<_>, Vec<_>) = spill_files
+                .into_iter()
+                .partition(|spill_file| spill_file.end_version <= recovered_version);
+            for spill_file in unfinished {
+                fs::remove_file(&spill_file.path)?;
+            }
+            let (Some(first), Some(last)) = (spill_files.first(), spill_files.last()) else {
+                continue;
+            };
+            info!(
+                table_name = table_name,
+                start_version = first.start_version,
+                end_version = last.end_version,
+                num_files = spill_files.len(),
+                "Recovering spilled parquet data",
+            );
+
+            let (rows, end_transaction_timestamp) = read_spill_files(parquet_type, &spill_files)?;
+            let recovered_batch = TransactionContext {
+                data: (),
+                metadata: TransactionMetadata {
+                    start_version: first.start_version,
+                    end_version: last.end_version,
+                    end_transaction_timestamp,
+                    ..TransactionMetadata::default()
+                },
+            };
+            // Batches without rows only advance the checkpoint. The rows are uploaded like a
+            // regular buffer, split into the date partitions of their own timestamps.
+            if rows.num_rows() > 0 {
+                uploader.set_batch_timestamp(parquet_type, &recovered_batch.metadata);
+                uploader.upload_buffer(rows).await.map_err(|e| {
+                    anyhow::anyhow!("Failed to upload spilled data for {table_name}: {e:?}")
+                })?;
+            }
+            processor_status_saver
+                .save_parquet_processor_status(&recovered_batch, &table_name)
+                .await?;
+
+            for spill_file in spill_files {
+                fs::remove_file(&spill_file.path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes a part file to a temporary file, fsyncs it and renames it into place.
+fn write_spill_file(table_dir: &Path, file_name: &str, bytes: &[u8]) -> Result<()> {
+    fs::create_dir_all(table_dir)?;
+    let tmp_path = table_dir.join(format!("{file_name}.{TMP_FILE_EXTENSION}"));
+    let path = table_dir.join(format!("{file_name}.{SPILL_FILE_EXTENSION}"));
+
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, &path)?;
+    // Persist the rename itself
+    File::open(table_dir)?.sync_all()?;
+    Ok(())
+}
+
+fn spill_writer_properties(
+    parquet_type: ParquetTypeEnum,
+    metadata: &TransactionMetadata,
+) -> WriterProperties {
+    let mut key_value_metadata = vec![parquet_type.schema_version_metadata()];
+    if let Some(timestamp) = metadata.end_transaction_timestamp.as_ref() {
+        key_value_metadata.push(KeyValue::new(
+            END_TIMESTAMP_SECONDS_KEY.to_string(),
+            timestamp.seconds.to_string(),
+        ));
+        key_value_metadata.push(KeyValue::new(
+            END_TIMESTAMP_NANOS_KEY.to_string(),
+            timestamp.nanos.to_string(),
+        ));
+    }
+    WriterProperties::builder()
+        .set_compression(parquet::basic::Compression::LZ4)
+        .set_key_value_metadata(Some(key_value_metadata))
+        .build()
+}
+
+/// Lists the complete part files of a table ordered by version. Leftover temporary files are
+/// from a write interrupted before its rename and are removed.
+fn list_spill_files(table_dir: &Path) -> Result<Vec<SpillFile>> {
+    if !table_dir.exists() {
+        return Ok(vec![]);
+    }
+    let mut spill_files = vec![];
+    for entry in fs::read_dir(table_dir)? {
+        let path = entry?.path();
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(TMP_FILE_EXTENSION) => {
+                warn!("Removing incomplete spill file {:?}", path);
+                fs::remove_file(&path)?;
+            },
+            Some(SPILL_FILE_EXTENSION) => {
+                let versions = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| stem.split_once('_'))
+                    .and_then(|(start, end)| Some((start.parse().ok()?, end.parse().ok()?)));
+                match versions {
+                    Some((start_version, end_version)) => spill_files.push(SpillFile {
+                        path,
+                        start_version,
+                        end_version,
+                    }),
+                    None => warn!("Ignoring unexpected file in spill directory {:?}", path),
+                }
+            },
+            _ => warn!("Ignoring unexpected file in spill directory {:?}", path),
+        }
+    }
+    spill_files.sort_by_key(|spill_file| spill_file.start_version);
+    Ok(spill_files)
+}
+
+/// Reads the rows of the part files of a table, along with the end timestamp of the last part
+/// file. Part files written with an older schema version can't be read into the current model, so
+/// they have to be recovered by the release that wrote them.
+fn read_spill_files(
+    parquet_type: ParquetTypeEnum,
+    spill_files: &[SpillFile],
+) -> Result<(ParquetTypeStructs, Option<Timestamp>)> {
+    let mut rows = ParquetTypeStructs::default_for_type(&parquet_type);
+    let mut end_transaction_timestamp = None;
+    for spill_file in spill_files {
+        let data = fs::read(&spill_file.path)?;
+        let metadata = SerializedFileReader::new(Bytes::from(data.clone()))?
+            .metadata()
+            .clone();
+        end_transaction_timestamp = read_end_transaction_timestamp(&metadata);
+        rows.extend_from_parquet(data)
+            .with_context(|| format!("Failed to read spill file {:?}", spill_file.path))?;
+    }
+    Ok((rows, end_transaction_timestamp))
+}
+
+fn read_end_transaction_timestamp(metadata: &ParquetMetaData) -> Option<Timestamp> {
+    let key_value_metadata = metadata.file_metadata().key_value_metadata()?;
+    let get = |key: &str| {
+        key_value_metadata
+            .iter()
+            .find(|kv| kv.key == key)
+            .and_then(|kv| kv.value.as_deref())
+    };
+    Some(Timestamp {
+        seconds: get(END_TIMESTAMP_SECONDS_KEY)?.parse().ok()?,
+        nanos: get(END_TIMESTAMP_NANOS_KEY)?.parse().ok()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processors::default::models::move_resources::ParquetMoveResource;
+    use aptos_indexer_processor_sdk::utils::errors::ProcessorError;
+    use std::{collections::HashMap, sync::Mutex};
+
+    fn test_store(name: &str) -> ParquetSpillStore {
+        let spill_dir =
+            std::env::temp_dir().join(format!("parquet_spill_store_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&spill_dir);
+        ParquetSpillStore::new(spill_dir, "test_processor").unwrap()
+    }
+
+    fn move_resources(versions: &[i64]) -> ParquetTypeStructs {
+        ParquetTypeStructs::MoveResource(
+            versions
+                .iter()
+                .map(|&txn_version| ParquetMoveResource {
+                    txn_version,
+                    ..ParquetMoveResource::default()
+                })
+                .collect(),
+        )
+    }
+
+    fn metadata(start_version: u64, end_version: u64) -> TransactionMetadata {
+        TransactionMetadata {
+            start_version,
+            end_version,
+            end_transaction_timestamp: Some(Timestamp {
+                seconds: end_version as i64,
+                nanos: 7,
+            }),
+            ..TransactionMetadata::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spill_read_and_prune() {
+        let store = test_store("read_and_prune");
+        store
+            .spill(&move_resources(&[1, 2]), &metadata(1, 10))
+            .await
+            .unwrap();
+        store
+            .spill(&move_resources(&[]), &metadata(11, 20))
+            .await
+            .unwrap();
+        store
+            .spill(&move_resources(&[25]), &metadata(21, 30))
+            .await
+            .unwrap();
+
+        let table_dir = store.table_dir("move_resources");
+        let spill_files = list_spill_files(&table_dir).unwrap();
+        assert_eq!(
+            spill_files
+                .iter()
+                .map(|f| (f.start_version, f.end_version))
+                .collect::<Vec<_>>(),
+            vec![(1, 10), (11, 20), (21, 30)]
+        );
+
+        let (rows, end_transaction_timestamp) =
+            read_spill_files(ParquetTypeEnum::MoveResources, &spill_files).unwrap();
+        assert_eq!(rows.version_range(), Some((1, 25)));
+        assert_eq!(rows.num_rows(), 3);
+        assert_eq!(
+            end_transaction_timestamp,
+            Some(Timestamp {
+                seconds: 30,
+                nanos: 7
+            })
+        );
+
+        store.prune(ParquetTypeEnum::MoveResources, 20).unwrap();
+        let spill_files = list_spill_files(&table_dir).unwrap();
+        assert_eq!(spill_files.len(), 1);
+        assert_eq!(spill_files[0].start_version, 21);
+
+        fs::remove_dir_all(&store.root).unwrap();
+    }
+
+    #[derive(Default)]
+    struct TestStatusSaver {
+        checkpoints: Mutex<HashMap<String, u64>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ParquetProcessorStatusSaverTrait for TestStatusSaver {
+        async fn save_parquet_processor_status(
+            &self,
+            last_success_batch: &TransactionContext<()>,
+            table_name: &str,
+        ) -> Result<(), ProcessorError> {
+            self.checkpoints.lock().unwrap().insert(
+                table_name.to_string(),
+                last_success_batch.metadata.end_version,
+            );
+            Ok(())
+        }
+
+        async fn get_parquet_processor_status(
+            &self,
+            table_name: &str,
+        ) -> Result<Option<u64>, ProcessorError> {
+            Ok(self.checkpoints.lock().unwrap().get(table_name).copied())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recover_skips_checkpointed_and_unfinished_batches() {
+        let store = test_store("recover");
+        // move_resources was checkpointed at 10 and has no rows after it
+        store
+            .spill(&move_resources(&[5]), &metadata(1, 10))
+            .await
+            .unwrap();
+        store
+            .spill(&move_resources(&[]), &metadata(11, 20))
+            .await
+            .unwrap();
+        // write_set_changes has no checkpoint yet and spilled one more batch before the crash
+        for (start_version, end_version) in [(1, 10), (11, 20), (21, 30)] {
+            store
+                .spill(
+                    &ParquetTypeStructs::WriteSetChange(vec![]),
+                    &metadata(start_version, end_version),
+                )
+                .await
+                .unwrap();
+        }
+        let saver = TestStatusSaver::default();
+        saver
+            .checkpoints
+            .lock()
+            .unwrap()
+            .insert("move_resources".to_string(), 10);
+
+        // Nothing is left to upload, so the client is never used
+        let gcs_client = Arc::new(google_cloud_storage::client::Client::new(
+            google_cloud_storage::client::ClientConfig::default(),
+        ));
+        let mut uploader = GCSUploader::new(
+            gcs_client,
+            HashMap::new(),
+            "bucket".to_string(),
+            "root".to_string(),
+            "test_processor".to_string(),
+        )
+        .unwrap();
+        store.recover(&mut uploader, &saver).await.unwrap();
+
+        let checkpoints = saver.checkpoints.lock().unwrap().clone();
+        assert_eq!(checkpoints.get("move_resources"), Some(&20));
+        assert_eq!(checkpoints.get("write_set_changes"), Some(&20));
+        assert!(list_spill_files(&store.table_dir("move_resources"))
+            .unwrap()
+            .is_empty());
+        assert!(list_spill_files(&store.table_dir("write_set_changes"))
+            .unwrap()
+            .is_empty());
+
+        fs::remove_dir_all(&store.root).unwrap();
+    }
+
+    #[test]
+    fn test_incomplete_spill_files_are_removed() {
+        let store = test_store("incomplete");
+        let table_dir = store.table_dir("move_resources");
+        fs::create_dir_all(&table_dir).unwrap();
+        let tmp_path = table_dir.join("00000000000000000001_00000000000000000010.tmp");
+        fs::write(&tmp_path, b"partial").unwrap();
+
+        assert!(list_spill_files(&table_dir).unwrap().is_empty());
+        assert!(!tmp_path.exists());
+
+        fs::remove_dir_all(&store.root).unwrap();
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{
+    config::processor_config::ParquetDefaultProcessorConfig, parquet_processors::ParquetTypeEnum,
+};
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, time::Duration};
+
+/// Upload scheduling of a single parquet table. Unset fields use the processor wide values.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ParquetTableUploadConfig {
+    // Seconds between uploads of the table's buffer
+    #[serde(default)]
+    pub upload_interval: Option<u64>,
+    // Encoded size in bytes at which the table's file is uploaded right away
+    #[serde(default)]
+    pub target_file_size: Option<usize>,
+    // Files smaller than this are held back on the upload interval and coalesced with the data of
+    // the following intervals
+    #[serde(default)]
+    pub min_file_size: Option<usize>,
+    // Seconds after which a file is uploaded even if it's smaller than `min_file_size`
+    #[serde(default)]
+    pub max_upload_interval: Option<u64>,
+}
+
+/// Resolved upload schedule of a table in the `ParquetBufferStep`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UploadSchedule {
+    pub upload_interval: Duration,
+    pub target_file_size: usize,
+    pub min_file_size: usize,
+    pub max_upload_interval: Duration,
+}
+
+impl UploadSchedule {
+    /// Uploads every `upload_interval` or once `target_file_size` is reached, without coalescing.
+    pub fn new(upload_interval: Duration, target_file_size: usize) -> Self {
+        Self {
+            upload_interval,
+            target_file_size,
+            min_file_size: 0,
+            max_upload_interval: upload_interval,
+        }
+    }
+}
+
+/// Resolves the upload schedule of every table of a processor from the processor wide values and
+/// the per table overrides. Overrides for tables the processor doesn't write are rejected.
+pub fn build_upload_schedules<'a>(
+    config: &ParquetDefaultProcessorConfig,
+    parquet_types: impl IntoIterator<Item = &'a ParquetTypeEnum>,
+) -> Result<HashMap<ParquetTypeEnum, UploadSchedule>> {
+    let parquet_types: Vec<ParquetTypeEnum> = parquet_types.into_iter().copied().collect();
+    for table_name in config.per_table_upload.keys() {
+        ensure!(
+            parquet_types
+                .iter()
+                .any(|parquet_type| parquet_type.to_string() == *table_name),
+            "Upload config configured for unknown table '{}'",
+            table_name
+        );
+    }
+
+    parquet_types
+        .into_iter()
+        .map(|parquet_type| {
+            let table_config = config
+                .per_table_upload
+                .get(&parquet_type.to_string())
+                .cloned()
+                .unwrap_or_default();
+            let upload_interval = table_config
+                .upload_interval
+                .unwrap_or(config.upload_interval);
+            let schedule = UploadSchedule {
+                upload_interval: Duration::from_secs(upload_interval),
+                target_file_size: table_config
+                    .target_file_size
+                    .unwrap_or(config.target_file_size),
+                min_file_size: table_config.min_file_size.unwrap_or(config.min_file_size),
+                max_upload_interval: Duration::from_secs(
+                    table_config
+                        .max_upload_interval
+                        .unwrap_or(config.max_upload_interval),
+                ),
+            };
+
+            ensure!(
+                upload_interval > 0,
+                "upload_interval of {parquet_type} must be positive"
+            );
+            ensure!(
+                schedule.min_file_size <= schedule.target_file_size,
+                "min_file_size of {parquet_type} can't be larger than its target_file_size"
+            );
+            // Without coalescing the max upload interval is never used
+            ensure!(
+                schedule.min_file_size == 0
+                    || schedule.max_upload_interval >= schedule.upload_interval,
+                "max_upload_interval of {parquet_type} can't be shorter than its upload_interval"
+            );
+            Ok((parquet_type, schedule))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn processor_config(
+        per_table_upload: HashMap<String, ParquetTableUploadConfig>,
+    ) -> ParquetDefaultProcessorConfig {
+        ParquetDefaultProcessorConfig {
+            upload_interval: 600,
+            target_file_size: 1000,
+            min_file_size: 10,
+            max_upload_interval: 3600,
+            per_table_upload,
+            ..ParquetDefaultProcessorConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_per_table_upload_schedules() {
+        let config = processor_config(HashMap::from([(
+            "current_ans_lookup_v2".to_string(),
+            ParquetTableUploadConfig {
+                upload_interval: Some(1800),
+                min_file_size: Some(500),
+                ..ParquetTableUploadConfig::default()
+            },
+        )]));
+        let schedules = build_upload_schedules(&config, &[
+            ParquetTypeEnum::CurrentAnsLookupV2,
+            ParquetTypeEnum::AnsLookupV2,
+        ])
+        .unwrap();
+
+        assert_eq!(
+            schedules[&ParquetTypeEnum::CurrentAnsLookupV2],
+            UploadSchedule {
+                upload_interval: Duration::from_secs(1800),
+                target_file_size: 1000,
+                min_file_size: 500,
+                max_upload_interval: Duration::from_secs(3600),
+            }
+        );
+        assert_eq!(schedules[&ParquetTypeEnum::AnsLookupV2], UploadSchedule {
+            upload_interval: Duration::from_secs(600),
+            target_file_size: 1000,
+            min_file_size: 10,
+            max_upload_interval: Duration::from_secs(3600),
+        });
+    }
+
+    #[test]
+    fn test_invalid_upload_schedules() {
+        let parquet_types = [ParquetTypeEnum::AnsLookupV2];
+        let invalid_configs = [
+            ("events", ParquetTableUploadConfig::default()),
+            ("ans_lookup_v2", ParquetTableUploadConfig {
+                upload_interval: Some(0),
+                ..ParquetTableUploadConfig::default()
+            }),
+            ("ans_lookup_v2", ParquetTableUploadConfig {
+                min_file_size: Some(2000),
+                ..ParquetTableUploadConfig::default()
+            }),
+            ("ans_lookup_v2", ParquetTableUploadConfig {
+                max_upload_interval: Some(60),
+                ..ParquetTableUploadConfig::default()
+            }),
+        ];
+        for (table_name, table_config) in invalid_configs {
+            let config = processor_config(HashMap::from([(table_name.to_string(), table_config)]));
+            assert!(build_upload_schedules(&config, &parquet_types).is_err());
+        }
+    }
+}
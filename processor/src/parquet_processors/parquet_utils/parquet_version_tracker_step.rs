@@ -1,4 +1,6 @@
-use crate::parquet_processors::ParquetTypeEnum;
+use crate::parquet_processors::{
+    parquet_utils::parquet_spill_store::ParquetSpillStore, ParquetTypeEnum,
+};
 use anyhow::Result;
 use aptos_indexer_processor_sdk::{
     traits::{
@@ -21,6 +23,12 @@ pub trait ParquetProcessorStatusSaverTrait {
         last_success_batch: &TransactionContext<()>,
         table_name: &str,
     ) -> Result<(), ProcessorError>;
+
+    /// Returns the last checkpointed version of the table, if any.
+    async fn get_parquet_processor_status(
+        &self,
+        table_name: &str,
+    ) -> Result<Option<u64>, ProcessorError>;
 }
 
 /// Tracks the versioned processing of sequential transactions, ensuring no gaps
@@ -37,6 +45,7 @@ where
     last_success_batch: HashMap<ParquetTypeEnum, TransactionContext<()>>,
     polling_interval_secs: u64,
     processor_status_saver: S,
+    spill_store: Option<ParquetSpillStore>,
 }
 
 impl<S> ParquetVersionTrackerStep<S>
@@ -49,15 +58,31 @@ where
             last_success_batch: HashMap::new(),
             processor_status_saver,
             polling_interval_secs,
+            spill_store: None,
         }
     }
 
+    /// Prunes the spilled part files of a table once a checkpoint covering them is saved. Until
+    /// then, the part files are what makes the uploaded-but-not-checkpointed and the
+    /// buffered-but-not-uploaded data recoverable.
+    pub fn with_spill_store(mut self, spill_store: Option<ParquetSpillStore>) -> Self {
+        self.spill_store = spill_store;
+        self
+    }
+
     async fn save_processor_status(&mut self) -> Result<(), ProcessorError> {
         for (parquet_type, last_success_batch) in &self.last_success_batch {
             let table_name = parquet_type.to_string();
             self.processor_status_saver
                 .save_parquet_processor_status(last_success_batch, &table_name)
                 .await?;
+            if let Some(spill_store) = &self.spill_store {
+                spill_store
+                    .prune(*parquet_type, last_success_batch.metadata.end_version)
+                    .map_err(|e| ProcessorError::ProcessError {
+                        message: format!("Failed to prune spill files for {table_name}: {e:?}"),
+                    })?;
+            }
         }
         Ok(())
     }
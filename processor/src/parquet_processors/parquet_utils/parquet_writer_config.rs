@@ -0,0 +1,291 @@
+use crate::parquet_processors::ParquetTypeEnum;
+use anyhow::{ensure, Context, Result};
+use parquet::{
+    basic::{Compression, ZstdLevel},
+    file::properties::{EnabledStatistics, WriterProperties, WriterPropertiesBuilder},
+    schema::types::{ColumnPath, Type},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetCompression {
+    Uncompressed,
+    #[default]
+    Lz4,
+    Zstd,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetStatistics {
+    None,
+    Chunk,
+    Page,
+}
+
+impl From<ParquetStatistics> for EnabledStatistics {
+    fn from(statistics: ParquetStatistics) -> Self {
+        match statistics {
+            ParquetStatistics::None => EnabledStatistics::None,
+            ParquetStatistics::Chunk => EnabledStatistics::Chunk,
+            ParquetStatistics::Page => EnabledStatistics::Page,
+        }
+    }
+}
+
+/// Writer properties for a single parquet table. Unset fields keep the parquet defaults, except
+/// for compression which defaults to LZ4.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ParquetWriterConfig {
+    #[serde(default)]
+    pub compression: ParquetCompression,
+    // Only applies to zstd
+    #[serde(default)]
+    pub compression_level: Option<i32>,
+    // Rows per row group, buffered rows are encoded as soon as a row group is full
+    #[serde(default)]
+    pub max_row_group_size: Option<usize>,
+    // Default for all columns, `dictionary_columns` are always dictionary encoded
+    #[serde(default)]
+    pub dictionary_enabled: Option<bool>,
+    #[serde(default)]
+    pub dictionary_columns: Vec<String>,
+    #[serde(default)]
+    pub statistics: Option<ParquetStatistics>,
+    #[serde(default)]
+    pub bloom_filter_columns: Vec<String>,
+    // False positive probability of the bloom filters
+    #[serde(default)]
+    pub bloom_filter_fpp: Option<f64>,
+}
+
+impl ParquetWriterConfig {
+    /// Builds the writer properties, making sure every configured column exists in the table schema.
+    pub fn writer_properties(&self, schema: &Type) -> Result<WriterProperties> {
+        Ok(self.writer_properties_builder(schema)?.build())
+    }
+
+    /// Writer properties of a table, with its schema version in the file metadata.
+    pub fn table_writer_properties(
+        &self,
+        parquet_type: ParquetTypeEnum,
+        schema: &Type,
+    ) -> Result<WriterProperties> {
+        Ok(self
+            .writer_properties_builder(schema)?
+            .set_key_value_metadata(Some(vec![parquet_type.schema_version_metadata()]))
+            .build())
+    }
+
+    fn writer_properties_builder(&self, schema: &Type) -> Result<WriterPropertiesBuilder> {
+        let column_names: HashSet<&str> = schema
+            .get_fields()
+            .iter()
+            .map(|field| field.name())
+            .collect();
+        for column in self
+            .dictionary_columns
+            .iter()
+            .chain(self.bloom_filter_columns.iter())
+        {
+            ensure!(
+                column_names.contains(column.as_str()),
+                "Column '{}' does not exist in the table schema",
+                column
+            );
+        }
+
+        let compression = match (self.compression, self.compression_level) {
+            (ParquetCompression::Zstd, level) => Compression::ZSTD(
+                level
+                    .map(ZstdLevel::try_new)
+                    .transpose()?
+                    .unwrap_or_default(),
+            ),
+            (_, Some(_)) => anyhow::bail!("compression_level is only supported with zstd"),
+            (ParquetCompression::Lz4, None) => Compression::LZ4,
+            (ParquetCompression::Uncompressed, None) => Compression::UNCOMPRESSED,
+        };
+
+        let mut builder = WriterProperties::builder().set_compression(compression);
+        if let Some(max_row_group_size) = self.max_row_group_size {
+            ensure!(
+                max_row_group_size > 0,
+                "max_row_group_size must be positive"
+            );
+            builder = builder.set_max_row_group_size(max_row_group_size);
+        }
+        if let Some(dictionary_enabled) = self.dictionary_enabled {
+            builder = builder.set_dictionary_enabled(dictionary_enabled);
+        }
+        for column in &self.dictionary_columns {
+            builder =
+                builder.set_column_dictionary_enabled(ColumnPath::from(column.as_str()), true);
+        }
+        if let Some(statistics) = self.statistics {
+            builder = builder.set_statistics_enabled(statistics.into());
+        }
+        if let Some(fpp) = self.bloom_filter_fpp {
+            ensure!(
+                fpp > 0.0 && fpp < 1.0,
+                "bloom_filter_fpp must be between 0 and 1"
+            );
+        }
+        for column in &self.bloom_filter_columns {
+            let column_path = ColumnPath::from(column.as_str());
+            builder = builder.set_column_bloom_filter_enabled(column_path.clone(), true);
+            if let Some(fpp) = self.bloom_filter_fpp {
+                builder = builder.set_column_bloom_filter_fpp(column_path, fpp);
+            }
+        }
+        Ok(builder)
+    }
+}
+
+/// Resolves the per table writer configs of a processor into writer properties. Tables without a
+/// config use the default properties, tables without a max row group size use
+/// `default_max_row_group_size` and configs for tables the processor doesn't write are rejected.
+pub fn build_writer_properties(
+    writer_configs: &HashMap<String, ParquetWriterConfig>,
+    parquet_type_to_schemas: &HashMap<ParquetTypeEnum, Arc<Type>>,
+    default_max_row_group_size: usize,
+) -> Result<HashMap<ParquetTypeEnum, Arc<WriterProperties>>> {
+    for table_name in writer_configs.keys() {
+        ensure!(
+            parquet_type_to_schemas
+                .keys()
+                .any(|parquet_type| parquet_type.to_string() == *table_name),
+            "Writer properties configured for unknown table '{}'",
+            table_name
+        );
+    }
+
+    parquet_type_to_schemas
+        .iter()
+        .map(|(parquet_type, schema)| {
+            let mut writer_config = writer_configs
+                .get(&parquet_type.to_string())
+                .cloned()
+                .unwrap_or_default();
+            writer_config
+                .max_row_group_size
+                .get_or_insert(default_max_row_group_size);
+            let properties = writer_config
+                .table_writer_properties(*parquet_type, schema)
+                .with_context(|| format!("Invalid writer properties for {parquet_type}"))?;
+            Ok((*parquet_type, Arc::new(properties)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parquet_processors::parquet_utils::util::HasParquetSchema,
+        processors::default::models::write_set_changes::ParquetWriteSetChange,
+    };
+
+    fn write_set_change_schemas() -> HashMap<ParquetTypeEnum, Arc<Type>> {
+        HashMap::from([(
+            ParquetTypeEnum::WriteSetChanges,
+            ParquetWriteSetChange::schema(),
+        )])
+    }
+
+    #[test]
+    fn test_default_writer_properties() {
+        let properties =
+            build_writer_properties(&HashMap::new(), &write_set_change_schemas(), 50_000)
+                .unwrap()
+                .remove(&ParquetTypeEnum::WriteSetChanges)
+                .unwrap();
+        assert_eq!(
+            properties.compression(&ColumnPath::from("resource_address")),
+            Compression::LZ4
+        );
+        assert_eq!(properties.max_row_group_size(), 50_000);
+        assert_eq!(
+            properties.key_value_metadata(),
+            Some(&vec![
+                ParquetTypeEnum::WriteSetChanges.schema_version_metadata()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_configured_writer_properties() {
+        let writer_config = ParquetWriterConfig {
+            compression: ParquetCompression::Zstd,
+            compression_level: Some(3),
+            max_row_group_size: Some(10_000),
+            dictionary_enabled: Some(false),
+            dictionary_columns: vec!["change_type".to_string()],
+            statistics: Some(ParquetStatistics::Chunk),
+            bloom_filter_columns: vec!["resource_address".to_string()],
+            bloom_filter_fpp: Some(0.01),
+        };
+        let properties = build_writer_properties(
+            &HashMap::from([("write_set_changes".to_string(), writer_config)]),
+            &write_set_change_schemas(),
+            50_000,
+        )
+        .unwrap()
+        .remove(&ParquetTypeEnum::WriteSetChanges)
+        .unwrap();
+
+        let address = ColumnPath::from("resource_address");
+        assert_eq!(
+            properties.compression(&address),
+            Compression::ZSTD(ZstdLevel::try_new(3).unwrap())
+        );
+        assert_eq!(properties.max_row_group_size(), 10_000);
+        assert!(!properties.dictionary_enabled(&address));
+        assert!(properties.dictionary_enabled(&ColumnPath::from("change_type")));
+        assert_eq!(
+            properties.statistics_enabled(&address),
+            EnabledStatistics::Chunk
+        );
+        assert_eq!(
+            properties.bloom_filter_properties(&address).unwrap().fpp,
+            0.01
+        );
+    }
+
+    #[test]
+    fn test_invalid_writer_properties() {
+        let schemas = write_set_change_schemas();
+        let unknown_column = ParquetWriterConfig {
+            bloom_filter_columns: vec!["not_a_column".to_string()],
+            ..ParquetWriterConfig::default()
+        };
+        let error = build_writer_properties(
+            &HashMap::from([("write_set_changes".to_string(), unknown_column)]),
+            &schemas,
+            50_000,
+        )
+        .unwrap_err();
+        assert!(format!("{error:?}").contains("Column 'not_a_column' does not exist"));
+
+        let unknown_table = build_writer_properties(
+            &HashMap::from([("events".to_string(), ParquetWriterConfig::default())]),
+            &schemas,
+            50_000,
+        );
+        assert!(unknown_table.is_err());
+
+        let level_without_zstd = ParquetWriterConfig {
+            compression_level: Some(3),
+            ..ParquetWriterConfig::default()
+        };
+        assert!(level_without_zstd
+            .writer_properties(&ParquetWriteSetChange::schema())
+            .is_err());
+    }
+}
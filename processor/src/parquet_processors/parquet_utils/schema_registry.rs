@@ -0,0 +1,743 @@
+use crate::parquet_processors::ParquetTypeEnum;
+use parquet::{
+    file::metadata::{FileMetaData, KeyValue},
+    schema::types::Type,
+};
+
+/// Key of the file key-value metadata holding the schema version a file was written with.
+pub const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// Version of files written before schema versions were recorded.
+const UNVERSIONED_SCHEMA_VERSION: u32 = 1;
+
+impl ParquetTypeEnum {
+    /// Current schema version of the table, i.e. the number of schemas in its history.
+    pub fn schema_version(&self) -> u32 {
+        schema_history(*self).len() as u32
+    }
+
+    pub fn schema_version_metadata(&self) -> KeyValue {
+        KeyValue::new(
+            SCHEMA_VERSION_KEY.to_string(),
+            self.schema_version().to_string(),
+        )
+    }
+}
+
+/// Schema version recorded in the metadata of a parquet file.
+pub fn read_schema_version(file_metadata: &FileMetaData) -> u32 {
+    file_metadata
+        .key_value_metadata()
+        .and_then(|key_value_metadata| {
+            key_value_metadata
+                .iter()
+                .find(|kv| kv.key == SCHEMA_VERSION_KEY)
+        })
+        .and_then(|kv| kv.value.as_deref())
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(UNVERSIONED_SCHEMA_VERSION)
+}
+
+/// Columns of a schema as `{name}: {repetition} {physical type}`, in order.
+pub fn schema_columns(schema: &Type) -> Vec<String> {
+    schema
+        .get_fields()
+        .iter()
+        .map(|field| {
+            let physical_type = if field.is_primitive() {
+                format!("{:?}", field.get_physical_type())
+            } else {
+                "GROUP".to_string()
+            };
+            format!(
+                "{}: {:?} {}",
+                field.name(),
+                field.get_basic_info().repetition(),
+                physical_type
+            )
+        })
+        .collect()
+}
+
+/// Every schema a table has been written with, oldest first; version `n` is entry `n - 1`.
+///
+/// Files of different versions are written under different prefixes, so readers can pick the
+/// schema from here. Never edit an existing entry: when a parquet struct changes, append its new
+/// columns, which bumps the table's schema version.
+pub fn schema_history(parquet_type: ParquetTypeEnum) -> &'static [&'static [&'static str]] {
+    match parquet_type {
+        ParquetTypeEnum::MoveResources => &[&[
+            "txn_version: REQUIRED INT64",
+            "write_set_change_index: REQUIRED INT64",
+            "block_height: REQUIRED INT64",
+            "block_timestamp: REQUIRED INT64",
+            "resource_address: REQUIRED BYTE_ARRAY",
+            "resource_type: REQUIRED BYTE_ARRAY",
+            "module: REQUIRED BYTE_ARRAY",
+            "fun: REQUIRED BYTE_ARRAY",
+            "is_deleted: REQUIRED BOOLEAN",
+            "generic_type_params: OPTIONAL BYTE_ARRAY",
+            "data: OPTIONAL BYTE_ARRAY",
+            "state_key_hash: REQUIRED BYTE_ARRAY",
+        ]],
+        ParquetTypeEnum::WriteSetChanges => &[&[
+            "txn_version: REQUIRED INT64",
+            "write_set_change_index: REQUIRED INT64",
+            "state_key_hash: REQUIRED BYTE_ARRAY",
+            "change_type: REQUIRED BYTE_ARRAY",
+            "resource_address: REQUIRED BYTE_ARRAY",
+            "block_height: REQUIRED INT64",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::Transactions => &[&[
+            "txn_version: REQUIRED INT64",
+            "block_height: REQUIRED INT64",
+            "epoch: REQUIRED INT64",
+            "txn_type: REQUIRED BYTE_ARRAY",
+            "payload: OPTIONAL BYTE_ARRAY",
+            "payload_type: OPTIONAL BYTE_ARRAY",
+            "gas_used: REQUIRED INT64",
+            "success: REQUIRED BOOLEAN",
+            "vm_status: REQUIRED BYTE_ARRAY",
+            "num_events: REQUIRED INT64",
+            "num_write_set_changes: REQUIRED INT64",
+            "txn_hash: REQUIRED BYTE_ARRAY",
+            "state_change_hash: REQUIRED BYTE_ARRAY",
+            "event_root_hash: REQUIRED BYTE_ARRAY",
+            "state_checkpoint_hash: OPTIONAL BYTE_ARRAY",
+            "accumulator_root_hash: REQUIRED BYTE_ARRAY",
+            "txn_total_bytes: REQUIRED INT64",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::TableItems => &[&[
+            "txn_version: REQUIRED INT64",
+            "block_timestamp: REQUIRED INT64",
+            "write_set_change_index: REQUIRED INT64",
+            "transaction_block_height: REQUIRED INT64",
+            "table_key: REQUIRED BYTE_ARRAY",
+            "table_handle: REQUIRED BYTE_ARRAY",
+            "decoded_key: REQUIRED BYTE_ARRAY",
+            "decoded_value: OPTIONAL BYTE_ARRAY",
+            "is_deleted: REQUIRED BOOLEAN",
+        ]],
+        ParquetTypeEnum::MoveModules => &[&[
+            "txn_version: REQUIRED INT64",
+            "write_set_change_index: REQUIRED INT64",
+            "block_height: REQUIRED INT64",
+            "name: REQUIRED BYTE_ARRAY",
+            "address: REQUIRED BYTE_ARRAY",
+            "bytecode: REQUIRED BYTE_ARRAY",
+            "exposed_functions: OPTIONAL BYTE_ARRAY",
+            "friends: OPTIONAL BYTE_ARRAY",
+            "structs: OPTIONAL BYTE_ARRAY",
+            "is_deleted: REQUIRED BOOLEAN",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::CurrentTableItems => &[&[
+            "table_handle: REQUIRED BYTE_ARRAY",
+            "key_hash: REQUIRED BYTE_ARRAY",
+            "key: REQUIRED BYTE_ARRAY",
+            "decoded_key: REQUIRED BYTE_ARRAY",
+            "decoded_value: OPTIONAL BYTE_ARRAY",
+            "last_transaction_version: REQUIRED INT64",
+            "is_deleted: REQUIRED BOOLEAN",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::BlockMetadataTransactions => &[&[
+            "txn_version: REQUIRED INT64",
+            "block_height: REQUIRED INT64",
+            "block_id: REQUIRED BYTE_ARRAY",
+            "round: REQUIRED INT64",
+            "epoch: REQUIRED INT64",
+            "previous_block_votes_bitvec: REQUIRED BYTE_ARRAY",
+            "proposer: REQUIRED BYTE_ARRAY",
+            "failed_proposer_indices: REQUIRED BYTE_ARRAY",
+            "block_timestamp: REQUIRED INT64",
+            "since_unix_epoch: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::TableMetadata => &[&[
+            "handle: REQUIRED BYTE_ARRAY",
+            "key_type: REQUIRED BYTE_ARRAY",
+            "value_type: REQUIRED BYTE_ARRAY",
+        ]],
+        ParquetTypeEnum::BlockEpilogueTransactions => &[&[
+            "txn_version: REQUIRED INT64",
+            "block_height: REQUIRED INT64",
+            "epoch: REQUIRED INT64",
+            "block_gas_limit_reached: OPTIONAL BOOLEAN",
+            "block_output_limit_reached: OPTIONAL BOOLEAN",
+            "block_effective_block_gas_units: OPTIONAL INT64",
+            "block_approx_output_size: OPTIONAL INT64",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::ValidatorTransactions => &[&[
+            "txn_version: REQUIRED INT64",
+            "block_height: REQUIRED INT64",
+            "epoch: REQUIRED INT64",
+            "validator_transaction_type: REQUIRED BYTE_ARRAY",
+            "jwk_issuer: OPTIONAL BYTE_ARRAY",
+            "jwk_version: OPTIONAL INT64",
+            "num_jwks: OPTIONAL INT64",
+            "dkg_epoch: OPTIONAL INT64",
+            "dkg_author: OPTIONAL BYTE_ARRAY",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::Events => &[&[
+            "txn_version: REQUIRED INT64",
+            "account_address: REQUIRED BYTE_ARRAY",
+            "sequence_number: REQUIRED INT64",
+            "creation_number: REQUIRED INT64",
+            "block_height: REQUIRED INT64",
+            "event_type: REQUIRED BYTE_ARRAY",
+            "data: REQUIRED BYTE_ARRAY",
+            "event_index: REQUIRED INT64",
+            "indexed_type: REQUIRED BYTE_ARRAY",
+            "type_tag_bytes: REQUIRED INT64",
+            "total_bytes: REQUIRED INT64",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::UserTransactions => &[
+            &[
+                "txn_version: REQUIRED INT64",
+                "block_height: REQUIRED INT64",
+                "block_timestamp: REQUIRED INT64",
+                "epoch: REQUIRED INT64",
+                "sender: REQUIRED BYTE_ARRAY",
+                "sequence_number: OPTIONAL INT64",
+                "replay_protection_nonce: OPTIONAL BYTE_ARRAY",
+                "entry_function_id_str: REQUIRED BYTE_ARRAY",
+                "expiration_timestamp_secs: REQUIRED INT64",
+                "parent_signature_type: REQUIRED BYTE_ARRAY",
+                "gas_fee_payer_address: OPTIONAL BYTE_ARRAY",
+                "gas_used_unit: REQUIRED INT64",
+                "gas_unit_price: REQUIRED INT64",
+                "max_gas_octa: REQUIRED INT64",
+                "storage_refund_octa: REQUIRED INT64",
+                "is_transaction_success: REQUIRED BOOLEAN",
+                "num_signatures: REQUIRED INT64",
+            ],
+            &[
+                "txn_version: REQUIRED INT64",
+                "block_height: REQUIRED INT64",
+                "block_timestamp: REQUIRED INT64",
+                "epoch: REQUIRED INT64",
+                "sender: REQUIRED BYTE_ARRAY",
+                "sequence_number: OPTIONAL INT64",
+                "replay_protection_nonce: OPTIONAL BYTE_ARRAY",
+                "entry_function_id_str: REQUIRED BYTE_ARRAY",
+                "expiration_timestamp_secs: REQUIRED INT64",
+                "parent_signature_type: REQUIRED BYTE_ARRAY",
+                "gas_fee_payer_address: OPTIONAL BYTE_ARRAY",
+                "gas_used_unit: REQUIRED INT64",
+                "gas_unit_price: REQUIRED INT64",
+                "max_gas_octa: REQUIRED INT64",
+                "storage_refund_octa: REQUIRED INT64",
+                "is_transaction_success: REQUIRED BOOLEAN",
+                "num_signatures: REQUIRED INT64",
+                "total_charge_gas_units: OPTIONAL INT64",
+                "execution_gas_units: OPTIONAL INT64",
+                "io_gas_units: OPTIONAL INT64",
+                "storage_fee_octas: OPTIONAL INT64",
+            ],
+        ],
+        ParquetTypeEnum::Signatures => &[&[
+            "txn_version: REQUIRED INT64",
+            "multi_agent_index: REQUIRED INT64",
+            "multi_sig_index: REQUIRED INT64",
+            "is_sender_primary: REQUIRED BOOLEAN",
+            "block_height: REQUIRED INT64",
+            "signer: REQUIRED BYTE_ARRAY",
+            "account_signature_type: REQUIRED BYTE_ARRAY",
+            "any_signature_type: OPTIONAL BYTE_ARRAY",
+            "public_key_type: OPTIONAL BYTE_ARRAY",
+            "public_key: REQUIRED BYTE_ARRAY",
+            "signature: REQUIRED BYTE_ARRAY",
+            "threshold: OPTIONAL INT64",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::AnsPrimaryNameV2 => &[&[
+            "txn_version: REQUIRED INT64",
+            "write_set_change_index: REQUIRED INT64",
+            "registered_address: REQUIRED BYTE_ARRAY",
+            "token_standard: REQUIRED BYTE_ARRAY",
+            "domain: OPTIONAL BYTE_ARRAY",
+            "subdomain: OPTIONAL BYTE_ARRAY",
+            "token_name: OPTIONAL BYTE_ARRAY",
+            "is_deleted: REQUIRED BOOLEAN",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::CurrentAnsPrimaryNameV2 => &[&[
+            "registered_address: REQUIRED BYTE_ARRAY",
+            "token_standard: REQUIRED BYTE_ARRAY",
+            "domain: OPTIONAL BYTE_ARRAY",
+            "subdomain: OPTIONAL BYTE_ARRAY",
+            "token_name: OPTIONAL BYTE_ARRAY",
+            "is_deleted: REQUIRED BOOLEAN",
+            "last_transaction_version: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::AnsLookupV2 => &[&[
+            "txn_version: REQUIRED INT64",
+            "write_set_change_index: REQUIRED INT64",
+            "domain: REQUIRED BYTE_ARRAY",
+            "subdomain: REQUIRED BYTE_ARRAY",
+            "token_standard: REQUIRED BYTE_ARRAY",
+            "registered_address: OPTIONAL BYTE_ARRAY",
+            "expiration_timestamp: REQUIRED INT64",
+            "token_name: REQUIRED BYTE_ARRAY",
+            "is_deleted: REQUIRED BOOLEAN",
+            "subdomain_expiration_policy: OPTIONAL INT64",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::CurrentAnsLookupV2 => &[&[
+            "domain: REQUIRED BYTE_ARRAY",
+            "subdomain: REQUIRED BYTE_ARRAY",
+            "token_standard: REQUIRED BYTE_ARRAY",
+            "registered_address: OPTIONAL BYTE_ARRAY",
+            "last_transaction_version: REQUIRED INT64",
+            "expiration_timestamp: REQUIRED INT64",
+            "token_name: REQUIRED BYTE_ARRAY",
+            "is_deleted: REQUIRED BOOLEAN",
+            "subdomain_expiration_policy: OPTIONAL INT64",
+        ]],
+        ParquetTypeEnum::FungibleAssetActivities => &[&[
+            "txn_version: REQUIRED INT64",
+            "event_index: REQUIRED INT64",
+            "owner_address: OPTIONAL BYTE_ARRAY",
+            "storage_id: REQUIRED BYTE_ARRAY",
+            "asset_type: OPTIONAL BYTE_ARRAY",
+            "is_frozen: OPTIONAL BOOLEAN",
+            "amount: OPTIONAL BYTE_ARRAY",
+            "event_type: REQUIRED BYTE_ARRAY",
+            "is_gas_fee: REQUIRED BOOLEAN",
+            "gas_fee_payer_address: OPTIONAL BYTE_ARRAY",
+            "is_transaction_success: REQUIRED BOOLEAN",
+            "entry_function_id_str: OPTIONAL BYTE_ARRAY",
+            "block_height: REQUIRED INT64",
+            "token_standard: REQUIRED BYTE_ARRAY",
+            "block_timestamp: REQUIRED INT64",
+            "storage_refund_octa: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::FungibleAssetMetadata => &[&[
+            "asset_type: REQUIRED BYTE_ARRAY",
+            "creator_address: REQUIRED BYTE_ARRAY",
+            "name: REQUIRED BYTE_ARRAY",
+            "symbol: REQUIRED BYTE_ARRAY",
+            "decimals: REQUIRED INT32",
+            "icon_uri: OPTIONAL BYTE_ARRAY",
+            "project_uri: OPTIONAL BYTE_ARRAY",
+            "last_transaction_version: REQUIRED INT64",
+            "last_transaction_timestamp: REQUIRED INT64",
+            "supply_aggregator_table_handle_v1: OPTIONAL BYTE_ARRAY",
+            "supply_aggregator_table_key_v1: OPTIONAL BYTE_ARRAY",
+            "token_standard: REQUIRED BYTE_ARRAY",
+            "is_token_v2: OPTIONAL BOOLEAN",
+            "supply_v2: OPTIONAL BYTE_ARRAY",
+            "maximum_v2: OPTIONAL BYTE_ARRAY",
+        ]],
+        ParquetTypeEnum::FungibleAssetBalances => &[&[
+            "txn_version: REQUIRED INT64",
+            "write_set_change_index: REQUIRED INT64",
+            "storage_id: REQUIRED BYTE_ARRAY",
+            "owner_address: REQUIRED BYTE_ARRAY",
+            "asset_type: REQUIRED BYTE_ARRAY",
+            "is_primary: REQUIRED BOOLEAN",
+            "is_frozen: REQUIRED BOOLEAN",
+            "amount: REQUIRED BYTE_ARRAY",
+            "block_timestamp: REQUIRED INT64",
+            "token_standard: REQUIRED BYTE_ARRAY",
+        ]],
+        ParquetTypeEnum::FungibleAssetToCoinMappings => &[&[
+            "fungible_asset_metadata_address: REQUIRED BYTE_ARRAY",
+            "coin_type: REQUIRED BYTE_ARRAY",
+            "last_transaction_version: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::CurrentFungibleAssetBalances => &[&[
+            "storage_id: REQUIRED BYTE_ARRAY",
+            "owner_address: REQUIRED BYTE_ARRAY",
+            "asset_type_v1: OPTIONAL BYTE_ARRAY",
+            "asset_type_v2: OPTIONAL BYTE_ARRAY",
+            "is_primary: REQUIRED BOOLEAN",
+            "is_frozen: REQUIRED BOOLEAN",
+            "amount_v1: OPTIONAL BYTE_ARRAY",
+            "amount_v2: OPTIONAL BYTE_ARRAY",
+            "last_transaction_version_v1: OPTIONAL INT64",
+            "last_transaction_version_v2: OPTIONAL INT64",
+            "last_transaction_timestamp_v1: OPTIONAL INT64",
+            "last_transaction_timestamp_v2: OPTIONAL INT64",
+        ]],
+        ParquetTypeEnum::WriteSetSize => &[&[
+            "txn_version: REQUIRED INT64",
+            "change_index: REQUIRED INT64",
+            "key_bytes: REQUIRED INT64",
+            "value_bytes: REQUIRED INT64",
+            "total_bytes: REQUIRED INT64",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::EventSize => &[&[
+            "txn_version: REQUIRED INT64",
+            "event_index: REQUIRED INT64",
+            "type_tag_bytes: REQUIRED INT64",
+            "total_bytes: REQUIRED INT64",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::AccountTransactions => &[&[
+            "txn_version: REQUIRED INT64",
+            "account_address: REQUIRED BYTE_ARRAY",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::CurrentTokenPendingClaims => &[&[
+            "token_data_id_hash: REQUIRED BYTE_ARRAY",
+            "property_version: REQUIRED INT64",
+            "from_address: REQUIRED BYTE_ARRAY",
+            "to_address: REQUIRED BYTE_ARRAY",
+            "collection_data_id_hash: REQUIRED BYTE_ARRAY",
+            "creator_address: REQUIRED BYTE_ARRAY",
+            "collection_name: REQUIRED BYTE_ARRAY",
+            "name: REQUIRED BYTE_ARRAY",
+            "amount: REQUIRED BYTE_ARRAY",
+            "table_handle: REQUIRED BYTE_ARRAY",
+            "last_transaction_version: REQUIRED INT64",
+            "last_transaction_timestamp: REQUIRED INT64",
+            "token_data_id: REQUIRED BYTE_ARRAY",
+            "collection_id: REQUIRED BYTE_ARRAY",
+        ]],
+        ParquetTypeEnum::CurrentTokenRoyaltiesV1 => &[&[
+            "token_data_id: REQUIRED BYTE_ARRAY",
+            "payee_address: REQUIRED BYTE_ARRAY",
+            "royalty_points_numerator: REQUIRED BYTE_ARRAY",
+            "royalty_points_denominator: REQUIRED BYTE_ARRAY",
+            "last_transaction_version: REQUIRED INT64",
+            "last_transaction_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::CurrentTokenV2Metadata => &[&[
+            "object_address: REQUIRED BYTE_ARRAY",
+            "resource_type: REQUIRED BYTE_ARRAY",
+            "data: REQUIRED BYTE_ARRAY",
+            "state_key_hash: REQUIRED BYTE_ARRAY",
+            "last_transaction_version: REQUIRED INT64",
+            "last_transaction_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::TokenActivitiesV2 => &[&[
+            "txn_version: REQUIRED INT64",
+            "event_index: REQUIRED INT64",
+            "event_account_address: REQUIRED BYTE_ARRAY",
+            "token_data_id: REQUIRED BYTE_ARRAY",
+            "property_version_v1: REQUIRED INT64",
+            "event_type: REQUIRED BYTE_ARRAY",
+            "from_address: OPTIONAL BYTE_ARRAY",
+            "to_address: OPTIONAL BYTE_ARRAY",
+            "token_amount: REQUIRED BYTE_ARRAY",
+            "before_value: OPTIONAL BYTE_ARRAY",
+            "after_value: OPTIONAL BYTE_ARRAY",
+            "entry_function_id_str: OPTIONAL BYTE_ARRAY",
+            "token_standard: REQUIRED BYTE_ARRAY",
+            "is_fungible_v2: OPTIONAL BOOLEAN",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::TokenDatasV2 => &[&[
+            "txn_version: REQUIRED INT64",
+            "write_set_change_index: REQUIRED INT64",
+            "token_data_id: REQUIRED BYTE_ARRAY",
+            "collection_id: REQUIRED BYTE_ARRAY",
+            "token_name: REQUIRED BYTE_ARRAY",
+            "largest_property_version_v1: OPTIONAL BYTE_ARRAY",
+            "token_uri: REQUIRED BYTE_ARRAY",
+            "token_properties: REQUIRED BYTE_ARRAY",
+            "description: REQUIRED BYTE_ARRAY",
+            "token_standard: REQUIRED BYTE_ARRAY",
+            "is_fungible_v2: OPTIONAL BOOLEAN",
+            "block_timestamp: REQUIRED INT64",
+            "is_deleted_v2: OPTIONAL BOOLEAN",
+        ]],
+        ParquetTypeEnum::CurrentTokenDatasV2 => &[&[
+            "token_data_id: REQUIRED BYTE_ARRAY",
+            "collection_id: REQUIRED BYTE_ARRAY",
+            "token_name: REQUIRED BYTE_ARRAY",
+            "maximum: OPTIONAL BYTE_ARRAY",
+            "supply: OPTIONAL BYTE_ARRAY",
+            "largest_property_version_v1: OPTIONAL BYTE_ARRAY",
+            "token_uri: REQUIRED BYTE_ARRAY",
+            "token_properties: REQUIRED BYTE_ARRAY",
+            "description: REQUIRED BYTE_ARRAY",
+            "token_standard: REQUIRED BYTE_ARRAY",
+            "is_fungible_v2: OPTIONAL BOOLEAN",
+            "last_transaction_version: REQUIRED INT64",
+            "last_transaction_timestamp: REQUIRED INT64",
+            "decimals: OPTIONAL INT64",
+            "is_deleted_v2: OPTIONAL BOOLEAN",
+        ]],
+        ParquetTypeEnum::TokenOwnershipsV2 => &[&[
+            "txn_version: REQUIRED INT64",
+            "write_set_change_index: REQUIRED INT64",
+            "token_data_id: REQUIRED BYTE_ARRAY",
+            "property_version_v1: REQUIRED INT64",
+            "owner_address: OPTIONAL BYTE_ARRAY",
+            "storage_id: REQUIRED BYTE_ARRAY",
+            "amount: REQUIRED BYTE_ARRAY",
+            "table_type_v1: OPTIONAL BYTE_ARRAY",
+            "token_properties_mutated_v1: OPTIONAL BYTE_ARRAY",
+            "is_soulbound_v2: OPTIONAL BOOLEAN",
+            "token_standard: REQUIRED BYTE_ARRAY",
+            "block_timestamp: REQUIRED INT64",
+            "non_transferrable_by_owner: OPTIONAL BOOLEAN",
+        ]],
+        ParquetTypeEnum::CurrentTokenOwnershipsV2 => &[&[
+            "token_data_id: REQUIRED BYTE_ARRAY",
+            "property_version_v1: REQUIRED INT64",
+            "owner_address: REQUIRED BYTE_ARRAY",
+            "storage_id: REQUIRED BYTE_ARRAY",
+            "amount: REQUIRED BYTE_ARRAY",
+            "table_type_v1: OPTIONAL BYTE_ARRAY",
+            "token_properties_mutated_v1: OPTIONAL BYTE_ARRAY",
+            "is_soulbound_v2: OPTIONAL BOOLEAN",
+            "token_standard: REQUIRED BYTE_ARRAY",
+            "is_fungible_v2: OPTIONAL BOOLEAN",
+            "last_transaction_version: REQUIRED INT64",
+            "last_transaction_timestamp: REQUIRED INT64",
+            "non_transferrable_by_owner: OPTIONAL BOOLEAN",
+        ]],
+        ParquetTypeEnum::CollectionsV2 => &[&[
+            "txn_version: REQUIRED INT64",
+            "write_set_change_index: REQUIRED INT64",
+            "collection_id: REQUIRED BYTE_ARRAY",
+            "creator_address: REQUIRED BYTE_ARRAY",
+            "collection_name: REQUIRED BYTE_ARRAY",
+            "description: REQUIRED BYTE_ARRAY",
+            "uri: REQUIRED BYTE_ARRAY",
+            "current_supply: REQUIRED BYTE_ARRAY",
+            "max_supply: OPTIONAL BYTE_ARRAY",
+            "total_minted_v2: OPTIONAL BYTE_ARRAY",
+            "mutable_description: OPTIONAL BOOLEAN",
+            "mutable_uri: OPTIONAL BOOLEAN",
+            "table_handle_v1: OPTIONAL BYTE_ARRAY",
+            "collection_properties: OPTIONAL BYTE_ARRAY",
+            "token_standard: REQUIRED BYTE_ARRAY",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::CurrentCollectionsV2 => &[&[
+            "collection_id: REQUIRED BYTE_ARRAY",
+            "creator_address: REQUIRED BYTE_ARRAY",
+            "collection_name: REQUIRED BYTE_ARRAY",
+            "description: REQUIRED BYTE_ARRAY",
+            "uri: REQUIRED BYTE_ARRAY",
+            "current_supply: REQUIRED BYTE_ARRAY",
+            "max_supply: OPTIONAL BYTE_ARRAY",
+            "total_minted_v2: OPTIONAL BYTE_ARRAY",
+            "mutable_description: OPTIONAL BOOLEAN",
+            "mutable_uri: OPTIONAL BOOLEAN",
+            "table_handle_v1: OPTIONAL BYTE_ARRAY",
+            "token_standard: REQUIRED BYTE_ARRAY",
+            "collection_properties: OPTIONAL BYTE_ARRAY",
+            "last_transaction_version: REQUIRED INT64",
+            "last_transaction_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::DelegatedStakingActivities => &[&[
+            "transaction_version: REQUIRED INT64",
+            "event_index: REQUIRED INT64",
+            "delegator_address: REQUIRED BYTE_ARRAY",
+            "pool_address: REQUIRED BYTE_ARRAY",
+            "event_type: REQUIRED BYTE_ARRAY",
+            "amount: REQUIRED BYTE_ARRAY",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::CurrentDelegatorBalances => &[&[
+            "delegator_address: REQUIRED BYTE_ARRAY",
+            "pool_address: REQUIRED BYTE_ARRAY",
+            "pool_type: REQUIRED BYTE_ARRAY",
+            "table_handle: REQUIRED BYTE_ARRAY",
+            "last_transaction_version: REQUIRED INT64",
+            "shares: REQUIRED BYTE_ARRAY",
+            "parent_table_handle: REQUIRED BYTE_ARRAY",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::DelegatorBalances => &[&[
+            "transaction_version: REQUIRED INT64",
+            "write_set_change_index: REQUIRED INT64",
+            "delegator_address: REQUIRED BYTE_ARRAY",
+            "pool_address: REQUIRED BYTE_ARRAY",
+            "pool_type: REQUIRED BYTE_ARRAY",
+            "table_handle: REQUIRED BYTE_ARRAY",
+            "shares: REQUIRED BYTE_ARRAY",
+            "parent_table_handle: REQUIRED BYTE_ARRAY",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::ProposalVotes => &[&[
+            "transaction_version: REQUIRED INT64",
+            "proposal_id: REQUIRED INT64",
+            "voter_address: REQUIRED BYTE_ARRAY",
+            "staking_pool_address: REQUIRED BYTE_ARRAY",
+            "num_votes: REQUIRED BYTE_ARRAY",
+            "should_pass: REQUIRED BOOLEAN",
+            "transaction_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::DelegatedStakingPools => &[&[
+            "staking_pool_address: REQUIRED BYTE_ARRAY",
+            "first_transaction_version: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::CurrentDelegatedVoter => &[&[
+            "delegation_pool_address: REQUIRED BYTE_ARRAY",
+            "delegator_address: REQUIRED BYTE_ARRAY",
+            "table_handle: OPTIONAL BYTE_ARRAY",
+            "voter: OPTIONAL BYTE_ARRAY",
+            "pending_voter: OPTIONAL BYTE_ARRAY",
+            "last_transaction_version: REQUIRED INT64",
+            "last_transaction_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::CurrentStakingPoolVoter => &[&[
+            "staking_pool_address: REQUIRED BYTE_ARRAY",
+            "voter_address: REQUIRED BYTE_ARRAY",
+            "last_transaction_version: REQUIRED INT64",
+            "operator_address: REQUIRED BYTE_ARRAY",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::Objects => &[&[
+            "txn_version: REQUIRED INT64",
+            "write_set_change_index: REQUIRED INT64",
+            "object_address: REQUIRED BYTE_ARRAY",
+            "owner_address: REQUIRED BYTE_ARRAY",
+            "state_key_hash: REQUIRED BYTE_ARRAY",
+            "guid_creation_num: REQUIRED INT64",
+            "allow_ungated_transfer: REQUIRED BOOLEAN",
+            "is_deleted: REQUIRED BOOLEAN",
+            "untransferrable: REQUIRED BOOLEAN",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::CurrentObjects => &[&[
+            "object_address: REQUIRED BYTE_ARRAY",
+            "owner_address: REQUIRED BYTE_ARRAY",
+            "state_key_hash: REQUIRED BYTE_ARRAY",
+            "allow_ungated_transfer: REQUIRED BOOLEAN",
+            "last_guid_creation_num: REQUIRED INT64",
+            "last_transaction_version: REQUIRED INT64",
+            "is_deleted: REQUIRED BOOLEAN",
+            "untransferrable: REQUIRED BOOLEAN",
+            "block_timestamp: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::GasFees => &[
+            &[
+                "txn_version: REQUIRED INT64",
+                "owner_address: OPTIONAL BYTE_ARRAY",
+                "amount: OPTIONAL BYTE_ARRAY",
+                "gas_fee_payer_address: OPTIONAL BYTE_ARRAY",
+                "is_transaction_success: REQUIRED BOOLEAN",
+                "entry_function_id_str: OPTIONAL BYTE_ARRAY",
+                "block_height: REQUIRED INT64",
+                "storage_refund_amount: REQUIRED BYTE_ARRAY",
+                "block_timestamp: REQUIRED INT64",
+            ],
+            &[
+                "txn_version: REQUIRED INT64",
+                "owner_address: OPTIONAL BYTE_ARRAY",
+                "amount: OPTIONAL BYTE_ARRAY",
+                "gas_fee_payer_address: OPTIONAL BYTE_ARRAY",
+                "is_transaction_success: REQUIRED BOOLEAN",
+                "entry_function_id_str: OPTIONAL BYTE_ARRAY",
+                "block_height: REQUIRED INT64",
+                "storage_refund_amount: REQUIRED BYTE_ARRAY",
+                "block_timestamp: REQUIRED INT64",
+                "gas_unit_price: REQUIRED INT64",
+                "total_charge_gas_units: OPTIONAL INT64",
+                "execution_gas_units: OPTIONAL INT64",
+                "io_gas_units: OPTIONAL INT64",
+                "storage_fee_octas: OPTIONAL INT64",
+            ],
+        ],
+        ParquetTypeEnum::GasFeeBlockSummaries => &[&[
+            "block_height: REQUIRED INT64",
+            "block_timestamp: REQUIRED INT64",
+            "transaction_count: REQUIRED INT64",
+            "total_fee: REQUIRED BYTE_ARRAY",
+            "total_storage_refund: REQUIRED BYTE_ARRAY",
+            "fee_payer_transaction_count: REQUIRED INT64",
+            "median_gas_unit_price: REQUIRED INT64",
+            "p95_gas_unit_price: REQUIRED INT64",
+            "gas_unit_price_histogram: REQUIRED BYTE_ARRAY",
+            "last_transaction_version: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::GasFeeHourlySummaries => &[&[
+            "bucket_start: REQUIRED INT64",
+            "transaction_count: REQUIRED INT64",
+            "total_fee: REQUIRED BYTE_ARRAY",
+            "total_storage_refund: REQUIRED BYTE_ARRAY",
+            "fee_payer_transaction_count: REQUIRED INT64",
+            "median_gas_unit_price: REQUIRED INT64",
+            "p95_gas_unit_price: REQUIRED INT64",
+            "gas_unit_price_histogram: REQUIRED BYTE_ARRAY",
+            "last_transaction_version: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::GasFeeEntryFunctionHourlySummaries => &[&[
+            "bucket_start: REQUIRED INT64",
+            "entry_function_id_str: REQUIRED BYTE_ARRAY",
+            "transaction_count: REQUIRED INT64",
+            "total_fee: REQUIRED BYTE_ARRAY",
+            "total_storage_refund: REQUIRED BYTE_ARRAY",
+            "fee_payer_transaction_count: REQUIRED INT64",
+            "median_gas_unit_price: REQUIRED INT64",
+            "p95_gas_unit_price: REQUIRED INT64",
+            "gas_unit_price_histogram: REQUIRED BYTE_ARRAY",
+            "last_transaction_version: REQUIRED INT64",
+        ]],
+        ParquetTypeEnum::AuthKeyAccountAddresses => &[&[
+            "auth_key: REQUIRED BYTE_ARRAY",
+            "account_address: REQUIRED BYTE_ARRAY",
+            "last_transaction_version: REQUIRED INT64",
+            "is_auth_key_used: REQUIRED BOOLEAN",
+        ]],
+        ParquetTypeEnum::PublicKeyAuthKeys => &[&[
+            "public_key: REQUIRED BYTE_ARRAY",
+            "public_key_type: REQUIRED BYTE_ARRAY",
+            "auth_key: REQUIRED BYTE_ARRAY",
+            "account_public_key: REQUIRED BYTE_ARRAY",
+            "is_public_key_used: REQUIRED BOOLEAN",
+            "last_transaction_version: REQUIRED INT64",
+            "signature_type: REQUIRED BYTE_ARRAY",
+        ]],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parquet_processors::{ParquetTypeStructs, ParquetTypeTrait};
+    use parquet::schema::types::SchemaDescriptor;
+    use std::sync::Arc;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_schemas_match_latest_version() {
+        for parquet_type in ParquetTypeEnum::iter() {
+            let schema = ParquetTypeStructs::default_for_type(&parquet_type).parquet_schema();
+            let latest = schema_history(parquet_type)
+                .last()
+                .unwrap_or_else(|| panic!("{parquet_type} has no schema history"));
+            assert_eq!(
+                schema_columns(&schema),
+                *latest,
+                "The schema of {parquet_type} changed, append it to schema_history to bump the \
+                 schema version"
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_schema_version() {
+        let schema_descr = Arc::new(SchemaDescriptor::new(
+            ParquetTypeStructs::default_for_type(&ParquetTypeEnum::Events).parquet_schema(),
+        ));
+        let file_metadata = |key_value_metadata| {
+            FileMetaData::new(1, 0, None, key_value_metadata, schema_descr.clone(), None)
+        };
+
+        let versioned = file_metadata(Some(
+            vec![ParquetTypeEnum::Events.schema_version_metadata()],
+        ));
+        assert_eq!(
+            read_schema_version(&versioned),
+            ParquetTypeEnum::Events.schema_version()
+        );
+        assert_eq!(
+            read_schema_version(&file_metadata(None)),
+            UNVERSIONED_SCHEMA_VERSION
+        );
+    }
+}
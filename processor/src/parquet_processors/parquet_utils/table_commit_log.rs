@@ -0,0 +1,517 @@
+use crate::parquet_processors::parquet_utils::{object_store::ObjectStore, util::HasVersion};
+use anyhow::{bail, Context, Result};
+use parquet::schema::{printer::print_schema, types::Type};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Mutex};
+use tracing::info;
+
+const COMMIT_LOG_DIR: &str = "_commit_log";
+const COMMIT_FILE_EXTENSION: &str = ".json";
+
+/// A parquet file added to a table by a commit.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AddedFile {
+    pub path: String,
+    pub size_bytes: u64,
+    pub row_count: i64,
+    pub min_version: i64,
+    pub max_version: i64,
+}
+
+/// A file of a table snapshot as of a transaction version. Files straddling that version are part
+/// of the snapshot, but only their rows at or below it, which `filter_rows` keeps.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SnapshotFile {
+    pub file: AddedFile,
+    pub as_of_version: i64,
+}
+
+impl SnapshotFile {
+    pub fn filter_rows<T: HasVersion>(&self, mut rows: Vec<T>) -> Vec<T> {
+        if self.file.max_version > self.as_of_version {
+            rows.retain(|row| row.version() <= self.as_of_version);
+        }
+        rows
+    }
+}
+
+/// One entry of a table's commit log. Commits are numbered from 0 without gaps and each commit
+/// is an immutable object, so a reader listing the log sees a consistent set of files.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TableCommit {
+    pub commit_version: u64,
+    pub committed_at_ms: i64,
+    pub processor_name: String,
+    pub added_files: Vec<AddedFile>,
+    // Paths of files of earlier commits that are no longer part of the table, e.g. after
+    // compaction. The objects themselves may still exist for older snapshots.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_files: Vec<String>,
+    // Parquet message type of the added files
+    pub schema: String,
+}
+
+/// Delta Lake / Iceberg style transaction log for the parquet tables, stored next to the data as
+/// `{root}/_commit_log/{table_name}/{commit_version}.json`.
+///
+/// Every upload appends a commit listing the files it added. Commits are created with a
+/// put-if-absent, so concurrent writers never overwrite each other: the loser retries with the
+/// next commit version. Commits removing files, e.g. compactions, fail instead if a commit they
+/// haven't seen already removed one of the files. Files uploaded without a commit (e.g. a crash
+/// in between) are not part of any snapshot.
+pub struct TableCommitLog {
+    store: Box<dyn ObjectStore>,
+    root: String,
+    processor_name: String,
+    next_commit_versions: Mutex<HashMap<String, u64>>,
+}
+
+impl TableCommitLog {
+    pub fn new(store: Box<dyn ObjectStore>, root: &str, processor_name: &str) -> Self {
+        Self {
+            store,
+            root: root.trim_end_matches('/').to_string(),
+            processor_name: processor_name.to_string(),
+            next_commit_versions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn table_log_prefix(&self, table_name: &str) -> String {
+        if self.root.is_empty() {
+            format!("{COMMIT_LOG_DIR}/{table_name}/")
+        } else {
+            format!("{}/{COMMIT_LOG_DIR}/{table_name}/", self.root)
+        }
+    }
+
+    fn commit_path(&self, table_name: &str, commit_version: u64) -> String {
+        format!(
+            "{}{commit_version:020}{COMMIT_FILE_EXTENSION}",
+            self.table_log_prefix(table_name)
+        )
+    }
+
+    /// Appends a commit adding `added_files` to the table and returns its commit version.
+    pub async fn commit(
+        &self,
+        table_name: &str,
+        added_files: Vec<AddedFile>,
+        schema: &Type,
+    ) -> Result<u64> {
+        self.commit_changes(table_name, added_files, vec![], schema)
+            .await
+    }
+
+    /// Appends a commit that atomically replaces `removed_files` with `added_files` and returns
+    /// its commit version.
+    pub async fn commit_changes(
+        &self,
+        table_name: &str,
+        added_files: Vec<AddedFile>,
+        removed_files: Vec<String>,
+        schema: &Type,
+    ) -> Result<u64> {
+        let cached_version = self
+            .next_commit_versions
+            .lock()
+            .unwrap()
+            .get(table_name)
+            .copied();
+        let mut commit_version = match cached_version {
+            Some(commit_version) => commit_version,
+            None => self
+                .commit_versions(table_name)
+                .await?
+                .last()
+                .map_or(0, |latest| latest + 1),
+        };
+
+        let mut schema_bytes = vec![];
+        print_schema(&mut schema_bytes, schema);
+        let mut commit = TableCommit {
+            commit_version,
+            committed_at_ms: chrono::Utc::now().timestamp_millis(),
+            processor_name: self.processor_name.clone(),
+            added_files,
+            removed_files,
+            schema: String::from_utf8(schema_bytes).context("Invalid parquet schema")?,
+        };
+        loop {
+            if !commit.removed_files.is_empty() {
+                // The removed files may have been removed by a commit this one hasn't seen, e.g.
+                // by a concurrent compaction of the same files, which would make their rows live
+                // twice
+                commit_version = self
+                    .check_removed_files(table_name, &commit.removed_files)
+                    .await?;
+            }
+            commit.commit_version = commit_version;
+            let committed = self
+                .store
+                .put_if_absent(
+                    &self.commit_path(table_name, commit_version),
+                    serde_json::to_vec(&commit)?,
+                )
+                .await?;
+            if committed {
+                break;
+            }
+            // Another writer took this commit version
+            commit_version += 1;
+        }
+
+        self.next_commit_versions
+            .lock()
+            .unwrap()
+            .insert(table_name.to_string(), commit_version + 1);
+        info!(
+            table_name = table_name,
+            commit_version = commit_version,
+            num_files = commit.added_files.len(),
+            num_removed_files = commit.removed_files.len(),
+            "Committed parquet files to the table commit log",
+        );
+        Ok(commit_version)
+    }
+
+    /// Checks that the files are still part of the table snapshot and returns the next commit
+    /// version.
+    async fn check_removed_files(&self, table_name: &str, removed_files: &[String]) -> Result<u64> {
+        let commits = self.commits(table_name).await?;
+        for path in removed_files {
+            let mut is_live = false;
+            for commit in &commits {
+                if commit.removed_files.contains(path) {
+                    is_live = false;
+                }
+                if commit.added_files.iter().any(|file| &file.path == path) {
+                    is_live = true;
+                }
+            }
+            if !is_live {
+                bail!(
+                    "File {} is not part of table {} anymore, it may have been removed by a \
+                     concurrent commit",
+                    path,
+                    table_name
+                );
+            }
+        }
+        Ok(commits.last().map_or(0, |latest| latest.commit_version + 1))
+    }
+
+    async fn commit_versions(&self, table_name: &str) -> Result<Vec<u64>> {
+        let prefix = self.table_log_prefix(table_name);
+        let mut commit_versions: Vec<u64> = self
+            .store
+            .list(&prefix)
+            .await?
+            .iter()
+            .filter_map(|name| {
+                name.strip_prefix(&prefix)?
+                    .strip_suffix(COMMIT_FILE_EXTENSION)?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+        commit_versions.sort_unstable();
+        Ok(commit_versions)
+    }
+
+    /// Returns the commits of a table in order.
+    pub async fn commits(&self, table_name: &str) -> Result<Vec<TableCommit>> {
+        let mut commits = vec![];
+        for commit_version in self.commit_versions(table_name).await? {
+            let path = self.commit_path(table_name, commit_version);
+            let data = self
+                .store
+                .get(&path)
+                .await?
+                .with_context(|| format!("Commit {path} disappeared"))?;
+            commits.push(serde_json::from_slice(&data)?);
+        }
+        Ok(commits)
+    }
+
+    /// Returns the files of the table snapshot.
+    pub async fn snapshot(&self, table_name: &str) -> Result<Vec<AddedFile>> {
+        let mut files: Vec<AddedFile> = vec![];
+        for commit in self.commits(table_name).await? {
+            files.retain(|file| !commit.removed_files.contains(&file.path));
+            files.extend(commit.added_files);
+        }
+        Ok(files)
+    }
+
+    /// Returns the files of the table snapshot holding rows at or below a transaction version,
+    /// which gives a consistent view of the table as of that version once their rows are
+    /// filtered with `SnapshotFile::filter_rows`.
+    pub async fn snapshot_as_of(
+        &self,
+        table_name: &str,
+        as_of_version: i64,
+    ) -> Result<Vec<SnapshotFile>> {
+        Ok(self
+            .snapshot(table_name)
+            .await?
+            .into_iter()
+            .filter(|file| file.min_version <= as_of_version)
+            .map(|file| SnapshotFile {
+                file,
+                as_of_version,
+            })
+            .collect())
+    }
+
+    /// Returns the commit versions at which the table schema changed, with the new schema.
+    pub async fn schema_history(&self, table_name: &str) -> Result<Vec<(u64, String)>> {
+        let mut history: Vec<(u64, String)> = vec![];
+        for commit in self.commits(table_name).await? {
+            if history.last().map(|(_, schema)| schema) != Some(&commit.schema) {
+                history.push((commit.commit_version, commit.schema));
+            }
+        }
+        Ok(history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parquet_processors::parquet_utils::{
+            object_store::LocalFileSystemObjectStore, util::HasParquetSchema,
+        },
+        processors::default::models::{
+            move_resources::ParquetMoveResource, write_set_changes::ParquetWriteSetChange,
+        },
+    };
+    use std::path::PathBuf;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("table_commit_log_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn added_file(path: &str, min_version: i64, max_version: i64) -> AddedFile {
+        AddedFile {
+            path: path.to_string(),
+            size_bytes: 100,
+            row_count: 10,
+            min_version,
+            max_version,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_commit_snapshot_and_time_travel() {
+        let dir = test_dir("snapshot");
+        let commit_log = TableCommitLog::new(
+            Box::new(LocalFileSystemObjectStore::new(&dir)),
+            "root",
+            "test_processor",
+        );
+        let schema = ParquetMoveResource::schema();
+
+        let first = commit_log
+            .commit(
+                "move_resources",
+                vec![added_file("a.parquet", 1, 10)],
+                &schema,
+            )
+            .await
+            .unwrap();
+        let second = commit_log
+            .commit(
+                "move_resources",
+                vec![
+                    added_file("b.parquet", 11, 20),
+                    added_file("c.parquet", 11, 20),
+                ],
+                &schema,
+            )
+            .await
+            .unwrap();
+        assert_eq!((first, second), (0, 1));
+        assert!(dir
+            .join("root/_commit_log/move_resources/00000000000000000001.json")
+            .exists());
+
+        let snapshot = commit_log.snapshot("move_resources").await.unwrap();
+        assert_eq!(snapshot.len(), 3);
+        let as_of = commit_log
+            .snapshot_as_of("move_resources", 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            as_of.iter().map(|file| &file.file).collect::<Vec<_>>(),
+            vec![&added_file("a.parquet", 1, 10)]
+        );
+        // Files straddling the version are included and their later rows filtered out
+        let as_of = commit_log
+            .snapshot_as_of("move_resources", 15)
+            .await
+            .unwrap();
+        assert_eq!(
+            as_of
+                .iter()
+                .map(|file| file.file.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a.parquet", "b.parquet", "c.parquet"]
+        );
+        let rows = (11..=20)
+            .map(|txn_version| ParquetMoveResource {
+                txn_version,
+                ..ParquetMoveResource::default()
+            })
+            .collect();
+        let versions: Vec<i64> = as_of[1]
+            .filter_rows(rows)
+            .iter()
+            .map(HasVersion::version)
+            .collect();
+        assert_eq!(versions, (11..=15).collect::<Vec<_>>());
+        assert!(commit_log.snapshot("events").await.unwrap().is_empty());
+
+        // Compacting b and c replaces them in the snapshot
+        commit_log
+            .commit_changes(
+                "move_resources",
+                vec![added_file("d.parquet", 11, 20)],
+                vec!["b.parquet".to_string(), "c.parquet".to_string()],
+                &schema,
+            )
+            .await
+            .unwrap();
+        let snapshot = commit_log.snapshot("move_resources").await.unwrap();
+        assert_eq!(snapshot, vec![
+            added_file("a.parquet", 1, 10),
+            added_file("d.parquet", 11, 20)
+        ]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_writers_and_schema_history() {
+        let dir = test_dir("concurrent");
+        let writer = |processor_name| {
+            TableCommitLog::new(
+                Box::new(LocalFileSystemObjectStore::new(&dir)),
+                "",
+                processor_name,
+            )
+        };
+        let (first_writer, second_writer) = (writer("first"), writer("second"));
+
+        let resource_schema = ParquetMoveResource::schema();
+        first_writer
+            .commit(
+                "table",
+                vec![added_file("a.parquet", 1, 10)],
+                &resource_schema,
+            )
+            .await
+            .unwrap();
+        // The second writer hasn't seen commit 0 yet but must not overwrite it
+        second_writer
+            .commit(
+                "table",
+                vec![added_file("b.parquet", 11, 20)],
+                &resource_schema,
+            )
+            .await
+            .unwrap();
+        // The first writer's cached version is now taken by the second writer
+        let commit_version = first_writer
+            .commit(
+                "table",
+                vec![added_file("c.parquet", 21, 30)],
+                &ParquetWriteSetChange::schema(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(commit_version, 2);
+
+        let commits = first_writer.commits("table").await.unwrap();
+        assert_eq!(
+            commits
+                .iter()
+                .map(|commit| commit.processor_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["first", "second", "first"]
+        );
+        let schema_history = first_writer.schema_history("table").await.unwrap();
+        assert_eq!(
+            schema_history
+                .iter()
+                .map(|(commit_version, _)| *commit_version)
+                .collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_conflicting_removals() {
+        let dir = test_dir("conflicting_removals");
+        let writer = |processor_name| {
+            TableCommitLog::new(
+                Box::new(LocalFileSystemObjectStore::new(&dir)),
+                "",
+                processor_name,
+            )
+        };
+        let (first_writer, second_writer) = (writer("first"), writer("second"));
+        let schema = ParquetMoveResource::schema();
+        first_writer
+            .commit(
+                "table",
+                vec![
+                    added_file("a.parquet", 1, 10),
+                    added_file("b.parquet", 11, 20),
+                ],
+                &schema,
+            )
+            .await
+            .unwrap();
+
+        // Both writers compact a and b from the same snapshot, only the first one may commit
+        first_writer
+            .commit_changes(
+                "table",
+                vec![added_file("c.parquet", 1, 20)],
+                vec!["a.parquet".to_string(), "b.parquet".to_string()],
+                &schema,
+            )
+            .await
+            .unwrap();
+        assert!(second_writer
+            .commit_changes(
+                "table",
+                vec![added_file("d.parquet", 1, 20)],
+                vec!["a.parquet".to_string(), "b.parquet".to_string()],
+                &schema,
+            )
+            .await
+            .is_err());
+        // Files that were never part of the table can't be removed either
+        assert!(second_writer
+            .commit_changes(
+                "table",
+                vec![added_file("e.parquet", 1, 20)],
+                vec!["c.parquet".to_string(), "f.parquet".to_string()],
+                &schema,
+            )
+            .await
+            .is_err());
+
+        let snapshot = second_writer.snapshot("table").await.unwrap();
+        assert_eq!(snapshot, vec![added_file("c.parquet", 1, 20)]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -102,6 +102,14 @@ pub trait HasVersion {
     fn version(&self) -> i64;
 }
 
+/// Transaction timestamp of a row, used to partition the uploaded files by date. Rows without
+/// a timestamp are placed in the partition of the latest batch of their table.
+pub trait HasTimestamp {
+    fn timestamp(&self) -> Option<chrono::NaiveDateTime> {
+        None
+    }
+}
+
 pub trait HasParquetSchema {
     fn schema() -> Arc<parquet::schema::types::Type>;
 }
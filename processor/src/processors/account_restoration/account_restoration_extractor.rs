@@ -3,6 +3,7 @@
 
 use crate::processors::account_restoration::{
     account_restoration_models::{
+        account_key_rotations::AccountKeyRotation,
         auth_key_account_addresses::AuthKeyAccountAddress, public_key_auth_keys::PublicKeyAuthKey,
     },
     account_restoration_processor_helpers::parse_account_restoration_models,
@@ -22,18 +23,26 @@ where
 #[async_trait]
 impl Processable for AccountRestorationExtractor {
     type Input = Vec<Transaction>;
-    type Output = (Vec<AuthKeyAccountAddress>, Vec<PublicKeyAuthKey>);
+    type Output = (
+        Vec<AuthKeyAccountAddress>,
+        Vec<PublicKeyAuthKey>,
+        Vec<AccountKeyRotation>,
+    );
     type RunType = AsyncRunType;
 
     async fn process(
         &mut self,
         transactions: TransactionContext<Self::Input>,
     ) -> Result<Option<TransactionContext<Self::Output>>, ProcessorError> {
-        let (auth_key_account_addresses, public_key_auth_keys) =
+        let (auth_key_account_addresses, public_key_auth_keys, account_key_rotations) =
             parse_account_restoration_models(&transactions.data);
 
         Ok(Some(TransactionContext {
-            data: (auth_key_account_addresses, public_key_auth_keys),
+            data: (
+                auth_key_account_addresses,
+                public_key_auth_keys,
+                account_key_rotations,
+            ),
             metadata: transactions.metadata,
         }))
     }
@@ -0,0 +1,77 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    account_restoration_utils::KeyRotationToPublicKeyEvent,
+    public_key_auth_keys::{
+        AnyPublicKeyStruct, ED25519_SCHEME, MULTI_ED25519_SCHEME, MULTI_KEY_SCHEME,
+        SINGLE_KEY_SCHEME,
+    },
+};
+use crate::schema::account_key_rotations;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+/// Append-only history of authentication key changes. Unlike `auth_key_account_addresses`, a row
+/// is kept for every rotation so prior key relationships of an account are never lost.
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, account_address))]
+#[diesel(table_name = account_key_rotations)]
+pub struct AccountKeyRotation {
+    pub transaction_version: i64,
+    pub account_address: String,
+    /// Only known when the rotation emitted a key rotation event.
+    pub old_auth_key: Option<String>,
+    pub new_auth_key: String,
+    pub public_key: Option<String>,
+    pub public_key_type: Option<String>,
+    pub verified_public_key_indices: Option<serde_json::Value>,
+    /// False for rotations that didn't prove ownership of the new key.
+    pub is_verified: bool,
+    pub entry_function_id_str: Option<String>,
+}
+
+impl AccountKeyRotation {
+    pub fn new(
+        transaction_version: i64,
+        account_address: &str,
+        old_auth_key: Option<String>,
+        new_auth_key: &str,
+        key_rotation_event: Option<&KeyRotationToPublicKeyEvent>,
+        is_verified: bool,
+        entry_function_id_str: Option<&str>,
+    ) -> Self {
+        Self {
+            transaction_version,
+            account_address: account_address.to_string(),
+            old_auth_key,
+            new_auth_key: new_auth_key.to_string(),
+            public_key: key_rotation_event
+                .map(|event| format!("0x{}", hex::encode(&event.public_key))),
+            public_key_type: key_rotation_event.and_then(get_public_key_type),
+            verified_public_key_indices: key_rotation_event.map(|event| {
+                serde_json::Value::Array(
+                    event
+                        .get_verified_public_key_indices()
+                        .into_iter()
+                        .map(|index| serde_json::Value::from(index as i64))
+                        .collect(),
+                )
+            }),
+            is_verified,
+            entry_function_id_str: entry_function_id_str.map(|s| s.to_string()),
+        }
+    }
+}
+
+fn get_public_key_type(event: &KeyRotationToPublicKeyEvent) -> Option<String> {
+    match event.public_key_scheme {
+        ED25519_SCHEME => Some("ed25519".to_string()),
+        MULTI_ED25519_SCHEME => Some("multi_ed25519".to_string()),
+        SINGLE_KEY_SCHEME => bcs::from_bytes::<AnyPublicKeyStruct>(&event.public_key)
+            .ok()
+            .map(|key| key.get_public_key_type()),
+        MULTI_KEY_SCHEME => Some("multi_key".to_string()),
+        _ => None,
+    }
+}
@@ -4,8 +4,9 @@
 // This is required because a diesel macro makes clippy sad
 #![allow(clippy::extra_unused_lifetimes)]
 
-use aptos_indexer_processor_sdk::aptos_protos::transaction::v1::{
-    transaction::TxnData, Transaction,
+use aptos_indexer_processor_sdk::{
+    aptos_protos::transaction::v1::{transaction::TxnData, Event, Transaction},
+    utils::convert::standardize_address,
 };
 use hex;
 use serde::{de::Error, Deserialize, Deserializer, Serialize};
@@ -99,3 +100,61 @@ impl KeyRotationToPublicKeyEvent {
         indices
     }
 }
+
+#[derive(Deserialize)]
+struct KeyRotationEventData {
+    #[serde(default)]
+    account: Option<String>,
+    #[serde(deserialize_with = "deserialize_bytes_from_hex_with_0x")]
+    old_authentication_key: Vec<u8>,
+    #[serde(deserialize_with = "deserialize_bytes_from_hex_with_0x")]
+    new_authentication_key: Vec<u8>,
+}
+
+/// An authentication key rotation of an account, from either the `0x1::account::KeyRotation`
+/// module event or the legacy `0x1::account::KeyRotationEvent` handle event. The rotated account
+/// isn't necessarily the sender, e.g. with `rotate_authentication_key_with_rotation_capability`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyRotationEvent {
+    pub account_address: String,
+    pub old_auth_key: Vec<u8>,
+    pub new_auth_key: Vec<u8>,
+}
+
+impl KeyRotationEvent {
+    pub fn from_transaction(transaction: &Transaction) -> Vec<Self> {
+        match transaction.txn_data.as_ref() {
+            Some(TxnData::User(inner)) => inner
+                .events
+                .iter()
+                .filter_map(|event| Self::from_event(event, transaction.version as i64))
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    pub fn from_event(event: &Event, txn_version: i64) -> Option<Self> {
+        if event.type_str != "0x1::account::KeyRotation"
+            && event.type_str != "0x1::account::KeyRotationEvent"
+        {
+            return None;
+        }
+        let data: KeyRotationEventData = serde_json::from_str(&event.data).unwrap_or_else(|_| {
+            tracing::error!(
+                transaction_version = txn_version,
+                data = event.data,
+                "failed to parse event for key rotation"
+            );
+            panic!();
+        });
+        // The legacy event is emitted on a handle of the rotated account
+        let account_address = data
+            .account
+            .or_else(|| event.key.as_ref().map(|key| key.account_address.clone()))?;
+        Some(Self {
+            account_address: standardize_address(&account_address),
+            old_auth_key: data.old_authentication_key,
+            new_auth_key: data.new_authentication_key,
+        })
+    }
+}
@@ -1,8 +1,13 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::schema::auth_key_account_addresses;
+use crate::{
+    parquet_processors::parquet_utils::util::{HasVersion, NamedTable},
+    schema::auth_key_account_addresses,
+};
+use allocative_derive::Allocative;
 use field_count::FieldCount;
+use parquet_derive::ParquetRecordWriter;
 use serde::{Deserialize, Serialize};
 
 #[derive(
@@ -43,3 +48,35 @@ impl PartialOrd for AuthKeyAccountAddress {
         Some(self.cmp(other))
     }
 }
+
+// Parquet models
+#[derive(
+    Allocative, Clone, Debug, Default, Deserialize, FieldCount, ParquetRecordWriter, Serialize,
+)]
+pub struct ParquetAuthKeyAccountAddress {
+    pub auth_key: String,
+    pub account_address: String,
+    pub last_transaction_version: i64,
+    pub is_auth_key_used: bool,
+}
+
+impl NamedTable for ParquetAuthKeyAccountAddress {
+    const TABLE_NAME: &'static str = "auth_key_account_addresses";
+}
+
+impl HasVersion for ParquetAuthKeyAccountAddress {
+    fn version(&self) -> i64 {
+        self.last_transaction_version
+    }
+}
+
+impl From<AuthKeyAccountAddress> for ParquetAuthKeyAccountAddress {
+    fn from(base: AuthKeyAccountAddress) -> Self {
+        Self {
+            auth_key: base.auth_key,
+            account_address: base.account_address,
+            last_transaction_version: base.last_transaction_version,
+            is_auth_key_used: base.is_auth_key_used,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod account_key_rotations;
 pub mod account_restoration_utils;
 pub mod auth_key_account_addresses;
 pub mod public_key_auth_keys;
@@ -3,6 +3,7 @@
 
 use super::account_restoration_utils::KeyRotationToPublicKeyEvent;
 use crate::{
+    parquet_processors::parquet_utils::util::{HasVersion, NamedTable},
     processors::user_transaction::models::signature_utils::{
         account_signature_utils::{
             get_account_signature_type_from_enum, get_public_key_indices_from_multi_key_signature,
@@ -13,6 +14,7 @@ use crate::{
     schema::public_key_auth_keys,
 };
 use ahash::AHashMap;
+use allocative_derive::Allocative;
 use aptos_indexer_processor_sdk::aptos_protos::transaction::v1::{
     account_signature::{Signature as AccountSignature, Type as AccountSignatureTypeEnum},
     any_public_key::Type as AnyPublicKeyEnum,
@@ -20,14 +22,15 @@ use aptos_indexer_processor_sdk::aptos_protos::transaction::v1::{
     AnyPublicKey, MultiEd25519Signature, MultiKeySignature, Signature,
 };
 use field_count::FieldCount;
+use parquet_derive::ParquetRecordWriter;
 use serde::{Deserialize, Serialize};
 
 pub type PublicKeyAuthKeyMapping = AHashMap<(String, String, String, bool), PublicKeyAuthKey>;
 
-const ED25519_SCHEME: u8 = 0;
-const MULTI_ED25519_SCHEME: u8 = 1;
-const SINGLE_KEY_SCHEME: u8 = 2;
-const MULTI_KEY_SCHEME: u8 = 3;
+pub const ED25519_SCHEME: u8 = 0;
+pub const MULTI_ED25519_SCHEME: u8 = 1;
+pub const SINGLE_KEY_SCHEME: u8 = 2;
+pub const MULTI_KEY_SCHEME: u8 = 3;
 const MAX_ACCOUNT_PUBLIC_KEY_LENGTH: usize = 13000;
 
 #[derive(
@@ -60,6 +63,44 @@ impl PublicKeyAuthKey {
     }
 }
 
+// Parquet models
+#[derive(
+    Allocative, Clone, Debug, Default, Deserialize, FieldCount, ParquetRecordWriter, Serialize,
+)]
+pub struct ParquetPublicKeyAuthKey {
+    pub public_key: String,
+    pub public_key_type: String,
+    pub auth_key: String,
+    pub account_public_key: String,
+    pub is_public_key_used: bool,
+    pub last_transaction_version: i64,
+    pub signature_type: String,
+}
+
+impl NamedTable for ParquetPublicKeyAuthKey {
+    const TABLE_NAME: &'static str = "public_key_auth_keys";
+}
+
+impl HasVersion for ParquetPublicKeyAuthKey {
+    fn version(&self) -> i64 {
+        self.last_transaction_version
+    }
+}
+
+impl From<PublicKeyAuthKey> for ParquetPublicKeyAuthKey {
+    fn from(base: PublicKeyAuthKey) -> Self {
+        Self {
+            public_key: base.public_key,
+            public_key_type: base.public_key_type,
+            auth_key: base.auth_key,
+            account_public_key: base.account_public_key,
+            is_public_key_used: base.is_public_key_used,
+            last_transaction_version: base.last_transaction_version,
+            signature_type: base.signature_type,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct PublicKeyAuthKeyHelper {
     pub keys: Vec<PublicKeyAuthKeyHelperInner>,
@@ -5,8 +5,10 @@ use super::account_restoration_models::public_key_auth_keys::PublicKeyAuthKeyHel
 use crate::{
     db::resources::V2TokenResource,
     processors::account_restoration::account_restoration_models::{
-        account_restoration_utils::KeyRotationToPublicKeyEvent,
-        auth_key_account_addresses::AuthKeyAccountAddress, public_key_auth_keys::PublicKeyAuthKey,
+        account_key_rotations::AccountKeyRotation,
+        account_restoration_utils::{KeyRotationEvent, KeyRotationToPublicKeyEvent},
+        auth_key_account_addresses::AuthKeyAccountAddress,
+        public_key_auth_keys::PublicKeyAuthKey,
     },
 };
 use ahash::AHashMap;
@@ -48,17 +50,27 @@ impl TryFrom<&WriteResource> for Account {
     }
 }
 
+fn auth_key_to_string(auth_key: &[u8]) -> String {
+    standardize_address(&format!("0x{}", hex::encode(auth_key)))
+}
+
 pub fn parse_account_restoration_models(
     transactions: &Vec<Transaction>,
-) -> (Vec<AuthKeyAccountAddress>, Vec<PublicKeyAuthKey>) {
+) -> (
+    Vec<AuthKeyAccountAddress>,
+    Vec<PublicKeyAuthKey>,
+    Vec<AccountKeyRotation>,
+) {
     let mut all_auth_key_account_addresses = AHashMap::new();
     let mut all_public_key_auth_keys: Vec<PublicKeyAuthKey> = Vec::new();
+    let mut all_account_key_rotations: Vec<AccountKeyRotation> = Vec::new();
 
     let data: Vec<_> = transactions
         .par_iter()
         .map(|txn| {
             let mut auth_key_account_addresses = AHashMap::new();
             let mut public_key_auth_keys: Vec<PublicKeyAuthKey> = Vec::new();
+            let mut account_key_rotations: Vec<AccountKeyRotation> = Vec::new();
 
             let txn_version = txn.version as i64;
             let (entry_function_id_str, signature, sender) = match &txn.txn_data {
@@ -78,7 +90,11 @@ pub fn parse_account_restoration_models(
 
             let transaction_info = txn.info.as_ref().expect("Transaction info doesn't exist!");
             if !transaction_info.success {
-                return (auth_key_account_addresses, public_key_auth_keys);
+                return (
+                    auth_key_account_addresses,
+                    public_key_auth_keys,
+                    account_key_rotations,
+                );
             }
 
             // At the end of this loop we'll get all account addresses and their corresponding auth keys
@@ -88,6 +104,14 @@ pub fn parse_account_restoration_models(
             // 3. Multi-key transaction
 
             let key_rotation_event = KeyRotationToPublicKeyEvent::from_transaction(txn);
+            let key_rotations = KeyRotationEvent::from_transaction(txn);
+            let is_unverified_rotation = ROTATE_AUTH_KEY_UNVERIFIED_ENTRY_FUNCTIONS
+                .contains(&entry_function_id_str.as_deref().unwrap_or(""));
+            let is_verified_rotation = !is_unverified_rotation
+                && (ROTATE_AUTH_KEY_ENTRY_FUNCTIONS
+                    .contains(&entry_function_id_str.as_deref().unwrap_or(""))
+                    || key_rotation_event.is_some()
+                    || !key_rotations.is_empty());
             let mut multi_key_helper = signature.as_ref().and_then(|sig| {
                 PublicKeyAuthKeyHelper::get_multi_key_from_signature(sig, txn_version)
             });
@@ -98,12 +122,34 @@ pub fn parse_account_restoration_models(
                     {
                         let auth_key = standardize_address(&account.authentication_key);
                         let account_address = standardize_address(&wr.address);
-                        // If the this isn't a change on the sender account (i.e. it is a change of a recipient
-                        // account's token resource), we skip.
-                        if sender.as_ref() != Some(&account_address) {
+                        // If this is neither a change on the sender account nor on an account whose key was
+                        // rotated (e.g. it is a change of a recipient account), we skip.
+                        if sender.as_ref() != Some(&account_address)
+                            && !key_rotations
+                                .iter()
+                                .any(|rotation| rotation.account_address == account_address)
+                        {
                             continue;
                         }
 
+                        // Rotations from before key rotation events were emitted are taken from the
+                        // sender's account
+                        if key_rotations.is_empty()
+                            && (is_unverified_rotation || is_verified_rotation)
+                        {
+                            account_key_rotations.push(AccountKeyRotation::new(
+                                txn_version,
+                                &account_address,
+                                key_rotation_event
+                                    .as_ref()
+                                    .map(|event| auth_key_to_string(&event.old_auth_key)),
+                                &auth_key,
+                                key_rotation_event.as_ref(),
+                                is_verified_rotation,
+                                entry_function_id_str.as_deref(),
+                            ));
+                        }
+
                         // If the transaction is an unverified key rotation transaction, we need to insert the auth key account address
                         // with is_auth_key_used set to false.  This allows us to filter out accounts that are not actually owned by the
                         // owner of the auth key.
@@ -145,6 +191,35 @@ pub fn parse_account_restoration_models(
                 }
             }
 
+            // Every rotation is recorded in the append-only history, keyed on the rotated account
+            for rotation in &key_rotations {
+                // The public key event of a rotation has the same new auth key
+                let public_key_event = key_rotation_event
+                    .as_ref()
+                    .filter(|event| event.new_auth_key == rotation.new_auth_key);
+                account_key_rotations.push(AccountKeyRotation::new(
+                    txn_version,
+                    &rotation.account_address,
+                    Some(auth_key_to_string(&rotation.old_auth_key)),
+                    &auth_key_to_string(&rotation.new_auth_key),
+                    public_key_event,
+                    is_verified_rotation,
+                    entry_function_id_str.as_deref(),
+                ));
+            }
+
+            // The public keys of a KeyRotationToPublicKeyEvent belong to the rotated account, which
+            // isn't the sender when rotating with a rotation capability.
+            let public_key_account = key_rotation_event
+                .as_ref()
+                .and_then(|event| {
+                    key_rotations
+                        .iter()
+                        .find(|rotation| rotation.new_auth_key == event.new_auth_key)
+                })
+                .map(|rotation| rotation.account_address.clone())
+                .or(sender);
+
             // If there is a KeyRotationToPublicKeyEvent event, use the PublicKeyAuthKeyHelper constructed from it instead.
             // In the case of a single key, there is no helper to construct.
             if let Some(key_rotation_event) = key_rotation_event {
@@ -155,8 +230,8 @@ pub fn parse_account_restoration_models(
             }
 
             if let Some(helper) = &multi_key_helper {
-                if let Some(sender) = sender {
-                    if let Some(auth_key_account_address) = auth_key_account_addresses.get(&sender)
+                if let Some(account) = public_key_account {
+                    if let Some(auth_key_account_address) = auth_key_account_addresses.get(&account)
                     {
                         public_key_auth_keys.extend(
                             PublicKeyAuthKeyHelper::get_public_key_auth_keys(
@@ -169,12 +244,17 @@ pub fn parse_account_restoration_models(
                 }
             }
 
-            (auth_key_account_addresses, public_key_auth_keys)
+            (
+                auth_key_account_addresses,
+                public_key_auth_keys,
+                account_key_rotations,
+            )
         })
         .collect();
-    for (auth_key_account_addresses, public_key_auth_keys) in data {
+    for (auth_key_account_addresses, public_key_auth_keys, account_key_rotations) in data {
         all_auth_key_account_addresses.extend(auth_key_account_addresses);
         all_public_key_auth_keys.extend(public_key_auth_keys);
+        all_account_key_rotations.extend(account_key_rotations);
     }
 
     let mut all_auth_key_account_addresses = all_auth_key_account_addresses
@@ -217,5 +297,134 @@ pub fn parse_account_restoration_models(
     // Deduplicate auth key account addresses based on account_address. Since we sorted by account_address and last_transaction_version,
     // the latest entry will be the first one.
     all_auth_key_account_addresses.dedup_by(|a, b| a.account_address == b.account_address);
-    (all_auth_key_account_addresses, all_public_key_auth_keys)
+    (
+        all_auth_key_account_addresses,
+        all_public_key_auth_keys,
+        all_account_key_rotations,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_indexer_processor_sdk::aptos_protos::transaction::v1::{
+        transaction_payload::{Payload, Type as PayloadType},
+        EntryFunctionPayload, Event, EventKey, MoveStructTag, TransactionInfo, TransactionPayload,
+        UserTransaction, UserTransactionRequest, WriteSetChange,
+    };
+
+    const OLD_AUTH_KEY: &str = "0x00000000000000000000000000000000000000000000000000000000000000aa";
+    const NEW_AUTH_KEY: &str = "0x00000000000000000000000000000000000000000000000000000000000000bb";
+
+    fn rotation_transaction(
+        sender: &str,
+        entry_function_id_str: &str,
+        event: Event,
+    ) -> Transaction {
+        let rotated_account = event
+            .key
+            .as_ref()
+            .map(|key| key.account_address.clone())
+            .unwrap_or_else(|| sender.to_string());
+        Transaction {
+            version: 100,
+            info: Some(TransactionInfo {
+                success: true,
+                changes: vec![WriteSetChange {
+                    change: Some(Change::WriteResource(WriteResource {
+                        address: rotated_account,
+                        r#type: Some(MoveStructTag {
+                            address: "0x1".to_string(),
+                            module: "account".to_string(),
+                            name: "Account".to_string(),
+                            generic_type_params: vec![],
+                        }),
+                        type_str: "0x1::account::Account".to_string(),
+                        data: format!(r#"{{"authentication_key":"{NEW_AUTH_KEY}"}}"#),
+                        ..Default::default()
+                    })),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            txn_data: Some(TxnData::User(UserTransaction {
+                request: Some(UserTransactionRequest {
+                    sender: sender.to_string(),
+                    payload: Some(TransactionPayload {
+                        r#type: PayloadType::EntryFunctionPayload as i32,
+                        payload: Some(Payload::EntryFunctionPayload(EntryFunctionPayload {
+                            entry_function_id_str: entry_function_id_str.to_string(),
+                            ..Default::default()
+                        })),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                events: vec![event],
+            })),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_rotation_with_rotation_capability() {
+        // The legacy event is emitted on a handle of the rotated account, not the sender's
+        let event = Event {
+            key: Some(EventKey {
+                creation_number: 1,
+                account_address: standardize_address("0xb"),
+            }),
+            type_str: "0x1::account::KeyRotationEvent".to_string(),
+            data: format!(
+                r#"{{"old_authentication_key":"{OLD_AUTH_KEY}","new_authentication_key":"{NEW_AUTH_KEY}"}}"#
+            ),
+            ..Default::default()
+        };
+        let (auth_key_account_addresses, _, account_key_rotations) =
+            parse_account_restoration_models(&vec![rotation_transaction(
+                &standardize_address("0xa"),
+                "0x1::account::rotate_authentication_key_with_rotation_capability",
+                event,
+            )]);
+
+        assert_eq!(account_key_rotations.len(), 1);
+        let rotation = &account_key_rotations[0];
+        assert_eq!(rotation.account_address, standardize_address("0xb"));
+        assert_eq!(rotation.old_auth_key.as_deref(), Some(OLD_AUTH_KEY));
+        assert_eq!(rotation.new_auth_key, NEW_AUTH_KEY);
+        assert!(rotation.is_verified);
+
+        assert_eq!(auth_key_account_addresses.len(), 1);
+        assert_eq!(
+            auth_key_account_addresses[0].account_address,
+            standardize_address("0xb")
+        );
+        assert_eq!(auth_key_account_addresses[0].auth_key, NEW_AUTH_KEY);
+    }
+
+    #[test]
+    fn test_old_auth_key_from_key_rotation_event() {
+        let sender = standardize_address("0xa");
+        let event = Event {
+            type_str: "0x1::account::KeyRotation".to_string(),
+            data: format!(
+                r#"{{"account":"{sender}","old_authentication_key":"{OLD_AUTH_KEY}","new_authentication_key":"{NEW_AUTH_KEY}"}}"#
+            ),
+            ..Default::default()
+        };
+        let (_, _, account_key_rotations) =
+            parse_account_restoration_models(&vec![rotation_transaction(
+                &sender,
+                "0x1::account::rotate_authentication_key_call",
+                event,
+            )]);
+
+        assert_eq!(account_key_rotations.len(), 1);
+        let rotation = &account_key_rotations[0];
+        assert_eq!(rotation.account_address, sender);
+        assert_eq!(rotation.old_auth_key.as_deref(), Some(OLD_AUTH_KEY));
+        assert_eq!(rotation.new_auth_key, NEW_AUTH_KEY);
+        assert!(!rotation.is_verified);
+        assert_eq!(rotation.public_key, None);
+    }
 }
@@ -2,6 +2,7 @@ use crate::{
     config::processor_config::DefaultProcessorConfig,
     filter_datasets,
     processors::account_restoration::account_restoration_models::{
+        account_key_rotations::AccountKeyRotation,
         auth_key_account_addresses::AuthKeyAccountAddress, public_key_auth_keys::PublicKeyAuthKey,
     },
     schema,
@@ -48,7 +49,11 @@ impl AccountRestorationStorer {
 
 #[async_trait]
 impl Processable for AccountRestorationStorer {
-    type Input = (Vec<AuthKeyAccountAddress>, Vec<PublicKeyAuthKey>);
+    type Input = (
+        Vec<AuthKeyAccountAddress>,
+        Vec<PublicKeyAuthKey>,
+        Vec<AccountKeyRotation>,
+    );
     type Output = ();
     type RunType = AsyncRunType;
 
@@ -56,14 +61,15 @@ impl Processable for AccountRestorationStorer {
         &mut self,
         input: TransactionContext<Self::Input>,
     ) -> Result<Option<TransactionContext<Self::Output>>, ProcessorError> {
-        let (auth_key_address, public_key_auth_key) = input.data;
+        let (auth_key_address, public_key_auth_key, account_key_rotations) = input.data;
 
         let per_table_chunk_sizes: AHashMap<String, usize> =
             self.processor_config.per_table_chunk_sizes.clone();
 
-        let (auth_key_address, public_key_auth_key) = filter_datasets!(self, {
+        let (auth_key_address, public_key_auth_key, account_key_rotations) = filter_datasets!(self, {
             auth_key_address => TableFlags::AUTH_KEY_ACCOUNT_ADDRESSES,
             public_key_auth_key => TableFlags::PUBLIC_KEY_AUTH_KEYS,
+            account_key_rotations => TableFlags::ACCOUNT_KEY_ROTATIONS,
         });
 
         let auth_key_address_res = execute_in_chunks(
@@ -85,7 +91,21 @@ impl Processable for AccountRestorationStorer {
             ),
         );
 
-        futures::try_join!(auth_key_address_res, public_key_auth_key_res)?;
+        let account_key_rotations_res = execute_in_chunks(
+            self.conn_pool.clone(),
+            insert_account_key_rotations_query,
+            &account_key_rotations,
+            get_config_table_chunk_size::<AccountKeyRotation>(
+                "account_key_rotations",
+                &per_table_chunk_sizes,
+            ),
+        );
+
+        futures::try_join!(
+            auth_key_address_res,
+            public_key_auth_key_res,
+            account_key_rotations_res
+        )?;
 
         Ok(Some(TransactionContext {
             data: (),
@@ -140,3 +160,14 @@ pub fn insert_public_key_auth_keys_query(
         ))
         .filter(last_transaction_version.le(excluded(last_transaction_version)))
 }
+
+pub fn insert_account_key_rotations_query(
+    items_to_insert: Vec<AccountKeyRotation>,
+) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
+    use schema::account_key_rotations::dsl::*;
+
+    diesel::insert_into(schema::account_key_rotations::table)
+        .values(items_to_insert)
+        .on_conflict((transaction_version, account_address))
+        .do_nothing()
+}
@@ -167,3 +167,12 @@ impl From<AccountTransaction> for PostgresAccountTransaction {
         }
     }
 }
+
+impl From<ParquetAccountTransaction> for PostgresAccountTransaction {
+    fn from(parquet_account_transaction: ParquetAccountTransaction) -> Self {
+        Self {
+            transaction_version: parquet_account_transaction.txn_version,
+            account_address: parquet_account_transaction.account_address,
+        }
+    }
+}
@@ -3,6 +3,10 @@ use crate::{
         db_config::DbConfig, indexer_processor_config::IndexerProcessorConfig,
         processor_config::ProcessorConfig,
     },
+    db::{
+        partitioning::{VersionPartitionManager, VersionPartitionStep},
+        retention::start_history_pruning,
+    },
     processors::{
         account_transactions::{
             account_transactions_extractor::AccountTransactionsExtractor,
@@ -18,6 +22,7 @@ use crate::{
 use anyhow::Result;
 use aptos_indexer_processor_sdk::{
     aptos_indexer_transaction_stream::TransactionStreamConfig,
+    aptos_protos::transaction::v1::Transaction,
     builder::ProcessorBuilder,
     common_steps::{
         TransactionStreamStep, VersionTrackerStep, DEFAULT_UPDATE_PROCESSOR_STATUS_SECS,
@@ -113,6 +118,20 @@ impl ProcessorTrait for AccountTransactionsProcessor {
             ..self.config.transaction_stream_config.clone()
         })
         .await?;
+        let partition_manager = VersionPartitionManager::new(
+            self.db_pool.clone(),
+            processor_config.version_partitioning.as_ref(),
+            &["account_transactions"],
+        )
+        .await?;
+        let version_partition_step =
+            VersionPartitionStep::<Vec<Transaction>>::new(partition_manager);
+        start_history_pruning(
+            &self.config,
+            self.db_pool.clone(),
+            processor_config.retention.as_ref(),
+            &["account_transactions"],
+        )?;
         let acc_txns_extractor = AccountTransactionsExtractor {};
         let opt_in_tables = TableFlags::from_set(&processor_config.tables_to_write);
 
@@ -127,6 +146,7 @@ impl ProcessorTrait for AccountTransactionsProcessor {
         let (_, buffer_receiver) = ProcessorBuilder::new_with_inputless_first_step(
             transaction_stream.into_runnable_step(),
         )
+        .connect_to(version_partition_step.into_runnable_step(), channel_size)
         .connect_to(acc_txns_extractor.into_runnable_step(), channel_size)
         .connect_to(acc_txns_storer.into_runnable_step(), channel_size)
         .connect_to(version_tracker.into_runnable_step(), channel_size)
@@ -0,0 +1,207 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks of derived tables against the tables they are derived from.
+//!
+//! Consistency checks recompute the current state of a sample of keys from the latest rows of the
+//! history table. Keys without history rows, e.g. because the history was pruned, are skipped, but
+//! a check fails if the history table has no rows at all in the version range. The processors only
+//! write `fungible_asset_balances` and `token_ownerships_v2` when they're in `tables_to_write`.
+//! Invariant checks always cover every row in the version range.
+
+use diesel::{
+    sql_types::{BigInt, Text},
+    QueryableByName,
+};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter};
+
+#[derive(Clone, Copy, Debug, Deserialize, Display, EnumIter, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DataQualityCheck {
+    // `current_fungible_asset_balances` matches the latest `fungible_asset_balances` rows
+    CurrentFungibleAssetBalances,
+    // `current_token_ownerships_v2` matches the latest `token_ownerships_v2` rows
+    CurrentTokenOwnershipsV2,
+    // Fungible asset balances and token ownership amounts are never negative
+    NonNegativeBalances,
+    // `current_supply` of v2 collections matches the number of their non-burned tokens
+    CollectionSupply,
+}
+
+/// A key that failed a check. `total` is the number of failing keys, including unreported ones.
+#[derive(Debug, QueryableByName)]
+pub struct DataQualityMismatch {
+    #[diesel(sql_type = Text)]
+    pub key: String,
+    #[diesel(sql_type = Text)]
+    pub expected: String,
+    #[diesel(sql_type = Text)]
+    pub actual: String,
+    #[diesel(sql_type = BigInt)]
+    pub total: i64,
+}
+
+impl DataQualityCheck {
+    /// Query listing the failing keys. It binds the start and end version of the range the keys
+    /// last changed in and the maximum number of keys returned. Keys are sampled randomly if
+    /// `sample_size` is set.
+    pub fn query(&self, sample_size: Option<u64>) -> String {
+        let sample = sample_size
+            .map(|sample_size| format!("ORDER BY random() LIMIT {sample_size}"))
+            .unwrap_or_default();
+        match self {
+            // The v1 and v2 balances of a store are updated separately, `amount` and
+            // `last_transaction_version` combine them
+            DataQualityCheck::CurrentFungibleAssetBalances => checked_against_history(
+                &format!(
+                    "WITH keys AS ( \
+                       SELECT storage_id FROM current_fungible_asset_balances \
+                       WHERE last_transaction_version BETWEEN $1 AND $2 {sample} \
+                     ), latest AS ( \
+                       SELECT DISTINCT ON (h.storage_id, h.token_standard) h.storage_id, \
+                         h.token_standard, h.owner_address, h.is_frozen, h.amount, \
+                         h.transaction_version \
+                       FROM fungible_asset_balances h JOIN keys k ON k.storage_id = h.storage_id \
+                       ORDER BY h.storage_id, h.token_standard, h.transaction_version DESC, \
+                         h.write_set_change_index DESC \
+                     ), expected AS ( \
+                       SELECT storage_id, \
+                         (array_agg(owner_address ORDER BY transaction_version DESC))[1] \
+                           AS owner_address, \
+                         (array_agg(is_frozen ORDER BY transaction_version DESC))[1] AS is_frozen, \
+                         max(amount) FILTER (WHERE token_standard = 'v1') AS amount_v1, \
+                         max(transaction_version) FILTER (WHERE token_standard = 'v1') \
+                           AS version_v1, \
+                         max(amount) FILTER (WHERE token_standard = 'v2') AS amount_v2, \
+                         max(transaction_version) FILTER (WHERE token_standard = 'v2') \
+                           AS version_v2 \
+                       FROM latest GROUP BY storage_id \
+                     ) \
+                     SELECT c.storage_id AS key, \
+                       format('owner %s, frozen %s, v1 amount %s at %s, v2 amount %s at %s', \
+                         e.owner_address, e.is_frozen, e.amount_v1, e.version_v1, e.amount_v2, \
+                         e.version_v2) AS expected, \
+                       format('owner %s, frozen %s, v1 amount %s at %s, v2 amount %s at %s', \
+                         c.owner_address, c.is_frozen, c.amount_v1, c.last_transaction_version_v1, \
+                         c.amount_v2, c.last_transaction_version_v2) AS actual \
+                     FROM current_fungible_asset_balances c \
+                     JOIN expected e ON e.storage_id = c.storage_id \
+                     WHERE (c.owner_address, c.is_frozen) \
+                         IS DISTINCT FROM (e.owner_address, e.is_frozen) \
+                       OR (e.version_v1 IS NOT NULL AND (c.amount_v1, c.last_transaction_version_v1) \
+                         IS DISTINCT FROM (e.amount_v1, e.version_v1)) \
+                       OR (e.version_v2 IS NOT NULL AND (c.amount_v2, c.last_transaction_version_v2) \
+                         IS DISTINCT FROM (e.amount_v2, e.version_v2))"
+                ),
+                "current_fungible_asset_balances",
+                "fungible_asset_balances",
+            ),
+            DataQualityCheck::CurrentTokenOwnershipsV2 => checked_against_history(
+                &format!(
+                    "WITH keys AS ( \
+                       SELECT token_data_id, property_version_v1, owner_address, storage_id \
+                       FROM current_token_ownerships_v2 \
+                       WHERE last_transaction_version BETWEEN $1 AND $2 {sample} \
+                     ), latest AS ( \
+                       SELECT DISTINCT ON (h.token_data_id, h.property_version_v1, \
+                         h.owner_address, h.storage_id) h.token_data_id, h.property_version_v1, \
+                         h.owner_address, h.storage_id, h.amount, h.transaction_version \
+                       FROM token_ownerships_v2 h JOIN keys k ON k.token_data_id = h.token_data_id \
+                         AND k.property_version_v1 = h.property_version_v1 \
+                         AND k.owner_address = h.owner_address AND k.storage_id = h.storage_id \
+                       ORDER BY h.token_data_id, h.property_version_v1, h.owner_address, \
+                         h.storage_id, h.transaction_version DESC, h.write_set_change_index DESC \
+                     ) \
+                     SELECT concat_ws(',', c.token_data_id, c.property_version_v1, \
+                         c.owner_address, c.storage_id) AS key, \
+                       format('amount %s, version %s', l.amount, l.transaction_version) \
+                         AS expected, \
+                       format('amount %s, version %s', c.amount, c.last_transaction_version) \
+                         AS actual \
+                     FROM current_token_ownerships_v2 c \
+                     JOIN latest l ON l.token_data_id = c.token_data_id \
+                       AND l.property_version_v1 = c.property_version_v1 \
+                       AND l.owner_address = c.owner_address AND l.storage_id = c.storage_id \
+                     WHERE (c.amount, c.last_transaction_version) \
+                       IS DISTINCT FROM (l.amount, l.transaction_version)"
+                ),
+                "current_token_ownerships_v2",
+                "token_ownerships_v2",
+            ),
+            DataQualityCheck::NonNegativeBalances => "SELECT key, '>= 0' AS expected, \
+                   amount::text AS actual, COUNT(*) OVER () AS total \
+                 FROM ( \
+                   SELECT storage_id AS key, amount FROM current_fungible_asset_balances \
+                   WHERE amount < 0 AND last_transaction_version BETWEEN $1 AND $2 \
+                   UNION ALL \
+                   SELECT concat_ws(',', token_data_id, property_version_v1, owner_address, \
+                     storage_id) AS key, amount \
+                   FROM current_token_ownerships_v2 \
+                   WHERE amount < 0 AND last_transaction_version BETWEEN $1 AND $2 \
+                 ) negative \
+                 LIMIT $3"
+                .to_string(),
+            // Collections without a supply resource don't track their supply
+            DataQualityCheck::CollectionSupply => format!(
+                "WITH collections AS ( \
+                   SELECT collection_id, current_supply FROM current_collections_v2 \
+                   WHERE token_standard = 'v2' AND total_minted_v2 IS NOT NULL \
+                     AND last_transaction_version BETWEEN $1 AND $2 {sample} \
+                 ), token_counts AS ( \
+                   SELECT c.collection_id, c.current_supply, \
+                     COUNT(t.token_data_id) AS token_count \
+                   FROM collections c LEFT JOIN current_token_datas_v2 t \
+                     ON t.collection_id = c.collection_id AND t.is_deleted_v2 IS NOT TRUE \
+                   GROUP BY c.collection_id, c.current_supply \
+                 ) \
+                 SELECT collection_id AS key, token_count::text AS expected, \
+                   current_supply::text AS actual, COUNT(*) OVER () AS total \
+                 FROM token_counts WHERE current_supply <> token_count \
+                 LIMIT $3"
+            ),
+        }
+    }
+}
+
+/// Wraps a consistency check, which skips the keys without history rows, so that it also fails
+/// when the history table has no rows at all in a range where the current table changed, e.g.
+/// because the processor doesn't write the history table. The key of that failure is the name of
+/// the history table.
+fn checked_against_history(mismatches: &str, current_table: &str, history_table: &str) -> String {
+    format!(
+        "SELECT key, expected, actual, COUNT(*) OVER () AS total \
+         FROM ( \
+           ({mismatches}) \
+           UNION ALL \
+           SELECT '{history_table}', 'rows in the version range', 'no rows' \
+           WHERE EXISTS ( \
+               SELECT 1 FROM {current_table} WHERE last_transaction_version BETWEEN $1 AND $2 \
+             ) \
+             AND NOT EXISTS ( \
+               SELECT 1 FROM {history_table} WHERE transaction_version BETWEEN $1 AND $2 \
+             ) \
+         ) mismatches \
+         LIMIT $3"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_sampled_queries() {
+        for check in DataQualityCheck::iter() {
+            let full = check.query(None);
+            let sampled = check.query(Some(1000));
+            assert!(!full.contains("random()"));
+            match check {
+                DataQualityCheck::NonNegativeBalances => assert_eq!(full, sampled),
+                _ => assert!(sampled.contains("ORDER BY random() LIMIT 1000")),
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::{
+        db_config::DbConfig, indexer_processor_config::IndexerProcessorConfig,
+        processor_config::ProcessorConfig,
+    },
+    processors::data_quality::data_quality_checks::{DataQualityCheck, DataQualityMismatch},
+};
+use anyhow::{bail, Context, Result};
+use aptos_indexer_processor_sdk::{
+    postgres::utils::database::{new_db_pool, ArcDbPool},
+    traits::processor_trait::ProcessorTrait,
+};
+use diesel::sql_types::BigInt;
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use tracing::{info, warn};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DataQualityProcessorConfig {
+    // Checks to run, all of them if empty
+    #[serde(default)]
+    pub checks: Vec<DataQualityCheck>,
+    // Only keys last changed within the version range are checked
+    #[serde(default)]
+    pub start_version: i64,
+    #[serde(default)]
+    pub end_version: Option<i64>,
+    // Number of keys randomly sampled per check, every key in the range is checked if unset
+    #[serde(default)]
+    pub sample_size: Option<u64>,
+    #[serde(default = "DataQualityProcessorConfig::default_max_reported_mismatches")]
+    pub max_reported_mismatches: i64,
+}
+
+impl DataQualityProcessorConfig {
+    pub const fn default_max_reported_mismatches() -> i64 {
+        100
+    }
+
+    fn checks(&self) -> Vec<DataQualityCheck> {
+        if self.checks.is_empty() {
+            DataQualityCheck::iter().collect()
+        } else {
+            self.checks.clone()
+        }
+    }
+}
+
+/// Runs the data quality checks once and fails if any of them finds a mismatch.
+pub struct DataQualityProcessor {
+    pub config: IndexerProcessorConfig,
+    pub db_pool: ArcDbPool,
+}
+
+impl DataQualityProcessor {
+    pub async fn new(config: IndexerProcessorConfig) -> Result<Self> {
+        match config.db_config {
+            DbConfig::PostgresConfig(ref postgres_config) => {
+                let conn_pool = new_db_pool(
+                    &postgres_config.connection_string,
+                    Some(postgres_config.db_pool_size),
+                )
+                .await
+                .map_err(|e| {
+                    anyhow::anyhow!(
+                        "Failed to create connection pool for PostgresConfig: {:?}",
+                        e
+                    )
+                })?;
+
+                Ok(Self {
+                    config,
+                    db_pool: conn_pool,
+                })
+            },
+            _ => Err(anyhow::anyhow!(
+                "Invalid db config for DataQualityProcessor {:?}",
+                config.db_config
+            )),
+        }
+    }
+
+    async fn run_check(
+        &self,
+        check: DataQualityCheck,
+        data_quality_config: &DataQualityProcessorConfig,
+    ) -> Result<i64> {
+        let mut conn = self.db_pool.get().await?;
+        let mismatches: Vec<DataQualityMismatch> =
+            diesel::sql_query(check.query(data_quality_config.sample_size))
+                .bind::<BigInt, _>(data_quality_config.start_version)
+                .bind::<BigInt, _>(data_quality_config.end_version.unwrap_or(i64::MAX))
+                .bind::<BigInt, _>(data_quality_config.max_reported_mismatches)
+                .load(&mut conn)
+                .await
+                .with_context(|| format!("Failed to run data quality check '{check}'"))?;
+
+        let total = mismatches.first().map_or(0, |mismatch| mismatch.total);
+        for mismatch in &mismatches {
+            warn!(
+                check = %check,
+                key = mismatch.key,
+                expected = mismatch.expected,
+                actual = mismatch.actual,
+                "Data quality mismatch"
+            );
+        }
+        info!(
+            check = %check,
+            mismatches = total,
+            reported = mismatches.len(),
+            "Data quality check finished"
+        );
+        Ok(total)
+    }
+}
+
+#[async_trait::async_trait]
+impl ProcessorTrait for DataQualityProcessor {
+    fn name(&self) -> &'static str {
+        self.config.processor_config.name()
+    }
+
+    async fn run_processor(&self) -> Result<()> {
+        let data_quality_config = match &self.config.processor_config {
+            ProcessorConfig::DataQualityProcessor(data_quality_config) => data_quality_config,
+            _ => bail!(
+                "Invalid processor config for DataQualityProcessor: {:?}",
+                self.config.processor_config
+            ),
+        };
+        // Mismatches are counted from the reported rows, so at least one has to be reported
+        if data_quality_config.max_reported_mismatches < 1 {
+            bail!("max_reported_mismatches must be positive");
+        }
+
+        let mut failed_checks = vec![];
+        for check in data_quality_config.checks() {
+            if self.run_check(check, data_quality_config).await? > 0 {
+                failed_checks.push(check.to_string());
+            }
+        }
+        if !failed_checks.is_empty() {
+            bail!("Data quality checks failed: {}", failed_checks.join(", "));
+        }
+        info!("All data quality checks passed");
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+pub mod data_quality_checks;
+pub mod data_quality_processor;
@@ -1,8 +1,13 @@
 use super::models::move_modules::PostgresMoveModule;
 use crate::processors::default::{
     models::{
+        block_epilogue_transactions::PostgresBlockEpilogueTransaction,
         block_metadata_transactions::PostgresBlockMetadataTransaction,
+        move_module_abis::{
+            PostgresMoveModuleFunction, PostgresMoveModuleStruct, PostgresMoveModuleUpgrade,
+        },
         table_items::{PostgresCurrentTableItem, PostgresTableItem, PostgresTableMetadata},
+        validator_transactions::PostgresValidatorTransaction,
     },
     process_transactions,
 };
@@ -27,6 +32,11 @@ impl Processable for DefaultExtractor {
         Vec<PostgresCurrentTableItem>,
         Vec<PostgresTableMetadata>,
         Vec<PostgresMoveModule>,
+        Vec<PostgresMoveModuleFunction>,
+        Vec<PostgresMoveModuleStruct>,
+        Vec<PostgresMoveModuleUpgrade>,
+        Vec<PostgresBlockEpilogueTransaction>,
+        Vec<PostgresValidatorTransaction>,
     );
     type RunType = AsyncRunType;
 
@@ -41,6 +51,11 @@ impl Processable for DefaultExtractor {
                 Vec<PostgresCurrentTableItem>,
                 Vec<PostgresTableMetadata>,
                 Vec<PostgresMoveModule>,
+                Vec<PostgresMoveModuleFunction>,
+                Vec<PostgresMoveModuleStruct>,
+                Vec<PostgresMoveModuleUpgrade>,
+                Vec<PostgresBlockEpilogueTransaction>,
+                Vec<PostgresValidatorTransaction>,
             )>,
         >,
         ProcessorError,
@@ -51,6 +66,11 @@ impl Processable for DefaultExtractor {
             raw_current_table_items,
             raw_table_metadata,
             raw_move_modules,
+            raw_move_module_functions,
+            raw_move_module_structs,
+            raw_move_module_upgrades,
+            raw_block_epilogue_transactions,
+            raw_validator_transactions,
         ) = process_transactions(transactions.data.clone());
 
         let postgres_table_items: Vec<PostgresTableItem> = raw_table_items
@@ -74,6 +94,30 @@ impl Processable for DefaultExtractor {
             .into_iter()
             .map(PostgresMoveModule::from)
             .collect();
+        let postgres_move_module_functions: Vec<PostgresMoveModuleFunction> =
+            raw_move_module_functions
+                .into_iter()
+                .map(PostgresMoveModuleFunction::from)
+                .collect();
+        let postgres_move_module_structs: Vec<PostgresMoveModuleStruct> = raw_move_module_structs
+            .into_iter()
+            .map(PostgresMoveModuleStruct::from)
+            .collect();
+        let postgres_move_module_upgrades: Vec<PostgresMoveModuleUpgrade> =
+            raw_move_module_upgrades
+                .into_iter()
+                .map(PostgresMoveModuleUpgrade::from)
+                .collect();
+        let postgres_block_epilogue_transactions: Vec<PostgresBlockEpilogueTransaction> =
+            raw_block_epilogue_transactions
+                .into_iter()
+                .map(PostgresBlockEpilogueTransaction::from)
+                .collect();
+        let postgres_validator_transactions: Vec<PostgresValidatorTransaction> =
+            raw_validator_transactions
+                .into_iter()
+                .map(PostgresValidatorTransaction::from)
+                .collect();
 
         Ok(Some(TransactionContext {
             data: (
@@ -82,6 +126,11 @@ impl Processable for DefaultExtractor {
                 postgres_current_table_items,
                 postgres_table_metadata,
                 postgres_move_modules,
+                postgres_move_module_functions,
+                postgres_move_module_structs,
+                postgres_move_module_upgrades,
+                postgres_block_epilogue_transactions,
+                postgres_validator_transactions,
             ),
             metadata: transactions.metadata,
         }))
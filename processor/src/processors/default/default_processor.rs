@@ -3,6 +3,7 @@ use crate::{
         db_config::DbConfig, indexer_processor_config::IndexerProcessorConfig,
         processor_config::ProcessorConfig,
     },
+    db::partitioning::{VersionPartitionManager, VersionPartitionStep},
     processors::{
         default::{default_extractor::DefaultExtractor, default_storer::DefaultStorer},
         processor_status_saver::{
@@ -15,6 +16,7 @@ use crate::{
 use anyhow::Result;
 use aptos_indexer_processor_sdk::{
     aptos_indexer_transaction_stream::TransactionStreamConfig,
+    aptos_protos::transaction::v1::Transaction,
     builder::ProcessorBuilder,
     common_steps::{
         TransactionStreamStep, VersionTrackerStep, DEFAULT_UPDATE_PROCESSOR_STATUS_SECS,
@@ -112,6 +114,14 @@ impl ProcessorTrait for DefaultProcessor {
             ..self.config.transaction_stream_config.clone()
         })
         .await?;
+        let partition_manager = VersionPartitionManager::new(
+            self.db_pool.clone(),
+            processor_config.version_partitioning.as_ref(),
+            &["write_set_changes", "move_resources"],
+        )
+        .await?;
+        let version_partition_step =
+            VersionPartitionStep::<Vec<Transaction>>::new(partition_manager);
         let default_extractor = DefaultExtractor {};
         let default_storer =
             DefaultStorer::new(self.db_pool.clone(), processor_config, tables_to_write);
@@ -124,6 +134,7 @@ impl ProcessorTrait for DefaultProcessor {
         let (_, buffer_receiver) = ProcessorBuilder::new_with_inputless_first_step(
             transaction_stream.into_runnable_step(),
         )
+        .connect_to(version_partition_step.into_runnable_step(), channel_size)
         .connect_to(default_extractor.into_runnable_step(), channel_size)
         .connect_to(default_storer.into_runnable_step(), channel_size)
         .connect_to(version_tracker.into_runnable_step(), channel_size)
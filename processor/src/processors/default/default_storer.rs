@@ -5,9 +5,15 @@ use crate::{
     config::processor_config::DefaultProcessorConfig,
     filter_datasets,
     processors::default::models::{
+        block_epilogue_transactions::PostgresBlockEpilogueTransaction,
         block_metadata_transactions::PostgresBlockMetadataTransaction,
+        move_module_abis::{
+            PostgresMoveModuleFunction, PostgresMoveModuleStruct, PostgresMoveModuleUpgrade,
+        },
         move_modules::PostgresMoveModule,
         table_items::{PostgresCurrentTableItem, PostgresTableItem, PostgresTableMetadata},
+        transactions::PostgresTransaction,
+        validator_transactions::PostgresValidatorTransaction,
     },
     schema,
     utils::table_flags::{filter_data, TableFlags},
@@ -24,9 +30,10 @@ use async_trait::async_trait;
 use diesel::{
     pg::{upsert::excluded, Pg},
     query_builder::QueryFragment,
-    query_dsl::methods::FilterDsl,
+    query_dsl::methods::{DistinctOnDsl, FilterDsl, OrderDsl, SelectDsl},
     ExpressionMethods,
 };
+use diesel_async::RunQueryDsl;
 
 pub struct DefaultStorer
 where
@@ -49,6 +56,51 @@ impl DefaultStorer {
             tables_to_write,
         }
     }
+
+    /// Drops the upgrades that keep the bytecode of the module's latest stored row, e.g. the
+    /// first write of an unchanged module when its package is upgraded in a later batch.
+    async fn drop_unchanged_upgrades(
+        &self,
+        upgrades: Vec<PostgresMoveModuleUpgrade>,
+    ) -> Result<Vec<PostgresMoveModuleUpgrade>> {
+        let Some(min_version) = upgrades.iter().map(|u| u.transaction_version).min() else {
+            return Ok(upgrades);
+        };
+        let mut addresses: Vec<String> = upgrades.iter().map(|u| u.address.clone()).collect();
+        addresses.sort();
+        addresses.dedup();
+        let mut module_names: Vec<String> =
+            upgrades.iter().map(|u| u.module_name.clone()).collect();
+        module_names.sort();
+        module_names.dedup();
+
+        let mut conn = self.conn_pool.get().await?;
+        let mut latest_hashes: AHashMap<(String, String), String> = {
+            use schema::move_module_upgrades::dsl::*;
+            move_module_upgrades
+                .filter(address.eq_any(addresses))
+                .filter(module_name.eq_any(module_names))
+                .filter(transaction_version.lt(min_version))
+                .distinct_on((address, module_name))
+                .order((address, module_name, transaction_version.desc()))
+                .select((address, module_name, bytecode_hash))
+                .load::<(String, String, String)>(&mut conn)
+                .await?
+                .into_iter()
+                .map(|(addr, name, hash)| ((addr, name), hash))
+                .collect()
+        };
+
+        Ok(upgrades
+            .into_iter()
+            .filter(|upgrade| {
+                let key = (upgrade.address.clone(), upgrade.module_name.clone());
+                let unchanged = latest_hashes.get(&key) == Some(&upgrade.bytecode_hash);
+                latest_hashes.insert(key, upgrade.bytecode_hash.clone());
+                !unchanged
+            })
+            .collect())
+    }
 }
 
 #[async_trait]
@@ -59,6 +111,11 @@ impl Processable for DefaultStorer {
         Vec<PostgresCurrentTableItem>,
         Vec<PostgresTableMetadata>,
         Vec<PostgresMoveModule>,
+        Vec<PostgresMoveModuleFunction>,
+        Vec<PostgresMoveModuleStruct>,
+        Vec<PostgresMoveModuleUpgrade>,
+        Vec<PostgresBlockEpilogueTransaction>,
+        Vec<PostgresValidatorTransaction>,
     );
     type Output = ();
     type RunType = AsyncRunType;
@@ -78,6 +135,13 @@ impl Processable for DefaultStorer {
     ///   * `Vec<TableItem>` - A vector of table items.
     ///   * `Vec<CurrentTableItem>` - A vector of current table items.
     ///   * `Vec<TableMetadata>` - A vector of table metadata.
+    ///   * `Vec<MoveModule>` - A vector of move modules.
+    ///   * `Vec<MoveModuleFunction>` - A vector of functions decomposed from module ABIs.
+    ///   * `Vec<MoveModuleStruct>` - A vector of structs decomposed from module ABIs.
+    ///   * `Vec<MoveModuleUpgrade>` - A vector of module writes that change the bytecode. Writes
+    ///     that keep the bytecode of the module's latest stored row are dropped before inserting.
+    ///   * `Vec<BlockEpilogueTransactionModel>` - A vector of block epilogue transaction models.
+    ///   * `Vec<ValidatorTransactionModel>` - A vector of validator transaction models.
     ///
     /// # Returns
     ///
@@ -91,6 +155,11 @@ impl Processable for DefaultStorer {
             Vec<PostgresCurrentTableItem>,
             Vec<PostgresTableMetadata>,
             Vec<PostgresMoveModule>,
+            Vec<PostgresMoveModuleFunction>,
+            Vec<PostgresMoveModuleStruct>,
+            Vec<PostgresMoveModuleUpgrade>,
+            Vec<PostgresBlockEpilogueTransaction>,
+            Vec<PostgresValidatorTransaction>,
         )>,
     ) -> Result<Option<TransactionContext<()>>, ProcessorError> {
         let (
@@ -99,6 +168,11 @@ impl Processable for DefaultStorer {
             current_table_items,
             table_metadata,
             move_modules,
+            move_module_functions,
+            move_module_structs,
+            move_module_upgrades,
+            block_epilogue_transactions,
+            validator_transactions,
         ) = input.data;
 
         let (
@@ -107,14 +181,35 @@ impl Processable for DefaultStorer {
             current_table_items,
             table_metadata,
             move_modules,
+            move_module_functions,
+            move_module_structs,
+            move_module_upgrades,
+            block_epilogue_transactions,
+            validator_transactions,
         ) = filter_datasets!(self, {
             block_metadata_transactions => TableFlags::BLOCK_METADATA_TRANSACTIONS,
             table_items => TableFlags::TABLE_ITEMS,
             current_table_items => TableFlags::CURRENT_TABLE_ITEMS,
             table_metadata => TableFlags::TABLE_METADATA,
             move_modules => TableFlags::MOVE_MODULES,
+            move_module_functions => TableFlags::MOVE_MODULE_FUNCTIONS,
+            move_module_structs => TableFlags::MOVE_MODULE_STRUCTS,
+            move_module_upgrades => TableFlags::MOVE_MODULE_UPGRADES,
+            block_epilogue_transactions => TableFlags::BLOCK_EPILOGUE_TRANSACTIONS,
+            validator_transactions => TableFlags::VALIDATOR_TRANSACTIONS,
         });
 
+        let move_module_upgrades = self
+            .drop_unchanged_upgrades(move_module_upgrades)
+            .await
+            .map_err(|e| ProcessorError::DBStoreError {
+                message: format!(
+                    "Failed to load move module upgrades for versions {} to {}: {:?}",
+                    input.metadata.start_version, input.metadata.end_version, e,
+                ),
+                query: None,
+            })?;
+
         let per_table_chunk_sizes: AHashMap<String, usize> =
             self.processor_config.per_table_chunk_sizes.clone();
 
@@ -165,12 +260,67 @@ impl Processable for DefaultStorer {
             ),
         );
 
+        let move_module_functions_res = execute_in_chunks(
+            self.conn_pool.clone(),
+            insert_move_module_functions_query,
+            &move_module_functions,
+            get_config_table_chunk_size::<PostgresMoveModuleFunction>(
+                "move_module_functions",
+                &per_table_chunk_sizes,
+            ),
+        );
+
+        let move_module_structs_res = execute_in_chunks(
+            self.conn_pool.clone(),
+            insert_move_module_structs_query,
+            &move_module_structs,
+            get_config_table_chunk_size::<PostgresMoveModuleStruct>(
+                "move_module_structs",
+                &per_table_chunk_sizes,
+            ),
+        );
+
+        let move_module_upgrades_res = execute_in_chunks(
+            self.conn_pool.clone(),
+            insert_move_module_upgrades_query,
+            &move_module_upgrades,
+            get_config_table_chunk_size::<PostgresMoveModuleUpgrade>(
+                "move_module_upgrades",
+                &per_table_chunk_sizes,
+            ),
+        );
+
+        let bet_res = execute_in_chunks(
+            self.conn_pool.clone(),
+            insert_block_epilogue_transactions_query,
+            &block_epilogue_transactions,
+            get_config_table_chunk_size::<PostgresBlockEpilogueTransaction>(
+                "block_epilogue_transactions",
+                &per_table_chunk_sizes,
+            ),
+        );
+
+        let validator_transactions_res = execute_in_chunks(
+            self.conn_pool.clone(),
+            insert_validator_transactions_query,
+            &validator_transactions,
+            get_config_table_chunk_size::<PostgresValidatorTransaction>(
+                "validator_transactions",
+                &per_table_chunk_sizes,
+            ),
+        );
+
         futures::try_join!(
             bmt_res,
             table_items_res,
             current_table_items_res,
             table_metadata_res,
             move_modules_res,
+            move_module_functions_res,
+            move_module_structs_res,
+            move_module_upgrades_res,
+            bet_res,
+            validator_transactions_res,
         )?;
 
         Ok(Some(TransactionContext {
@@ -188,6 +338,17 @@ impl NamedStep for DefaultStorer {
     }
 }
 
+pub fn insert_transactions_query(
+    items_to_insert: Vec<PostgresTransaction>,
+) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
+    use schema::transactions::dsl::*;
+
+    diesel::insert_into(schema::transactions::table)
+        .values(items_to_insert)
+        .on_conflict(version)
+        .do_nothing()
+}
+
 pub fn insert_block_metadata_transactions_query(
     items_to_insert: Vec<PostgresBlockMetadataTransaction>,
 ) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
@@ -199,6 +360,28 @@ pub fn insert_block_metadata_transactions_query(
         .do_nothing()
 }
 
+pub fn insert_block_epilogue_transactions_query(
+    items_to_insert: Vec<PostgresBlockEpilogueTransaction>,
+) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
+    use schema::block_epilogue_transactions::dsl::*;
+
+    diesel::insert_into(schema::block_epilogue_transactions::table)
+        .values(items_to_insert)
+        .on_conflict(version)
+        .do_nothing()
+}
+
+pub fn insert_validator_transactions_query(
+    items_to_insert: Vec<PostgresValidatorTransaction>,
+) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
+    use schema::validator_transactions::dsl::*;
+
+    diesel::insert_into(schema::validator_transactions::table)
+        .values(items_to_insert)
+        .on_conflict(version)
+        .do_nothing()
+}
+
 pub fn insert_table_items_query(
     items_to_insert: Vec<PostgresTableItem>,
 ) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
@@ -255,3 +438,37 @@ pub fn insert_move_modules_query(
         .on_conflict((transaction_version, write_set_change_index))
         .do_nothing()
 }
+
+pub fn insert_move_module_functions_query(
+    items_to_insert: Vec<PostgresMoveModuleFunction>,
+) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
+    use schema::move_module_functions::dsl::*;
+
+    diesel::insert_into(schema::move_module_functions::table)
+        .values(items_to_insert)
+        .on_conflict((transaction_version, write_set_change_index, name))
+        .do_nothing()
+}
+
+pub fn insert_move_module_structs_query(
+    items_to_insert: Vec<PostgresMoveModuleStruct>,
+) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
+    use schema::move_module_structs::dsl::*;
+
+    diesel::insert_into(schema::move_module_structs::table)
+        .values(items_to_insert)
+        .on_conflict((transaction_version, write_set_change_index, name))
+        .do_nothing()
+}
+
+/// Upgrades are keyed on the version of the write, so a replayed batch doesn't duplicate rows.
+pub fn insert_move_module_upgrades_query(
+    items_to_insert: Vec<PostgresMoveModuleUpgrade>,
+) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
+    use schema::move_module_upgrades::dsl::*;
+
+    diesel::insert_into(schema::move_module_upgrades::table)
+        .values(items_to_insert)
+        .on_conflict((address, module_name, transaction_version))
+        .do_nothing()
+}
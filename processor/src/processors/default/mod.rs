@@ -5,11 +5,14 @@ pub mod models;
 
 use crate::{
     processors::default::models::{
+        block_epilogue_transactions::BlockEpilogueTransactionModel,
         block_metadata_transactions::BlockMetadataTransactionModel,
+        move_module_abis::{MoveModuleFunction, MoveModuleStruct, MoveModuleUpgrade},
         move_modules::ParquetMoveModule,
         move_resources::ParquetMoveResource,
         table_items::{CurrentTableItem, TableItem, TableMetadata},
         transactions::{ParquetTransaction, TransactionModel},
+        validator_transactions::ValidatorTransactionModel,
         write_set_changes::{ParquetWriteSetChange, WriteSetChangeDetail},
     },
     utils::counters::PROCESSOR_UNKNOWN_TYPE_COUNT,
@@ -23,8 +26,8 @@ use models::move_modules::MoveModule;
 // TODO: we can further optimize this by passing in a flag to selectively parse only the required data (e.g. table_items for parquet)
 /// Processes a list of transactions and extracts relevant data into different models.
 ///
-/// This function iterates over a list of transactions, extracting block metadata transactions,
-/// table items, current table items, and table metadata. It handles different types of
+/// This function iterates over a list of transactions, extracting block metadata, block epilogue
+/// and validator transactions, table items, current table items, and table metadata. It handles different types of
 /// transactions and write set changes, converting them into appropriate models. The function
 /// also sorts the extracted data to avoid PostgreSQL deadlocks during multi-threaded database
 /// writes.
@@ -40,6 +43,12 @@ use models::move_modules::MoveModule;
 /// * `Vec<RawTableItem>` - A vector of table items.
 /// * `Vec<RawCurrentTableItem>` - A vector of current table items, sorted by primary key.
 /// * `Vec<RawTableMetadata>` - A vector of table metadata, sorted by primary key.
+/// * `Vec<MoveModule>` - A vector of move modules.
+/// * `Vec<MoveModuleFunction>` - A vector of exposed functions decomposed from module ABIs.
+/// * `Vec<MoveModuleStruct>` - A vector of structs decomposed from module ABIs.
+/// * `Vec<MoveModuleUpgrade>` - A vector of module writes that change the bytecode, sorted by primary key.
+/// * `Vec<BlockEpilogueTransactionModel>` - A vector of block epilogue transaction models.
+/// * `Vec<ValidatorTransactionModel>` - A vector of validator transaction models.
 pub fn process_transactions(
     transactions: Vec<Transaction>,
 ) -> (
@@ -48,12 +57,22 @@ pub fn process_transactions(
     Vec<CurrentTableItem>,
     Vec<TableMetadata>,
     Vec<MoveModule>,
+    Vec<MoveModuleFunction>,
+    Vec<MoveModuleStruct>,
+    Vec<MoveModuleUpgrade>,
+    Vec<BlockEpilogueTransactionModel>,
+    Vec<ValidatorTransactionModel>,
 ) {
     let mut block_metadata_transactions = vec![];
+    let mut block_epilogue_transactions = vec![];
+    let mut validator_transactions = vec![];
     let mut table_items = vec![];
     let mut current_table_items = AHashMap::new();
     let mut table_metadata = AHashMap::new();
     let mut move_modules = vec![];
+    let mut move_module_functions = vec![];
+    let mut move_module_structs = vec![];
+    let mut move_module_upgrades = vec![];
 
     for transaction in transactions {
         let version = transaction.version as i64;
@@ -85,15 +104,38 @@ pub fn process_transactions(
                 continue;
             },
         };
-        if let TxnData::BlockMetadata(block_metadata_txn) = txn_data {
-            let bmt = BlockMetadataTransactionModel::from_bmt_transaction(
-                block_metadata_txn,
-                version,
-                block_height,
-                epoch,
-                timestamp,
-            );
-            block_metadata_transactions.push(bmt);
+        match txn_data {
+            TxnData::BlockMetadata(block_metadata_txn) => {
+                let bmt = BlockMetadataTransactionModel::from_bmt_transaction(
+                    block_metadata_txn,
+                    version,
+                    block_height,
+                    epoch,
+                    timestamp,
+                );
+                block_metadata_transactions.push(bmt);
+            },
+            TxnData::BlockEpilogue(block_epilogue_txn) => {
+                block_epilogue_transactions.push(
+                    BlockEpilogueTransactionModel::from_block_epilogue_transaction(
+                        block_epilogue_txn,
+                        version,
+                        block_height,
+                        epoch,
+                        timestamp,
+                    ),
+                );
+            },
+            TxnData::Validator(validator_txn) => {
+                validator_transactions.push(ValidatorTransactionModel::from_validator_transaction(
+                    validator_txn,
+                    version,
+                    block_height,
+                    epoch,
+                    timestamp,
+                ));
+            },
+            _ => {},
         }
 
         for (index, wsc) in transaction_info.changes.iter().enumerate() {
@@ -140,6 +182,19 @@ pub fn process_transactions(
                         block_height,
                         block_timestamp,
                     );
+                    move_module_functions.extend(MoveModuleFunction::from_write_module(
+                        inner,
+                        index as i64,
+                        version,
+                    ));
+                    move_module_structs.extend(MoveModuleStruct::from_write_module(
+                        inner,
+                        index as i64,
+                        version,
+                    ));
+                    if let Some(upgrade) = MoveModuleUpgrade::from_move_module(&move_module) {
+                        move_module_upgrades.push(upgrade);
+                    }
                     move_modules.push(move_module);
                 },
                 WriteSetChangeEnum::DeleteModule(inner) => {
@@ -166,6 +221,14 @@ pub fn process_transactions(
     current_table_items
         .sort_by(|a, b| (&a.table_handle, &a.key_hash).cmp(&(&b.table_handle, &b.key_hash)));
     table_metadata.sort_by(|a, b| a.handle.cmp(&b.handle));
+    move_module_upgrades.sort_by(|a, b| {
+        (&a.address, &a.module_name, a.txn_version).cmp(&(
+            &b.address,
+            &b.module_name,
+            b.txn_version,
+        ))
+    });
+    MoveModuleUpgrade::dedup_unchanged(&mut move_module_upgrades);
 
     (
         block_metadata_transactions,
@@ -173,6 +236,11 @@ pub fn process_transactions(
         current_table_items,
         table_metadata,
         move_modules,
+        move_module_functions,
+        move_module_structs,
+        move_module_upgrades,
+        block_epilogue_transactions,
+        validator_transactions,
     )
 }
 
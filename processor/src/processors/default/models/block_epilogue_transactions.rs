@@ -0,0 +1,176 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use crate::{
+    parquet_processors::parquet_utils::util::{HasVersion, NamedTable},
+    schema::block_epilogue_transactions,
+};
+use allocative_derive::Allocative;
+use aptos_indexer_processor_sdk::{
+    aptos_indexer_transaction_stream::utils::time::parse_timestamp,
+    aptos_protos::{
+        transaction::v1::BlockEpilogueTransaction as ProtoBlockEpilogueTransaction,
+        util::timestamp::Timestamp,
+    },
+};
+use field_count::FieldCount;
+use parquet_derive::ParquetRecordWriter;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BlockEpilogueTransaction {
+    pub version: i64,
+    pub block_height: i64,
+    pub epoch: i64,
+    pub block_gas_limit_reached: Option<bool>,
+    pub block_output_limit_reached: Option<bool>,
+    pub block_effective_block_gas_units: Option<i64>,
+    pub block_approx_output_size: Option<i64>,
+    pub timestamp: chrono::NaiveDateTime,
+}
+
+impl BlockEpilogueTransaction {
+    pub fn from_block_epilogue_transaction(
+        txn: &ProtoBlockEpilogueTransaction,
+        version: i64,
+        block_height: i64,
+        epoch: i64,
+        timestamp: &Timestamp,
+    ) -> Self {
+        // Block end info is only populated once the chain has enabled it
+        let block_end_info = txn.block_end_info.as_ref();
+        Self {
+            version,
+            block_height,
+            epoch,
+            block_gas_limit_reached: block_end_info.map(|info| info.block_gas_limit_reached),
+            block_output_limit_reached: block_end_info.map(|info| info.block_output_limit_reached),
+            block_effective_block_gas_units: block_end_info
+                .map(|info| info.block_effective_block_gas_units as i64),
+            block_approx_output_size: block_end_info
+                .map(|info| info.block_approx_output_size as i64),
+            timestamp: parse_timestamp(timestamp, version).naive_utc(),
+        }
+    }
+}
+
+// Prevent conflicts with other things named `Transaction`
+pub type BlockEpilogueTransactionModel = BlockEpilogueTransaction;
+
+// Postgres Model
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(version))]
+#[diesel(table_name = block_epilogue_transactions)]
+pub struct PostgresBlockEpilogueTransaction {
+    pub version: i64,
+    pub block_height: i64,
+    pub epoch: i64,
+    pub block_gas_limit_reached: Option<bool>,
+    pub block_output_limit_reached: Option<bool>,
+    pub block_effective_block_gas_units: Option<i64>,
+    pub block_approx_output_size: Option<i64>,
+    pub timestamp: chrono::NaiveDateTime,
+}
+
+impl From<BlockEpilogueTransaction> for PostgresBlockEpilogueTransaction {
+    fn from(base_item: BlockEpilogueTransaction) -> Self {
+        PostgresBlockEpilogueTransaction {
+            version: base_item.version,
+            block_height: base_item.block_height,
+            epoch: base_item.epoch,
+            block_gas_limit_reached: base_item.block_gas_limit_reached,
+            block_output_limit_reached: base_item.block_output_limit_reached,
+            block_effective_block_gas_units: base_item.block_effective_block_gas_units,
+            block_approx_output_size: base_item.block_approx_output_size,
+            timestamp: base_item.timestamp,
+        }
+    }
+}
+
+// Parquet Model
+#[derive(
+    Allocative, Clone, Debug, Default, Deserialize, FieldCount, Serialize, ParquetRecordWriter,
+)]
+pub struct ParquetBlockEpilogueTransaction {
+    pub txn_version: i64,
+    pub block_height: i64,
+    pub epoch: i64,
+    pub block_gas_limit_reached: Option<bool>,
+    pub block_output_limit_reached: Option<bool>,
+    pub block_effective_block_gas_units: Option<i64>,
+    pub block_approx_output_size: Option<i64>,
+    #[allocative(skip)]
+    pub block_timestamp: chrono::NaiveDateTime,
+}
+
+impl NamedTable for ParquetBlockEpilogueTransaction {
+    const TABLE_NAME: &'static str = "block_epilogue_transactions";
+}
+
+impl HasVersion for ParquetBlockEpilogueTransaction {
+    fn version(&self) -> i64 {
+        self.txn_version
+    }
+}
+
+impl From<BlockEpilogueTransaction> for ParquetBlockEpilogueTransaction {
+    fn from(base_item: BlockEpilogueTransaction) -> Self {
+        ParquetBlockEpilogueTransaction {
+            txn_version: base_item.version,
+            block_height: base_item.block_height,
+            epoch: base_item.epoch,
+            block_gas_limit_reached: base_item.block_gas_limit_reached,
+            block_output_limit_reached: base_item.block_output_limit_reached,
+            block_effective_block_gas_units: base_item.block_effective_block_gas_units,
+            block_approx_output_size: base_item.block_approx_output_size,
+            block_timestamp: base_item.timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_indexer_processor_sdk::aptos_protos::transaction::v1::BlockEndInfo;
+
+    #[test]
+    fn test_from_block_epilogue_transaction() {
+        let timestamp = Timestamp {
+            seconds: 1,
+            nanos: 0,
+        };
+        let txn = ProtoBlockEpilogueTransaction {
+            block_end_info: Some(BlockEndInfo {
+                block_gas_limit_reached: true,
+                block_output_limit_reached: false,
+                block_effective_block_gas_units: 1000,
+                block_approx_output_size: 2000,
+            }),
+        };
+        let base =
+            BlockEpilogueTransaction::from_block_epilogue_transaction(&txn, 10, 2, 3, &timestamp);
+        assert_eq!(base.block_gas_limit_reached, Some(true));
+        assert_eq!(base.block_output_limit_reached, Some(false));
+        assert_eq!(base.block_effective_block_gas_units, Some(1000));
+        assert_eq!(base.block_approx_output_size, Some(2000));
+
+        let parquet = ParquetBlockEpilogueTransaction::from(base);
+        assert_eq!(parquet.txn_version, 10);
+        assert_eq!(parquet.block_height, 2);
+        assert_eq!(parquet.epoch, 3);
+
+        let base = BlockEpilogueTransaction::from_block_epilogue_transaction(
+            &ProtoBlockEpilogueTransaction::default(),
+            10,
+            2,
+            3,
+            &timestamp,
+        );
+        assert_eq!(base.block_gas_limit_reached, None);
+        assert_eq!(base.block_approx_output_size, None);
+    }
+}
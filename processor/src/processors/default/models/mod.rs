@@ -1,6 +1,9 @@
+pub mod block_epilogue_transactions;
 pub mod block_metadata_transactions;
+pub mod move_module_abis;
 pub mod move_modules;
 pub mod move_resources;
 pub mod table_items;
 pub mod transactions;
+pub mod validator_transactions;
 pub mod write_set_changes;
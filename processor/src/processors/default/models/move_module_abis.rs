@@ -0,0 +1,403 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(clippy::extra_unused_lifetimes)]
+
+use super::move_modules::MoveModule;
+use crate::schema::{move_module_functions, move_module_structs, move_module_upgrades};
+use aptos_indexer_processor_sdk::{
+    aptos_protos::transaction::v1::{
+        move_function::Visibility, MoveAbility, MoveStruct, WriteModule,
+    },
+    utils::convert::{sha3_256, standardize_address},
+};
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Base model for the move_module_functions table. One row per exposed function of a module
+/// at the version it was published or upgraded.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MoveModuleFunction {
+    pub txn_version: i64,
+    pub write_set_change_index: i64,
+    pub address: String,
+    pub module_name: String,
+    pub name: String,
+    pub visibility: String,
+    pub is_entry: bool,
+    pub generic_type_params: Value,
+    pub params: Value,
+    pub return_types: Value,
+}
+
+/// Base model for the move_module_structs table. One row per struct of a module at the version
+/// it was published or upgraded.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MoveModuleStruct {
+    pub txn_version: i64,
+    pub write_set_change_index: i64,
+    pub address: String,
+    pub module_name: String,
+    pub name: String,
+    pub is_native: bool,
+    pub abilities: Value,
+    pub generic_type_params: Value,
+    pub fields: Value,
+}
+
+/// Base model for the move_module_upgrades table. A row is recorded for every write that changes a
+/// module's bytecode, so the table holds the publish and every upgrade, including reverts to
+/// earlier bytecode, but not the unchanged modules republished with a package upgrade.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MoveModuleUpgrade {
+    pub address: String,
+    pub module_name: String,
+    pub bytecode_hash: String,
+    pub txn_version: i64,
+    pub write_set_change_index: i64,
+    pub block_height: i64,
+    pub block_timestamp: chrono::NaiveDateTime,
+}
+
+impl MoveModuleFunction {
+    pub fn from_write_module(
+        write_module: &WriteModule,
+        write_set_change_index: i64,
+        txn_version: i64,
+    ) -> Vec<Self> {
+        let abi = match write_module.data.as_ref().and_then(|d| d.abi.as_ref()) {
+            Some(abi) => abi,
+            None => return vec![],
+        };
+        let address = standardize_address(&abi.address);
+        abi.exposed_functions
+            .iter()
+            .map(|function| Self {
+                txn_version,
+                write_set_change_index,
+                address: address.clone(),
+                module_name: abi.name.clone(),
+                name: function.name.clone(),
+                visibility: visibility_to_string(function.visibility()).to_string(),
+                is_entry: function.is_entry,
+                generic_type_params: serde_json::to_value(&function.generic_type_params).unwrap(),
+                params: serde_json::to_value(&function.params).unwrap(),
+                return_types: serde_json::to_value(&function.r#return).unwrap(),
+            })
+            .collect()
+    }
+}
+
+impl MoveModuleStruct {
+    pub fn from_write_module(
+        write_module: &WriteModule,
+        write_set_change_index: i64,
+        txn_version: i64,
+    ) -> Vec<Self> {
+        let abi = match write_module.data.as_ref().and_then(|d| d.abi.as_ref()) {
+            Some(abi) => abi,
+            None => return vec![],
+        };
+        let address = standardize_address(&abi.address);
+        abi.structs
+            .iter()
+            .map(|move_struct| {
+                Self::from_move_struct(
+                    move_struct,
+                    &address,
+                    &abi.name,
+                    write_set_change_index,
+                    txn_version,
+                )
+            })
+            .collect()
+    }
+
+    fn from_move_struct(
+        move_struct: &MoveStruct,
+        address: &str,
+        module_name: &str,
+        write_set_change_index: i64,
+        txn_version: i64,
+    ) -> Self {
+        let abilities: Vec<&str> = move_struct.abilities().map(ability_to_string).collect();
+        Self {
+            txn_version,
+            write_set_change_index,
+            address: address.to_string(),
+            module_name: module_name.to_string(),
+            name: move_struct.name.clone(),
+            is_native: move_struct.is_native,
+            abilities: serde_json::to_value(abilities).unwrap(),
+            generic_type_params: serde_json::to_value(&move_struct.generic_type_params).unwrap(),
+            fields: serde_json::to_value(&move_struct.fields).unwrap(),
+        }
+    }
+}
+
+impl MoveModuleUpgrade {
+    /// Deleted modules and modules without bytecode don't produce an upgrade record.
+    pub fn from_move_module(move_module: &MoveModule) -> Option<Self> {
+        if move_module.is_deleted || move_module.bytecode.is_empty() {
+            return None;
+        }
+        Some(Self {
+            address: move_module.address.clone(),
+            module_name: move_module.name.clone(),
+            bytecode_hash: format!("0x{}", hex::encode(sha3_256(&move_module.bytecode))),
+            txn_version: move_module.txn_version,
+            write_set_change_index: move_module.write_set_change_index,
+            block_height: move_module.block_height,
+            block_timestamp: move_module.block_timestamp,
+        })
+    }
+
+    /// Drops the writes that keep the bytecode of the module's previous write. The upgrades must
+    /// be sorted by (address, module_name, txn_version).
+    pub fn dedup_unchanged(upgrades: &mut Vec<Self>) {
+        upgrades.dedup_by(|upgrade, previous| {
+            upgrade.address == previous.address
+                && upgrade.module_name == previous.module_name
+                && upgrade.bytecode_hash == previous.bytecode_hash
+        });
+    }
+}
+
+fn visibility_to_string(visibility: Visibility) -> &'static str {
+    match visibility {
+        Visibility::Public => "public",
+        Visibility::Friend => "friend",
+        Visibility::Private => "private",
+        Visibility::Unspecified => "unspecified",
+    }
+}
+
+fn ability_to_string(ability: MoveAbility) -> &'static str {
+    match ability {
+        MoveAbility::Copy => "copy",
+        MoveAbility::Drop => "drop",
+        MoveAbility::Store => "store",
+        MoveAbility::Key => "key",
+        MoveAbility::Unspecified => "unspecified",
+    }
+}
+
+// Postgres Models
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, write_set_change_index, name))]
+#[diesel(table_name = move_module_functions)]
+pub struct PostgresMoveModuleFunction {
+    pub transaction_version: i64,
+    pub write_set_change_index: i64,
+    pub address: String,
+    pub module_name: String,
+    pub name: String,
+    pub visibility: String,
+    pub is_entry: bool,
+    pub generic_type_params: Value,
+    pub params: Value,
+    pub return_types: Value,
+}
+
+impl From<MoveModuleFunction> for PostgresMoveModuleFunction {
+    fn from(base_item: MoveModuleFunction) -> Self {
+        PostgresMoveModuleFunction {
+            transaction_version: base_item.txn_version,
+            write_set_change_index: base_item.write_set_change_index,
+            address: base_item.address,
+            module_name: base_item.module_name,
+            name: base_item.name,
+            visibility: base_item.visibility,
+            is_entry: base_item.is_entry,
+            generic_type_params: base_item.generic_type_params,
+            params: base_item.params,
+            return_types: base_item.return_types,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, write_set_change_index, name))]
+#[diesel(table_name = move_module_structs)]
+pub struct PostgresMoveModuleStruct {
+    pub transaction_version: i64,
+    pub write_set_change_index: i64,
+    pub address: String,
+    pub module_name: String,
+    pub name: String,
+    pub is_native: bool,
+    pub abilities: Value,
+    pub generic_type_params: Value,
+    pub fields: Value,
+}
+
+impl From<MoveModuleStruct> for PostgresMoveModuleStruct {
+    fn from(base_item: MoveModuleStruct) -> Self {
+        PostgresMoveModuleStruct {
+            transaction_version: base_item.txn_version,
+            write_set_change_index: base_item.write_set_change_index,
+            address: base_item.address,
+            module_name: base_item.module_name,
+            name: base_item.name,
+            is_native: base_item.is_native,
+            abilities: base_item.abilities,
+            generic_type_params: base_item.generic_type_params,
+            fields: base_item.fields,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(address, module_name, transaction_version))]
+#[diesel(table_name = move_module_upgrades)]
+pub struct PostgresMoveModuleUpgrade {
+    pub address: String,
+    pub module_name: String,
+    pub bytecode_hash: String,
+    pub transaction_version: i64,
+    pub write_set_change_index: i64,
+    pub transaction_block_height: i64,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl From<MoveModuleUpgrade> for PostgresMoveModuleUpgrade {
+    fn from(base_item: MoveModuleUpgrade) -> Self {
+        PostgresMoveModuleUpgrade {
+            address: base_item.address,
+            module_name: base_item.module_name,
+            bytecode_hash: base_item.bytecode_hash,
+            transaction_version: base_item.txn_version,
+            write_set_change_index: base_item.write_set_change_index,
+            transaction_block_height: base_item.block_height,
+            transaction_timestamp: base_item.block_timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_indexer_processor_sdk::aptos_protos::transaction::v1::{
+        MoveFunction, MoveModule as MoveModulePB, MoveModuleBytecode,
+    };
+
+    fn write_module(bytecode: Vec<u8>) -> WriteModule {
+        WriteModule {
+            address: "0x1".to_string(),
+            data: Some(MoveModuleBytecode {
+                bytecode,
+                abi: Some(MoveModulePB {
+                    address: "0x1".to_string(),
+                    name: "coin".to_string(),
+                    exposed_functions: vec![MoveFunction {
+                        name: "transfer".to_string(),
+                        visibility: Visibility::Public as i32,
+                        is_entry: true,
+                        ..Default::default()
+                    }],
+                    structs: vec![MoveStruct {
+                        name: "Coin".to_string(),
+                        abilities: vec![MoveAbility::Store as i32],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn move_module(bytecode: Vec<u8>, txn_version: i64) -> MoveModule {
+        MoveModule::from_write_module(
+            &write_module(bytecode),
+            0,
+            txn_version,
+            1,
+            chrono::NaiveDateTime::default(),
+        )
+    }
+
+    #[test]
+    fn test_functions_and_structs_from_write_module() {
+        let functions = MoveModuleFunction::from_write_module(&write_module(vec![1]), 3, 10);
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].address, standardize_address("0x1"));
+        assert_eq!(functions[0].module_name, "coin");
+        assert_eq!(functions[0].name, "transfer");
+        assert_eq!(functions[0].visibility, "public");
+        assert!(functions[0].is_entry);
+
+        let structs = MoveModuleStruct::from_write_module(&write_module(vec![1]), 3, 10);
+        assert_eq!(structs.len(), 1);
+        assert_eq!(structs[0].name, "Coin");
+        assert_eq!(structs[0].abilities, serde_json::json!(["store"]));
+
+        let no_abi = WriteModule::default();
+        assert!(MoveModuleFunction::from_write_module(&no_abi, 3, 10).is_empty());
+        assert!(MoveModuleStruct::from_write_module(&no_abi, 3, 10).is_empty());
+    }
+
+    #[test]
+    fn test_upgrades_keep_reverts_to_earlier_bytecode() {
+        let mut upgrades: Vec<MoveModuleUpgrade> = [
+            (vec![1], 10),
+            (vec![1], 15),
+            (vec![2], 20),
+            (vec![1], 30),
+            (vec![1], 40),
+        ]
+        .into_iter()
+        .filter_map(|(bytecode, txn_version)| {
+            MoveModuleUpgrade::from_move_module(&move_module(bytecode, txn_version))
+        })
+        .collect();
+        assert_eq!(upgrades.len(), 5);
+
+        MoveModuleUpgrade::dedup_unchanged(&mut upgrades);
+        assert_eq!(upgrades[0].bytecode_hash, upgrades[2].bytecode_hash);
+        assert_ne!(upgrades[0].bytecode_hash, upgrades[1].bytecode_hash);
+        assert_eq!(
+            upgrades.iter().map(|u| u.txn_version).collect::<Vec<_>>(),
+            vec![10, 20, 30]
+        );
+    }
+
+    #[test]
+    fn test_dedup_unchanged_is_per_module() {
+        let mut other_module = move_module(vec![1], 15);
+        other_module.name = "aptos_coin".to_string();
+        let mut upgrades: Vec<MoveModuleUpgrade> = [
+            other_module,
+            move_module(vec![1], 10),
+            move_module(vec![1], 20),
+        ]
+        .iter()
+        .filter_map(MoveModuleUpgrade::from_move_module)
+        .collect();
+        upgrades.sort_by(|a, b| {
+            (&a.address, &a.module_name, a.txn_version).cmp(&(
+                &b.address,
+                &b.module_name,
+                b.txn_version,
+            ))
+        });
+
+        MoveModuleUpgrade::dedup_unchanged(&mut upgrades);
+        assert_eq!(
+            upgrades
+                .iter()
+                .map(|u| (u.module_name.as_str(), u.txn_version))
+                .collect::<Vec<_>>(),
+            vec![("aptos_coin", 15), ("coin", 10)]
+        );
+    }
+
+    #[test]
+    fn test_no_upgrade_without_bytecode() {
+        assert!(MoveModuleUpgrade::from_move_module(&move_module(vec![], 10)).is_none());
+        let mut deleted = move_module(vec![1], 10);
+        deleted.is_deleted = true;
+        assert!(MoveModuleUpgrade::from_move_module(&deleted).is_none());
+    }
+}
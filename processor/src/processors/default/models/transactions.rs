@@ -8,9 +8,11 @@
 use super::write_set_changes::{WriteSetChangeDetail, WriteSetChangeModel};
 use crate::{
     parquet_processors::parquet_utils::util::{HasVersion, NamedTable},
+    schema::transactions,
     utils::counters::PROCESSOR_UNKNOWN_TYPE_COUNT,
 };
 use allocative_derive::Allocative;
+use anyhow::Context;
 use aptos_indexer_processor_sdk::{
     aptos_protos::transaction::v1::{
         transaction::{TransactionType, TxnData},
@@ -21,6 +23,7 @@ use aptos_indexer_processor_sdk::{
         extract::{get_clean_payload, get_clean_writeset, get_payload_type},
     },
 };
+use bigdecimal::BigDecimal;
 use field_count::FieldCount;
 use parquet_derive::ParquetRecordWriter;
 use serde::{Deserialize, Serialize};
@@ -409,3 +412,55 @@ impl From<Transaction> for ParquetTransaction {
         }
     }
 }
+
+// Postgres Model
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(version))]
+#[diesel(table_name = transactions)]
+pub struct PostgresTransaction {
+    pub version: i64,
+    pub block_height: i64,
+    pub hash: String,
+    pub type_: String,
+    pub payload: Option<serde_json::Value>,
+    pub state_change_hash: String,
+    pub event_root_hash: String,
+    pub state_checkpoint_hash: Option<String>,
+    pub gas_used: BigDecimal,
+    pub success: bool,
+    pub vm_status: String,
+    pub accumulator_root_hash: String,
+    pub num_events: i64,
+    pub num_write_set_changes: i64,
+    pub epoch: i64,
+    pub payload_type: Option<String>,
+}
+
+impl TryFrom<ParquetTransaction> for PostgresTransaction {
+    type Error = anyhow::Error;
+
+    fn try_from(parquet_item: ParquetTransaction) -> anyhow::Result<Self> {
+        Ok(Self {
+            version: parquet_item.txn_version,
+            block_height: parquet_item.block_height,
+            hash: parquet_item.txn_hash,
+            type_: parquet_item.txn_type,
+            payload: parquet_item
+                .payload
+                .map(|payload| serde_json::from_str(&payload))
+                .transpose()
+                .context("Failed to parse payload")?,
+            state_change_hash: parquet_item.state_change_hash,
+            event_root_hash: parquet_item.event_root_hash,
+            state_checkpoint_hash: parquet_item.state_checkpoint_hash,
+            gas_used: BigDecimal::from(parquet_item.gas_used),
+            success: parquet_item.success,
+            vm_status: parquet_item.vm_status,
+            accumulator_root_hash: parquet_item.accumulator_root_hash,
+            num_events: parquet_item.num_events,
+            num_write_set_changes: parquet_item.num_write_set_changes,
+            epoch: parquet_item.epoch,
+            payload_type: parquet_item.payload_type,
+        })
+    }
+}
@@ -0,0 +1,218 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use crate::{
+    parquet_processors::parquet_utils::util::{HasVersion, NamedTable},
+    schema::validator_transactions,
+};
+use allocative_derive::Allocative;
+use aptos_indexer_processor_sdk::{
+    aptos_indexer_transaction_stream::utils::time::parse_timestamp,
+    aptos_protos::{
+        transaction::v1::{
+            validator_transaction::ValidatorTransactionType,
+            ValidatorTransaction as ProtoValidatorTransaction,
+        },
+        util::timestamp::Timestamp,
+    },
+    utils::convert::standardize_address,
+};
+use field_count::FieldCount;
+use parquet_derive::ParquetRecordWriter;
+use serde::{Deserialize, Serialize};
+
+const OBSERVED_JWK_UPDATE: &str = "observed_jwk_update";
+const DKG_UPDATE: &str = "dkg_update";
+const UNKNOWN: &str = "unknown";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ValidatorTransaction {
+    pub version: i64,
+    pub block_height: i64,
+    pub epoch: i64,
+    pub validator_transaction_type: String,
+    pub jwk_issuer: Option<String>,
+    pub jwk_version: Option<i64>,
+    pub num_jwks: Option<i64>,
+    pub dkg_epoch: Option<i64>,
+    pub dkg_author: Option<String>,
+    pub timestamp: chrono::NaiveDateTime,
+}
+
+impl ValidatorTransaction {
+    pub fn from_validator_transaction(
+        txn: &ProtoValidatorTransaction,
+        version: i64,
+        block_height: i64,
+        epoch: i64,
+        timestamp: &Timestamp,
+    ) -> Self {
+        let mut validator_transaction = Self {
+            version,
+            block_height,
+            epoch,
+            validator_transaction_type: UNKNOWN.to_string(),
+            jwk_issuer: None,
+            jwk_version: None,
+            num_jwks: None,
+            dkg_epoch: None,
+            dkg_author: None,
+            timestamp: parse_timestamp(timestamp, version).naive_utc(),
+        };
+        match txn.validator_transaction_type.as_ref() {
+            Some(ValidatorTransactionType::ObservedJwkUpdate(jwk_update)) => {
+                validator_transaction.validator_transaction_type = OBSERVED_JWK_UPDATE.to_string();
+                if let Some(update) = jwk_update
+                    .quorum_certified_update
+                    .as_ref()
+                    .and_then(|quorum_certified_update| quorum_certified_update.update.as_ref())
+                {
+                    validator_transaction.jwk_issuer = Some(update.issuer.clone());
+                    validator_transaction.jwk_version = Some(update.version as i64);
+                    validator_transaction.num_jwks = Some(update.jwks.len() as i64);
+                }
+            },
+            Some(ValidatorTransactionType::DkgUpdate(dkg_update)) => {
+                validator_transaction.validator_transaction_type = DKG_UPDATE.to_string();
+                if let Some(dkg_transcript) = dkg_update.dkg_transcript.as_ref() {
+                    validator_transaction.dkg_epoch = Some(dkg_transcript.epoch as i64);
+                    validator_transaction.dkg_author =
+                        Some(standardize_address(dkg_transcript.author.as_str()));
+                }
+            },
+            None => {
+                tracing::warn!(
+                    transaction_version = version,
+                    "Validator transaction type doesn't exist",
+                );
+            },
+        }
+        validator_transaction
+    }
+}
+
+// Prevent conflicts with other things named `Transaction`
+pub type ValidatorTransactionModel = ValidatorTransaction;
+
+// Postgres Model
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(version))]
+#[diesel(table_name = validator_transactions)]
+pub struct PostgresValidatorTransaction {
+    pub version: i64,
+    pub block_height: i64,
+    pub epoch: i64,
+    pub validator_transaction_type: String,
+    pub jwk_issuer: Option<String>,
+    pub jwk_version: Option<i64>,
+    pub num_jwks: Option<i64>,
+    pub dkg_epoch: Option<i64>,
+    pub dkg_author: Option<String>,
+    pub timestamp: chrono::NaiveDateTime,
+}
+
+impl From<ValidatorTransaction> for PostgresValidatorTransaction {
+    fn from(base_item: ValidatorTransaction) -> Self {
+        PostgresValidatorTransaction {
+            version: base_item.version,
+            block_height: base_item.block_height,
+            epoch: base_item.epoch,
+            validator_transaction_type: base_item.validator_transaction_type,
+            jwk_issuer: base_item.jwk_issuer,
+            jwk_version: base_item.jwk_version,
+            num_jwks: base_item.num_jwks,
+            dkg_epoch: base_item.dkg_epoch,
+            dkg_author: base_item.dkg_author,
+            timestamp: base_item.timestamp,
+        }
+    }
+}
+
+// Parquet Model
+#[derive(
+    Allocative, Clone, Debug, Default, Deserialize, FieldCount, Serialize, ParquetRecordWriter,
+)]
+pub struct ParquetValidatorTransaction {
+    pub txn_version: i64,
+    pub block_height: i64,
+    pub epoch: i64,
+    pub validator_transaction_type: String,
+    pub jwk_issuer: Option<String>,
+    pub jwk_version: Option<i64>,
+    pub num_jwks: Option<i64>,
+    pub dkg_epoch: Option<i64>,
+    pub dkg_author: Option<String>,
+    #[allocative(skip)]
+    pub block_timestamp: chrono::NaiveDateTime,
+}
+
+impl NamedTable for ParquetValidatorTransaction {
+    const TABLE_NAME: &'static str = "validator_transactions";
+}
+
+impl HasVersion for ParquetValidatorTransaction {
+    fn version(&self) -> i64 {
+        self.txn_version
+    }
+}
+
+impl From<ValidatorTransaction> for ParquetValidatorTransaction {
+    fn from(base_item: ValidatorTransaction) -> Self {
+        ParquetValidatorTransaction {
+            txn_version: base_item.version,
+            block_height: base_item.block_height,
+            epoch: base_item.epoch,
+            validator_transaction_type: base_item.validator_transaction_type,
+            jwk_issuer: base_item.jwk_issuer,
+            jwk_version: base_item.jwk_version,
+            num_jwks: base_item.num_jwks,
+            dkg_epoch: base_item.dkg_epoch,
+            dkg_author: base_item.dkg_author,
+            block_timestamp: base_item.timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_indexer_processor_sdk::aptos_protos::transaction::v1::validator_transaction::{
+        dkg_update::DkgTranscript, DkgUpdate,
+    };
+
+    #[test]
+    fn test_from_dkg_update() {
+        let timestamp = Timestamp {
+            seconds: 1,
+            nanos: 0,
+        };
+        let txn = ProtoValidatorTransaction {
+            validator_transaction_type: Some(ValidatorTransactionType::DkgUpdate(DkgUpdate {
+                dkg_transcript: Some(DkgTranscript {
+                    epoch: 7,
+                    author: "0x1".to_string(),
+                    ..Default::default()
+                }),
+            })),
+            ..Default::default()
+        };
+        let base = ValidatorTransaction::from_validator_transaction(&txn, 10, 2, 6, &timestamp);
+        assert_eq!(base.validator_transaction_type, DKG_UPDATE);
+        assert_eq!(base.dkg_epoch, Some(7));
+        assert_eq!(base.dkg_author, Some(standardize_address("0x1")));
+        assert_eq!(base.jwk_issuer, None);
+
+        let base = ValidatorTransaction::from_validator_transaction(
+            &ProtoValidatorTransaction::default(),
+            10,
+            2,
+            6,
+            &timestamp,
+        );
+        assert_eq!(base.validator_transaction_type, UNKNOWN);
+    }
+}
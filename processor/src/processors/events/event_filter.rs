@@ -0,0 +1,283 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    processors::events::{event_registry::EventTypePattern, events_model::Event},
+    utils::counters::EVENTS_FILTERED_COUNT,
+};
+use anyhow::Context;
+use aptos_indexer_processor_sdk::{
+    aptos_protos::transaction::v1::{transaction::TxnData, Transaction},
+    utils::{convert::standardize_address, extract::get_entry_function_from_user_request},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Filters applied to events before they are stored. Every include list that is set must match
+/// and no exclude list may match for an event to be kept.
+///
+/// Event type and entry function filters use the same patterns as the event registry: exact
+/// (`0xabc::module::Name`), module wildcard (`0xabc::module::*`) or address (`0xabc::*`).
+///
+/// Example:
+/// ```yaml
+/// event_filter:
+///   include_event_types: ["0xabc::*"]
+///   exclude_event_types: ["0x1::transaction_fee::FeeStatement"]
+///   only_successful_transactions: true
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventFilterConfig {
+    #[serde(default)]
+    pub include_event_types: Vec<String>,
+    #[serde(default)]
+    pub exclude_event_types: Vec<String>,
+    #[serde(default)]
+    pub include_account_addresses: HashSet<String>,
+    #[serde(default)]
+    pub exclude_account_addresses: HashSet<String>,
+    /// Matched against the entry function of the user transaction that emitted the event.
+    /// Events from transactions without an entry function never match.
+    #[serde(default)]
+    pub include_entry_functions: Vec<String>,
+    #[serde(default)]
+    pub exclude_entry_functions: Vec<String>,
+    #[serde(default)]
+    pub only_successful_transactions: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+enum FilterReason {
+    TransactionFailed,
+    EntryFunction,
+    EventType,
+    AccountAddress,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct EventFilter {
+    include_event_types: Vec<EventTypePattern>,
+    exclude_event_types: Vec<EventTypePattern>,
+    include_account_addresses: HashSet<String>,
+    exclude_account_addresses: HashSet<String>,
+    include_entry_functions: Vec<EventTypePattern>,
+    exclude_entry_functions: Vec<EventTypePattern>,
+    only_successful_transactions: bool,
+}
+
+fn parse_patterns(patterns: &[String], field: &str) -> anyhow::Result<Vec<EventTypePattern>> {
+    patterns
+        .iter()
+        .map(|p| p.parse().with_context(|| format!("Invalid {field}")))
+        .collect()
+}
+
+impl TryFrom<&EventFilterConfig> for EventFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(config: &EventFilterConfig) -> anyhow::Result<Self> {
+        let standardize = |addresses: &HashSet<String>| {
+            addresses
+                .iter()
+                .map(|a| standardize_address(a))
+                .collect::<HashSet<String>>()
+        };
+        Ok(Self {
+            include_event_types: parse_patterns(
+                &config.include_event_types,
+                "include_event_types",
+            )?,
+            exclude_event_types: parse_patterns(
+                &config.exclude_event_types,
+                "exclude_event_types",
+            )?,
+            include_account_addresses: standardize(&config.include_account_addresses),
+            exclude_account_addresses: standardize(&config.exclude_account_addresses),
+            include_entry_functions: parse_patterns(
+                &config.include_entry_functions,
+                "include_entry_functions",
+            )?,
+            exclude_entry_functions: parse_patterns(
+                &config.exclude_entry_functions,
+                "exclude_entry_functions",
+            )?,
+            only_successful_transactions: config.only_successful_transactions,
+        })
+    }
+}
+
+impl EventFilter {
+    pub fn is_empty(&self) -> bool {
+        self.include_event_types.is_empty()
+            && self.exclude_event_types.is_empty()
+            && self.include_account_addresses.is_empty()
+            && self.exclude_account_addresses.is_empty()
+            && self.include_entry_functions.is_empty()
+            && self.exclude_entry_functions.is_empty()
+            && !self.only_successful_transactions
+    }
+
+    /// Drops the events of a transaction that don't pass the filter and records why.
+    pub fn filter(
+        &self,
+        transaction: &Transaction,
+        events: Vec<Event>,
+        processor_name: &str,
+    ) -> Vec<Event> {
+        if self.is_empty() || events.is_empty() {
+            return events;
+        }
+
+        let is_success = transaction.info.as_ref().map_or(true, |info| info.success);
+        let entry_function = match transaction.txn_data.as_ref() {
+            Some(TxnData::User(inner)) => inner
+                .request
+                .as_ref()
+                .and_then(get_entry_function_from_user_request),
+            _ => None,
+        };
+
+        // Transaction level checks apply to every event in the transaction
+        let transaction_reason = self.check_transaction(is_success, entry_function.as_deref());
+        if let Some(reason) = transaction_reason {
+            Self::record_dropped(processor_name, reason, events.len());
+            return vec![];
+        }
+
+        let mut kept = Vec::with_capacity(events.len());
+        for event in events {
+            match self.check_event(&event) {
+                Some(reason) => Self::record_dropped(processor_name, reason, 1),
+                None => kept.push(event),
+            }
+        }
+        kept
+    }
+
+    fn check_transaction(
+        &self,
+        is_success: bool,
+        entry_function: Option<&str>,
+    ) -> Option<FilterReason> {
+        if self.only_successful_transactions && !is_success {
+            return Some(FilterReason::TransactionFailed);
+        }
+        let entry_function_matches = |patterns: &[EventTypePattern]| {
+            entry_function.is_some_and(|f| patterns.iter().any(|p| p.matches(f)))
+        };
+        if (!self.include_entry_functions.is_empty()
+            && !entry_function_matches(&self.include_entry_functions))
+            || entry_function_matches(&self.exclude_entry_functions)
+        {
+            return Some(FilterReason::EntryFunction);
+        }
+        None
+    }
+
+    fn check_event(&self, event: &Event) -> Option<FilterReason> {
+        let type_matches =
+            |patterns: &[EventTypePattern]| patterns.iter().any(|p| p.matches(&event.indexed_type));
+        if (!self.include_event_types.is_empty() && !type_matches(&self.include_event_types))
+            || type_matches(&self.exclude_event_types)
+        {
+            return Some(FilterReason::EventType);
+        }
+        if (!self.include_account_addresses.is_empty()
+            && !self
+                .include_account_addresses
+                .contains(&event.account_address))
+            || self
+                .exclude_account_addresses
+                .contains(&event.account_address)
+        {
+            return Some(FilterReason::AccountAddress);
+        }
+        None
+    }
+
+    fn record_dropped(processor_name: &str, reason: FilterReason, count: usize) {
+        EVENTS_FILTERED_COUNT
+            .with_label_values(&[processor_name, reason.into()])
+            .inc_by(count as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_event(type_: &str, account_address: &str) -> Event {
+        Event {
+            sequence_number: 0,
+            creation_number: 0,
+            account_address: standardize_address(account_address),
+            transaction_version: 1,
+            transaction_block_height: 1,
+            type_: type_.to_string(),
+            data: "{}".to_string(),
+            event_index: 0,
+            indexed_type: type_.to_string(),
+            block_timestamp: None,
+            type_tag_bytes: None,
+            total_bytes: None,
+        }
+    }
+
+    #[test]
+    fn test_event_type_and_address_filters() {
+        let filter = EventFilter::try_from(&EventFilterConfig {
+            include_event_types: vec!["0xabc::*".to_string()],
+            exclude_event_types: vec!["0xabc::market::Heartbeat".to_string()],
+            exclude_account_addresses: HashSet::from(["0xdead".to_string()]),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(
+            filter.check_event(&test_event("0xabc::market::Listed", "0x1")),
+            None
+        );
+        assert_eq!(
+            filter.check_event(&test_event("0xabc::market::Heartbeat", "0x1")),
+            Some(FilterReason::EventType)
+        );
+        assert_eq!(
+            filter.check_event(&test_event("0x1::coin::CoinDeposit", "0x1")),
+            Some(FilterReason::EventType)
+        );
+        assert_eq!(
+            filter.check_event(&test_event("0xabc::market::Listed", "0xdead")),
+            Some(FilterReason::AccountAddress)
+        );
+    }
+
+    #[test]
+    fn test_transaction_filters() {
+        let filter = EventFilter::try_from(&EventFilterConfig {
+            include_entry_functions: vec!["0xabc::market::*".to_string()],
+            only_successful_transactions: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(
+            filter.check_transaction(true, Some("0xabc::market::list")),
+            None
+        );
+        assert_eq!(
+            filter.check_transaction(false, Some("0xabc::market::list")),
+            Some(FilterReason::TransactionFailed)
+        );
+        assert_eq!(
+            filter.check_transaction(true, Some("0x1::aptos_account::transfer")),
+            Some(FilterReason::EntryFunction)
+        );
+        assert_eq!(
+            filter.check_transaction(true, None),
+            Some(FilterReason::EntryFunction)
+        );
+        assert!(EventFilter::default().is_empty());
+    }
+}
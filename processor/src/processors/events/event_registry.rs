@@ -0,0 +1,760 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Routes events to dedicated tables based on their type.
+//!
+//! Each route matches event types by exact type, by module (`0xabc::module::*`) or by address
+//! (`0xabc::*`) and writes matching events into its own table, optionally projecting fields of
+//! the event data into typed columns. Tables are created by the events processor on startup and
+//! gain a column for every projection added to their route later on.
+
+use crate::{processors::events::events_model::Event, schema, utils::table_flags::TableFlags};
+use anyhow::{bail, Context};
+use aptos_indexer_processor_sdk::{
+    postgres::utils::database::{execute_with_better_error, ArcDbPool},
+    utils::convert::standardize_address,
+};
+use bigdecimal::BigDecimal;
+use diesel::{
+    pg::Pg,
+    query_builder::{BoxedSqlQuery, SqlQuery},
+    sql_types::{BigInt, Bool, Jsonb, Nullable, Numeric, Text},
+    QueryableByName,
+};
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{collections::HashSet, str::FromStr};
+
+/// Postgres limits a single statement to 65535 bind parameters.
+const MAX_BIND_PARAMS: usize = u16::MAX as usize;
+/// Postgres truncates identifiers longer than this.
+const MAX_IDENTIFIER_LENGTH: usize = 63;
+/// Columns every routed table has, in insertion order.
+const BASE_COLUMNS: [&str; 8] = [
+    "transaction_version",
+    "event_index",
+    "account_address",
+    "creation_number",
+    "sequence_number",
+    "transaction_block_height",
+    "type",
+    "data",
+];
+/// Types of the base columns and `inserted_at`, as reported by `information_schema.columns`.
+const BASE_COLUMN_TYPES: [(&str, &str); 9] = [
+    ("transaction_version", "bigint"),
+    ("event_index", "bigint"),
+    ("account_address", "character varying"),
+    ("creation_number", "bigint"),
+    ("sequence_number", "bigint"),
+    ("transaction_block_height", "bigint"),
+    ("type", "text"),
+    ("data", "jsonb"),
+    ("inserted_at", "timestamp without time zone"),
+];
+/// Tables of the indexer that aren't declared in the schema.
+const RESERVED_TABLE_NAMES: [&str; 1] = ["__diesel_schema_migrations"];
+
+/// A single entry of the event registry.
+///
+/// Example:
+/// ```yaml
+/// event_registry:
+///   - event_type: "0xabc::marketplace::*"
+///     table_name: "marketplace_events"
+///     projections:
+///       - field: "listing.price"
+///         column: "price"
+///         column_type: "numeric"
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventRouteConfig {
+    /// Exact event type (`0xabc::module::Event`), module wildcard (`0xabc::module::*`) or
+    /// address prefix (`0xabc::*`).
+    pub event_type: String,
+    pub table_name: String,
+    #[serde(default)]
+    pub projections: Vec<FieldProjection>,
+}
+
+/// Projects a field of the event's JSON data into a typed column.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldProjection {
+    /// Dot separated path into the event data, e.g. `listing.price`.
+    pub field: String,
+    pub column: String,
+    pub column_type: ProjectedColumnType,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectedColumnType {
+    Text,
+    BigInt,
+    Numeric,
+    Boolean,
+    Jsonb,
+}
+
+impl ProjectedColumnType {
+    fn sql_type(&self) -> &'static str {
+        match self {
+            ProjectedColumnType::Text => "TEXT",
+            ProjectedColumnType::BigInt => "BIGINT",
+            ProjectedColumnType::Numeric => "NUMERIC",
+            ProjectedColumnType::Boolean => "BOOLEAN",
+            ProjectedColumnType::Jsonb => "JSONB",
+        }
+    }
+
+    fn information_schema_type(&self) -> &'static str {
+        match self {
+            ProjectedColumnType::Text => "text",
+            ProjectedColumnType::BigInt => "bigint",
+            ProjectedColumnType::Numeric => "numeric",
+            ProjectedColumnType::Boolean => "boolean",
+            ProjectedColumnType::Jsonb => "jsonb",
+        }
+    }
+
+    /// Move serializes u64 and larger integers as strings, so numbers are accepted in either
+    /// form. Values that can't be converted are stored as null.
+    fn project(&self, value: Option<&Value>) -> ProjectedValue {
+        let value = value.filter(|v| !v.is_null());
+        match self {
+            ProjectedColumnType::Text => ProjectedValue::Text(value.map(|v| match v {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })),
+            ProjectedColumnType::BigInt => ProjectedValue::BigInt(value.and_then(|v| match v {
+                Value::String(s) => s.parse::<i64>().ok(),
+                other => other.as_i64(),
+            })),
+            ProjectedColumnType::Numeric => ProjectedValue::Numeric(value.and_then(|v| match v {
+                Value::String(s) => BigDecimal::from_str(s).ok(),
+                Value::Number(n) => BigDecimal::from_str(&n.to_string()).ok(),
+                _ => None,
+            })),
+            ProjectedColumnType::Boolean => ProjectedValue::Boolean(value.and_then(|v| match v {
+                Value::String(s) => s.parse::<bool>().ok(),
+                other => other.as_bool(),
+            })),
+            ProjectedColumnType::Jsonb => ProjectedValue::Jsonb(value.cloned()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum ProjectedValue {
+    Text(Option<String>),
+    BigInt(Option<i64>),
+    Numeric(Option<BigDecimal>),
+    Boolean(Option<bool>),
+    Jsonb(Option<Value>),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EventTypePattern {
+    Exact {
+        address: String,
+        module: String,
+        name: String,
+    },
+    Module {
+        address: String,
+        module: String,
+    },
+    Address {
+        address: String,
+    },
+}
+
+impl FromStr for EventTypePattern {
+    type Err = anyhow::Error;
+
+    fn from_str(pattern: &str) -> anyhow::Result<Self> {
+        let parts: Vec<&str> = pattern.trim().splitn(3, "::").collect();
+        let address = match parts.first() {
+            Some(address) if address.starts_with("0x") => standardize_address(address),
+            _ => bail!(
+                "Event type pattern '{}' must start with an address",
+                pattern
+            ),
+        };
+        match parts.as_slice() {
+            [_] | [_, "*"] => Ok(EventTypePattern::Address { address }),
+            [_, module, "*"] if !module.is_empty() => Ok(EventTypePattern::Module {
+                address,
+                module: module.to_string(),
+            }),
+            [_, module, name] if !module.is_empty() && !name.is_empty() && !name.contains('*') => {
+                Ok(EventTypePattern::Exact {
+                    address,
+                    module: module.to_string(),
+                    name: name.to_string(),
+                })
+            },
+            _ => bail!("Invalid event type pattern '{}'", pattern),
+        }
+    }
+}
+
+impl EventTypePattern {
+    pub fn matches(&self, event_type: &str) -> bool {
+        let (address, module, name) = match split_event_type(event_type) {
+            Some(parts) => parts,
+            None => return false,
+        };
+        match self {
+            EventTypePattern::Address { address: a } => *a == address,
+            EventTypePattern::Module {
+                address: a,
+                module: m,
+            } => *a == address && m == module,
+            EventTypePattern::Exact {
+                address: a,
+                module: m,
+                name: n,
+            } => {
+                // Without explicit type arguments the pattern matches every instantiation
+                let name = if n.contains('<') {
+                    name
+                } else {
+                    name.split('<').next().unwrap_or(name)
+                };
+                *a == address && m == module && n == name
+            },
+        }
+    }
+}
+
+/// Splits `0x1::coin::CoinDeposit<0x1::aptos_coin::AptosCoin>` into its standardized address,
+/// module and struct name (type arguments included).
+fn split_event_type(event_type: &str) -> Option<(String, &str, &str)> {
+    let mut parts = event_type.splitn(3, "::");
+    let address = parts.next()?;
+    let module = parts.next()?;
+    let name = parts.next()?;
+    Some((standardize_address(address), module, name))
+}
+
+fn validate_identifier(identifier: &str) -> anyhow::Result<()> {
+    let valid = !identifier.is_empty()
+        && identifier.len() <= MAX_IDENTIFIER_LENGTH
+        && identifier
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && identifier
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        bail!(
+            "Invalid identifier '{}': expected lowercase letters, digits and underscores, at most {} characters",
+            identifier,
+            MAX_IDENTIFIER_LENGTH
+        );
+    }
+    Ok(())
+}
+
+/// Route tables must not be one of the indexer's own tables, which would otherwise be written
+/// into by the route.
+fn validate_table_name(table_name: &str) -> anyhow::Result<()> {
+    validate_identifier(table_name)?;
+    if RESERVED_TABLE_NAMES.contains(&table_name)
+        || schema::TABLE_NAMES.contains(&table_name)
+        || TableFlags::from_name(&table_name.to_uppercase()).is_some()
+    {
+        bail!(
+            "Table '{}' belongs to the indexer and can't be used by an event route",
+            table_name
+        );
+    }
+    Ok(())
+}
+
+/// A column of an existing route table.
+#[derive(Clone, Debug, QueryableByName)]
+pub struct TableColumn {
+    #[diesel(sql_type = Text)]
+    pub column_name: String,
+    #[diesel(sql_type = Text)]
+    pub data_type: String,
+    #[diesel(sql_type = Bool)]
+    pub is_nullable: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct EventRoute {
+    pub pattern: EventTypePattern,
+    pub table_name: String,
+    pub projections: Vec<FieldProjection>,
+}
+
+impl TryFrom<&EventRouteConfig> for EventRoute {
+    type Error = anyhow::Error;
+
+    fn try_from(config: &EventRouteConfig) -> anyhow::Result<Self> {
+        let pattern = config.event_type.parse::<EventTypePattern>()?;
+        validate_table_name(&config.table_name)?;
+        let mut columns: Vec<&str> = BASE_COLUMNS.to_vec();
+        columns.push("inserted_at");
+        for projection in &config.projections {
+            validate_identifier(&projection.column)
+                .with_context(|| format!("In route for table '{}'", config.table_name))?;
+            if columns.contains(&projection.column.as_str()) {
+                bail!(
+                    "Duplicate column '{}' in route for table '{}'",
+                    projection.column,
+                    config.table_name
+                );
+            }
+            columns.push(projection.column.as_str());
+        }
+        Ok(Self {
+            pattern,
+            table_name: config.table_name.clone(),
+            projections: config.projections.clone(),
+        })
+    }
+}
+
+impl EventRoute {
+    pub fn create_table_sql(&self) -> String {
+        let projected_columns: String = self
+            .projections
+            .iter()
+            .map(|p| format!("  {} {},\n", p.column, p.column_type.sql_type()))
+            .collect();
+        format!(
+            "CREATE TABLE IF NOT EXISTS {table} (\n  transaction_version BIGINT NOT NULL,\n  event_index BIGINT NOT NULL,\n  account_address VARCHAR(66) NOT NULL,\n  creation_number BIGINT NOT NULL,\n  sequence_number BIGINT NOT NULL,\n  transaction_block_height BIGINT NOT NULL,\n  type TEXT NOT NULL,\n  data JSONB NOT NULL,\n{projected_columns}  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),\n  PRIMARY KEY (transaction_version, event_index)\n)",
+            table = self.table_name,
+        )
+    }
+
+    /// Statements adding the projected columns missing from the existing table. Fails when the
+    /// table isn't a table of this route, i.e. a column has a different type or the table has
+    /// required columns the route doesn't write.
+    pub fn alter_table_sql(&self, existing_columns: &[TableColumn]) -> anyhow::Result<Vec<String>> {
+        let find = |name: &str| existing_columns.iter().find(|c| c.column_name == name);
+        let expected_columns = BASE_COLUMN_TYPES.iter().copied().chain(
+            self.projections
+                .iter()
+                .map(|p| (p.column.as_str(), p.column_type.information_schema_type())),
+        );
+        let mut statements = vec![];
+        for (name, data_type) in expected_columns {
+            match find(name) {
+                Some(column) if column.data_type == data_type => {},
+                Some(column) => bail!(
+                    "Column '{}' of table '{}' is {} but the event route expects {}",
+                    name,
+                    self.table_name,
+                    column.data_type,
+                    data_type
+                ),
+                None if BASE_COLUMN_TYPES.iter().any(|(base, _)| *base == name) => bail!(
+                    "Table '{}' is missing column '{}' and isn't an event route table",
+                    self.table_name,
+                    name
+                ),
+                None => {
+                    let projection = self.projections.iter().find(|p| p.column == name).unwrap();
+                    statements.push(format!(
+                        "ALTER TABLE {} ADD COLUMN IF NOT EXISTS {} {}",
+                        self.table_name,
+                        projection.column,
+                        projection.column_type.sql_type()
+                    ));
+                },
+            }
+        }
+        // Columns of projections removed from the route are left in place, they are nullable
+        if let Some(column) = existing_columns.iter().find(|c| {
+            !c.is_nullable
+                && !BASE_COLUMN_TYPES
+                    .iter()
+                    .any(|(base, _)| *base == c.column_name)
+        }) {
+            bail!(
+                "Table '{}' has required column '{}' the event route doesn't write",
+                self.table_name,
+                column.column_name
+            );
+        }
+        Ok(statements)
+    }
+
+    fn project(&self, event: &Event) -> Option<RoutedEvent> {
+        let data: Value = serde_json::from_str(&event.data).ok()?;
+        let projected_values = self
+            .projections
+            .iter()
+            .map(|p| {
+                let value = p
+                    .field
+                    .split('.')
+                    .try_fold(&data, |value, key| value.get(key));
+                p.column_type.project(value)
+            })
+            .collect();
+        Some(RoutedEvent {
+            table_name: self.table_name.clone(),
+            projected_columns: self.projections.iter().map(|p| p.column.clone()).collect(),
+            transaction_version: event.transaction_version,
+            event_index: event.event_index,
+            account_address: event.account_address.clone(),
+            creation_number: event.creation_number,
+            sequence_number: event.sequence_number,
+            transaction_block_height: event.transaction_block_height,
+            type_: event.type_.clone(),
+            data,
+            projected_values,
+        })
+    }
+}
+
+/// The compiled set of routes. An event is written to every route it matches.
+#[derive(Clone, Debug, Default)]
+pub struct EventRegistry {
+    routes: Vec<EventRoute>,
+}
+
+impl EventRegistry {
+    pub fn new(configs: &[EventRouteConfig]) -> anyhow::Result<Self> {
+        let routes = configs
+            .iter()
+            .map(EventRoute::try_from)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let mut seen = HashSet::new();
+        for route in &routes {
+            if !seen.insert(route.table_name.as_str()) {
+                bail!(
+                    "Table '{}' is used by more than one route",
+                    route.table_name
+                );
+            }
+        }
+        Ok(Self { routes })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    pub fn route(&self, event: &Event) -> Vec<RoutedEvent> {
+        self.routes
+            .iter()
+            .filter(|route| route.pattern.matches(&event.type_))
+            .filter_map(|route| route.project(event))
+            .collect()
+    }
+
+    /// Creates the destination table of every route if it doesn't exist yet and adds the
+    /// columns of new projections to existing ones.
+    pub async fn create_tables(&self, db_pool: ArcDbPool) -> anyhow::Result<()> {
+        for route in &self.routes {
+            execute_with_better_error(db_pool.clone(), diesel::sql_query(route.create_table_sql()))
+                .await
+                .with_context(|| format!("Failed to create table '{}'", route.table_name))?;
+            let existing_columns: Vec<TableColumn> = {
+                let mut conn = db_pool.get().await?;
+                diesel::sql_query(
+                    "SELECT column_name::TEXT, data_type::TEXT, is_nullable = 'YES' AS is_nullable \
+                     FROM information_schema.columns \
+                     WHERE table_schema = current_schema() AND table_name = $1",
+                )
+                .bind::<Text, _>(&route.table_name)
+                .load(&mut conn)
+                .await?
+            };
+            for statement in route.alter_table_sql(&existing_columns)? {
+                execute_with_better_error(db_pool.clone(), diesel::sql_query(statement))
+                    .await
+                    .with_context(|| format!("Failed to alter table '{}'", route.table_name))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Number of rows per insert statement for the given route table.
+    pub fn chunk_size(&self, table_name: &str) -> usize {
+        let num_columns = self
+            .routes
+            .iter()
+            .find(|r| r.table_name == table_name)
+            .map_or(BASE_COLUMNS.len(), |r| {
+                BASE_COLUMNS.len() + r.projections.len()
+            });
+        MAX_BIND_PARAMS / num_columns
+    }
+}
+
+/// An event destined for a route table, carrying the projected column values.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RoutedEvent {
+    pub table_name: String,
+    pub projected_columns: Vec<String>,
+    pub transaction_version: i64,
+    pub event_index: i64,
+    pub account_address: String,
+    pub creation_number: i64,
+    pub sequence_number: i64,
+    pub transaction_block_height: i64,
+    pub type_: String,
+    pub data: Value,
+    pub projected_values: Vec<ProjectedValue>,
+}
+
+/// Builds the insert for a chunk of routed events. All items must belong to the same route,
+/// which the storer guarantees by grouping on table name.
+pub fn insert_routed_events_query(
+    items_to_insert: Vec<RoutedEvent>,
+) -> BoxedSqlQuery<'static, Pg, SqlQuery> {
+    let first = items_to_insert
+        .first()
+        .expect("Routed events insert requires at least one item");
+    let columns: Vec<&str> = BASE_COLUMNS
+        .iter()
+        .copied()
+        .chain(first.projected_columns.iter().map(String::as_str))
+        .collect();
+    let num_columns = columns.len();
+    let rows = (0..items_to_insert.len())
+        .map(|row| {
+            let placeholders = (1..=num_columns)
+                .map(|col| format!("${}", row * num_columns + col))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("({placeholders})")
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(
+        "INSERT INTO {} ({}) VALUES {} ON CONFLICT (transaction_version, event_index) DO NOTHING",
+        first.table_name,
+        columns.join(", "),
+        rows
+    );
+
+    let mut query = diesel::sql_query(sql).into_boxed::<Pg>();
+    for item in items_to_insert {
+        query = query
+            .bind::<BigInt, _>(item.transaction_version)
+            .bind::<BigInt, _>(item.event_index)
+            .bind::<Text, _>(item.account_address)
+            .bind::<BigInt, _>(item.creation_number)
+            .bind::<BigInt, _>(item.sequence_number)
+            .bind::<BigInt, _>(item.transaction_block_height)
+            .bind::<Text, _>(item.type_)
+            .bind::<Jsonb, _>(item.data);
+        for value in item.projected_values {
+            query = match value {
+                ProjectedValue::Text(v) => query.bind::<Nullable<Text>, _>(v),
+                ProjectedValue::BigInt(v) => query.bind::<Nullable<BigInt>, _>(v),
+                ProjectedValue::Numeric(v) => query.bind::<Nullable<Numeric>, _>(v),
+                ProjectedValue::Boolean(v) => query.bind::<Nullable<Bool>, _>(v),
+                ProjectedValue::Jsonb(v) => query.bind::<Nullable<Jsonb>, _>(v),
+            };
+        }
+    }
+    query
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_event(type_: &str, data: &str) -> Event {
+        Event {
+            sequence_number: 0,
+            creation_number: 0,
+            account_address: standardize_address("0x1"),
+            transaction_version: 1,
+            transaction_block_height: 1,
+            type_: type_.to_string(),
+            data: data.to_string(),
+            event_index: 0,
+            indexed_type: type_.to_string(),
+            block_timestamp: None,
+            type_tag_bytes: None,
+            total_bytes: None,
+        }
+    }
+
+    #[test]
+    fn test_pattern_parsing() {
+        let address = standardize_address("0xabc");
+        assert_eq!(
+            "0xabc::*".parse::<EventTypePattern>().unwrap(),
+            EventTypePattern::Address {
+                address: address.clone()
+            }
+        );
+        assert_eq!(
+            "0xabc::market::*".parse::<EventTypePattern>().unwrap(),
+            EventTypePattern::Module {
+                address: address.clone(),
+                module: "market".to_string()
+            }
+        );
+        assert_eq!(
+            "0xabc::market::Listed".parse::<EventTypePattern>().unwrap(),
+            EventTypePattern::Exact {
+                address,
+                module: "market".to_string(),
+                name: "Listed".to_string()
+            }
+        );
+        assert!("market::Listed".parse::<EventTypePattern>().is_err());
+        assert!("0xabc::market::List*".parse::<EventTypePattern>().is_err());
+    }
+
+    #[test]
+    fn test_pattern_matching() {
+        let exact: EventTypePattern = "0x1::coin::CoinDeposit".parse().unwrap();
+        assert!(exact.matches("0x1::coin::CoinDeposit"));
+        assert!(exact.matches("0x1::coin::CoinDeposit<0x1::aptos_coin::AptosCoin>"));
+        assert!(!exact.matches("0x1::coin::CoinWithdraw"));
+
+        let module: EventTypePattern = "0x1::coin::*".parse().unwrap();
+        assert!(module.matches(
+            "0x0000000000000000000000000000000000000000000000000000000000000001::coin::CoinWithdraw"
+        ));
+        assert!(!module.matches("0x1::fungible_asset::Deposit"));
+
+        let address: EventTypePattern = "0x1".parse().unwrap();
+        assert!(address.matches("0x1::fungible_asset::Deposit"));
+        assert!(!address.matches("0x2::fungible_asset::Deposit"));
+    }
+
+    #[test]
+    fn test_route_projection() {
+        let registry = EventRegistry::new(&[EventRouteConfig {
+            event_type: "0x1::coin::*".to_string(),
+            table_name: "coin_events".to_string(),
+            projections: vec![
+                FieldProjection {
+                    field: "amount".to_string(),
+                    column: "amount".to_string(),
+                    column_type: ProjectedColumnType::Numeric,
+                },
+                FieldProjection {
+                    field: "store.inner".to_string(),
+                    column: "store".to_string(),
+                    column_type: ProjectedColumnType::Text,
+                },
+                FieldProjection {
+                    field: "missing".to_string(),
+                    column: "missing".to_string(),
+                    column_type: ProjectedColumnType::BigInt,
+                },
+            ],
+        }])
+        .unwrap();
+
+        let routed = registry.route(&test_event(
+            "0x1::coin::CoinDeposit",
+            r#"{"amount":"100","store":{"inner":"0xa"}}"#,
+        ));
+        assert_eq!(routed.len(), 1);
+        let values = &routed[0].projected_values;
+        assert!(
+            matches!(&values[0], ProjectedValue::Numeric(Some(v)) if *v == BigDecimal::from(100))
+        );
+        assert!(matches!(&values[1], ProjectedValue::Text(Some(v)) if v == "0xa"));
+        assert!(matches!(&values[2], ProjectedValue::BigInt(None)));
+
+        assert!(registry
+            .route(&test_event("0x1::object::Transfer", "{}"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_invalid_routes() {
+        let route = |table_name: &str, column: &str| EventRouteConfig {
+            event_type: "0x1::coin::*".to_string(),
+            table_name: table_name.to_string(),
+            projections: vec![FieldProjection {
+                field: "amount".to_string(),
+                column: column.to_string(),
+                column_type: ProjectedColumnType::Numeric,
+            }],
+        };
+        assert!(EventRegistry::new(&[route("coin_events; DROP TABLE events", "amount")]).is_err());
+        assert!(EventRegistry::new(&[route("coin_events", "data")]).is_err());
+        assert!(EventRegistry::new(&[route("events", "amount")]).is_err());
+        assert!(EventRegistry::new(&[route("processor_status", "amount")]).is_err());
+        // Indexer tables without a table flag
+        assert!(EventRegistry::new(&[route("processor_safe_versions", "amount")]).is_err());
+        assert!(EventRegistry::new(&[route("spam_assets", "amount")]).is_err());
+        assert!(EventRegistry::new(&[route("coin_activities", "amount")]).is_err());
+        assert!(EventRegistry::new(&[
+            route("coin_events", "amount"),
+            route("coin_events", "amount")
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_schema_table_names() {
+        let declared: Vec<&str> = include_str!("../../db/schema.rs")
+            .split("diesel::table! {")
+            .skip(1)
+            .map(|table| table.trim_start().split([' ', '(']).next().unwrap())
+            .collect();
+        let mut table_names = schema::TABLE_NAMES.to_vec();
+        table_names.sort();
+        assert_eq!(declared, table_names);
+    }
+
+    #[test]
+    fn test_alter_table_sql() {
+        let column = |name: &str, data_type: &str, is_nullable: bool| TableColumn {
+            column_name: name.to_string(),
+            data_type: data_type.to_string(),
+            is_nullable,
+        };
+        let base_columns: Vec<TableColumn> = BASE_COLUMN_TYPES
+            .iter()
+            .map(|(name, data_type)| column(name, data_type, false))
+            .collect();
+        let route = EventRoute::try_from(&EventRouteConfig {
+            event_type: "0x1::coin::*".to_string(),
+            table_name: "coin_events".to_string(),
+            projections: vec![FieldProjection {
+                field: "amount".to_string(),
+                column: "amount".to_string(),
+                column_type: ProjectedColumnType::Numeric,
+            }],
+        })
+        .unwrap();
+
+        // New projection on an existing table
+        assert_eq!(
+            route.alter_table_sql(&base_columns).unwrap(),
+            vec!["ALTER TABLE coin_events ADD COLUMN IF NOT EXISTS amount NUMERIC".to_string()]
+        );
+
+        // Up to date, with the column of a removed projection left in place
+        let mut columns = base_columns.clone();
+        columns.push(column("amount", "numeric", true));
+        columns.push(column("removed", "text", true));
+        assert!(route.alter_table_sql(&columns).unwrap().is_empty());
+
+        // Projection with a different type
+        let mut columns = base_columns.clone();
+        columns.push(column("amount", "text", true));
+        assert!(route.alter_table_sql(&columns).is_err());
+
+        // Not a route table, e.g. the events table
+        let mut columns = base_columns.clone();
+        columns.push(column("indexed_type", "character varying", false));
+        assert!(route.alter_table_sql(&columns).is_err());
+        assert!(route.alter_table_sql(&base_columns[1..]).is_err());
+    }
+}
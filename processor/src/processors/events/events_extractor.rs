@@ -1,4 +1,9 @@
-use crate::processors::events::{events_model::PostgresEvent, parse_events};
+use crate::processors::events::{
+    event_filter::EventFilter,
+    event_registry::{EventRegistry, RoutedEvent},
+    events_model::{Event, PostgresEvent},
+    parse_events,
+};
 use aptos_indexer_processor_sdk::{
     aptos_protos::transaction::v1::Transaction,
     traits::{async_step::AsyncRunType, AsyncStep, NamedStep, Processable},
@@ -10,27 +15,55 @@ use rayon::prelude::*;
 
 pub struct EventsExtractor
 where
-    Self: Sized + Send + 'static, {}
+    Self: Sized + Send + 'static,
+{
+    event_registry: EventRegistry,
+    event_filter: EventFilter,
+}
+
+impl EventsExtractor {
+    pub fn new(event_registry: EventRegistry, event_filter: EventFilter) -> Self {
+        Self {
+            event_registry,
+            event_filter,
+        }
+    }
+}
 
 #[async_trait]
 impl Processable for EventsExtractor {
     type Input = Vec<Transaction>;
-    type Output = Vec<PostgresEvent>;
+    type Output = (Vec<PostgresEvent>, Vec<RoutedEvent>);
     type RunType = AsyncRunType;
 
     async fn process(
         &mut self,
         item: TransactionContext<Vec<Transaction>>,
-    ) -> Result<Option<TransactionContext<Vec<PostgresEvent>>>, ProcessorError> {
-        let events: Vec<PostgresEvent> = item
+    ) -> Result<Option<TransactionContext<(Vec<PostgresEvent>, Vec<RoutedEvent>)>>, ProcessorError>
+    {
+        let processor_name = self.name();
+        let events: Vec<Event> = item
             .data
             .par_iter()
-            .map(|txn| parse_events(txn, self.name().as_str()))
-            .flatten()
-            .map(|e| e.into())
+            .flat_map(|txn| {
+                self.event_filter.filter(
+                    txn,
+                    parse_events(txn, processor_name.as_str()),
+                    processor_name.as_str(),
+                )
+            })
             .collect();
+        let routed_events: Vec<RoutedEvent> = if self.event_registry.is_empty() {
+            vec![]
+        } else {
+            events
+                .par_iter()
+                .flat_map(|event| self.event_registry.route(event))
+                .collect()
+        };
+        let events: Vec<PostgresEvent> = events.into_iter().map(|e| e.into()).collect();
         Ok(Some(TransactionContext {
-            data: events,
+            data: (events, routed_events),
             metadata: item.metadata,
         }))
     }
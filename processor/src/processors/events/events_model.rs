@@ -200,3 +200,21 @@ impl From<Event> for PostgresEvent {
         }
     }
 }
+
+impl TryFrom<ParquetEvent> for PostgresEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(parquet_event: ParquetEvent) -> anyhow::Result<Self> {
+        Ok(PostgresEvent {
+            sequence_number: parquet_event.sequence_number,
+            creation_number: parquet_event.creation_number,
+            account_address: parquet_event.account_address,
+            transaction_version: parquet_event.txn_version,
+            transaction_block_height: parquet_event.block_height,
+            type_: parquet_event.event_type,
+            data: serde_json::from_str(&parquet_event.data)?,
+            event_index: parquet_event.event_index,
+            indexed_type: parquet_event.indexed_type,
+        })
+    }
+}
@@ -1,19 +1,32 @@
 use crate::{
     config::{
-        db_config::DbConfig, indexer_processor_config::IndexerProcessorConfig,
-        processor_config::ProcessorConfig,
+        db_config::DbConfig,
+        indexer_processor_config::IndexerProcessorConfig,
+        processor_config::{DefaultProcessorConfig, ProcessorConfig},
+    },
+    db::{
+        copy_insert::CopyInserter,
+        partitioning::{VersionPartitionManager, VersionPartitionStep},
+        retention::start_history_pruning,
     },
     processors::{
-        events::{events_extractor::EventsExtractor, events_storer::EventsStorer},
+        events::{
+            event_filter::{EventFilter, EventFilterConfig},
+            event_registry::{EventRegistry, EventRouteConfig},
+            events_extractor::EventsExtractor,
+            events_storer::EventsStorer,
+        },
         processor_status_saver::{
             get_end_version, get_starting_version, PostgresProcessorStatusSaver,
         },
     },
+    utils::table_flags::TableFlags,
     MIGRATIONS,
 };
 use anyhow::Result;
 use aptos_indexer_processor_sdk::{
     aptos_indexer_transaction_stream::TransactionStreamConfig,
+    aptos_protos::transaction::v1::Transaction,
     builder::ProcessorBuilder,
     common_steps::{
         TransactionStreamStep, VersionTrackerStep, DEFAULT_UPDATE_PROCESSOR_STATUS_SECS,
@@ -25,8 +38,23 @@ use aptos_indexer_processor_sdk::{
     traits::{processor_trait::ProcessorTrait, IntoRunnableStep},
     utils::chain_id_check::check_or_update_chain_id,
 };
+use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
 
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventsProcessorConfig {
+    #[serde(flatten)]
+    pub default_config: DefaultProcessorConfig,
+    /// Routes matching events into dedicated tables in addition to `events`. To only write the
+    /// routed tables, leave `EVENTS` out of `tables_to_write`.
+    #[serde(default)]
+    pub event_registry: Vec<EventRouteConfig>,
+    /// Drops events before they are written to `events` or any routed table.
+    #[serde(default)]
+    pub event_filter: EventFilterConfig,
+}
+
 pub struct EventsProcessor {
     pub config: IndexerProcessorConfig,
     pub db_pool: ArcDbPool,
@@ -100,7 +128,11 @@ impl ProcessorTrait for EventsProcessor {
                 ))
             },
         };
-        let channel_size = processor_config.channel_size;
+        let channel_size = processor_config.default_config.channel_size;
+        let tables_to_write =
+            TableFlags::from_set(&processor_config.default_config.tables_to_write);
+        let event_registry = EventRegistry::new(&processor_config.event_registry)?;
+        event_registry.create_tables(self.db_pool.clone()).await?;
 
         // Define processor steps
         let transaction_stream = TransactionStreamStep::new(TransactionStreamConfig {
@@ -109,8 +141,37 @@ impl ProcessorTrait for EventsProcessor {
             ..self.config.transaction_stream_config.clone()
         })
         .await?;
-        let events_extractor = EventsExtractor {};
-        let events_storer = EventsStorer::new(self.db_pool.clone(), processor_config);
+        let partition_manager = VersionPartitionManager::new(
+            self.db_pool.clone(),
+            processor_config
+                .default_config
+                .version_partitioning
+                .as_ref(),
+            &["events"],
+        )
+        .await?;
+        let version_partition_step =
+            VersionPartitionStep::<Vec<Transaction>>::new(partition_manager);
+        start_history_pruning(
+            &self.config,
+            self.db_pool.clone(),
+            processor_config.default_config.retention.as_ref(),
+            &["events"],
+        )?;
+        let event_filter = EventFilter::try_from(&processor_config.event_filter)?;
+        let events_extractor = EventsExtractor::new(event_registry.clone(), event_filter);
+        let copy_inserter = CopyInserter::for_table(
+            &self.config.db_config,
+            &processor_config.default_config.copy_tables,
+            "events",
+        )?;
+        let events_storer = EventsStorer::new(
+            self.db_pool.clone(),
+            processor_config.default_config,
+            tables_to_write,
+            event_registry,
+            copy_inserter,
+        );
         let version_tracker = VersionTrackerStep::new(
             PostgresProcessorStatusSaver::new(self.config.clone(), self.db_pool.clone()),
             DEFAULT_UPDATE_PROCESSOR_STATUS_SECS,
@@ -120,6 +181,7 @@ impl ProcessorTrait for EventsProcessor {
         let (_, buffer_receiver) = ProcessorBuilder::new_with_inputless_first_step(
             transaction_stream.into_runnable_step(),
         )
+        .connect_to(version_partition_step.into_runnable_step(), channel_size)
         .connect_to(events_extractor.into_runnable_step(), channel_size)
         .connect_to(events_storer.into_runnable_step(), channel_size)
         .connect_to(version_tracker.into_runnable_step(), channel_size)
@@ -142,3 +204,53 @@ impl ProcessorTrait for EventsProcessor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_config_parsing() {
+        let config = json!({
+            "type": "events_processor",
+            "channel_size": 10,
+            "tables_to_write": ["EVENTS"],
+            "event_registry": [{
+                "event_type": "0x1::coin::*",
+                "table_name": "coin_events",
+                "projections": [
+                    { "field": "amount", "column": "amount", "column_type": "numeric" }
+                ]
+            }],
+            "event_filter": { "exclude_event_types": ["0x1::block::NewBlockEvent"] }
+        });
+        let ProcessorConfig::EventsProcessor(parsed) =
+            serde_json::from_value::<ProcessorConfig>(config.clone()).unwrap()
+        else {
+            panic!("Parsed the wrong processor config");
+        };
+        assert_eq!(parsed.default_config.channel_size, 10);
+        assert_eq!(
+            parsed.default_config.tables_to_write,
+            HashSet::from(["EVENTS".to_string()])
+        );
+        assert_eq!(parsed.event_registry.len(), 1);
+        assert_eq!(parsed.event_registry[0].table_name, "coin_events");
+        assert_eq!(
+            parsed.event_filter.exclude_event_types,
+            vec!["0x1::block::NewBlockEvent".to_string()]
+        );
+
+        // Misspelled fields of either the default or the events config are rejected
+        for unknown_field in ["channel_sizes", "event_registries"] {
+            let mut config = config.clone();
+            config
+                .as_object_mut()
+                .unwrap()
+                .insert(unknown_field.to_string(), json!([]));
+            assert!(serde_json::from_value::<ProcessorConfig>(config).is_err());
+        }
+    }
+}
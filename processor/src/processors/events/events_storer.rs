@@ -1,6 +1,11 @@
 use crate::{
     config::processor_config::DefaultProcessorConfig,
-    processors::events::events_model::PostgresEvent,
+    db::copy_insert::{execute_copy_or_in_chunks, CopyInsertable, CopyInserter, CopyValue},
+    processors::events::{
+        event_registry::{insert_routed_events_query, EventRegistry, RoutedEvent},
+        events_model::PostgresEvent,
+    },
+    utils::table_flags::{filter_data, TableFlags},
 };
 use ahash::AHashMap;
 use anyhow::Result;
@@ -19,36 +24,76 @@ where
 {
     conn_pool: ArcDbPool,
     processor_config: DefaultProcessorConfig,
+    tables_to_write: TableFlags,
+    event_registry: EventRegistry,
+    copy_inserter: Option<CopyInserter>,
 }
 
 impl EventsStorer {
-    pub fn new(conn_pool: ArcDbPool, processor_config: DefaultProcessorConfig) -> Self {
+    pub fn new(
+        conn_pool: ArcDbPool,
+        processor_config: DefaultProcessorConfig,
+        tables_to_write: TableFlags,
+        event_registry: EventRegistry,
+        copy_inserter: Option<CopyInserter>,
+    ) -> Self {
         Self {
             conn_pool,
             processor_config,
+            tables_to_write,
+            event_registry,
+            copy_inserter,
         }
     }
 }
 
 #[async_trait]
 impl Processable for EventsStorer {
-    type Input = Vec<PostgresEvent>;
+    type Input = (Vec<PostgresEvent>, Vec<RoutedEvent>);
     type Output = ();
     type RunType = AsyncRunType;
 
     async fn process(
         &mut self,
-        events: TransactionContext<Vec<PostgresEvent>>,
+        events: TransactionContext<(Vec<PostgresEvent>, Vec<RoutedEvent>)>,
     ) -> Result<Option<TransactionContext<()>>, ProcessorError> {
+        let (postgres_events, routed_events) = events.data;
+        let postgres_events =
+            filter_data(&self.tables_to_write, TableFlags::EVENTS, postgres_events);
+
         let per_table_chunk_sizes: AHashMap<String, usize> =
             self.processor_config.per_table_chunk_sizes.clone();
-        let execute_res = execute_in_chunks(
+        let events_res = execute_copy_or_in_chunks(
+            self.copy_inserter.as_mut(),
             self.conn_pool.clone(),
             insert_events_query,
-            &events.data,
+            &postgres_events,
             get_config_table_chunk_size::<PostgresEvent>("events", &per_table_chunk_sizes),
-        )
-        .await;
+        );
+
+        // Each route has its own table and columns, so they are inserted separately
+        let mut routed_events_by_table: AHashMap<String, Vec<RoutedEvent>> = AHashMap::new();
+        for routed_event in routed_events {
+            routed_events_by_table
+                .entry(routed_event.table_name.clone())
+                .or_default()
+                .push(routed_event);
+        }
+        let routed_events_res =
+            futures::future::try_join_all(routed_events_by_table.iter().map(|(table, items)| {
+                let chunk_size = per_table_chunk_sizes
+                    .get(table)
+                    .copied()
+                    .unwrap_or_else(|| self.event_registry.chunk_size(table));
+                execute_in_chunks(
+                    self.conn_pool.clone(),
+                    insert_routed_events_query,
+                    items,
+                    chunk_size,
+                )
+            }));
+
+        let execute_res = futures::try_join!(events_res, routed_events_res);
         match execute_res {
             Ok(_) => {
                 debug!(
@@ -86,6 +131,7 @@ use diesel::{
     query_builder::QueryFragment,
     ExpressionMethods,
 };
+use tokio_postgres::types::Type;
 
 pub fn insert_events_query(
     items_to_insert: Vec<PostgresEvent>,
@@ -101,3 +147,45 @@ pub fn insert_events_query(
             indexed_type.eq(excluded(indexed_type)),
         ))
 }
+
+impl CopyInsertable for PostgresEvent {
+    fn table_name() -> &'static str {
+        "events"
+    }
+
+    fn columns() -> Vec<(&'static str, Type)> {
+        vec![
+            ("sequence_number", Type::INT8),
+            ("creation_number", Type::INT8),
+            ("account_address", Type::VARCHAR),
+            ("transaction_version", Type::INT8),
+            ("transaction_block_height", Type::INT8),
+            ("type", Type::TEXT),
+            ("data", Type::JSONB),
+            ("event_index", Type::INT8),
+            ("indexed_type", Type::VARCHAR),
+        ]
+    }
+
+    fn conflict_columns() -> &'static [&'static str] {
+        &["transaction_version", "event_index"]
+    }
+
+    fn update_columns() -> &'static [&'static str] {
+        &["inserted_at", "indexed_type"]
+    }
+
+    fn copy_row(&self) -> Vec<CopyValue<'_>> {
+        vec![
+            Box::new(self.sequence_number),
+            Box::new(self.creation_number),
+            Box::new(&self.account_address),
+            Box::new(self.transaction_version),
+            Box::new(self.transaction_block_height),
+            Box::new(&self.type_),
+            Box::new(&self.data),
+            Box::new(self.event_index),
+            Box::new(&self.indexed_type),
+        ]
+    }
+}
@@ -1,3 +1,5 @@
+pub mod event_filter;
+pub mod event_registry;
 pub mod events_extractor;
 pub mod events_processor;
 pub mod events_storer;
@@ -35,6 +35,7 @@ use bigdecimal::{BigDecimal, Zero};
 use field_count::FieldCount;
 use parquet_derive::ParquetRecordWriter;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 pub const GAS_FEE_EVENT: &str = "0x1::aptos_coin::GasFeeEvent";
 // We will never have a negative number on chain so this will avoid collision in postgres
@@ -419,3 +420,33 @@ impl From<FungibleAssetActivity> for PostgresFungibleAssetActivity {
         }
     }
 }
+
+impl TryFrom<ParquetFungibleAssetActivity> for PostgresFungibleAssetActivity {
+    type Error = anyhow::Error;
+
+    fn try_from(parquet_item: ParquetFungibleAssetActivity) -> anyhow::Result<Self> {
+        Ok(Self {
+            transaction_version: parquet_item.txn_version,
+            event_index: parquet_item.event_index,
+            owner_address: parquet_item.owner_address,
+            storage_id: parquet_item.storage_id,
+            asset_type: parquet_item.asset_type,
+            is_frozen: parquet_item.is_frozen,
+            amount: parquet_item
+                .amount
+                .as_deref()
+                .map(BigDecimal::from_str)
+                .transpose()
+                .context("Failed to parse fungible asset activity amount")?,
+            type_: parquet_item.event_type,
+            is_gas_fee: parquet_item.is_gas_fee,
+            gas_fee_payer_address: parquet_item.gas_fee_payer_address,
+            is_transaction_success: parquet_item.is_transaction_success,
+            entry_function_id_str: parquet_item.entry_function_id_str,
+            block_height: parquet_item.block_height,
+            token_standard: parquet_item.token_standard,
+            transaction_timestamp: parquet_item.block_timestamp,
+            storage_refund_amount: BigDecimal::from(parquet_item.storage_refund_octa),
+        })
+    }
+}
@@ -13,7 +13,7 @@ use super::{
 };
 use crate::{
     db::resources::FromWriteResource,
-    parquet_processors::parquet_utils::util::{HasVersion, NamedTable},
+    parquet_processors::parquet_utils::util::{HasTimestamp, HasVersion, NamedTable},
     processors::{
         default::models::move_resources::MoveResource,
         fungible_asset::{
@@ -424,6 +424,7 @@ impl HasVersion for ParquetFungibleAssetBalance {
         self.txn_version
     }
 }
+
 impl From<FungibleAssetBalance> for ParquetFungibleAssetBalance {
     fn from(raw: FungibleAssetBalance) -> Self {
         Self {
@@ -466,10 +467,19 @@ impl NamedTable for ParquetCurrentUnifiedFungibleAssetBalance {
     const TABLE_NAME: &'static str = "current_fungible_asset_balances";
 }
 
-/// This will be deprecated.
 impl HasVersion for ParquetCurrentUnifiedFungibleAssetBalance {
     fn version(&self) -> i64 {
-        -1
+        // Each row is either a v1 or a v2 balance
+        self.last_transaction_version_v1
+            .max(self.last_transaction_version_v2)
+            .unwrap_or_default()
+    }
+}
+
+impl HasTimestamp for ParquetCurrentUnifiedFungibleAssetBalance {
+    fn timestamp(&self) -> Option<chrono::NaiveDateTime> {
+        self.last_transaction_timestamp_v1
+            .max(self.last_transaction_timestamp_v2)
     }
 }
 
@@ -582,6 +592,37 @@ impl From<CurrentUnifiedFungibleAssetBalance> for PostgresCurrentUnifiedFungible
     }
 }
 
+impl TryFrom<ParquetCurrentUnifiedFungibleAssetBalance>
+    for PostgresCurrentUnifiedFungibleAssetBalance
+{
+    type Error = anyhow::Error;
+
+    fn try_from(parquet_item: ParquetCurrentUnifiedFungibleAssetBalance) -> anyhow::Result<Self> {
+        Ok(Self {
+            storage_id: parquet_item.storage_id,
+            owner_address: parquet_item.owner_address,
+            asset_type_v1: parquet_item.asset_type_v1,
+            asset_type_v2: parquet_item.asset_type_v2,
+            is_primary: parquet_item.is_primary,
+            is_frozen: parquet_item.is_frozen,
+            amount_v1: parquet_item
+                .amount_v1
+                .as_deref()
+                .map(BigDecimal::from_str)
+                .transpose()?,
+            amount_v2: parquet_item
+                .amount_v2
+                .as_deref()
+                .map(BigDecimal::from_str)
+                .transpose()?,
+            last_transaction_version_v1: parquet_item.last_transaction_version_v1,
+            last_transaction_version_v2: parquet_item.last_transaction_version_v2,
+            last_transaction_timestamp_v1: parquet_item.last_transaction_timestamp_v1,
+            last_transaction_timestamp_v2: parquet_item.last_transaction_timestamp_v2,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
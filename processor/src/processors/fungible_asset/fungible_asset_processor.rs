@@ -6,6 +6,11 @@ use crate::{
         },
         processor_config::{DefaultProcessorConfig, ProcessorConfig},
     },
+    db::{
+        copy_insert::CopyInserter,
+        partitioning::{VersionPartitionManager, VersionPartitionStep},
+        retention::start_history_pruning,
+    },
     processors::{
         fungible_asset::{
             fungible_asset_extractor::FungibleAssetExtractor,
@@ -21,6 +26,7 @@ use crate::{
 use anyhow::Result;
 use aptos_indexer_processor_sdk::{
     aptos_indexer_transaction_stream::TransactionStreamConfig,
+    aptos_protos::transaction::v1::Transaction,
     builder::ProcessorBuilder,
     common_steps::{
         TransactionStreamStep, VersionTrackerStep, DEFAULT_UPDATE_PROCESSOR_STATUS_SECS,
@@ -134,15 +140,35 @@ impl ProcessorTrait for FungibleAssetProcessor {
             ..self.config.transaction_stream_config.clone()
         })
         .await?;
+        let partition_manager = VersionPartitionManager::new(
+            self.db_pool.clone(),
+            processor_config.version_partitioning.as_ref(),
+            &["fungible_asset_activities"],
+        )
+        .await?;
+        let version_partition_step =
+            VersionPartitionStep::<Vec<Transaction>>::new(partition_manager);
+        start_history_pruning(
+            &self.config,
+            self.db_pool.clone(),
+            processor_config.retention.as_ref(),
+            &["fungible_asset_activities"],
+        )?;
 
         let mut fa_extractor = FungibleAssetExtractor::new();
         fa_extractor
             .bootstrap_fa_to_coin_mapping(self.db_pool.clone())
             .await?;
+        let copy_inserter = CopyInserter::for_table(
+            &self.config.db_config,
+            &processor_config.copy_tables,
+            "fungible_asset_activities",
+        )?;
         let fa_storer = FungibleAssetStorer::new(
             self.db_pool.clone(),
             processor_config.clone(),
             deprecated_table_flags,
+            copy_inserter,
         );
         let version_tracker = VersionTrackerStep::new(
             PostgresProcessorStatusSaver::new(self.config.clone(), self.db_pool.clone()),
@@ -152,6 +178,7 @@ impl ProcessorTrait for FungibleAssetProcessor {
         let (_, buffer_receiver) = ProcessorBuilder::new_with_inputless_first_step(
             transaction_stream.into_runnable_step(),
         )
+        .connect_to(version_partition_step.into_runnable_step(), channel_size)
         .connect_to(fa_extractor.into_runnable_step(), channel_size)
         .connect_to(fa_storer.into_runnable_step(), channel_size)
         .connect_to(version_tracker.into_runnable_step(), channel_size)
@@ -3,6 +3,9 @@
 
 use crate::{
     config::processor_config::DefaultProcessorConfig,
+    db::copy_insert::{
+        execute_copy_or_in_chunks, CopyInsertable, CopyInserter, CopyValue, PgNumeric,
+    },
     filter_datasets,
     processors::fungible_asset::{
         coin_models::coin_supply::CoinSupply,
@@ -16,7 +19,7 @@ use crate::{
         },
     },
     schema,
-    utils::table_flags::{filter_data, TableFlags},
+    utils::table_flags::{filter_data, filter_opt_in_data, TableFlags},
 };
 use ahash::AHashMap;
 use anyhow::Result;
@@ -35,6 +38,7 @@ use diesel::{
     sql_types::{Nullable, Text},
     BoolExpressionMethods, ExpressionMethods,
 };
+use tokio_postgres::types::Type;
 
 pub struct FungibleAssetStorer
 where
@@ -43,6 +47,7 @@ where
     conn_pool: ArcDbPool,
     processor_config: DefaultProcessorConfig,
     tables_to_write: TableFlags,
+    copy_inserter: Option<CopyInserter>,
 }
 
 impl FungibleAssetStorer {
@@ -50,11 +55,13 @@ impl FungibleAssetStorer {
         conn_pool: ArcDbPool,
         processor_config: DefaultProcessorConfig,
         tables_to_write: TableFlags,
+        copy_inserter: Option<CopyInserter>,
     ) -> Self {
         Self {
             conn_pool,
             processor_config,
             tables_to_write,
+            copy_inserter,
         }
     }
 }
@@ -92,7 +99,7 @@ impl Processable for FungibleAssetStorer {
         let (
             fungible_asset_activities,
             fungible_asset_metadata,
-            _,
+            fungible_asset_balances,
             (current_unified_fab_v1, current_unified_fab_v2),
             _,
             fa_to_coin_mappings,
@@ -114,8 +121,15 @@ impl Processable for FungibleAssetStorer {
             fungible_asset_metadata => TableFlags::FUNGIBLE_ASSET_METADATA,
             fa_to_coin_mappings => TableFlags::FUNGIBLE_ASSET_TO_COIN_MAPPINGS,
         });
+        // The balance history is only written when it's opted in
+        let fungible_asset_balances = filter_opt_in_data(
+            &self.tables_to_write,
+            TableFlags::FUNGIBLE_ASSET_BALANCES,
+            fungible_asset_balances,
+        );
 
-        let faa = execute_in_chunks(
+        let faa = execute_copy_or_in_chunks(
+            self.copy_inserter.as_mut(),
             self.conn_pool.clone(),
             insert_fungible_asset_activities_query,
             &fungible_asset_activities,
@@ -133,6 +147,15 @@ impl Processable for FungibleAssetStorer {
                 &per_table_chunk_sizes,
             ),
         );
+        let fab = execute_in_chunks(
+            self.conn_pool.clone(),
+            insert_fungible_asset_balances_query,
+            &fungible_asset_balances,
+            get_config_table_chunk_size::<PostgresFungibleAssetBalance>(
+                "fungible_asset_balances",
+                &per_table_chunk_sizes,
+            ),
+        );
         let cufab_v1 = execute_in_chunks(
             self.conn_pool.clone(),
             insert_current_unified_fungible_asset_balances_v1_query,
@@ -160,9 +183,9 @@ impl Processable for FungibleAssetStorer {
                 &per_table_chunk_sizes,
             ),
         );
-        let (faa_res, fam_res, cufab1_res, cufab2_res, fatcm_res) =
-            tokio::join!(faa, fam, cufab_v1, cufab_v2, fatcm);
-        for res in [faa_res, fam_res, cufab1_res, cufab2_res, fatcm_res] {
+        let (faa_res, fam_res, fab_res, cufab1_res, cufab2_res, fatcm_res) =
+            tokio::join!(faa, fam, fab, cufab_v1, cufab_v2, fatcm);
+        for res in [faa_res, fam_res, fab_res, cufab1_res, cufab2_res, fatcm_res] {
             match res {
                 Ok(_) => {},
                 Err(e) => {
@@ -204,6 +227,62 @@ pub fn insert_fungible_asset_activities_query(
         .set(storage_id.eq(excluded(storage_id)))
 }
 
+impl CopyInsertable for PostgresFungibleAssetActivity {
+    fn table_name() -> &'static str {
+        "fungible_asset_activities"
+    }
+
+    fn columns() -> Vec<(&'static str, Type)> {
+        vec![
+            ("transaction_version", Type::INT8),
+            ("event_index", Type::INT8),
+            ("owner_address", Type::VARCHAR),
+            ("storage_id", Type::VARCHAR),
+            ("asset_type", Type::VARCHAR),
+            ("is_frozen", Type::BOOL),
+            ("amount", Type::NUMERIC),
+            ("type", Type::VARCHAR),
+            ("is_gas_fee", Type::BOOL),
+            ("gas_fee_payer_address", Type::VARCHAR),
+            ("is_transaction_success", Type::BOOL),
+            ("entry_function_id_str", Type::VARCHAR),
+            ("block_height", Type::INT8),
+            ("token_standard", Type::VARCHAR),
+            ("transaction_timestamp", Type::TIMESTAMP),
+            ("storage_refund_amount", Type::NUMERIC),
+        ]
+    }
+
+    fn conflict_columns() -> &'static [&'static str] {
+        &["transaction_version", "event_index"]
+    }
+
+    fn update_columns() -> &'static [&'static str] {
+        &["storage_id"]
+    }
+
+    fn copy_row(&self) -> Vec<CopyValue<'_>> {
+        vec![
+            Box::new(self.transaction_version),
+            Box::new(self.event_index),
+            Box::new(&self.owner_address),
+            Box::new(&self.storage_id),
+            Box::new(&self.asset_type),
+            Box::new(self.is_frozen),
+            Box::new(self.amount.as_ref().map(PgNumeric)),
+            Box::new(&self.type_),
+            Box::new(self.is_gas_fee),
+            Box::new(&self.gas_fee_payer_address),
+            Box::new(self.is_transaction_success),
+            Box::new(&self.entry_function_id_str),
+            Box::new(self.block_height),
+            Box::new(&self.token_standard),
+            Box::new(self.transaction_timestamp),
+            Box::new(PgNumeric(&self.storage_refund_amount)),
+        ]
+    }
+}
+
 pub fn insert_fungible_asset_metadata_query(
     items_to_insert: Vec<PostgresFungibleAssetMetadataModel>,
 ) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
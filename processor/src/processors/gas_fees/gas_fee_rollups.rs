@@ -0,0 +1,520 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rollups of `gas_fees` per block, per hour and per entry function and hour.
+//!
+//! Gas unit prices are kept as a histogram of transactions per price so that the rows of a bucket
+//! can be merged across batches and the percentiles recomputed from the merged histogram. Each row
+//! records the last version it aggregated; merging skips versions at or below it, which makes
+//! replaying a batch a no-op. Rollups assume versions are processed in order.
+
+use super::models::GasFee;
+use crate::{
+    parquet_processors::parquet_utils::util::{HasVersion, NamedTable},
+    schema::{
+        gas_fee_block_summaries, gas_fee_entry_function_hourly_summaries, gas_fee_hourly_summaries,
+    },
+};
+use allocative_derive::Allocative;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{NaiveDateTime, Timelike};
+use field_count::FieldCount;
+use parquet_derive::ParquetRecordWriter;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Mergeable aggregate of the gas fees of a bucket.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GasFeeAggregate {
+    pub transaction_count: i64,
+    pub total_fee: BigDecimal,
+    pub total_storage_refund: BigDecimal,
+    pub fee_payer_transaction_count: i64,
+    // Number of transactions per gas unit price
+    pub gas_unit_price_histogram: BTreeMap<i64, i64>,
+    pub last_transaction_version: i64,
+}
+
+impl Default for GasFeeAggregate {
+    fn default() -> Self {
+        Self {
+            transaction_count: 0,
+            total_fee: BigDecimal::zero(),
+            total_storage_refund: BigDecimal::zero(),
+            fee_payer_transaction_count: 0,
+            gas_unit_price_histogram: BTreeMap::new(),
+            last_transaction_version: -1,
+        }
+    }
+}
+
+impl GasFeeAggregate {
+    /// Adds a gas fee to the aggregate, unless its version was already aggregated.
+    pub fn add(&mut self, gas_fee: &GasFee) {
+        if gas_fee.transaction_version <= self.last_transaction_version {
+            return;
+        }
+        self.transaction_count += 1;
+        if let Some(amount) = &gas_fee.amount {
+            self.total_fee += amount;
+        }
+        self.total_storage_refund += &gas_fee.storage_refund_amount;
+        if gas_fee.gas_fee_payer_address.is_some() {
+            self.fee_payer_transaction_count += 1;
+        }
+        *self
+            .gas_unit_price_histogram
+            .entry(gas_fee.gas_unit_price)
+            .or_default() += 1;
+        self.last_transaction_version = gas_fee.transaction_version;
+    }
+
+    /// Nearest rank `percentile` of the gas unit prices, 0 if there are none.
+    pub fn gas_unit_price_percentile(&self, percentile: i64) -> i64 {
+        let count: i64 = self.gas_unit_price_histogram.values().sum();
+        let rank = ((count * percentile + 99) / 100).max(1);
+        let mut seen = 0;
+        for (gas_unit_price, transactions) in &self.gas_unit_price_histogram {
+            seen += transactions;
+            if seen >= rank {
+                return *gas_unit_price;
+            }
+        }
+        0
+    }
+
+    fn histogram_to_json(&self) -> serde_json::Value {
+        serde_json::to_value(&self.gas_unit_price_histogram)
+            .expect("Gas unit price histogram is serializable")
+    }
+
+    fn histogram_from_json(histogram: &serde_json::Value) -> BTreeMap<i64, i64> {
+        serde_json::from_value(histogram.clone())
+            .expect("gas_unit_price_histogram maps gas unit prices to transaction counts")
+    }
+}
+
+/// Aggregate stored in the columns that every rollup row has.
+macro_rules! row_aggregate {
+    ($row:expr) => {
+        GasFeeAggregate {
+            transaction_count: $row.transaction_count,
+            total_fee: $row.total_fee.clone(),
+            total_storage_refund: $row.total_storage_refund.clone(),
+            fee_payer_transaction_count: $row.fee_payer_transaction_count,
+            gas_unit_price_histogram: GasFeeAggregate::histogram_from_json(
+                &$row.gas_unit_price_histogram,
+            ),
+            last_transaction_version: $row.last_transaction_version,
+        }
+    };
+}
+
+/// A rollup table of gas fees, with one row per bucket.
+pub trait GasFeeRollup: Sized {
+    type Key: Clone + Ord;
+
+    /// Bucket of the gas fee, none if the gas fee isn't part of the rollup.
+    fn bucket(gas_fee: &GasFee) -> Option<Self::Key>;
+
+    fn key(&self) -> Self::Key;
+
+    fn aggregate(&self) -> GasFeeAggregate;
+
+    /// Row of the bucket of `gas_fee`.
+    fn from_aggregate(gas_fee: &GasFee, aggregate: GasFeeAggregate) -> Self;
+}
+
+/// Rolls the gas fees up into the rows of their buckets, on top of the `existing` rows of the
+/// buckets if any.
+pub fn roll_up<R: GasFeeRollup>(gas_fees: &[GasFee], existing: Vec<R>) -> Vec<R> {
+    let mut existing: BTreeMap<R::Key, GasFeeAggregate> = existing
+        .into_iter()
+        .map(|row| (row.key(), row.aggregate()))
+        .collect();
+    let mut buckets: BTreeMap<R::Key, (&GasFee, GasFeeAggregate)> = BTreeMap::new();
+    for gas_fee in gas_fees {
+        let Some(key) = R::bucket(gas_fee) else {
+            continue;
+        };
+        let (_, aggregate) = buckets
+            .entry(key.clone())
+            .or_insert_with(|| (gas_fee, existing.remove(&key).unwrap_or_default()));
+        aggregate.add(gas_fee);
+    }
+    buckets
+        .into_values()
+        .map(|(gas_fee, aggregate)| R::from_aggregate(gas_fee, aggregate))
+        .collect()
+}
+
+/// Start of the hour of the timestamp.
+fn hour_bucket(timestamp: NaiveDateTime) -> NaiveDateTime {
+    timestamp
+        .date()
+        .and_hms_opt(timestamp.hour(), 0, 0)
+        .expect("Start of an hour is a valid time")
+}
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Insertable, Queryable, Selectable, Serialize)]
+#[diesel(primary_key(block_height))]
+#[diesel(table_name = gas_fee_block_summaries)]
+pub struct GasFeeBlockSummary {
+    pub block_height: i64,
+    pub block_timestamp: NaiveDateTime,
+    pub transaction_count: i64,
+    pub total_fee: BigDecimal,
+    pub total_storage_refund: BigDecimal,
+    pub fee_payer_transaction_count: i64,
+    pub median_gas_unit_price: i64,
+    pub p95_gas_unit_price: i64,
+    pub gas_unit_price_histogram: serde_json::Value,
+    pub last_transaction_version: i64,
+}
+
+impl GasFeeRollup for GasFeeBlockSummary {
+    type Key = i64;
+
+    fn bucket(gas_fee: &GasFee) -> Option<i64> {
+        Some(gas_fee.block_height)
+    }
+
+    fn key(&self) -> i64 {
+        self.block_height
+    }
+
+    fn aggregate(&self) -> GasFeeAggregate {
+        row_aggregate!(self)
+    }
+
+    fn from_aggregate(gas_fee: &GasFee, aggregate: GasFeeAggregate) -> Self {
+        Self {
+            block_height: gas_fee.block_height,
+            block_timestamp: gas_fee.transaction_timestamp,
+            transaction_count: aggregate.transaction_count,
+            median_gas_unit_price: aggregate.gas_unit_price_percentile(50),
+            p95_gas_unit_price: aggregate.gas_unit_price_percentile(95),
+            gas_unit_price_histogram: aggregate.histogram_to_json(),
+            total_fee: aggregate.total_fee,
+            total_storage_refund: aggregate.total_storage_refund,
+            fee_payer_transaction_count: aggregate.fee_payer_transaction_count,
+            last_transaction_version: aggregate.last_transaction_version,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Insertable, Queryable, Selectable, Serialize)]
+#[diesel(primary_key(bucket_start))]
+#[diesel(table_name = gas_fee_hourly_summaries)]
+pub struct GasFeeHourlySummary {
+    pub bucket_start: NaiveDateTime,
+    pub transaction_count: i64,
+    pub total_fee: BigDecimal,
+    pub total_storage_refund: BigDecimal,
+    pub fee_payer_transaction_count: i64,
+    pub median_gas_unit_price: i64,
+    pub p95_gas_unit_price: i64,
+    pub gas_unit_price_histogram: serde_json::Value,
+    pub last_transaction_version: i64,
+}
+
+impl GasFeeRollup for GasFeeHourlySummary {
+    type Key = NaiveDateTime;
+
+    fn bucket(gas_fee: &GasFee) -> Option<NaiveDateTime> {
+        Some(hour_bucket(gas_fee.transaction_timestamp))
+    }
+
+    fn key(&self) -> NaiveDateTime {
+        self.bucket_start
+    }
+
+    fn aggregate(&self) -> GasFeeAggregate {
+        row_aggregate!(self)
+    }
+
+    fn from_aggregate(gas_fee: &GasFee, aggregate: GasFeeAggregate) -> Self {
+        Self {
+            bucket_start: hour_bucket(gas_fee.transaction_timestamp),
+            transaction_count: aggregate.transaction_count,
+            median_gas_unit_price: aggregate.gas_unit_price_percentile(50),
+            p95_gas_unit_price: aggregate.gas_unit_price_percentile(95),
+            gas_unit_price_histogram: aggregate.histogram_to_json(),
+            total_fee: aggregate.total_fee,
+            total_storage_refund: aggregate.total_storage_refund,
+            fee_payer_transaction_count: aggregate.fee_payer_transaction_count,
+            last_transaction_version: aggregate.last_transaction_version,
+        }
+    }
+}
+
+/// Hourly rollup per entry function. Script transactions have no entry function and aren't part
+/// of it.
+#[derive(Clone, Debug, Deserialize, FieldCount, Insertable, Queryable, Selectable, Serialize)]
+#[diesel(primary_key(bucket_start, entry_function_id_str))]
+#[diesel(table_name = gas_fee_entry_function_hourly_summaries)]
+pub struct GasFeeEntryFunctionHourlySummary {
+    pub bucket_start: NaiveDateTime,
+    pub entry_function_id_str: String,
+    pub transaction_count: i64,
+    pub total_fee: BigDecimal,
+    pub total_storage_refund: BigDecimal,
+    pub fee_payer_transaction_count: i64,
+    pub median_gas_unit_price: i64,
+    pub p95_gas_unit_price: i64,
+    pub gas_unit_price_histogram: serde_json::Value,
+    pub last_transaction_version: i64,
+}
+
+impl GasFeeRollup for GasFeeEntryFunctionHourlySummary {
+    type Key = (NaiveDateTime, String);
+
+    fn bucket(gas_fee: &GasFee) -> Option<(NaiveDateTime, String)> {
+        gas_fee
+            .entry_function_id_str
+            .clone()
+            .map(|entry_function_id_str| {
+                (
+                    hour_bucket(gas_fee.transaction_timestamp),
+                    entry_function_id_str,
+                )
+            })
+    }
+
+    fn key(&self) -> (NaiveDateTime, String) {
+        (self.bucket_start, self.entry_function_id_str.clone())
+    }
+
+    fn aggregate(&self) -> GasFeeAggregate {
+        row_aggregate!(self)
+    }
+
+    fn from_aggregate(gas_fee: &GasFee, aggregate: GasFeeAggregate) -> Self {
+        Self {
+            bucket_start: hour_bucket(gas_fee.transaction_timestamp),
+            entry_function_id_str: gas_fee.entry_function_id_str.clone().unwrap_or_default(),
+            transaction_count: aggregate.transaction_count,
+            median_gas_unit_price: aggregate.gas_unit_price_percentile(50),
+            p95_gas_unit_price: aggregate.gas_unit_price_percentile(95),
+            gas_unit_price_histogram: aggregate.histogram_to_json(),
+            total_fee: aggregate.total_fee,
+            total_storage_refund: aggregate.total_storage_refund,
+            fee_payer_transaction_count: aggregate.fee_payer_transaction_count,
+            last_transaction_version: aggregate.last_transaction_version,
+        }
+    }
+}
+
+// Parquet models. Parquet files can't be updated, so buckets are rolled up per batch: a bucket
+// spanning batches has a row per batch. Rows of a bucket are merged by summing the counts, totals
+// and histograms, the percentiles of a row only cover its batch.
+#[derive(
+    Allocative, Clone, Debug, Default, Deserialize, FieldCount, ParquetRecordWriter, Serialize,
+)]
+pub struct ParquetGasFeeBlockSummary {
+    pub block_height: i64,
+    #[allocative(skip)]
+    pub block_timestamp: NaiveDateTime,
+    pub transaction_count: i64,
+    pub total_fee: String,            // BigDecimal
+    pub total_storage_refund: String, // BigDecimal
+    pub fee_payer_transaction_count: i64,
+    pub median_gas_unit_price: i64,
+    pub p95_gas_unit_price: i64,
+    pub gas_unit_price_histogram: String, // json
+    pub last_transaction_version: i64,
+}
+
+impl NamedTable for ParquetGasFeeBlockSummary {
+    const TABLE_NAME: &'static str = "gas_fee_block_summaries";
+}
+
+impl HasVersion for ParquetGasFeeBlockSummary {
+    fn version(&self) -> i64 {
+        self.last_transaction_version
+    }
+}
+
+impl From<GasFeeBlockSummary> for ParquetGasFeeBlockSummary {
+    fn from(base: GasFeeBlockSummary) -> Self {
+        Self {
+            block_height: base.block_height,
+            block_timestamp: base.block_timestamp,
+            transaction_count: base.transaction_count,
+            total_fee: base.total_fee.to_string(),
+            total_storage_refund: base.total_storage_refund.to_string(),
+            fee_payer_transaction_count: base.fee_payer_transaction_count,
+            median_gas_unit_price: base.median_gas_unit_price,
+            p95_gas_unit_price: base.p95_gas_unit_price,
+            gas_unit_price_histogram: base.gas_unit_price_histogram.to_string(),
+            last_transaction_version: base.last_transaction_version,
+        }
+    }
+}
+
+#[derive(
+    Allocative, Clone, Debug, Default, Deserialize, FieldCount, ParquetRecordWriter, Serialize,
+)]
+pub struct ParquetGasFeeHourlySummary {
+    #[allocative(skip)]
+    pub bucket_start: NaiveDateTime,
+    pub transaction_count: i64,
+    pub total_fee: String,            // BigDecimal
+    pub total_storage_refund: String, // BigDecimal
+    pub fee_payer_transaction_count: i64,
+    pub median_gas_unit_price: i64,
+    pub p95_gas_unit_price: i64,
+    pub gas_unit_price_histogram: String, // json
+    pub last_transaction_version: i64,
+}
+
+impl NamedTable for ParquetGasFeeHourlySummary {
+    const TABLE_NAME: &'static str = "gas_fee_hourly_summaries";
+}
+
+impl HasVersion for ParquetGasFeeHourlySummary {
+    fn version(&self) -> i64 {
+        self.last_transaction_version
+    }
+}
+
+impl From<GasFeeHourlySummary> for ParquetGasFeeHourlySummary {
+    fn from(base: GasFeeHourlySummary) -> Self {
+        Self {
+            bucket_start: base.bucket_start,
+            transaction_count: base.transaction_count,
+            total_fee: base.total_fee.to_string(),
+            total_storage_refund: base.total_storage_refund.to_string(),
+            fee_payer_transaction_count: base.fee_payer_transaction_count,
+            median_gas_unit_price: base.median_gas_unit_price,
+            p95_gas_unit_price: base.p95_gas_unit_price,
+            gas_unit_price_histogram: base.gas_unit_price_histogram.to_string(),
+            last_transaction_version: base.last_transaction_version,
+        }
+    }
+}
+
+#[derive(
+    Allocative, Clone, Debug, Default, Deserialize, FieldCount, ParquetRecordWriter, Serialize,
+)]
+pub struct ParquetGasFeeEntryFunctionHourlySummary {
+    #[allocative(skip)]
+    pub bucket_start: NaiveDateTime,
+    pub entry_function_id_str: String,
+    pub transaction_count: i64,
+    pub total_fee: String,            // BigDecimal
+    pub total_storage_refund: String, // BigDecimal
+    pub fee_payer_transaction_count: i64,
+    pub median_gas_unit_price: i64,
+    pub p95_gas_unit_price: i64,
+    pub gas_unit_price_histogram: String, // json
+    pub last_transaction_version: i64,
+}
+
+impl NamedTable for ParquetGasFeeEntryFunctionHourlySummary {
+    const TABLE_NAME: &'static str = "gas_fee_entry_function_hourly_summaries";
+}
+
+impl HasVersion for ParquetGasFeeEntryFunctionHourlySummary {
+    fn version(&self) -> i64 {
+        self.last_transaction_version
+    }
+}
+
+impl From<GasFeeEntryFunctionHourlySummary> for ParquetGasFeeEntryFunctionHourlySummary {
+    fn from(base: GasFeeEntryFunctionHourlySummary) -> Self {
+        Self {
+            bucket_start: base.bucket_start,
+            entry_function_id_str: base.entry_function_id_str,
+            transaction_count: base.transaction_count,
+            total_fee: base.total_fee.to_string(),
+            total_storage_refund: base.total_storage_refund.to_string(),
+            fee_payer_transaction_count: base.fee_payer_transaction_count,
+            median_gas_unit_price: base.median_gas_unit_price,
+            p95_gas_unit_price: base.p95_gas_unit_price,
+            gas_unit_price_histogram: base.gas_unit_price_histogram.to_string(),
+            last_transaction_version: base.last_transaction_version,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn gas_fee(
+        transaction_version: i64,
+        block_height: i64,
+        minute: u32,
+        gas_unit_price: i64,
+        entry_function_id_str: Option<&str>,
+    ) -> GasFee {
+        GasFee {
+            transaction_version,
+            owner_address: Some("0x1".to_string()),
+            amount: Some(BigDecimal::from(gas_unit_price * 10)),
+            gas_fee_payer_address: (transaction_version % 2 == 0).then(|| "0x2".to_string()),
+            is_transaction_success: true,
+            entry_function_id_str: entry_function_id_str.map(str::to_string),
+            block_height,
+            transaction_timestamp: NaiveDate::from_ymd_opt(2025, 7, 1)
+                .unwrap()
+                .and_hms_opt(10, minute, 0)
+                .unwrap(),
+            storage_refund_amount: BigDecimal::from(1),
+            gas_unit_price,
+            total_charge_gas_units: Some(BigDecimal::from(10)),
+            execution_gas_units: Some(BigDecimal::from(4)),
+            io_gas_units: Some(BigDecimal::from(3)),
+            storage_fee_octas: Some(BigDecimal::from(300)),
+        }
+    }
+
+    #[test]
+    fn test_gas_unit_price_percentiles() {
+        let mut aggregate = GasFeeAggregate::default();
+        assert_eq!(aggregate.gas_unit_price_percentile(50), 0);
+        for version in 0..100 {
+            aggregate.add(&gas_fee(
+                version,
+                1,
+                0,
+                if version < 90 { 100 } else { 150 },
+                None,
+            ));
+        }
+        assert_eq!(aggregate.gas_unit_price_percentile(50), 100);
+        assert_eq!(aggregate.gas_unit_price_percentile(95), 150);
+    }
+
+    #[test]
+    fn test_roll_up_is_idempotent() {
+        let gas_fees = vec![
+            gas_fee(10, 1, 5, 100, Some("0x1::aptos_account::transfer")),
+            gas_fee(11, 1, 5, 200, None),
+            gas_fee(12, 2, 59, 100, Some("0x1::aptos_account::transfer")),
+        ];
+        let blocks = roll_up::<GasFeeBlockSummary>(&gas_fees, vec![]);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].transaction_count, 2);
+        assert_eq!(blocks[0].total_fee, BigDecimal::from(3000));
+        assert_eq!(blocks[0].fee_payer_transaction_count, 1);
+        assert_eq!(blocks[0].last_transaction_version, 11);
+
+        let hours = roll_up::<GasFeeHourlySummary>(&gas_fees, vec![]);
+        assert_eq!(hours.len(), 1);
+        assert_eq!(hours[0].transaction_count, 3);
+        let entry_functions = roll_up::<GasFeeEntryFunctionHourlySummary>(&gas_fees, vec![]);
+        assert_eq!(entry_functions.len(), 1);
+        assert_eq!(entry_functions[0].transaction_count, 2);
+
+        // Replaying the batch, even partially, doesn't change the rollup
+        let replayed = roll_up::<GasFeeHourlySummary>(&gas_fees[1..], hours.clone());
+        assert_eq!(replayed[0].aggregate(), hours[0].aggregate());
+
+        let next = roll_up::<GasFeeHourlySummary>(&[gas_fee(13, 3, 30, 300, None)], hours);
+        assert_eq!(next[0].transaction_count, 4);
+        assert_eq!(next[0].p95_gas_unit_price, 300);
+    }
+}
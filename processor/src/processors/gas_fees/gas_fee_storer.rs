@@ -1,10 +1,16 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use super::models::GasFee;
+use super::{
+    gas_fee_rollups::{
+        roll_up, GasFeeBlockSummary, GasFeeEntryFunctionHourlySummary, GasFeeHourlySummary,
+        GasFeeRollup,
+    },
+    models::GasFee,
+};
 use crate::{
     config::processor_config::DefaultProcessorConfig,
-    schema,
+    filter_datasets, schema,
     utils::table_flags::{filter_data, TableFlags},
 };
 use ahash::AHashMap;
@@ -16,7 +22,13 @@ use aptos_indexer_processor_sdk::{
     utils::errors::ProcessorError,
 };
 use async_trait::async_trait;
-use diesel::{pg::Pg, query_builder::QueryFragment};
+use diesel::{
+    pg::{upsert::excluded, Pg},
+    query_builder::QueryFragment,
+    query_dsl::methods::{FilterDsl, SelectDsl},
+    ExpressionMethods, SelectableHelper,
+};
+use diesel_async::RunQueryDsl;
 
 pub struct GasFeeStorer
 where
@@ -39,6 +51,63 @@ impl GasFeeStorer {
             tables_to_write,
         }
     }
+
+    /// Rolls the gas fees up on top of the stored rows of their buckets.
+    async fn roll_up_batch(
+        &self,
+        gas_fees: &[GasFee],
+    ) -> Result<(
+        Vec<GasFeeBlockSummary>,
+        Vec<GasFeeHourlySummary>,
+        Vec<GasFeeEntryFunctionHourlySummary>,
+    )> {
+        let mut block_heights: Vec<i64> = gas_fees.iter().map(|fee| fee.block_height).collect();
+        block_heights.dedup();
+        let mut hours: Vec<_> = gas_fees
+            .iter()
+            .filter_map(GasFeeHourlySummary::bucket)
+            .collect();
+        hours.dedup();
+        let mut entry_functions: Vec<String> = gas_fees
+            .iter()
+            .filter_map(|fee| fee.entry_function_id_str.clone())
+            .collect();
+        entry_functions.sort();
+        entry_functions.dedup();
+
+        let mut conn = self.conn_pool.get().await?;
+        let block_summaries = {
+            use schema::gas_fee_block_summaries::dsl::*;
+            gas_fee_block_summaries
+                .filter(block_height.eq_any(block_heights))
+                .select(GasFeeBlockSummary::as_select())
+                .load(&mut conn)
+                .await?
+        };
+        let hourly_summaries = {
+            use schema::gas_fee_hourly_summaries::dsl::*;
+            gas_fee_hourly_summaries
+                .filter(bucket_start.eq_any(hours.clone()))
+                .select(GasFeeHourlySummary::as_select())
+                .load(&mut conn)
+                .await?
+        };
+        let entry_function_summaries = {
+            use schema::gas_fee_entry_function_hourly_summaries::dsl::*;
+            gas_fee_entry_function_hourly_summaries
+                .filter(bucket_start.eq_any(hours))
+                .filter(entry_function_id_str.eq_any(entry_functions))
+                .select(GasFeeEntryFunctionHourlySummary::as_select())
+                .load(&mut conn)
+                .await?
+        };
+
+        Ok((
+            roll_up(gas_fees, block_summaries),
+            roll_up(gas_fees, hourly_summaries),
+            roll_up(gas_fees, entry_function_summaries),
+        ))
+    }
 }
 
 #[async_trait]
@@ -56,7 +125,23 @@ impl Processable for GasFeeStorer {
         let per_table_chunk_sizes: AHashMap<String, usize> =
             self.processor_config.per_table_chunk_sizes.clone();
 
-        let gas_fees = filter_data(&self.tables_to_write, TableFlags::GAS_FEES, gas_fees);
+        let (block_summaries, hourly_summaries, entry_function_summaries) = self
+            .roll_up_batch(&gas_fees)
+            .await
+            .map_err(|e| ProcessorError::DBStoreError {
+                message: format!(
+                    "Failed to load gas fee rollups for versions {} to {}: {:?}",
+                    input.metadata.start_version, input.metadata.end_version, e,
+                ),
+                query: None,
+            })?;
+
+        let (gas_fees, block_summaries, hourly_summaries, entry_function_summaries) = filter_datasets!(self, {
+            gas_fees => TableFlags::GAS_FEES,
+            block_summaries => TableFlags::GAS_FEE_BLOCK_SUMMARIES,
+            hourly_summaries => TableFlags::GAS_FEE_HOURLY_SUMMARIES,
+            entry_function_summaries => TableFlags::GAS_FEE_ENTRY_FUNCTION_HOURLY_SUMMARIES,
+        });
 
         let gf = execute_in_chunks(
             self.conn_pool.clone(),
@@ -64,8 +149,35 @@ impl Processable for GasFeeStorer {
             &gas_fees,
             get_config_table_chunk_size::<GasFee>("gas_fees", &per_table_chunk_sizes),
         );
+        let gfbs = execute_in_chunks(
+            self.conn_pool.clone(),
+            insert_gas_fee_block_summaries_query,
+            &block_summaries,
+            get_config_table_chunk_size::<GasFeeBlockSummary>(
+                "gas_fee_block_summaries",
+                &per_table_chunk_sizes,
+            ),
+        );
+        let gfhs = execute_in_chunks(
+            self.conn_pool.clone(),
+            insert_gas_fee_hourly_summaries_query,
+            &hourly_summaries,
+            get_config_table_chunk_size::<GasFeeHourlySummary>(
+                "gas_fee_hourly_summaries",
+                &per_table_chunk_sizes,
+            ),
+        );
+        let gfefhs = execute_in_chunks(
+            self.conn_pool.clone(),
+            insert_gas_fee_entry_function_hourly_summaries_query,
+            &entry_function_summaries,
+            get_config_table_chunk_size::<GasFeeEntryFunctionHourlySummary>(
+                "gas_fee_entry_function_hourly_summaries",
+                &per_table_chunk_sizes,
+            ),
+        );
 
-        match gf.await {
+        match futures::try_join!(gf, gfbs, gfhs, gfefhs) {
             Ok(_) => {},
             Err(e) => {
                 return Err(ProcessorError::DBStoreError {
@@ -103,3 +215,73 @@ fn insert_gas_fee_query(
         .on_conflict(transaction_version)
         .do_nothing()
 }
+
+fn insert_gas_fee_block_summaries_query(
+    items_to_insert: Vec<GasFeeBlockSummary>,
+) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
+    use schema::gas_fee_block_summaries::dsl::*;
+
+    diesel::insert_into(schema::gas_fee_block_summaries::table)
+        .values(items_to_insert)
+        .on_conflict(block_height)
+        .do_update()
+        .set((
+            block_timestamp.eq(excluded(block_timestamp)),
+            transaction_count.eq(excluded(transaction_count)),
+            total_fee.eq(excluded(total_fee)),
+            total_storage_refund.eq(excluded(total_storage_refund)),
+            fee_payer_transaction_count.eq(excluded(fee_payer_transaction_count)),
+            median_gas_unit_price.eq(excluded(median_gas_unit_price)),
+            p95_gas_unit_price.eq(excluded(p95_gas_unit_price)),
+            gas_unit_price_histogram.eq(excluded(gas_unit_price_histogram)),
+            last_transaction_version.eq(excluded(last_transaction_version)),
+            inserted_at.eq(excluded(inserted_at)),
+        ))
+        .filter(last_transaction_version.le(excluded(last_transaction_version)))
+}
+
+fn insert_gas_fee_hourly_summaries_query(
+    items_to_insert: Vec<GasFeeHourlySummary>,
+) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
+    use schema::gas_fee_hourly_summaries::dsl::*;
+
+    diesel::insert_into(schema::gas_fee_hourly_summaries::table)
+        .values(items_to_insert)
+        .on_conflict(bucket_start)
+        .do_update()
+        .set((
+            transaction_count.eq(excluded(transaction_count)),
+            total_fee.eq(excluded(total_fee)),
+            total_storage_refund.eq(excluded(total_storage_refund)),
+            fee_payer_transaction_count.eq(excluded(fee_payer_transaction_count)),
+            median_gas_unit_price.eq(excluded(median_gas_unit_price)),
+            p95_gas_unit_price.eq(excluded(p95_gas_unit_price)),
+            gas_unit_price_histogram.eq(excluded(gas_unit_price_histogram)),
+            last_transaction_version.eq(excluded(last_transaction_version)),
+            inserted_at.eq(excluded(inserted_at)),
+        ))
+        .filter(last_transaction_version.le(excluded(last_transaction_version)))
+}
+
+fn insert_gas_fee_entry_function_hourly_summaries_query(
+    items_to_insert: Vec<GasFeeEntryFunctionHourlySummary>,
+) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
+    use schema::gas_fee_entry_function_hourly_summaries::dsl::*;
+
+    diesel::insert_into(schema::gas_fee_entry_function_hourly_summaries::table)
+        .values(items_to_insert)
+        .on_conflict((bucket_start, entry_function_id_str))
+        .do_update()
+        .set((
+            transaction_count.eq(excluded(transaction_count)),
+            total_fee.eq(excluded(total_fee)),
+            total_storage_refund.eq(excluded(total_storage_refund)),
+            fee_payer_transaction_count.eq(excluded(fee_payer_transaction_count)),
+            median_gas_unit_price.eq(excluded(median_gas_unit_price)),
+            p95_gas_unit_price.eq(excluded(p95_gas_unit_price)),
+            gas_unit_price_histogram.eq(excluded(gas_unit_price_histogram)),
+            last_transaction_version.eq(excluded(last_transaction_version)),
+            inserted_at.eq(excluded(inserted_at)),
+        ))
+        .filter(last_transaction_version.le(excluded(last_transaction_version)))
+}
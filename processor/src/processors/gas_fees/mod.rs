@@ -1,4 +1,5 @@
 pub mod gas_fee_extractor;
 pub mod gas_fee_processor;
+pub mod gas_fee_rollups;
 pub mod gas_fee_storer;
 pub mod models;
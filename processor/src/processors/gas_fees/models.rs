@@ -1,23 +1,26 @@
 use crate::{
+    parquet_processors::parquet_utils::util::{HasVersion, NamedTable},
     processors::{
         fungible_asset::fungible_asset_models::v2_fungible_asset_utils::FeeStatement,
         user_transaction::models::signature_utils::parent_signature_utils::get_fee_payer_address,
     },
     schema::gas_fees,
 };
+use allocative_derive::Allocative;
 use aptos_indexer_processor_sdk::{
     aptos_indexer_transaction_stream::utils::time::parse_timestamp,
     aptos_protos::transaction::v1::{
         transaction::TxnData, Transaction, TransactionInfo, UserTransactionRequest,
     },
     utils::{
-        convert::{standardize_address, u64_to_bigdecimal},
+        convert::{bigdecimal_to_u64, standardize_address, u64_to_bigdecimal},
         extract::get_entry_function_from_user_request,
     },
 };
 use bigdecimal::{BigDecimal, Zero};
 use chrono::NaiveDateTime;
 use field_count::FieldCount;
+use parquet_derive::ParquetRecordWriter;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
@@ -33,6 +36,11 @@ pub struct GasFee {
     pub block_height: i64,
     pub transaction_timestamp: NaiveDateTime,
     pub storage_refund_amount: BigDecimal,
+    pub gas_unit_price: i64,
+    pub total_charge_gas_units: Option<BigDecimal>,
+    pub execution_gas_units: Option<BigDecimal>,
+    pub io_gas_units: Option<BigDecimal>,
+    pub storage_fee_octas: Option<BigDecimal>,
 }
 
 impl GasFee {
@@ -106,8 +114,73 @@ impl GasFee {
             block_height,
             transaction_timestamp,
             storage_refund_amount: fee_statement
+                .as_ref()
                 .map(|fs| u64_to_bigdecimal(fs.storage_fee_refund_octas))
                 .unwrap_or(BigDecimal::zero()),
+            gas_unit_price: user_transaction_request.gas_unit_price as i64,
+            total_charge_gas_units: fee_statement
+                .as_ref()
+                .map(|fs| u64_to_bigdecimal(fs.total_charge_gas_units)),
+            execution_gas_units: fee_statement
+                .as_ref()
+                .map(|fs| u64_to_bigdecimal(fs.execution_gas_units)),
+            io_gas_units: fee_statement
+                .as_ref()
+                .map(|fs| u64_to_bigdecimal(fs.io_gas_units)),
+            storage_fee_octas: fee_statement.map(|fs| u64_to_bigdecimal(fs.storage_fee_octas)),
+        }
+    }
+}
+
+// Parquet models
+#[derive(
+    Allocative, Clone, Debug, Default, Deserialize, FieldCount, ParquetRecordWriter, Serialize,
+)]
+pub struct ParquetGasFee {
+    pub txn_version: i64,
+    pub owner_address: Option<String>,
+    pub amount: Option<String>, // BigDecimal
+    pub gas_fee_payer_address: Option<String>,
+    pub is_transaction_success: bool,
+    pub entry_function_id_str: Option<String>,
+    pub block_height: i64,
+    pub storage_refund_amount: String, // BigDecimal
+    #[allocative(skip)]
+    pub block_timestamp: chrono::NaiveDateTime,
+    pub gas_unit_price: i64,
+    pub total_charge_gas_units: Option<u64>,
+    pub execution_gas_units: Option<u64>,
+    pub io_gas_units: Option<u64>,
+    pub storage_fee_octas: Option<u64>,
+}
+
+impl NamedTable for ParquetGasFee {
+    const TABLE_NAME: &'static str = "gas_fees";
+}
+
+impl HasVersion for ParquetGasFee {
+    fn version(&self) -> i64 {
+        self.txn_version
+    }
+}
+
+impl From<GasFee> for ParquetGasFee {
+    fn from(base: GasFee) -> Self {
+        Self {
+            txn_version: base.transaction_version,
+            owner_address: base.owner_address,
+            amount: base.amount.map(|amount| amount.to_string()),
+            gas_fee_payer_address: base.gas_fee_payer_address,
+            is_transaction_success: base.is_transaction_success,
+            entry_function_id_str: base.entry_function_id_str,
+            block_height: base.block_height,
+            storage_refund_amount: base.storage_refund_amount.to_string(),
+            block_timestamp: base.transaction_timestamp,
+            gas_unit_price: base.gas_unit_price,
+            total_charge_gas_units: base.total_charge_gas_units.as_ref().map(bigdecimal_to_u64),
+            execution_gas_units: base.execution_gas_units.as_ref().map(bigdecimal_to_u64),
+            io_gas_units: base.io_gas_units.as_ref().map(bigdecimal_to_u64),
+            storage_fee_octas: base.storage_fee_octas.as_ref().map(bigdecimal_to_u64),
         }
     }
 }
@@ -1,6 +1,7 @@
 pub mod account_restoration;
 pub mod account_transactions;
 pub mod ans;
+pub mod data_quality;
 pub mod default;
 pub mod events;
 pub mod fungible_asset;
@@ -9,5 +10,6 @@ pub mod monitoring;
 pub mod objects;
 pub mod processor_status_saver;
 pub mod stake;
+pub mod storage_usage;
 pub mod token_v2;
 pub mod user_transaction;
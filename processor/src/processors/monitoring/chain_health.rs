@@ -0,0 +1,337 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+
+use crate::schema::chain_health_snapshots;
+use aptos_indexer_processor_sdk::{
+    aptos_indexer_transaction_stream::utils::time::parse_timestamp,
+    aptos_protos::transaction::v1::{transaction::TxnData, Transaction},
+    utils::convert::standardize_address,
+};
+use bigdecimal::{BigDecimal, ToPrimitive, Zero};
+use chrono::NaiveDateTime;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Digits kept for the rates of a snapshot.
+const RATE_SCALE: i64 = 4;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChainHealthConfig {
+    // Write a snapshot per batch to `chain_health_snapshots`, metrics are always exported
+    #[serde(default)]
+    pub write_snapshots: bool,
+    #[serde(default)]
+    pub thresholds: ChainHealthThresholds,
+}
+
+/// Thresholds that raise an alert when breached by a batch. Unset thresholds are not checked.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChainHealthThresholds {
+    pub max_block_interval_ms: Option<i64>,
+    pub min_tps: Option<f64>,
+    pub max_failed_transaction_rate: Option<f64>,
+    pub max_failed_proposals: Option<i64>,
+    pub max_stream_lag_ms: Option<i64>,
+}
+
+impl ChainHealthThresholds {
+    /// Names of the thresholds breached by the snapshot.
+    pub fn breaches(&self, snapshot: &ChainHealthSnapshot) -> Vec<&'static str> {
+        let mut breaches = vec![];
+        if let (Some(max), Some(interval)) =
+            (self.max_block_interval_ms, snapshot.max_block_interval_ms)
+        {
+            if interval > max {
+                breaches.push("block_interval");
+            }
+        }
+        // A batch spanning a single timestamp has no meaningful rate
+        if let Some(min) = self.min_tps {
+            if snapshot.end_timestamp > snapshot.start_timestamp
+                && snapshot.tps.to_f64().unwrap_or_default() < min
+            {
+                breaches.push("tps");
+            }
+        }
+        if let Some(max) = self.max_failed_transaction_rate {
+            if snapshot
+                .failed_transaction_rate
+                .to_f64()
+                .unwrap_or_default()
+                > max
+            {
+                breaches.push("failed_transaction_rate");
+            }
+        }
+        if let Some(max) = self.max_failed_proposals {
+            if snapshot.failed_proposal_count > max {
+                breaches.push("failed_proposals");
+            }
+        }
+        if let Some(max) = self.max_stream_lag_ms {
+            if snapshot.stream_lag_ms > max {
+                breaches.push("stream_lag");
+            }
+        }
+        breaches
+    }
+}
+
+/// Health of the chain over a batch of transactions.
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(start_version))]
+#[diesel(table_name = chain_health_snapshots)]
+pub struct ChainHealthSnapshot {
+    pub start_version: i64,
+    pub end_version: i64,
+    pub start_block_height: i64,
+    pub end_block_height: i64,
+    pub start_timestamp: NaiveDateTime,
+    pub end_timestamp: NaiveDateTime,
+    pub epoch: i64,
+    pub epoch_changes: i64,
+    pub block_count: i64,
+    pub avg_block_interval_ms: Option<i64>,
+    pub max_block_interval_ms: Option<i64>,
+    pub transaction_count: i64,
+    pub user_transaction_count: i64,
+    pub failed_transaction_count: i64,
+    // User transactions per second
+    pub tps: BigDecimal,
+    // Share of user transactions that failed
+    pub failed_transaction_rate: BigDecimal,
+    pub failed_proposal_count: i64,
+    // Number of blocks proposed by each validator
+    pub proposer_counts: serde_json::Value,
+    pub stream_lag_ms: i64,
+}
+
+/// Builds chain health snapshots from consecutive batches. Block intervals and epoch changes
+/// carry over from the previous batch, so batches have to be fed in order.
+#[derive(Debug, Default)]
+pub struct ChainHealthTracker {
+    last_block_timestamp: Option<NaiveDateTime>,
+    last_epoch: Option<i64>,
+}
+
+impl ChainHealthTracker {
+    /// Snapshot of the batch, `None` if it is empty. `now` is used for the stream lag.
+    pub fn snapshot(
+        &mut self,
+        transactions: &[Transaction],
+        now: NaiveDateTime,
+    ) -> Option<ChainHealthSnapshot> {
+        let first = transactions.first()?;
+        let last = transactions.last()?;
+        let timestamp = |txn: &Transaction| {
+            parse_timestamp(txn.timestamp.as_ref().unwrap(), txn.version as i64).naive_utc()
+        };
+        let start_timestamp = timestamp(first);
+        let end_timestamp = timestamp(last);
+
+        let mut epoch_changes = 0;
+        let mut block_count = 0;
+        let mut block_intervals = vec![];
+        let mut user_transaction_count = 0;
+        let mut failed_transaction_count = 0;
+        let mut failed_proposal_count = 0;
+        let mut proposer_counts: BTreeMap<String, i64> = BTreeMap::new();
+
+        for txn in transactions {
+            let epoch = txn.epoch as i64;
+            if self
+                .last_epoch
+                .is_some_and(|last_epoch| last_epoch != epoch)
+            {
+                epoch_changes += 1;
+            }
+            self.last_epoch = Some(epoch);
+
+            match txn.txn_data.as_ref() {
+                Some(TxnData::BlockMetadata(block_metadata)) => {
+                    let block_timestamp = timestamp(txn);
+                    if let Some(last_block_timestamp) = self.last_block_timestamp {
+                        block_intervals
+                            .push((block_timestamp - last_block_timestamp).num_milliseconds());
+                    }
+                    self.last_block_timestamp = Some(block_timestamp);
+                    block_count += 1;
+                    failed_proposal_count += block_metadata.failed_proposer_indices.len() as i64;
+                    *proposer_counts
+                        .entry(standardize_address(&block_metadata.proposer))
+                        .or_default() += 1;
+                },
+                Some(TxnData::User(_)) => {
+                    user_transaction_count += 1;
+                    if !txn.info.as_ref().is_some_and(|info| info.success) {
+                        failed_transaction_count += 1;
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        let duration_ms = (end_timestamp - start_timestamp).num_milliseconds();
+        let tps = if duration_ms > 0 {
+            (BigDecimal::from(user_transaction_count * 1000) / BigDecimal::from(duration_ms))
+                .with_scale(RATE_SCALE)
+        } else {
+            BigDecimal::zero()
+        };
+        let failed_transaction_rate = if user_transaction_count > 0 {
+            (BigDecimal::from(failed_transaction_count) / BigDecimal::from(user_transaction_count))
+                .with_scale(RATE_SCALE)
+        } else {
+            BigDecimal::zero()
+        };
+
+        Some(ChainHealthSnapshot {
+            start_version: first.version as i64,
+            end_version: last.version as i64,
+            start_block_height: first.block_height as i64,
+            end_block_height: last.block_height as i64,
+            start_timestamp,
+            end_timestamp,
+            epoch: last.epoch as i64,
+            epoch_changes,
+            block_count,
+            avg_block_interval_ms: (!block_intervals.is_empty())
+                .then(|| block_intervals.iter().sum::<i64>() / block_intervals.len() as i64),
+            max_block_interval_ms: block_intervals.iter().max().copied(),
+            transaction_count: transactions.len() as i64,
+            user_transaction_count,
+            failed_transaction_count,
+            tps,
+            failed_transaction_rate,
+            failed_proposal_count,
+            proposer_counts: serde_json::to_value(&proposer_counts).unwrap(),
+            stream_lag_ms: (now - end_timestamp).num_milliseconds(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_indexer_processor_sdk::aptos_protos::{
+        transaction::v1::{BlockMetadataTransaction, TransactionInfo, UserTransaction},
+        util::timestamp::Timestamp,
+    };
+    use chrono::DateTime;
+    use std::str::FromStr;
+
+    fn transaction(version: u64, epoch: u64, millis: i64, txn_data: TxnData) -> Transaction {
+        Transaction {
+            version,
+            epoch,
+            block_height: version,
+            timestamp: Some(Timestamp {
+                seconds: millis / 1000,
+                nanos: ((millis % 1000) * 1_000_000) as i32,
+            }),
+            info: Some(TransactionInfo {
+                success: version % 4 != 3,
+                ..Default::default()
+            }),
+            txn_data: Some(txn_data),
+            ..Default::default()
+        }
+    }
+
+    fn block(version: u64, epoch: u64, millis: i64, proposer: &str) -> Transaction {
+        transaction(
+            version,
+            epoch,
+            millis,
+            TxnData::BlockMetadata(BlockMetadataTransaction {
+                proposer: proposer.to_string(),
+                failed_proposer_indices: vec![0],
+                ..Default::default()
+            }),
+        )
+    }
+
+    fn user(version: u64, epoch: u64, millis: i64) -> Transaction {
+        transaction(
+            version,
+            epoch,
+            millis,
+            TxnData::User(UserTransaction::default()),
+        )
+    }
+
+    fn at(millis: i64) -> NaiveDateTime {
+        DateTime::from_timestamp_millis(millis).unwrap().naive_utc()
+    }
+
+    #[test]
+    fn test_snapshot_carries_over_batches() {
+        let mut tracker = ChainHealthTracker::default();
+        let first = tracker
+            .snapshot(
+                &[
+                    block(0, 1, 1_000, "0xa"),
+                    user(1, 1, 1_000),
+                    user(2, 1, 1_000),
+                    user(3, 1, 1_000),
+                    block(4, 1, 1_300, "0xb"),
+                    user(5, 1, 1_300),
+                ],
+                at(2_000),
+            )
+            .unwrap();
+        assert_eq!(first.block_count, 2);
+        assert_eq!(first.max_block_interval_ms, Some(300));
+        assert_eq!(first.user_transaction_count, 4);
+        assert_eq!(first.failed_transaction_count, 1);
+        assert_eq!(first.failed_transaction_rate, BigDecimal::from(1) / 4);
+        assert_eq!(first.tps, BigDecimal::from_str("13.3333").unwrap());
+        assert_eq!(first.failed_proposal_count, 2);
+        assert_eq!(first.stream_lag_ms, 700);
+
+        let second = tracker
+            .snapshot(
+                &[block(6, 2, 1_800, "0xa"), block(7, 2, 2_000, "0xa")],
+                at(2_000),
+            )
+            .unwrap();
+        assert_eq!(second.epoch_changes, 1);
+        assert_eq!(second.avg_block_interval_ms, Some(350));
+        assert_eq!(second.max_block_interval_ms, Some(500));
+        assert_eq!(
+            second.proposer_counts,
+            serde_json::json!({ standardize_address("0xa"): 2 })
+        );
+        assert_eq!(second.tps, BigDecimal::zero());
+    }
+
+    #[test]
+    fn test_breaches() {
+        let snapshot = ChainHealthTracker::default()
+            .snapshot(
+                &[
+                    block(0, 1, 1_000, "0xa"),
+                    user(3, 1, 1_000),
+                    user(4, 1, 2_000),
+                ],
+                at(10_000),
+            )
+            .unwrap();
+        let thresholds = ChainHealthThresholds {
+            min_tps: Some(5.0),
+            max_failed_transaction_rate: Some(0.1),
+            max_stream_lag_ms: Some(60_000),
+            ..Default::default()
+        };
+        assert_eq!(
+            thresholds.breaches(&snapshot),
+            vec!["tps", "failed_transaction_rate"]
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{
+    processors::monitoring::chain_health::{
+        ChainHealthSnapshot, ChainHealthThresholds, ChainHealthTracker,
+    },
+    utils::counters::{
+        CHAIN_HEALTH_ALERTS_COUNT, CHAIN_HEALTH_BLOCK_INTERVAL_MS, CHAIN_HEALTH_EPOCH,
+        CHAIN_HEALTH_EPOCH_CHANGES_COUNT, CHAIN_HEALTH_FAILED_PROPOSALS_COUNT,
+        CHAIN_HEALTH_FAILED_TRANSACTION_RATE, CHAIN_HEALTH_PROPOSED_BLOCKS_COUNT,
+        CHAIN_HEALTH_STREAM_LAG_MS, CHAIN_HEALTH_TPS,
+    },
+};
+use anyhow::Result;
+use aptos_indexer_processor_sdk::{
+    aptos_protos::transaction::v1::Transaction,
+    traits::{async_step::AsyncRunType, AsyncStep, NamedStep, Processable},
+    types::transaction_context::TransactionContext,
+    utils::errors::ProcessorError,
+};
+use async_trait::async_trait;
+use bigdecimal::ToPrimitive;
+use tracing::warn;
+
+/// Computes the chain health of each batch, exports it as metrics and alerts on breached
+/// thresholds.
+pub struct ChainHealthExtractor
+where
+    Self: Sized + Send + 'static,
+{
+    processor_name: String,
+    thresholds: ChainHealthThresholds,
+    tracker: ChainHealthTracker,
+}
+
+impl ChainHealthExtractor {
+    pub fn new(processor_name: String, thresholds: ChainHealthThresholds) -> Self {
+        Self {
+            processor_name,
+            thresholds,
+            tracker: ChainHealthTracker::default(),
+        }
+    }
+
+    fn export_metrics(&self, snapshot: &ChainHealthSnapshot) {
+        let processor_name = self.processor_name.as_str();
+        if let Some(avg) = snapshot.avg_block_interval_ms {
+            CHAIN_HEALTH_BLOCK_INTERVAL_MS
+                .with_label_values(&[processor_name, "avg"])
+                .set(avg);
+        }
+        if let Some(max) = snapshot.max_block_interval_ms {
+            CHAIN_HEALTH_BLOCK_INTERVAL_MS
+                .with_label_values(&[processor_name, "max"])
+                .set(max);
+        }
+        CHAIN_HEALTH_TPS
+            .with_label_values(&[processor_name])
+            .set(snapshot.tps.to_f64().unwrap_or_default());
+        CHAIN_HEALTH_FAILED_TRANSACTION_RATE
+            .with_label_values(&[processor_name])
+            .set(
+                snapshot
+                    .failed_transaction_rate
+                    .to_f64()
+                    .unwrap_or_default(),
+            );
+        CHAIN_HEALTH_EPOCH
+            .with_label_values(&[processor_name])
+            .set(snapshot.epoch);
+        CHAIN_HEALTH_EPOCH_CHANGES_COUNT
+            .with_label_values(&[processor_name])
+            .inc_by(snapshot.epoch_changes as u64);
+        CHAIN_HEALTH_FAILED_PROPOSALS_COUNT
+            .with_label_values(&[processor_name])
+            .inc_by(snapshot.failed_proposal_count as u64);
+        if let Some(proposer_counts) = snapshot.proposer_counts.as_object() {
+            for (proposer, count) in proposer_counts {
+                CHAIN_HEALTH_PROPOSED_BLOCKS_COUNT
+                    .with_label_values(&[processor_name, proposer])
+                    .inc_by(count.as_u64().unwrap_or_default());
+            }
+        }
+        CHAIN_HEALTH_STREAM_LAG_MS
+            .with_label_values(&[processor_name])
+            .set(snapshot.stream_lag_ms);
+    }
+
+    fn alert(&self, snapshot: &ChainHealthSnapshot) {
+        for breach in self.thresholds.breaches(snapshot) {
+            CHAIN_HEALTH_ALERTS_COUNT
+                .with_label_values(&[self.processor_name.as_str(), breach])
+                .inc();
+            warn!(
+                processor_name = self.processor_name,
+                alert = breach,
+                start_version = snapshot.start_version,
+                end_version = snapshot.end_version,
+                max_block_interval_ms = snapshot.max_block_interval_ms,
+                tps = %snapshot.tps,
+                failed_transaction_rate = %snapshot.failed_transaction_rate,
+                failed_proposal_count = snapshot.failed_proposal_count,
+                stream_lag_ms = snapshot.stream_lag_ms,
+                "Chain health threshold breached"
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl Processable for ChainHealthExtractor {
+    type Input = Vec<Transaction>;
+    type Output = Vec<ChainHealthSnapshot>;
+    type RunType = AsyncRunType;
+
+    async fn process(
+        &mut self,
+        transactions: TransactionContext<Vec<Transaction>>,
+    ) -> Result<Option<TransactionContext<Vec<ChainHealthSnapshot>>>, ProcessorError> {
+        let snapshot = self
+            .tracker
+            .snapshot(&transactions.data, chrono::Utc::now().naive_utc());
+        if let Some(snapshot) = &snapshot {
+            self.export_metrics(snapshot);
+            self.alert(snapshot);
+        }
+
+        Ok(Some(TransactionContext {
+            data: snapshot.into_iter().collect(),
+            metadata: transactions.metadata,
+        }))
+    }
+}
+
+impl AsyncStep for ChainHealthExtractor {}
+
+impl NamedStep for ChainHealthExtractor {
+    fn name(&self) -> String {
+        "chain_health_extractor".to_string()
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::chain_health::ChainHealthSnapshot;
+use crate::{config::processor_config::DefaultProcessorConfig, schema};
+use ahash::AHashMap;
+use anyhow::Result;
+use aptos_indexer_processor_sdk::{
+    postgres::utils::database::{execute_in_chunks, get_config_table_chunk_size, ArcDbPool},
+    traits::{async_step::AsyncRunType, AsyncStep, NamedStep, Processable},
+    types::transaction_context::TransactionContext,
+    utils::errors::ProcessorError,
+};
+use async_trait::async_trait;
+use diesel::{pg::Pg, query_builder::QueryFragment};
+
+/// Writes chain health snapshots to `chain_health_snapshots` if enabled.
+pub struct ChainHealthStorer
+where
+    Self: Sized + Send + 'static,
+{
+    conn_pool: ArcDbPool,
+    processor_config: DefaultProcessorConfig,
+    write_snapshots: bool,
+}
+
+impl ChainHealthStorer {
+    pub fn new(
+        conn_pool: ArcDbPool,
+        processor_config: DefaultProcessorConfig,
+        write_snapshots: bool,
+    ) -> Self {
+        Self {
+            conn_pool,
+            processor_config,
+            write_snapshots,
+        }
+    }
+}
+
+#[async_trait]
+impl Processable for ChainHealthStorer {
+    type Input = Vec<ChainHealthSnapshot>;
+    type Output = ();
+    type RunType = AsyncRunType;
+
+    async fn process(
+        &mut self,
+        input: TransactionContext<Vec<ChainHealthSnapshot>>,
+    ) -> Result<Option<TransactionContext<Self::Output>>, ProcessorError> {
+        if self.write_snapshots {
+            let per_table_chunk_sizes: AHashMap<String, usize> =
+                self.processor_config.per_table_chunk_sizes.clone();
+            execute_in_chunks(
+                self.conn_pool.clone(),
+                insert_chain_health_snapshots_query,
+                &input.data,
+                get_config_table_chunk_size::<ChainHealthSnapshot>(
+                    "chain_health_snapshots",
+                    &per_table_chunk_sizes,
+                ),
+            )
+            .await
+            .map_err(|e| ProcessorError::DBStoreError {
+                message: format!(
+                    "Failed to store versions {} to {}: {:?}",
+                    input.metadata.start_version, input.metadata.end_version, e,
+                ),
+                query: None,
+            })?;
+        }
+
+        Ok(Some(TransactionContext {
+            data: (),
+            metadata: input.metadata,
+        }))
+    }
+}
+
+impl NamedStep for ChainHealthStorer {
+    fn name(&self) -> String {
+        "chain_health_storer".to_string()
+    }
+}
+
+impl AsyncStep for ChainHealthStorer {}
+
+fn insert_chain_health_snapshots_query(
+    items_to_insert: Vec<ChainHealthSnapshot>,
+) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
+    use schema::chain_health_snapshots::dsl::*;
+
+    diesel::insert_into(schema::chain_health_snapshots::table)
+        .values(items_to_insert)
+        .on_conflict(start_version)
+        .do_nothing()
+}
@@ -1 +1,5 @@
+pub mod chain_health;
+pub mod chain_health_extractor;
+pub mod chain_health_storer;
 pub mod monitoring_processor;
+pub mod processor_lag;
@@ -1,10 +1,17 @@
 use crate::{
     config::{
         db_config::DbConfig, indexer_processor_config::IndexerProcessorConfig,
-        processor_config::ProcessorConfig,
+        processor_config::{DefaultProcessorConfig, ProcessorConfig},
     },
-    processors::processor_status_saver::{
-        get_end_version, get_starting_version, PostgresProcessorStatusSaver,
+    processors::{
+        monitoring::{
+            chain_health::ChainHealthConfig, chain_health_extractor::ChainHealthExtractor,
+            chain_health_storer::ChainHealthStorer,
+            processor_lag::{start_processor_lag_monitor, ProcessorLagConfig},
+        },
+        processor_status_saver::{
+            get_end_version, get_starting_version, PostgresProcessorStatusSaver,
+        },
     },
     MIGRATIONS,
 };
@@ -22,8 +29,21 @@ use aptos_indexer_processor_sdk::{
     traits::{processor_trait::ProcessorTrait, IntoRunnableStep},
     utils::chain_id_check::check_or_update_chain_id,
 };
+use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
 
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MonitoringProcessorConfig {
+    #[serde(flatten)]
+    pub default_config: DefaultProcessorConfig,
+    #[serde(default)]
+    pub chain_health: ChainHealthConfig,
+    // Exports the lag of every processor and writes `processor_safe_versions` if set
+    #[serde(default)]
+    pub processor_lag: Option<ProcessorLagConfig>,
+}
+
 pub struct MonitoringProcessor {
     pub config: IndexerProcessorConfig,
     pub db_pool: ArcDbPool,
@@ -64,7 +84,8 @@ impl ProcessorTrait for MonitoringProcessor {
         self.config.processor_config.name()
     }
 
-    /// This processor no-ops and is used for monitoring purposes.
+    /// Exports chain health metrics and alerts, and optionally writes `chain_health_snapshots`.
+    /// Also monitors the lag of the other processors if `processor_lag` is set.
     async fn run_processor(&self) -> Result<()> {
         // Run migrations
         if let DbConfig::PostgresConfig(ref postgres_config) = self.config.db_config {
@@ -98,7 +119,12 @@ impl ProcessorTrait for MonitoringProcessor {
                 ))
             },
         };
-        let channel_size = processor_config.channel_size;
+        let channel_size = processor_config.default_config.channel_size;
+
+        start_processor_lag_monitor(
+            self.db_pool.clone(),
+            processor_config.processor_lag.as_ref(),
+        )?;
 
         // Define processor steps
         let transaction_stream = TransactionStreamStep::new(TransactionStreamConfig {
@@ -107,6 +133,15 @@ impl ProcessorTrait for MonitoringProcessor {
             ..self.config.transaction_stream_config.clone()
         })
         .await?;
+        let chain_health_extractor = ChainHealthExtractor::new(
+            self.name().to_string(),
+            processor_config.chain_health.thresholds.clone(),
+        );
+        let chain_health_storer = ChainHealthStorer::new(
+            self.db_pool.clone(),
+            processor_config.default_config.clone(),
+            processor_config.chain_health.write_snapshots,
+        );
         let version_tracker = VersionTrackerStep::new(
             PostgresProcessorStatusSaver::new(self.config.clone(), self.db_pool.clone()),
             DEFAULT_UPDATE_PROCESSOR_STATUS_SECS,
@@ -116,6 +151,8 @@ impl ProcessorTrait for MonitoringProcessor {
         let (_, buffer_receiver) = ProcessorBuilder::new_with_inputless_first_step(
             transaction_stream.into_runnable_step(),
         )
+        .connect_to(chain_health_extractor.into_runnable_step(), channel_size)
+        .connect_to(chain_health_storer.into_runnable_step(), channel_size)
         .connect_to(version_tracker.into_runnable_step(), channel_size)
         .end_and_return_output_receiver(channel_size);
 
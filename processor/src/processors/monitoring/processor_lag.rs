@@ -0,0 +1,273 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+
+//! Lag and consistency across processors.
+//!
+//! Polls every `processor_status` and `backfill_processor_status` row and exports how far each
+//! processor is behind the most advanced one. Safe version sets record the latest version every
+//! processor of the set has processed, so readers joining their tables can stay consistent.
+
+use crate::{
+    db::backfill_processor_status::{BackfillProcessorStatusQuery, BackfillStatus},
+    schema::{backfill_processor_status, processor_safe_versions},
+    utils::counters::{PROCESSOR_LAG_SECS, PROCESSOR_LAG_VERSIONS, PROCESSOR_SAFE_VERSION},
+};
+use anyhow::{bail, Context, Result};
+use aptos_indexer_processor_sdk::postgres::{
+    models::processor_status::ProcessorStatusQuery,
+    processor_metadata_schema::processor_metadata::processor_status,
+    utils::database::{execute_with_better_error, ArcDbPool},
+};
+use chrono::{NaiveDateTime, Utc};
+use diesel::{
+    pg::{upsert::excluded, Pg},
+    query_builder::QueryFragment,
+    ExpressionMethods, Insertable,
+};
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, time::Duration};
+use tracing::{error, warn};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessorLagConfig {
+    #[serde(default = "ProcessorLagConfig::default_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    // Processors of each safe version set written to `processor_safe_versions`, keyed by set name
+    #[serde(default)]
+    pub safe_version_sets: HashMap<String, Vec<String>>,
+}
+
+impl ProcessorLagConfig {
+    pub const fn default_poll_interval_secs() -> u64 {
+        30
+    }
+}
+
+#[derive(Clone, Debug, Insertable, PartialEq)]
+#[diesel(table_name = processor_safe_versions)]
+pub struct ProcessorSafeVersion {
+    pub set_name: String,
+    pub safe_version: i64,
+    pub safe_transaction_timestamp: Option<NaiveDateTime>,
+    // Processor of the set that is the furthest behind
+    pub lagging_processor: String,
+}
+
+/// Progress of a processor or backfill.
+#[derive(Clone, Debug)]
+struct Progress {
+    name: String,
+    last_success_version: i64,
+    last_transaction_timestamp: Option<NaiveDateTime>,
+}
+
+impl From<ProcessorStatusQuery> for Progress {
+    fn from(status: ProcessorStatusQuery) -> Self {
+        Self {
+            name: status.processor,
+            last_success_version: status.last_success_version,
+            last_transaction_timestamp: status.last_transaction_timestamp,
+        }
+    }
+}
+
+/// Validates the lag config and starts polling the processor statuses in the background.
+pub fn start_processor_lag_monitor(
+    conn_pool: ArcDbPool,
+    config: Option<&ProcessorLagConfig>,
+) -> Result<()> {
+    let Some(config) = config else {
+        return Ok(());
+    };
+    if config.poll_interval_secs == 0 {
+        bail!("poll_interval_secs must be positive");
+    }
+    if let Some((set_name, _)) = config
+        .safe_version_sets
+        .iter()
+        .find(|(_, processors)| processors.is_empty())
+    {
+        bail!("Safe version set '{set_name}' has no processors");
+    }
+
+    let monitor = ProcessorLagMonitor {
+        conn_pool,
+        config: config.clone(),
+    };
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(monitor.config.poll_interval_secs));
+        loop {
+            interval.tick().await;
+            if let Err(e) = monitor.poll().await {
+                error!(error = ?e, "Failed to poll processor statuses");
+            }
+        }
+    });
+    Ok(())
+}
+
+struct ProcessorLagMonitor {
+    conn_pool: ArcDbPool,
+    config: ProcessorLagConfig,
+}
+
+impl ProcessorLagMonitor {
+    async fn poll(&self) -> Result<()> {
+        let mut conn = self.conn_pool.get().await?;
+        let processors: Vec<Progress> = processor_status::table
+            .load::<ProcessorStatusQuery>(&mut conn)
+            .await
+            .context("Failed to query processor_status")?
+            .into_iter()
+            .map(Progress::from)
+            .collect();
+        let backfills = backfill_processor_status::table
+            .load::<BackfillProcessorStatusQuery>(&mut conn)
+            .await
+            .context("Failed to query backfill_processor_status")?;
+        drop(conn);
+
+        let now = Utc::now().naive_utc();
+        let Some(head_version) = processors.iter().map(|p| p.last_success_version).max() else {
+            // Nothing processed yet
+            return Ok(());
+        };
+        for processor in &processors {
+            export_lag(processor, "processor", head_version, now);
+        }
+        for backfill in backfills {
+            // Backfills catch up to their end version, or the head if they have none
+            let target_version = match backfill.backfill_status {
+                BackfillStatus::Complete => backfill.last_success_version,
+                BackfillStatus::InProgress => backfill.backfill_end_version.unwrap_or(head_version),
+            };
+            let progress = Progress {
+                name: backfill.backfill_alias,
+                last_success_version: backfill.last_success_version,
+                last_transaction_timestamp: backfill.last_transaction_timestamp,
+            };
+            export_lag(&progress, "backfill", target_version, now);
+        }
+
+        let mut safe_versions = vec![];
+        for (set_name, set_processors) in &self.config.safe_version_sets {
+            match safe_version(set_name, set_processors, &processors) {
+                Some(safe_version) => {
+                    PROCESSOR_SAFE_VERSION
+                        .with_label_values(&[set_name.as_str()])
+                        .set(safe_version.safe_version);
+                    safe_versions.push(safe_version);
+                },
+                None => warn!(
+                    set_name,
+                    "Not every processor of the safe version set has a status yet"
+                ),
+            }
+        }
+        if !safe_versions.is_empty() {
+            execute_with_better_error(
+                self.conn_pool.clone(),
+                insert_processor_safe_versions_query(safe_versions),
+            )
+            .await
+            .context("Failed to store processor_safe_versions")?;
+        }
+        Ok(())
+    }
+}
+
+fn export_lag(progress: &Progress, kind: &str, target_version: i64, now: NaiveDateTime) {
+    let labels = [progress.name.as_str(), kind];
+    PROCESSOR_LAG_VERSIONS
+        .with_label_values(&labels)
+        .set((target_version - progress.last_success_version).max(0));
+    if let Some(timestamp) = progress.last_transaction_timestamp {
+        PROCESSOR_LAG_SECS
+            .with_label_values(&labels)
+            .set((now - timestamp).num_seconds().max(0));
+    }
+}
+
+/// Minimum progress across the processors of the set, `None` if one of them has no status.
+fn safe_version(
+    set_name: &str,
+    set_processors: &[String],
+    processors: &[Progress],
+) -> Option<ProcessorSafeVersion> {
+    let lagging = set_processors
+        .iter()
+        .map(|name| processors.iter().find(|p| &p.name == name))
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .min_by_key(|p| p.last_success_version)?;
+    Some(ProcessorSafeVersion {
+        set_name: set_name.to_string(),
+        safe_version: lagging.last_success_version,
+        safe_transaction_timestamp: lagging.last_transaction_timestamp,
+        lagging_processor: lagging.name.clone(),
+    })
+}
+
+fn insert_processor_safe_versions_query(
+    items_to_insert: Vec<ProcessorSafeVersion>,
+) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
+    use processor_safe_versions::dsl::*;
+
+    diesel::insert_into(processor_safe_versions::table)
+        .values(items_to_insert)
+        .on_conflict(set_name)
+        .do_update()
+        .set((
+            safe_version.eq(excluded(safe_version)),
+            safe_transaction_timestamp.eq(excluded(safe_transaction_timestamp)),
+            lagging_processor.eq(excluded(lagging_processor)),
+            last_updated.eq(diesel::dsl::now),
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(name: &str, last_success_version: i64) -> Progress {
+        Progress {
+            name: name.to_string(),
+            last_success_version,
+            last_transaction_timestamp: None,
+        }
+    }
+
+    #[test]
+    fn test_safe_version() {
+        let processors = vec![
+            progress("token_v2_processor", 120),
+            progress("ans_processor", 100),
+            progress("objects_processor", 150),
+        ];
+        let set = vec![
+            "token_v2_processor".to_string(),
+            "ans_processor".to_string(),
+        ];
+        assert_eq!(
+            safe_version("ans", &set, &processors),
+            Some(ProcessorSafeVersion {
+                set_name: "ans".to_string(),
+                safe_version: 100,
+                safe_transaction_timestamp: None,
+                lagging_processor: "ans_processor".to_string(),
+            })
+        );
+
+        let set = vec![
+            "token_v2_processor".to_string(),
+            "stake_processor".to_string(),
+        ];
+        assert_eq!(safe_version("stake", &set, &processors), None);
+    }
+}
@@ -150,93 +150,70 @@ impl Object {
                 },
             };
 
-            // Add a logid here to handle None conn
-            if db_context.is_none() {
-                // This is a hack to prevent the program for parquet
-                Ok(Some((
-                    Self {
-                        transaction_version: txn_version,
-                        write_set_change_index,
-                        object_address: resource.resource_address.clone(),
-                        owner_address: DELETED_RESOURCE_OWNER_ADDRESS.to_string(),
-                        state_key_hash: resource.state_key_hash.clone(),
-                        guid_creation_num: BigDecimal::default(),
-                        allow_ungated_transfer: false,
-                        is_deleted: true,
-                        untransferrable: false,
-                        block_timestamp,
-                    },
-                    CurrentObject {
-                        object_address: resource.resource_address.clone(),
-                        owner_address: DELETED_RESOURCE_OWNER_ADDRESS.to_string(),
-                        state_key_hash: resource.state_key_hash.clone(),
-                        last_guid_creation_num: BigDecimal::default(),
-                        allow_ungated_transfer: false,
-                        last_transaction_version: txn_version,
-                        is_deleted: true,
-                        untransferrable: false,
-                        block_timestamp,
-                    },
-                )))
-            } else {
-                let previous_object = if let Some(object) =
-                    object_mapping.get(&resource.resource_address)
+            let previous_object = if let Some(object) =
+                object_mapping.get(&resource.resource_address)
+            {
+                object.clone()
+            } else if let Some(db_context) = db_context {
+                match Self::get_current_object(
+                    &mut db_context.conn,
+                    &resource.resource_address,
+                    db_context.query_retries,
+                    db_context.query_retry_delay_ms,
+                )
+                .await
                 {
-                    object.clone()
-                } else if let Some(db_context) = db_context {
-                    match Self::get_current_object(
-                        &mut db_context.conn,
-                        &resource.resource_address,
-                        db_context.query_retries,
-                        db_context.query_retry_delay_ms,
-                    )
-                    .await
-                    {
-                        Ok(object) => object,
-                        Err(_) => {
-                            tracing::error!(
+                    Ok(object) => object,
+                    Err(_) => {
+                        tracing::error!(
                             transaction_version = txn_version,
                             lookup_key = &resource.resource_address,
                             "Missing current_object for object_address: {}. You probably should backfill db.",
                             resource.resource_address,
                         );
-                            return Ok(None);
-                        },
-                    }
-                } else {
-                    tracing::error!(
-                        transaction_version = txn_version,
-                        lookup_key = &resource.resource_address,
-                        "Connection to DB is missing. You may need to investigate",
-                    );
-                    return Ok(None);
-                };
-                Ok(Some((
-                    Self {
-                        transaction_version: txn_version,
-                        write_set_change_index,
-                        object_address: resource.resource_address.clone(),
-                        owner_address: previous_object.owner_address.clone(),
-                        state_key_hash: resource.state_key_hash.clone(),
-                        guid_creation_num: previous_object.last_guid_creation_num.clone(),
-                        allow_ungated_transfer: previous_object.allow_ungated_transfer,
-                        is_deleted: true,
-                        untransferrable: previous_object.untransferrable,
-                        block_timestamp,
+                        return Ok(None);
                     },
-                    CurrentObject {
-                        object_address: resource.resource_address.clone(),
-                        owner_address: previous_object.owner_address.clone(),
-                        state_key_hash: resource.state_key_hash,
-                        last_guid_creation_num: previous_object.last_guid_creation_num.clone(),
-                        allow_ungated_transfer: previous_object.allow_ungated_transfer,
-                        last_transaction_version: txn_version,
-                        is_deleted: true,
-                        untransferrable: previous_object.untransferrable,
-                        block_timestamp,
-                    },
-                )))
-            }
+                }
+            } else {
+                // Without a db (parquet) objects from earlier batches can't be looked up, so the
+                // deletion is still recorded but without the previous owner
+                CurrentObject {
+                    object_address: resource.resource_address.clone(),
+                    owner_address: DELETED_RESOURCE_OWNER_ADDRESS.to_string(),
+                    state_key_hash: resource.state_key_hash.clone(),
+                    last_guid_creation_num: BigDecimal::default(),
+                    allow_ungated_transfer: false,
+                    last_transaction_version: txn_version,
+                    is_deleted: true,
+                    untransferrable: false,
+                    block_timestamp,
+                }
+            };
+            Ok(Some((
+                Self {
+                    transaction_version: txn_version,
+                    write_set_change_index,
+                    object_address: resource.resource_address.clone(),
+                    owner_address: previous_object.owner_address.clone(),
+                    state_key_hash: resource.state_key_hash.clone(),
+                    guid_creation_num: previous_object.last_guid_creation_num.clone(),
+                    allow_ungated_transfer: previous_object.allow_ungated_transfer,
+                    is_deleted: true,
+                    untransferrable: previous_object.untransferrable,
+                    block_timestamp,
+                },
+                CurrentObject {
+                    object_address: resource.resource_address.clone(),
+                    owner_address: previous_object.owner_address.clone(),
+                    state_key_hash: resource.state_key_hash,
+                    last_guid_creation_num: previous_object.last_guid_creation_num.clone(),
+                    allow_ungated_transfer: previous_object.allow_ungated_transfer,
+                    last_transaction_version: txn_version,
+                    is_deleted: true,
+                    untransferrable: previous_object.untransferrable,
+                    block_timestamp,
+                },
+            )))
         } else {
             Ok(None)
         }
@@ -355,7 +332,7 @@ pub struct ParquetCurrentObject {
 }
 
 impl NamedTable for ParquetCurrentObject {
-    const TABLE_NAME: &'static str = "objects";
+    const TABLE_NAME: &'static str = "current_objects";
 }
 
 impl HasVersion for ParquetCurrentObject {
@@ -412,6 +389,22 @@ impl From<Object> for PostgresObject {
     }
 }
 
+impl From<ParquetObject> for PostgresObject {
+    fn from(parquet_item: ParquetObject) -> Self {
+        Self {
+            transaction_version: parquet_item.txn_version,
+            write_set_change_index: parquet_item.write_set_change_index,
+            object_address: parquet_item.object_address,
+            owner_address: parquet_item.owner_address,
+            state_key_hash: parquet_item.state_key_hash,
+            guid_creation_num: BigDecimal::from(parquet_item.guid_creation_num),
+            allow_ungated_transfer: parquet_item.allow_ungated_transfer,
+            is_deleted: parquet_item.is_deleted,
+            untransferrable: parquet_item.untransferrable,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
 #[diesel(primary_key(object_address))]
 #[diesel(table_name = current_objects)]
@@ -440,3 +433,18 @@ impl From<CurrentObject> for PostgresCurrentObject {
         }
     }
 }
+
+impl From<ParquetCurrentObject> for PostgresCurrentObject {
+    fn from(parquet_item: ParquetCurrentObject) -> Self {
+        Self {
+            object_address: parquet_item.object_address,
+            owner_address: parquet_item.owner_address,
+            state_key_hash: parquet_item.state_key_hash,
+            allow_ungated_transfer: parquet_item.allow_ungated_transfer,
+            last_guid_creation_num: BigDecimal::from(parquet_item.last_guid_creation_num),
+            last_transaction_version: parquet_item.last_transaction_version,
+            is_deleted: parquet_item.is_deleted,
+            untransferrable: parquet_item.untransferrable,
+        }
+    }
+}
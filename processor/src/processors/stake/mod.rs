@@ -129,45 +129,45 @@ pub async fn parse_stake_data(
                 .unwrap();
             all_delegator_balances.append(&mut delegator_balances);
             all_current_delegator_balances.extend(current_delegator_balances);
+        }
 
-            // this write table item indexing is to get delegator address, table handle, and voter & pending voter
-            for wsc in &transaction_info.changes {
-                if let Change::WriteTableItem(write_table_item) = wsc.change.as_ref().unwrap() {
-                    let voter_map = CurrentDelegatedVoter::from_write_table_item(
-                        write_table_item,
-                        txn_version,
-                        txn_timestamp,
-                        &all_vote_delegation_handle_to_pool_address,
-                        conn,
-                        query_retries,
-                        query_retry_delay_ms,
-                    )
-                    .await
-                    .unwrap();
-
-                    all_current_delegated_voter.extend(voter_map);
-                }
+        // this write table item indexing is to get delegator address, table handle, and voter & pending voter.
+        // Without a db connection (parquet) only what can be resolved from the batch is kept.
+        for wsc in &transaction_info.changes {
+            if let Change::WriteTableItem(write_table_item) = wsc.change.as_ref().unwrap() {
+                let voter_map = CurrentDelegatedVoter::from_write_table_item(
+                    write_table_item,
+                    txn_version,
+                    txn_timestamp,
+                    &all_vote_delegation_handle_to_pool_address,
+                    conn.as_mut(),
+                    query_retries,
+                    query_retry_delay_ms,
+                )
+                .await
+                .unwrap();
+
+                all_current_delegated_voter.extend(voter_map);
             }
+        }
 
-            // we need one last loop to prefill delegators that got in before the delegated voting contract was deployed
-            for wsc in &transaction_info.changes {
-                if let Change::WriteTableItem(write_table_item) = wsc.change.as_ref().unwrap() {
-                    if let Some(voter) =
-                        CurrentDelegatedVoter::get_delegators_pre_contract_deployment(
-                            write_table_item,
-                            txn_version,
-                            txn_timestamp,
-                            &active_pool_to_staking_pool,
-                            &all_current_delegated_voter,
-                            conn,
-                            query_retries,
-                            query_retry_delay_ms,
-                        )
-                        .await
-                        .unwrap()
-                    {
-                        all_current_delegated_voter.insert(voter.pk(), voter);
-                    }
+        // we need one last loop to prefill delegators that got in before the delegated voting contract was deployed
+        for wsc in &transaction_info.changes {
+            if let Change::WriteTableItem(write_table_item) = wsc.change.as_ref().unwrap() {
+                if let Some(voter) = CurrentDelegatedVoter::get_delegators_pre_contract_deployment(
+                    write_table_item,
+                    txn_version,
+                    txn_timestamp,
+                    &active_pool_to_staking_pool,
+                    &all_current_delegated_voter,
+                    conn.as_mut(),
+                    query_retries,
+                    query_retry_delay_ms,
+                )
+                .await
+                .unwrap()
+                {
+                    all_current_delegated_voter.insert(voter.pk(), voter);
                 }
             }
         }
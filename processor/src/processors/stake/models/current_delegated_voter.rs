@@ -6,12 +6,14 @@
 
 use super::delegator_balances::ShareToStakingPoolMapping;
 use crate::{
+    parquet_processors::parquet_utils::util::{HasVersion, NamedTable},
     processors::stake::models::{
         delegator_balances::CurrentDelegatorBalance, stake_utils::VoteDelegationTableItem,
     },
     schema::current_delegated_voter,
 };
 use ahash::AHashMap;
+use allocative_derive::Allocative;
 use aptos_indexer_processor_sdk::{
     aptos_protos::transaction::v1::WriteTableItem, postgres::utils::database::DbPoolConnection,
     utils::convert::standardize_address,
@@ -19,6 +21,7 @@ use aptos_indexer_processor_sdk::{
 use diesel::prelude::*;
 use diesel_async::RunQueryDsl;
 use field_count::FieldCount;
+use parquet_derive::ParquetRecordWriter;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Identifiable, Queryable)]
@@ -77,7 +80,7 @@ impl CurrentDelegatedVoter {
         txn_version: i64,
         txn_timestamp: chrono::NaiveDateTime,
         vote_delegation_handle_to_pool_address: &VoteDelegationTableHandleToPool,
-        conn: &mut DbPoolConnection<'_>,
+        conn: Option<&mut DbPoolConnection<'_>>,
         query_retries: u32,
         query_retry_delay_ms: u64,
     ) -> anyhow::Result<CurrentDelegatedVoterMap> {
@@ -94,9 +97,9 @@ impl CurrentDelegatedVoter {
         {
             let pool_address = match vote_delegation_handle_to_pool_address.get(&table_handle) {
                 Some(pool_address) => pool_address.clone(),
-                None => {
+                None => match conn {
                     // look up from db
-                    Self::get_delegation_pool_address_by_table_handle(conn, &table_handle, query_retries, query_retry_delay_ms).await
+                    Some(conn) => Self::get_delegation_pool_address_by_table_handle(conn, &table_handle, query_retries, query_retry_delay_ms).await
                         .unwrap_or_else(|_| {
                             tracing::error!(
                                 transaction_version = txn_version,
@@ -104,7 +107,9 @@ impl CurrentDelegatedVoter {
                                 "Missing pool address for table handle. You probably should backfill db.",
                             );
                             "".to_string()
-                        })
+                        }),
+                    // Without a db only pools whose governance records are in the batch can be resolved
+                    None => "".to_string(),
                 },
             };
             if !pool_address.is_empty() {
@@ -139,7 +144,7 @@ impl CurrentDelegatedVoter {
         txn_timestamp: chrono::NaiveDateTime,
         active_pool_to_staking_pool: &ShareToStakingPoolMapping,
         previous_delegated_voters: &CurrentDelegatedVoterMap,
-        conn: &mut DbPoolConnection<'_>,
+        conn: Option<&mut DbPoolConnection<'_>>,
         query_retries: u32,
         query_retry_delay_ms: u64,
     ) -> anyhow::Result<Option<Self>> {
@@ -160,16 +165,21 @@ impl CurrentDelegatedVoter {
                 .get(&(pool_address.clone(), delegator_address.clone()))
             {
                 Some(_) => true,
-                None => {
-                    // look up from db
-                    Self::get_existence_by_pk(
-                        conn,
-                        &delegator_address,
-                        &pool_address,
-                        query_retries,
-                        query_retry_delay_ms,
-                    )
-                    .await
+                // look up from db
+                None => match conn {
+                    Some(conn) => {
+                        Self::get_existence_by_pk(
+                            conn,
+                            &delegator_address,
+                            &pool_address,
+                            query_retries,
+                            query_retry_delay_ms,
+                        )
+                        .await
+                    },
+                    // Without a db we can't tell whether the voter was set in an earlier batch, so
+                    // don't risk overwriting it with the default
+                    None => true,
                 },
             };
             if !already_exists {
@@ -281,3 +291,42 @@ impl PartialOrd for CurrentDelegatedVoter {
         Some(self.cmp(other))
     }
 }
+
+// Parquet models
+#[derive(
+    Allocative, Clone, Debug, Default, Deserialize, FieldCount, ParquetRecordWriter, Serialize,
+)]
+pub struct ParquetCurrentDelegatedVoter {
+    pub delegation_pool_address: String,
+    pub delegator_address: String,
+    pub table_handle: Option<String>,
+    pub voter: Option<String>,
+    pub pending_voter: Option<String>,
+    pub last_transaction_version: i64,
+    #[allocative(skip)]
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl NamedTable for ParquetCurrentDelegatedVoter {
+    const TABLE_NAME: &'static str = "current_delegated_voter";
+}
+
+impl HasVersion for ParquetCurrentDelegatedVoter {
+    fn version(&self) -> i64 {
+        self.last_transaction_version
+    }
+}
+
+impl From<CurrentDelegatedVoter> for ParquetCurrentDelegatedVoter {
+    fn from(base: CurrentDelegatedVoter) -> Self {
+        Self {
+            delegation_pool_address: base.delegation_pool_address,
+            delegator_address: base.delegator_address,
+            table_handle: base.table_handle,
+            voter: base.voter,
+            pending_voter: base.pending_voter,
+            last_transaction_version: base.last_transaction_version,
+            last_transaction_timestamp: base.last_transaction_timestamp,
+        }
+    }
+}
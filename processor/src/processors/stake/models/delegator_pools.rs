@@ -6,6 +6,7 @@
 
 use super::stake_utils::{StakeResource, StakeTableItem};
 use crate::{
+    parquet_processors::parquet_utils::util::{HasVersion, NamedTable},
     schema::{
         current_delegated_staking_pool_balances, delegated_staking_pool_balances,
         delegated_staking_pools,
@@ -13,6 +14,7 @@ use crate::{
     utils::counters::PROCESSOR_UNKNOWN_TYPE_COUNT,
 };
 use ahash::AHashMap;
+use allocative_derive::Allocative;
 use aptos_indexer_processor_sdk::{
     aptos_indexer_transaction_stream::utils::time::parse_timestamp,
     aptos_protos::transaction::v1::{
@@ -22,6 +24,7 @@ use aptos_indexer_processor_sdk::{
 };
 use bigdecimal::BigDecimal;
 use field_count::FieldCount;
+use parquet_derive::ParquetRecordWriter;
 use serde::{Deserialize, Serialize};
 
 type StakingPoolAddress = String;
@@ -366,3 +369,31 @@ impl From<CurrentDelegatorPoolBalance> for PostgresCurrentDelegatorPoolBalance {
         }
     }
 }
+
+// Parquet models
+#[derive(
+    Allocative, Clone, Debug, Default, Deserialize, FieldCount, ParquetRecordWriter, Serialize,
+)]
+pub struct ParquetDelegatorPool {
+    pub staking_pool_address: String,
+    pub first_transaction_version: i64,
+}
+
+impl NamedTable for ParquetDelegatorPool {
+    const TABLE_NAME: &'static str = "delegated_staking_pools";
+}
+
+impl HasVersion for ParquetDelegatorPool {
+    fn version(&self) -> i64 {
+        self.first_transaction_version
+    }
+}
+
+impl From<DelegatorPool> for ParquetDelegatorPool {
+    fn from(base: DelegatorPool) -> Self {
+        Self {
+            staking_pool_address: base.staking_pool_address,
+            first_transaction_version: base.first_transaction_version,
+        }
+    }
+}
@@ -5,15 +5,19 @@
 #![allow(clippy::extra_unused_lifetimes)]
 
 use crate::{
-    processors::stake::models::stake_utils::StakeResource, schema::current_staking_pool_voter,
+    parquet_processors::parquet_utils::util::{HasVersion, NamedTable},
+    processors::stake::models::stake_utils::StakeResource,
+    schema::current_staking_pool_voter,
 };
 use ahash::AHashMap;
+use allocative_derive::Allocative;
 use aptos_indexer_processor_sdk::{
     aptos_indexer_transaction_stream::utils::time::parse_timestamp,
     aptos_protos::transaction::v1::{write_set_change::Change, Transaction},
     utils::convert::standardize_address,
 };
 use field_count::FieldCount;
+use parquet_derive::ParquetRecordWriter;
 use serde::{Deserialize, Serialize};
 
 type StakingPoolAddress = String;
@@ -46,13 +50,16 @@ impl CurrentStakingPoolVoter {
                 )? {
                     let staking_pool_address =
                         standardize_address(&write_resource.address.to_string());
-                    staking_pool_voters.insert(staking_pool_address.clone(), Self {
-                        staking_pool_address,
-                        voter_address: inner.get_delegated_voter(),
-                        last_transaction_version: txn_version,
-                        operator_address: inner.get_operator_address(),
-                        block_timestamp,
-                    });
+                    staking_pool_voters.insert(
+                        staking_pool_address.clone(),
+                        Self {
+                            staking_pool_address,
+                            voter_address: inner.get_delegated_voter(),
+                            last_transaction_version: txn_version,
+                            operator_address: inner.get_operator_address(),
+                            block_timestamp,
+                        },
+                    );
                 }
             }
         }
@@ -82,3 +89,38 @@ impl From<CurrentStakingPoolVoter> for PostgresCurrentStakingPoolVoter {
         }
     }
 }
+
+// Parquet models
+#[derive(
+    Allocative, Clone, Debug, Default, Deserialize, FieldCount, ParquetRecordWriter, Serialize,
+)]
+pub struct ParquetCurrentStakingPoolVoter {
+    pub staking_pool_address: String,
+    pub voter_address: String,
+    pub last_transaction_version: i64,
+    pub operator_address: String,
+    #[allocative(skip)]
+    pub block_timestamp: chrono::NaiveDateTime,
+}
+
+impl NamedTable for ParquetCurrentStakingPoolVoter {
+    const TABLE_NAME: &'static str = "current_staking_pool_voter";
+}
+
+impl HasVersion for ParquetCurrentStakingPoolVoter {
+    fn version(&self) -> i64 {
+        self.last_transaction_version
+    }
+}
+
+impl From<CurrentStakingPoolVoter> for ParquetCurrentStakingPoolVoter {
+    fn from(base: CurrentStakingPoolVoter) -> Self {
+        Self {
+            staking_pool_address: base.staking_pool_address,
+            voter_address: base.voter_address,
+            last_transaction_version: base.last_transaction_version,
+            operator_address: base.operator_address,
+            block_timestamp: base.block_timestamp,
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod storage_usage_extractor;
+pub mod storage_usage_models;
+pub mod storage_usage_processor;
+pub mod storage_usage_storer;
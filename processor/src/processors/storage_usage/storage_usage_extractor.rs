@@ -0,0 +1,45 @@
+use crate::processors::storage_usage::storage_usage_models::StateItemWrite;
+use anyhow::Result;
+use aptos_indexer_processor_sdk::{
+    aptos_protos::transaction::v1::Transaction,
+    traits::{async_step::AsyncRunType, AsyncStep, NamedStep, Processable},
+    types::transaction_context::TransactionContext,
+    utils::errors::ProcessorError,
+};
+use async_trait::async_trait;
+
+/// Extracts the state items written by transactions with their sizes
+pub struct StorageUsageExtractor
+where
+    Self: Sized + Send + 'static, {}
+
+#[async_trait]
+impl Processable for StorageUsageExtractor {
+    type Input = Vec<Transaction>;
+    type Output = Vec<StateItemWrite>;
+    type RunType = AsyncRunType;
+
+    async fn process(
+        &mut self,
+        transactions: TransactionContext<Vec<Transaction>>,
+    ) -> Result<Option<TransactionContext<Vec<StateItemWrite>>>, ProcessorError> {
+        let writes = transactions
+            .data
+            .iter()
+            .flat_map(StateItemWrite::from_transaction)
+            .collect();
+
+        Ok(Some(TransactionContext {
+            data: writes,
+            metadata: transactions.metadata,
+        }))
+    }
+}
+
+impl AsyncStep for StorageUsageExtractor {}
+
+impl NamedStep for StorageUsageExtractor {
+    fn name(&self) -> String {
+        "storage_usage_extractor".to_string()
+    }
+}
@@ -0,0 +1,388 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+
+//! Storage usage per account and resource type, from the write set size info.
+//!
+//! The size of a state item is the key and value bytes of its last write, or nothing once it is
+//! deleted. Every write changes the usage of its account and resource type by the difference to
+//! the stored size of the item. Items record the last version that was accounted for; writes at or
+//! below it are skipped, which makes replaying a batch a no-op. Versions must be processed in order.
+//!
+//! Resource types drop their generic type parameters, e.g. every `0x1::coin::CoinStore` is one
+//! type. Table items are accounted to their table handle and modules to their account, with the
+//! `table_item` and `module` types.
+
+use crate::schema::{current_state_item_sizes, current_storage_usage, storage_usage_changes};
+use ahash::AHashMap;
+use aptos_indexer_processor_sdk::{
+    aptos_indexer_transaction_stream::utils::time::parse_timestamp,
+    aptos_protos::transaction::v1::{
+        write_set_change::Change as WriteSetChangeEnum, Transaction, WriteOpSizeInfo,
+        WriteSetChange,
+    },
+    utils::convert::standardize_address,
+};
+use chrono::NaiveDateTime;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::warn;
+
+pub const TABLE_ITEM_TYPE: &str = "table_item";
+pub const MODULE_TYPE: &str = "module";
+
+/// A state item written by a transaction, with its size after the write.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StateItemWrite {
+    pub transaction_version: i64,
+    pub transaction_timestamp: NaiveDateTime,
+    pub state_key_hash: String,
+    pub address: String,
+    pub resource_type: String,
+    pub key_bytes: i64,
+    pub value_bytes: i64,
+    pub is_deleted: bool,
+}
+
+impl StateItemWrite {
+    pub fn from_transaction(transaction: &Transaction) -> Vec<Self> {
+        let transaction_version = transaction.version as i64;
+        let Some(size_info) = transaction.size_info.as_ref() else {
+            warn!(
+                version = transaction.version,
+                "Transaction size info not found"
+            );
+            return vec![];
+        };
+        let transaction_timestamp = parse_timestamp(
+            transaction
+                .timestamp
+                .as_ref()
+                .expect("Transaction timestamp doesn't exist!"),
+            transaction_version,
+        )
+        .naive_utc();
+        let transaction_info = transaction
+            .info
+            .as_ref()
+            .expect("Transaction info doesn't exist!");
+
+        // Write op size info is in the order of the write set
+        transaction_info
+            .changes
+            .iter()
+            .zip(size_info.write_op_size_info.iter())
+            .filter_map(|(change, write_op_size_info)| {
+                Self::from_write_set_change(
+                    change,
+                    write_op_size_info,
+                    transaction_version,
+                    transaction_timestamp,
+                )
+            })
+            .collect()
+    }
+
+    pub fn from_write_set_change(
+        change: &WriteSetChange,
+        write_op_size_info: &WriteOpSizeInfo,
+        transaction_version: i64,
+        transaction_timestamp: NaiveDateTime,
+    ) -> Option<Self> {
+        let (state_key_hash, address, resource_type, is_deleted) = match change.change.as_ref()? {
+            WriteSetChangeEnum::WriteResource(inner) => (
+                &inner.state_key_hash,
+                standardize_address(&inner.address),
+                strip_generic_type_params(&inner.type_str),
+                false,
+            ),
+            WriteSetChangeEnum::DeleteResource(inner) => (
+                &inner.state_key_hash,
+                standardize_address(&inner.address),
+                strip_generic_type_params(&inner.type_str),
+                true,
+            ),
+            WriteSetChangeEnum::WriteTableItem(inner) => (
+                &inner.state_key_hash,
+                standardize_address(&inner.handle),
+                TABLE_ITEM_TYPE.to_string(),
+                false,
+            ),
+            WriteSetChangeEnum::DeleteTableItem(inner) => (
+                &inner.state_key_hash,
+                standardize_address(&inner.handle),
+                TABLE_ITEM_TYPE.to_string(),
+                true,
+            ),
+            WriteSetChangeEnum::WriteModule(inner) => (
+                &inner.state_key_hash,
+                standardize_address(&inner.address),
+                MODULE_TYPE.to_string(),
+                false,
+            ),
+            WriteSetChangeEnum::DeleteModule(inner) => (
+                &inner.state_key_hash,
+                standardize_address(&inner.address),
+                MODULE_TYPE.to_string(),
+                true,
+            ),
+        };
+        // Deleted items don't take up any storage
+        let (key_bytes, value_bytes) = if is_deleted {
+            (0, 0)
+        } else {
+            (
+                write_op_size_info.key_bytes as i64,
+                write_op_size_info.value_bytes as i64,
+            )
+        };
+        Some(Self {
+            transaction_version,
+            transaction_timestamp,
+            state_key_hash: standardize_address(hex::encode(state_key_hash.as_slice()).as_str()),
+            address,
+            resource_type,
+            key_bytes,
+            value_bytes,
+            is_deleted,
+        })
+    }
+}
+
+fn strip_generic_type_params(type_str: &str) -> String {
+    type_str
+        .split_once('<')
+        .map_or(type_str, |(type_str, _)| type_str)
+        .to_string()
+}
+
+#[derive(
+    Clone, Debug, Deserialize, FieldCount, Insertable, PartialEq, Queryable, Selectable, Serialize,
+)]
+#[diesel(primary_key(state_key_hash))]
+#[diesel(table_name = current_state_item_sizes)]
+pub struct CurrentStateItemSize {
+    pub state_key_hash: String,
+    pub address: String,
+    pub resource_type: String,
+    pub key_bytes: i64,
+    pub value_bytes: i64,
+    pub is_deleted: bool,
+    pub last_transaction_version: i64,
+}
+
+impl CurrentStateItemSize {
+    /// Stored bytes and items.
+    fn usage(&self) -> (i64, i64) {
+        if self.is_deleted {
+            (0, 0)
+        } else {
+            (self.key_bytes + self.value_bytes, 1)
+        }
+    }
+}
+
+impl From<&StateItemWrite> for CurrentStateItemSize {
+    fn from(write: &StateItemWrite) -> Self {
+        Self {
+            state_key_hash: write.state_key_hash.clone(),
+            address: write.address.clone(),
+            resource_type: write.resource_type.clone(),
+            key_bytes: write.key_bytes,
+            value_bytes: write.value_bytes,
+            is_deleted: write.is_deleted,
+            last_transaction_version: write.transaction_version,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Insertable, PartialEq, Serialize)]
+#[diesel(primary_key(transaction_version, address, resource_type))]
+#[diesel(table_name = storage_usage_changes)]
+pub struct StorageUsageChange {
+    pub transaction_version: i64,
+    pub address: String,
+    pub resource_type: String,
+    pub bytes_delta: i64,
+    pub items_delta: i64,
+    pub transaction_timestamp: NaiveDateTime,
+}
+
+#[derive(
+    Clone, Debug, Deserialize, FieldCount, Insertable, PartialEq, Queryable, Selectable, Serialize,
+)]
+#[diesel(primary_key(address, resource_type))]
+#[diesel(table_name = current_storage_usage)]
+pub struct CurrentStorageUsage {
+    pub address: String,
+    pub resource_type: String,
+    pub bytes: i64,
+    pub items: i64,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: NaiveDateTime,
+}
+
+/// Accounts the writes on top of the `stored_sizes` of their items and the `stored_usage` of their
+/// accounts and resource types. Returns the updated item sizes, the non-zero usage changes of each
+/// transaction and the updated usage, each sorted by primary key.
+pub fn account_storage_usage(
+    writes: &[StateItemWrite],
+    stored_sizes: Vec<CurrentStateItemSize>,
+    stored_usage: Vec<CurrentStorageUsage>,
+) -> (
+    Vec<CurrentStateItemSize>,
+    Vec<StorageUsageChange>,
+    Vec<CurrentStorageUsage>,
+) {
+    let mut sizes: AHashMap<String, CurrentStateItemSize> = stored_sizes
+        .into_iter()
+        .map(|size| (size.state_key_hash.clone(), size))
+        .collect();
+    let mut stored_usage: AHashMap<(String, String), CurrentStorageUsage> = stored_usage
+        .into_iter()
+        .map(|usage| ((usage.address.clone(), usage.resource_type.clone()), usage))
+        .collect();
+    let mut updated_sizes: BTreeMap<String, CurrentStateItemSize> = BTreeMap::new();
+    let mut changes: BTreeMap<(i64, String, String), StorageUsageChange> = BTreeMap::new();
+    let mut usage: BTreeMap<(String, String), CurrentStorageUsage> = BTreeMap::new();
+
+    for write in writes {
+        let previous = sizes.get(&write.state_key_hash);
+        if previous.is_some_and(|size| size.last_transaction_version >= write.transaction_version) {
+            continue;
+        }
+        let (previous_bytes, previous_items) = previous.map_or((0, 0), CurrentStateItemSize::usage);
+        let size = CurrentStateItemSize::from(write);
+        let (bytes, items) = size.usage();
+        let (bytes_delta, items_delta) = (bytes - previous_bytes, items - previous_items);
+        sizes.insert(write.state_key_hash.clone(), size.clone());
+        updated_sizes.insert(write.state_key_hash.clone(), size);
+
+        let change = changes
+            .entry((
+                write.transaction_version,
+                write.address.clone(),
+                write.resource_type.clone(),
+            ))
+            .or_insert_with(|| StorageUsageChange {
+                transaction_version: write.transaction_version,
+                address: write.address.clone(),
+                resource_type: write.resource_type.clone(),
+                bytes_delta: 0,
+                items_delta: 0,
+                transaction_timestamp: write.transaction_timestamp,
+            });
+        change.bytes_delta += bytes_delta;
+        change.items_delta += items_delta;
+
+        let key = (write.address.clone(), write.resource_type.clone());
+        let current = usage.entry(key.clone()).or_insert_with(|| {
+            stored_usage
+                .remove(&key)
+                .unwrap_or_else(|| CurrentStorageUsage {
+                    address: write.address.clone(),
+                    resource_type: write.resource_type.clone(),
+                    bytes: 0,
+                    items: 0,
+                    last_transaction_version: write.transaction_version,
+                    last_transaction_timestamp: write.transaction_timestamp,
+                })
+        });
+        current.bytes += bytes_delta;
+        current.items += items_delta;
+        current.last_transaction_version = write.transaction_version;
+        current.last_transaction_timestamp = write.transaction_timestamp;
+    }
+
+    (
+        updated_sizes.into_values().collect(),
+        changes
+            .into_values()
+            .filter(|change| change.bytes_delta != 0 || change.items_delta != 0)
+            .collect(),
+        usage.into_values().collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(
+        transaction_version: i64,
+        state_key_hash: &str,
+        value_bytes: i64,
+        is_deleted: bool,
+    ) -> StateItemWrite {
+        StateItemWrite {
+            transaction_version,
+            transaction_timestamp: NaiveDateTime::default(),
+            state_key_hash: state_key_hash.to_string(),
+            address: "0x1".to_string(),
+            resource_type: "0x1::coin::CoinStore".to_string(),
+            key_bytes: if is_deleted { 0 } else { 10 },
+            value_bytes,
+            is_deleted,
+        }
+    }
+
+    #[test]
+    fn test_strip_generic_type_params() {
+        assert_eq!(
+            strip_generic_type_params("0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>"),
+            "0x1::coin::CoinStore"
+        );
+        assert_eq!(
+            strip_generic_type_params("0x1::account::Account"),
+            "0x1::account::Account"
+        );
+    }
+
+    #[test]
+    fn test_account_storage_usage() {
+        let stored_sizes = vec![CurrentStateItemSize::from(&write(5, "0xa", 100, false))];
+        let stored_usage = vec![CurrentStorageUsage {
+            address: "0x1".to_string(),
+            resource_type: "0x1::coin::CoinStore".to_string(),
+            bytes: 110,
+            items: 1,
+            last_transaction_version: 5,
+            last_transaction_timestamp: NaiveDateTime::default(),
+        }];
+        let writes = vec![
+            // Already accounted
+            write(5, "0xa", 100, false),
+            // Grows an existing item
+            write(6, "0xa", 150, false),
+            // Creates a new item
+            write(6, "0xb", 40, false),
+            // Deletes the existing item
+            write(7, "0xa", 0, true),
+        ];
+
+        let (sizes, changes, usage) = account_storage_usage(&writes, stored_sizes, stored_usage);
+
+        assert_eq!(sizes.len(), 2);
+        assert_eq!(sizes[0].last_transaction_version, 7);
+        assert!(sizes[0].is_deleted);
+        assert_eq!(sizes[1].value_bytes, 40);
+        let deltas: Vec<(i64, i64, i64)> = changes
+            .iter()
+            .map(|change| {
+                (
+                    change.transaction_version,
+                    change.bytes_delta,
+                    change.items_delta,
+                )
+            })
+            .collect();
+        assert_eq!(deltas, vec![(6, 100, 1), (7, -160, -1)]);
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].bytes, 50);
+        assert_eq!(usage[0].items, 1);
+        assert_eq!(usage[0].last_transaction_version, 7);
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{
+    config::{
+        db_config::DbConfig, indexer_processor_config::IndexerProcessorConfig,
+        processor_config::ProcessorConfig,
+    },
+    processors::{
+        processor_status_saver::{
+            get_end_version, get_starting_version, PostgresProcessorStatusSaver,
+        },
+        storage_usage::{
+            storage_usage_extractor::StorageUsageExtractor,
+            storage_usage_storer::StorageUsageStorer,
+        },
+    },
+    schema::current_state_item_sizes,
+    utils::table_flags::TableFlags,
+    MIGRATIONS,
+};
+use anyhow::{bail, Result};
+use aptos_indexer_processor_sdk::{
+    aptos_indexer_transaction_stream::TransactionStreamConfig,
+    builder::ProcessorBuilder,
+    common_steps::{
+        TransactionStreamStep, VersionTrackerStep, DEFAULT_UPDATE_PROCESSOR_STATUS_SECS,
+    },
+    postgres::utils::{
+        checkpoint::PostgresChainIdChecker,
+        database::{new_db_pool, run_migrations, ArcDbPool},
+    },
+    traits::{processor_trait::ProcessorTrait, IntoRunnableStep},
+    utils::chain_id_check::check_or_update_chain_id,
+};
+use diesel::{OptionalExtension, QueryDsl};
+use diesel_async::RunQueryDsl;
+use tracing::{debug, info};
+
+pub struct StorageUsageProcessor {
+    pub config: IndexerProcessorConfig,
+    pub db_pool: ArcDbPool,
+}
+
+impl StorageUsageProcessor {
+    pub async fn new(config: IndexerProcessorConfig) -> Result<Self> {
+        match config.db_config {
+            DbConfig::PostgresConfig(ref postgres_config) => {
+                let conn_pool = new_db_pool(
+                    &postgres_config.connection_string,
+                    Some(postgres_config.db_pool_size),
+                )
+                .await
+                .map_err(|e| {
+                    anyhow::anyhow!(
+                        "Failed to create connection pool for PostgresConfig: {:?}",
+                        e
+                    )
+                })?;
+
+                Ok(Self {
+                    config,
+                    db_pool: conn_pool,
+                })
+            },
+            _ => Err(anyhow::anyhow!(
+                "Invalid db config for StorageUsageProcessor {:?}",
+                config.db_config
+            )),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ProcessorTrait for StorageUsageProcessor {
+    fn name(&self) -> &'static str {
+        self.config.processor_config.name()
+    }
+
+    async fn run_processor(&self) -> Result<()> {
+        //  Run migrations
+        if let DbConfig::PostgresConfig(ref postgres_config) = self.config.db_config {
+            run_migrations(
+                postgres_config.connection_string.clone(),
+                self.db_pool.clone(),
+                MIGRATIONS,
+            )
+            .await;
+        }
+
+        //  Merge the starting version from config and the latest processed version from the DB
+        let (starting_version, ending_version) = (
+            get_starting_version(&self.config, self.db_pool.clone()).await?,
+            get_end_version(&self.config, self.db_pool.clone()).await?,
+        );
+
+        // Check and update the ledger chain id to ensure we're indexing the correct chain
+        check_or_update_chain_id(
+            &self.config.transaction_stream_config,
+            &PostgresChainIdChecker::new(self.db_pool.clone()),
+        )
+        .await?;
+
+        let processor_config = match &self.config.processor_config {
+            ProcessorConfig::StorageUsageProcessor(processor_config) => processor_config,
+            _ => return Err(anyhow::anyhow!("Processor config is wrong type")),
+        };
+        let channel_size = processor_config.channel_size;
+        let opt_in_tables = TableFlags::from_set(&processor_config.tables_to_write);
+        // The usage is accounted on top of the stored item sizes, they can't be left behind
+        if !opt_in_tables.is_empty()
+            && !opt_in_tables.contains(TableFlags::CURRENT_STATE_ITEM_SIZES)
+        {
+            bail!("tables_to_write must include CURRENT_STATE_ITEM_SIZES");
+        }
+        // A write's previous size is looked up in the stored item sizes, so starting past genesis
+        // without them would count every overwritten item as new
+        if let Some(starting_version) = starting_version.filter(|version| *version > 0) {
+            let mut conn = self.db_pool.get().await?;
+            let stored_item_size = current_state_item_sizes::table
+                .select(current_state_item_sizes::state_key_hash)
+                .first::<String>(&mut conn)
+                .await
+                .optional()?;
+            if stored_item_size.is_none() {
+                bail!(
+                    "Starting version {starting_version} is past genesis but \
+                     current_state_item_sizes is empty. The storage usage processor has to be \
+                     run from version 0"
+                );
+            }
+        }
+
+        // Define processor steps
+        let transaction_stream = TransactionStreamStep::new(TransactionStreamConfig {
+            starting_version,
+            request_ending_version: ending_version,
+            ..self.config.transaction_stream_config.clone()
+        })
+        .await?;
+
+        let storage_usage_extractor = StorageUsageExtractor {};
+        let storage_usage_storer = StorageUsageStorer::new(
+            self.db_pool.clone(),
+            processor_config.clone(),
+            opt_in_tables,
+        );
+        let version_tracker = VersionTrackerStep::new(
+            PostgresProcessorStatusSaver::new(self.config.clone(), self.db_pool.clone()),
+            DEFAULT_UPDATE_PROCESSOR_STATUS_SECS,
+        );
+        // Connect processor steps together
+        let (_, buffer_receiver) = ProcessorBuilder::new_with_inputless_first_step(
+            transaction_stream.into_runnable_step(),
+        )
+        .connect_to(storage_usage_extractor.into_runnable_step(), channel_size)
+        .connect_to(storage_usage_storer.into_runnable_step(), channel_size)
+        .connect_to(version_tracker.into_runnable_step(), channel_size)
+        .end_and_return_output_receiver(channel_size);
+
+        // (Optional) Parse the results
+        loop {
+            match buffer_receiver.recv().await {
+                Ok(txn_context) => {
+                    debug!(
+                        "Finished processing versions [{:?}, {:?}]",
+                        txn_context.metadata.start_version, txn_context.metadata.end_version,
+                    );
+                },
+                Err(e) => {
+                    info!("No more transactions in channel: {:?}", e);
+                    break Ok(());
+                },
+            }
+        }
+    }
+}
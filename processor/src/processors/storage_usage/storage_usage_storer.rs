@@ -0,0 +1,250 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::storage_usage_models::{
+    account_storage_usage, CurrentStateItemSize, CurrentStorageUsage, StateItemWrite,
+    StorageUsageChange,
+};
+use crate::{
+    config::processor_config::DefaultProcessorConfig,
+    schema,
+    utils::table_flags::{filter_data, TableFlags},
+};
+use ahash::AHashMap;
+use anyhow::Result;
+use aptos_indexer_processor_sdk::{
+    postgres::utils::database::{get_config_table_chunk_size, ArcDbPool},
+    traits::{async_step::AsyncRunType, AsyncStep, NamedStep, Processable},
+    types::transaction_context::TransactionContext,
+    utils::errors::ProcessorError,
+};
+use async_trait::async_trait;
+use diesel::{
+    pg::{upsert::excluded, Pg},
+    query_builder::QueryFragment,
+    query_dsl::methods::{FilterDsl, SelectDsl},
+    ExpressionMethods, SelectableHelper,
+};
+use diesel_async::{
+    scoped_futures::ScopedFutureExt, AsyncConnection, AsyncPgConnection, RunQueryDsl,
+};
+
+pub struct StorageUsageStorer
+where
+    Self: Sized + Send + 'static,
+{
+    conn_pool: ArcDbPool,
+    processor_config: DefaultProcessorConfig,
+    tables_to_write: TableFlags,
+}
+
+impl StorageUsageStorer {
+    pub fn new(
+        conn_pool: ArcDbPool,
+        processor_config: DefaultProcessorConfig,
+        tables_to_write: TableFlags,
+    ) -> Self {
+        Self {
+            conn_pool,
+            processor_config,
+            tables_to_write,
+        }
+    }
+
+    /// Accounts the writes on top of the stored sizes of their items and usage of their accounts.
+    async fn account_batch(
+        conn: &mut AsyncPgConnection,
+        writes: &[StateItemWrite],
+    ) -> Result<(
+        Vec<CurrentStateItemSize>,
+        Vec<StorageUsageChange>,
+        Vec<CurrentStorageUsage>,
+    )> {
+        if writes.is_empty() {
+            return Ok((vec![], vec![], vec![]));
+        }
+        let mut state_key_hashes: Vec<String> = writes
+            .iter()
+            .map(|write| write.state_key_hash.clone())
+            .collect();
+        state_key_hashes.sort();
+        state_key_hashes.dedup();
+        let mut addresses: Vec<String> = writes.iter().map(|write| write.address.clone()).collect();
+        addresses.sort();
+        addresses.dedup();
+        let mut resource_types: Vec<String> = writes
+            .iter()
+            .map(|write| write.resource_type.clone())
+            .collect();
+        resource_types.sort();
+        resource_types.dedup();
+
+        let stored_sizes = {
+            use schema::current_state_item_sizes::dsl::*;
+            current_state_item_sizes
+                .filter(state_key_hash.eq_any(state_key_hashes))
+                .select(CurrentStateItemSize::as_select())
+                .load(conn)
+                .await?
+        };
+        // Loads a superset of the usage rows of the batch, the rest are ignored
+        let stored_usage = {
+            use schema::current_storage_usage::dsl::*;
+            current_storage_usage
+                .filter(address.eq_any(addresses))
+                .filter(resource_type.eq_any(resource_types))
+                .select(CurrentStorageUsage::as_select())
+                .load(conn)
+                .await?
+        };
+
+        Ok(account_storage_usage(writes, stored_sizes, stored_usage))
+    }
+}
+
+#[async_trait]
+impl Processable for StorageUsageStorer {
+    type Input = Vec<StateItemWrite>;
+    type Output = ();
+    type RunType = AsyncRunType;
+
+    async fn process(
+        &mut self,
+        input: TransactionContext<Vec<StateItemWrite>>,
+    ) -> Result<Option<TransactionContext<Self::Output>>, ProcessorError> {
+        let per_table_chunk_sizes: AHashMap<String, usize> =
+            self.processor_config.per_table_chunk_sizes.clone();
+        let state_item_sizes_chunk_size = get_config_table_chunk_size::<CurrentStateItemSize>(
+            "current_state_item_sizes",
+            &per_table_chunk_sizes,
+        );
+        let storage_usage_changes_chunk_size = get_config_table_chunk_size::<StorageUsageChange>(
+            "storage_usage_changes",
+            &per_table_chunk_sizes,
+        );
+        let storage_usage_chunk_size = get_config_table_chunk_size::<CurrentStorageUsage>(
+            "current_storage_usage",
+            &per_table_chunk_sizes,
+        );
+
+        // The stored sizes are only valid for the writes made on top of them, so they are loaded
+        // and written back in one transaction
+        let tables_to_write = self.tables_to_write;
+        let writes = &input.data;
+        let store_batch = async {
+            let mut conn = self.conn_pool.get().await?;
+            conn.transaction::<_, anyhow::Error, _>(|conn| {
+                async move {
+                    let (state_item_sizes, storage_usage_changes, storage_usage) =
+                        Self::account_batch(conn, writes).await?;
+                    let state_item_sizes = filter_data(
+                        &tables_to_write,
+                        TableFlags::CURRENT_STATE_ITEM_SIZES,
+                        state_item_sizes,
+                    );
+                    let storage_usage_changes = filter_data(
+                        &tables_to_write,
+                        TableFlags::STORAGE_USAGE_CHANGES,
+                        storage_usage_changes,
+                    );
+                    let storage_usage = filter_data(
+                        &tables_to_write,
+                        TableFlags::CURRENT_STORAGE_USAGE,
+                        storage_usage,
+                    );
+
+                    for chunk in state_item_sizes.chunks(state_item_sizes_chunk_size) {
+                        insert_current_state_item_sizes_query(chunk.to_vec())
+                            .execute(conn)
+                            .await?;
+                    }
+                    for chunk in storage_usage_changes.chunks(storage_usage_changes_chunk_size) {
+                        insert_storage_usage_changes_query(chunk.to_vec())
+                            .execute(conn)
+                            .await?;
+                    }
+                    for chunk in storage_usage.chunks(storage_usage_chunk_size) {
+                        insert_current_storage_usage_query(chunk.to_vec())
+                            .execute(conn)
+                            .await?;
+                    }
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await
+        };
+
+        if let Err(e) = store_batch.await {
+            return Err(ProcessorError::DBStoreError {
+                message: format!(
+                    "Failed to store versions {} to {}: {:?}",
+                    input.metadata.start_version, input.metadata.end_version, e,
+                ),
+                query: None,
+            });
+        }
+
+        Ok(Some(TransactionContext {
+            data: (),
+            metadata: input.metadata,
+        }))
+    }
+}
+
+impl NamedStep for StorageUsageStorer {
+    fn name(&self) -> String {
+        "storage_usage_storer".to_string()
+    }
+}
+
+impl AsyncStep for StorageUsageStorer {}
+
+fn insert_current_state_item_sizes_query(
+    items_to_insert: Vec<CurrentStateItemSize>,
+) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
+    use schema::current_state_item_sizes::dsl::*;
+
+    diesel::insert_into(schema::current_state_item_sizes::table)
+        .values(items_to_insert)
+        .on_conflict(state_key_hash)
+        .do_update()
+        .set((
+            key_bytes.eq(excluded(key_bytes)),
+            value_bytes.eq(excluded(value_bytes)),
+            is_deleted.eq(excluded(is_deleted)),
+            last_transaction_version.eq(excluded(last_transaction_version)),
+            inserted_at.eq(excluded(inserted_at)),
+        ))
+        .filter(last_transaction_version.le(excluded(last_transaction_version)))
+}
+
+fn insert_storage_usage_changes_query(
+    items_to_insert: Vec<StorageUsageChange>,
+) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
+    use schema::storage_usage_changes::dsl::*;
+
+    diesel::insert_into(schema::storage_usage_changes::table)
+        .values(items_to_insert)
+        .on_conflict((transaction_version, address, resource_type))
+        .do_nothing()
+}
+
+fn insert_current_storage_usage_query(
+    items_to_insert: Vec<CurrentStorageUsage>,
+) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
+    use schema::current_storage_usage::dsl::*;
+
+    diesel::insert_into(schema::current_storage_usage::table)
+        .values(items_to_insert)
+        .on_conflict((address, resource_type))
+        .do_update()
+        .set((
+            bytes.eq(excluded(bytes)),
+            items.eq(excluded(items)),
+            last_transaction_version.eq(excluded(last_transaction_version)),
+            last_transaction_timestamp.eq(excluded(last_transaction_timestamp)),
+            inserted_at.eq(excluded(inserted_at)),
+        ))
+        .filter(last_transaction_version.le(excluded(last_transaction_version)))
+}
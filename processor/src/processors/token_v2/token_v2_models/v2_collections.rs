@@ -384,6 +384,7 @@ impl HasVersion for ParquetCollectionV2 {
         self.txn_version
     }
 }
+
 impl From<CollectionV2> for ParquetCollectionV2 {
     fn from(collection: CollectionV2) -> Self {
         ParquetCollectionV2 {
@@ -408,3 +409,59 @@ impl From<CollectionV2> for ParquetCollectionV2 {
         }
     }
 }
+
+#[derive(
+    Allocative, Clone, Debug, Default, Deserialize, FieldCount, ParquetRecordWriter, Serialize,
+)]
+pub struct ParquetCurrentCollectionV2 {
+    pub collection_id: String,
+    pub creator_address: String,
+    pub collection_name: String,
+    pub description: String,
+    pub uri: String,
+    pub current_supply: String,          // BigDecimal
+    pub max_supply: Option<String>,      // BigDecimal
+    pub total_minted_v2: Option<String>, // BigDecimal
+    pub mutable_description: Option<bool>,
+    pub mutable_uri: Option<bool>,
+    pub table_handle_v1: Option<String>,
+    pub token_standard: String,
+    pub collection_properties: Option<String>, // json
+    pub last_transaction_version: i64,
+    #[allocative(skip)]
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl NamedTable for ParquetCurrentCollectionV2 {
+    const TABLE_NAME: &'static str = "current_collections_v2";
+}
+
+impl HasVersion for ParquetCurrentCollectionV2 {
+    fn version(&self) -> i64 {
+        self.last_transaction_version
+    }
+}
+
+impl From<CurrentCollectionV2> for ParquetCurrentCollectionV2 {
+    fn from(collection: CurrentCollectionV2) -> Self {
+        Self {
+            collection_id: collection.collection_id,
+            creator_address: collection.creator_address,
+            collection_name: collection.collection_name,
+            description: collection.description,
+            uri: collection.uri,
+            current_supply: collection.current_supply.to_string(),
+            max_supply: collection.max_supply.map(|v| v.to_string()),
+            total_minted_v2: collection.total_minted_v2.map(|v| v.to_string()),
+            mutable_description: collection.mutable_description,
+            mutable_uri: collection.mutable_uri,
+            table_handle_v1: collection.table_handle_v1,
+            token_standard: collection.token_standard,
+            collection_properties: collection
+                .collection_properties
+                .map(|v| serde_json::to_string(&v).unwrap()),
+            last_transaction_version: collection.last_transaction_version,
+            last_transaction_timestamp: collection.last_transaction_timestamp,
+        }
+    }
+}
@@ -27,6 +27,7 @@ use bigdecimal::{BigDecimal, One, ToPrimitive, Zero};
 use field_count::FieldCount;
 use parquet_derive::ParquetRecordWriter;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct TokenActivityV2 {
@@ -511,3 +512,27 @@ impl From<TokenActivityV2> for PostgresTokenActivityV2 {
         }
     }
 }
+
+impl TryFrom<ParquetTokenActivityV2> for PostgresTokenActivityV2 {
+    type Error = anyhow::Error;
+
+    fn try_from(parquet_item: ParquetTokenActivityV2) -> anyhow::Result<Self> {
+        Ok(Self {
+            transaction_version: parquet_item.txn_version,
+            event_index: parquet_item.event_index,
+            event_account_address: parquet_item.event_account_address,
+            token_data_id: parquet_item.token_data_id,
+            property_version_v1: BigDecimal::from(parquet_item.property_version_v1),
+            type_: parquet_item.event_type,
+            from_address: parquet_item.from_address,
+            to_address: parquet_item.to_address,
+            token_amount: BigDecimal::from_str(&parquet_item.token_amount)?,
+            before_value: parquet_item.before_value,
+            after_value: parquet_item.after_value,
+            entry_function_id_str: parquet_item.entry_function_id_str,
+            token_standard: parquet_item.token_standard,
+            is_fungible_v2: parquet_item.is_fungible_v2,
+            transaction_timestamp: parquet_item.block_timestamp,
+        })
+    }
+}
@@ -23,7 +23,7 @@ use crate::{
             },
         },
     },
-    schema::current_token_ownerships_v2,
+    schema::{current_token_ownerships_v2, token_ownerships_v2},
 };
 use ahash::AHashMap;
 use allocative_derive::Allocative;
@@ -41,6 +41,7 @@ use diesel_async::RunQueryDsl;
 use field_count::FieldCount;
 use parquet_derive::ParquetRecordWriter;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 use tracing::error;
 
 // PK of current_token_ownerships_v2, i.e. token_data_id, property_version_v1, owner_address, storage_id
@@ -738,6 +739,76 @@ impl From<TokenOwnershipV2> for ParquetTokenOwnershipV2 {
     }
 }
 
+/// This is the postgres version of TokenOwnershipV2
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, write_set_change_index))]
+#[diesel(table_name = token_ownerships_v2)]
+pub struct PostgresTokenOwnershipV2 {
+    pub transaction_version: i64,
+    pub write_set_change_index: i64,
+    pub token_data_id: String,
+    pub property_version_v1: BigDecimal,
+    pub owner_address: Option<String>,
+    pub storage_id: String,
+    pub amount: BigDecimal,
+    pub table_type_v1: Option<String>,
+    pub token_properties_mutated_v1: Option<serde_json::Value>,
+    pub is_soulbound_v2: Option<bool>,
+    pub token_standard: String,
+    pub is_fungible_v2: Option<bool>,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+    pub non_transferrable_by_owner: Option<bool>,
+}
+
+impl From<TokenOwnershipV2> for PostgresTokenOwnershipV2 {
+    fn from(raw_item: TokenOwnershipV2) -> Self {
+        Self {
+            transaction_version: raw_item.transaction_version,
+            write_set_change_index: raw_item.write_set_change_index,
+            token_data_id: raw_item.token_data_id,
+            property_version_v1: raw_item.property_version_v1,
+            owner_address: raw_item.owner_address,
+            storage_id: raw_item.storage_id,
+            amount: raw_item.amount,
+            table_type_v1: raw_item.table_type_v1,
+            token_properties_mutated_v1: raw_item.token_properties_mutated_v1,
+            is_soulbound_v2: raw_item.is_soulbound_v2,
+            token_standard: raw_item.token_standard,
+            is_fungible_v2: raw_item.is_fungible_v2,
+            transaction_timestamp: raw_item.transaction_timestamp,
+            non_transferrable_by_owner: raw_item.non_transferrable_by_owner,
+        }
+    }
+}
+
+impl TryFrom<ParquetTokenOwnershipV2> for PostgresTokenOwnershipV2 {
+    type Error = anyhow::Error;
+
+    fn try_from(parquet_item: ParquetTokenOwnershipV2) -> anyhow::Result<Self> {
+        Ok(Self {
+            transaction_version: parquet_item.txn_version,
+            write_set_change_index: parquet_item.write_set_change_index,
+            token_data_id: parquet_item.token_data_id,
+            property_version_v1: BigDecimal::from(parquet_item.property_version_v1),
+            owner_address: parquet_item.owner_address,
+            storage_id: parquet_item.storage_id,
+            amount: BigDecimal::from_str(&parquet_item.amount)?,
+            table_type_v1: parquet_item.table_type_v1,
+            token_properties_mutated_v1: parquet_item
+                .token_properties_mutated_v1
+                .map(|v| serde_json::from_str(&v))
+                .transpose()
+                .context("Failed to parse token_properties_mutated_v1")?,
+            is_soulbound_v2: parquet_item.is_soulbound_v2,
+            token_standard: parquet_item.token_standard,
+            // The parquet model doesn't keep is_fungible_v2
+            is_fungible_v2: None,
+            transaction_timestamp: parquet_item.block_timestamp,
+            non_transferrable_by_owner: parquet_item.non_transferrable_by_owner,
+        })
+    }
+}
+
 #[derive(
     Allocative, Clone, Debug, Default, Deserialize, FieldCount, ParquetRecordWriter, Serialize,
 )]
@@ -856,3 +927,31 @@ impl From<CurrentTokenOwnershipV2> for PostgresCurrentTokenOwnershipV2 {
         }
     }
 }
+
+impl TryFrom<ParquetCurrentTokenOwnershipV2> for PostgresCurrentTokenOwnershipV2 {
+    type Error = anyhow::Error;
+
+    fn try_from(parquet_item: ParquetCurrentTokenOwnershipV2) -> anyhow::Result<Self> {
+        Ok(Self {
+            token_data_id: parquet_item.token_data_id,
+            property_version_v1: BigDecimal::from(parquet_item.property_version_v1),
+            owner_address: parquet_item.owner_address,
+            storage_id: parquet_item.storage_id,
+            amount: BigDecimal::from_str(&parquet_item.amount)?,
+            table_type_v1: parquet_item.table_type_v1,
+            // The parquet writer stores missing properties as DEFAULT_NONE
+            token_properties_mutated_v1: parquet_item
+                .token_properties_mutated_v1
+                .filter(|v| v != DEFAULT_NONE)
+                .map(|v| serde_json::from_str(&v))
+                .transpose()
+                .context("Failed to parse token_properties_mutated_v1")?,
+            is_soulbound_v2: parquet_item.is_soulbound_v2,
+            token_standard: parquet_item.token_standard,
+            is_fungible_v2: parquet_item.is_fungible_v2,
+            last_transaction_version: parquet_item.last_transaction_version,
+            last_transaction_timestamp: parquet_item.last_transaction_timestamp,
+            non_transferrable_by_owner: parquet_item.non_transferrable_by_owner,
+        })
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
         },
         processor_config::{DefaultProcessorConfig, ProcessorConfig},
     },
+    db::{copy_insert::CopyInserter, retention::start_history_pruning},
     processors::{
         processor_status_saver::{
             get_end_version, get_starting_version, PostgresProcessorStatusSaver,
@@ -129,16 +130,28 @@ impl ProcessorTrait for TokenV2Processor {
             ..self.config.transaction_stream_config.clone()
         })
         .await?;
+        start_history_pruning(
+            &self.config,
+            self.db_pool.clone(),
+            processor_config.default_config.retention.as_ref(),
+            &["token_activities_v2"],
+        )?;
         let token_v2_extractor = TokenV2Extractor::new(
             processor_config.query_retries,
             processor_config.query_retry_delay_ms,
             self.db_pool.clone(),
         );
         let opt_in_tables = TableFlags::from_set(&processor_config.default_config.tables_to_write);
+        let copy_inserter = CopyInserter::for_table(
+            &self.config.db_config,
+            &processor_config.default_config.copy_tables,
+            "token_activities_v2",
+        )?;
         let token_v2_storer = TokenV2Storer::new(
             self.db_pool.clone(),
             processor_config.clone(),
             opt_in_tables,
+            copy_inserter,
         );
         let version_tracker = VersionTrackerStep::new(
             PostgresProcessorStatusSaver::new(self.config.clone(), self.db_pool.clone()),
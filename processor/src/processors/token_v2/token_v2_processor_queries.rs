@@ -2,15 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    db::copy_insert::{CopyInsertable, CopyValue, PgNumeric},
     processors::token_v2::{
         token_models::{
             token_claims::PostgresCurrentTokenPendingClaim,
             token_royalty::PostgresCurrentTokenRoyaltyV1,
         },
         token_v2_models::{
-            v2_collections::CurrentCollectionV2, v2_token_activities::PostgresTokenActivityV2,
+            v2_collections::CurrentCollectionV2,
+            v2_token_activities::PostgresTokenActivityV2,
             v2_token_datas::PostgresCurrentTokenDataV2,
-            v2_token_ownerships::PostgresCurrentTokenOwnershipV2,
+            v2_token_ownerships::{PostgresCurrentTokenOwnershipV2, PostgresTokenOwnershipV2},
         },
     },
     schema,
@@ -21,6 +23,7 @@ use diesel::{
     query_dsl::methods::FilterDsl,
     ExpressionMethods,
 };
+use tokio_postgres::types::Type;
 
 pub fn insert_current_collections_v2_query(
     items_to_insert: Vec<CurrentCollectionV2>,
@@ -128,6 +131,17 @@ pub fn insert_current_token_ownerships_v2_query(
         .filter(last_transaction_version.le(excluded(last_transaction_version)))
 }
 
+pub fn insert_token_ownerships_v2_query(
+    items_to_insert: Vec<PostgresTokenOwnershipV2>,
+) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
+    use schema::token_ownerships_v2::dsl::*;
+
+    diesel::insert_into(schema::token_ownerships_v2::table)
+        .values(items_to_insert)
+        .on_conflict((transaction_version, write_set_change_index))
+        .do_nothing()
+}
+
 pub fn insert_current_deleted_token_ownerships_v2_query(
     items_to_insert: Vec<PostgresCurrentTokenOwnershipV2>,
 ) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
@@ -167,6 +181,60 @@ pub fn insert_token_activities_v2_query(
         ))
 }
 
+impl CopyInsertable for PostgresTokenActivityV2 {
+    fn table_name() -> &'static str {
+        "token_activities_v2"
+    }
+
+    fn columns() -> Vec<(&'static str, Type)> {
+        vec![
+            ("transaction_version", Type::INT8),
+            ("event_index", Type::INT8),
+            ("event_account_address", Type::VARCHAR),
+            ("token_data_id", Type::VARCHAR),
+            ("property_version_v1", Type::NUMERIC),
+            ("type", Type::VARCHAR),
+            ("from_address", Type::VARCHAR),
+            ("to_address", Type::VARCHAR),
+            ("token_amount", Type::NUMERIC),
+            ("before_value", Type::TEXT),
+            ("after_value", Type::TEXT),
+            ("entry_function_id_str", Type::VARCHAR),
+            ("token_standard", Type::VARCHAR),
+            ("is_fungible_v2", Type::BOOL),
+            ("transaction_timestamp", Type::TIMESTAMP),
+        ]
+    }
+
+    fn conflict_columns() -> &'static [&'static str] {
+        &["transaction_version", "event_index"]
+    }
+
+    fn update_columns() -> &'static [&'static str] {
+        &["is_fungible_v2", "inserted_at"]
+    }
+
+    fn copy_row(&self) -> Vec<CopyValue<'_>> {
+        vec![
+            Box::new(self.transaction_version),
+            Box::new(self.event_index),
+            Box::new(&self.event_account_address),
+            Box::new(&self.token_data_id),
+            Box::new(PgNumeric(&self.property_version_v1)),
+            Box::new(&self.type_),
+            Box::new(&self.from_address),
+            Box::new(&self.to_address),
+            Box::new(PgNumeric(&self.token_amount)),
+            Box::new(&self.before_value),
+            Box::new(&self.after_value),
+            Box::new(&self.entry_function_id_str),
+            Box::new(&self.token_standard),
+            Box::new(self.is_fungible_v2),
+            Box::new(self.transaction_timestamp),
+        ]
+    }
+}
+
 pub fn insert_current_token_royalties_v1_query(
     items_to_insert: Vec<PostgresCurrentTokenRoyaltyV1>,
 ) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
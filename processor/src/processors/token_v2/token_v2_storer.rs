@@ -1,4 +1,5 @@
 use crate::{
+    db::copy_insert::{execute_copy_or_in_chunks, CopyInserter},
     filter_datasets,
     processors::token_v2::{
         token_models::{
@@ -6,9 +7,10 @@ use crate::{
             token_royalty::PostgresCurrentTokenRoyaltyV1,
         },
         token_v2_models::{
-            v2_collections::CurrentCollectionV2, v2_token_activities::PostgresTokenActivityV2,
+            v2_collections::CurrentCollectionV2,
+            v2_token_activities::PostgresTokenActivityV2,
             v2_token_datas::PostgresCurrentTokenDataV2,
-            v2_token_ownerships::PostgresCurrentTokenOwnershipV2,
+            v2_token_ownerships::{PostgresCurrentTokenOwnershipV2, PostgresTokenOwnershipV2},
         },
         token_v2_processor::TokenV2ProcessorConfig,
         token_v2_processor_queries::{
@@ -16,9 +18,10 @@ use crate::{
             insert_current_deleted_token_ownerships_v2_query, insert_current_token_claims_query,
             insert_current_token_datas_v2_query, insert_current_token_ownerships_v2_query,
             insert_current_token_royalties_v1_query, insert_token_activities_v2_query,
+            insert_token_ownerships_v2_query,
         },
     },
-    utils::table_flags::{filter_data, TableFlags},
+    utils::table_flags::{filter_data, filter_opt_in_data, TableFlags},
 };
 use ahash::AHashMap;
 use anyhow::Result;
@@ -38,6 +41,7 @@ where
     conn_pool: ArcDbPool,
     processor_config: TokenV2ProcessorConfig,
     tables_to_write: TableFlags,
+    copy_inserter: Option<CopyInserter>,
 }
 
 impl TokenV2Storer {
@@ -45,11 +49,13 @@ impl TokenV2Storer {
         conn_pool: ArcDbPool,
         processor_config: TokenV2ProcessorConfig,
         tables_to_write: TableFlags,
+        copy_inserter: Option<CopyInserter>,
     ) -> Self {
         Self {
             conn_pool,
             processor_config,
             tables_to_write,
+            copy_inserter,
         }
     }
 }
@@ -62,6 +68,7 @@ impl Processable for TokenV2Storer {
         Vec<PostgresCurrentTokenDataV2>,
         Vec<PostgresCurrentTokenOwnershipV2>,
         Vec<PostgresCurrentTokenOwnershipV2>,
+        Vec<PostgresTokenOwnershipV2>,
         Vec<PostgresTokenActivityV2>,
         Vec<PostgresCurrentTokenRoyaltyV1>,
         Vec<PostgresCurrentTokenPendingClaim>,
@@ -77,6 +84,7 @@ impl Processable for TokenV2Storer {
             Vec<PostgresCurrentTokenDataV2>,
             Vec<PostgresCurrentTokenOwnershipV2>,
             Vec<PostgresCurrentTokenOwnershipV2>,
+            Vec<PostgresTokenOwnershipV2>,
             Vec<PostgresTokenActivityV2>,
             Vec<PostgresCurrentTokenRoyaltyV1>,
             Vec<PostgresCurrentTokenPendingClaim>,
@@ -88,6 +96,7 @@ impl Processable for TokenV2Storer {
             current_deleted_token_datas_v2,
             current_token_ownerships_v2,
             current_deleted_token_ownerships_v2,
+            token_ownerships_v2,
             token_activities_v2,
             current_token_royalties_v1,
             current_token_claims,
@@ -112,6 +121,12 @@ impl Processable for TokenV2Storer {
             current_token_royalties_v1 => TableFlags::CURRENT_TOKEN_ROYALTY_V1,
             current_token_claims => TableFlags::CURRENT_TOKEN_PENDING_CLAIMS,
         });
+        // The ownership history is only written when it's opted in
+        let token_ownerships_v2 = filter_opt_in_data(
+            &self.tables_to_write,
+            TableFlags::TOKEN_OWNERSHIPS_V2,
+            token_ownerships_v2,
+        );
 
         let per_table_chunk_sizes: AHashMap<String, usize> = self
             .processor_config
@@ -164,7 +179,17 @@ impl Processable for TokenV2Storer {
                 &per_table_chunk_sizes,
             ),
         );
-        let ta_v2 = execute_in_chunks(
+        let to_v2 = execute_in_chunks(
+            self.conn_pool.clone(),
+            insert_token_ownerships_v2_query,
+            &token_ownerships_v2,
+            get_config_table_chunk_size::<PostgresTokenOwnershipV2>(
+                "token_ownerships_v2",
+                &per_table_chunk_sizes,
+            ),
+        );
+        let ta_v2 = execute_copy_or_in_chunks(
+            self.copy_inserter.as_mut(),
             self.conn_pool.clone(),
             insert_token_activities_v2_query,
             &token_activities_v2,
@@ -198,10 +223,11 @@ impl Processable for TokenV2Storer {
             cdtd_v2_res,
             cto_v2_res,
             cdto_v2_res,
+            to_v2_res,
             ta_v2_res,
             ctr_v1_res,
             ctc_v1_res,
-        ) = tokio::join!(cc_v2, ctd_v2, cdtd_v2, cto_v2, cdto_v2, ta_v2, ctr_v1, ctc_v1);
+        ) = tokio::join!(cc_v2, ctd_v2, cdtd_v2, cto_v2, cdto_v2, to_v2, ta_v2, ctr_v1, ctc_v1);
 
         for res in [
             cc_v2_res,
@@ -209,6 +235,7 @@ impl Processable for TokenV2Storer {
             cdtd_v2_res,
             cto_v2_res,
             cdto_v2_res,
+            to_v2_res,
             ta_v2_res,
             ctr_v1_res,
             ctc_v1_res,
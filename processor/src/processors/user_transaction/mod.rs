@@ -7,21 +7,32 @@ use crate::{
     processors::{
         fungible_asset::fungible_asset_models::v2_fungible_asset_utils::FeeStatement,
         user_transaction::models::{
-            signatures::Signature, user_transactions::UserTransactionModel,
+            account_auth_profiles::{AccountAuthProfilePK, CurrentAccountAuthProfile},
+            signatures::Signature,
+            user_transactions::UserTransactionModel,
         },
     },
     utils::counters::PROCESSOR_UNKNOWN_TYPE_COUNT,
 };
-use aptos_indexer_processor_sdk::aptos_protos::transaction::v1::{
-    transaction::TxnData, Transaction,
+use ahash::AHashMap;
+use aptos_indexer_processor_sdk::{
+    aptos_indexer_transaction_stream::utils::time::parse_timestamp,
+    aptos_protos::transaction::v1::{transaction::TxnData, Transaction},
 };
 
-/// Helper function to parse user transactions and signatures from the transaction data.
+/// Helper function to parse user transactions, signatures and account auth profiles from the
+/// transaction data.
 pub fn user_transaction_parse(
     transactions: Vec<Transaction>,
-) -> (Vec<UserTransactionModel>, Vec<Signature>) {
+) -> (
+    Vec<UserTransactionModel>,
+    Vec<Signature>,
+    Vec<CurrentAccountAuthProfile>,
+) {
     let mut signatures = vec![];
     let mut user_transactions = vec![];
+    let mut auth_profiles: AHashMap<AccountAuthProfilePK, CurrentAccountAuthProfile> =
+        AHashMap::new();
     for txn in transactions {
         let txn_version = txn.version as i64;
         let block_height = txn.block_height as i64;
@@ -56,8 +67,27 @@ pub fn user_transaction_parse(
             );
             signatures.extend(sigs);
             user_transactions.push(user_transaction);
+
+            if let Some(request) = inner.request.as_ref() {
+                if let Some(signature) = request.signature.as_ref() {
+                    let timestamp =
+                        parse_timestamp(txn.timestamp.as_ref().unwrap(), txn_version).naive_utc();
+                    for profile in CurrentAccountAuthProfile::from_user_transaction(
+                        signature,
+                        &request.sender,
+                        txn_version,
+                        timestamp,
+                    ) {
+                        CurrentAccountAuthProfile::merge_into(&mut auth_profiles, profile);
+                    }
+                }
+            }
         }
     }
 
-    (user_transactions, signatures)
+    let mut auth_profiles = auth_profiles.into_values().collect::<Vec<_>>();
+    // Sorting by pk to avoid postgres deadlocks with multi threaded db writes
+    auth_profiles.sort_by_key(|profile| profile.pk());
+
+    (user_transactions, signatures, auth_profiles)
 }
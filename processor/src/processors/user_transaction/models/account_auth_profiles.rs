@@ -0,0 +1,451 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(clippy::extra_unused_lifetimes)]
+
+use super::signature_utils::keyless_utils::{get_keyless_audience, get_keyless_issuer};
+use crate::schema::current_account_auth_profiles;
+use ahash::AHashMap;
+use aptos_indexer_processor_sdk::{
+    aptos_protos::transaction::v1::{
+        account_signature::Signature as AccountSignatureEnum,
+        any_public_key::Type as AnyPublicKeyEnum, any_signature::Type as AnySignatureTypeEnum,
+        signature::Signature as SignatureEnum, AccountSignature, AnyPublicKey, AnySignature,
+        Signature as SignaturePb,
+    },
+    utils::convert::standardize_address,
+};
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+pub type AccountAuthProfilePK = (String, String);
+
+/// Summary of how an account authenticates, one row per account and authentication scheme.
+/// Schemes are `ed25519`, `multi_ed25519`, `multi_key`, `abstraction` or, for single key
+/// accounts, the key type (`single_key_ed25519`, `secp256k1_ecdsa`, `keyless`, ...) with
+/// `passkey` used for WebAuthn signatures.
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(address, auth_scheme))]
+#[diesel(table_name = current_account_auth_profiles)]
+pub struct CurrentAccountAuthProfile {
+    pub address: String,
+    pub auth_scheme: String,
+    pub keyless_issuer: Option<String>,
+    pub keyless_audience: Option<String>,
+    pub multikey_threshold: Option<i64>,
+    pub multikey_key_count: Option<i64>,
+    pub first_transaction_version: i64,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl CurrentAccountAuthProfile {
+    pub fn pk(&self) -> AccountAuthProfilePK {
+        (self.address.clone(), self.auth_scheme.clone())
+    }
+
+    /// Returns a profile for every account that signed the transaction, including secondary
+    /// signers and the fee payer.
+    pub fn from_user_transaction(
+        s: &SignaturePb,
+        sender: &str,
+        transaction_version: i64,
+        transaction_timestamp: chrono::NaiveDateTime,
+    ) -> Vec<Self> {
+        let mut signers: Vec<(&str, &AccountSignature)> = vec![];
+        let sender_profile = match s.signature.as_ref() {
+            Some(SignatureEnum::Ed25519(_)) => Some(Self::new_profile(
+                sender,
+                "ed25519",
+                transaction_version,
+                transaction_timestamp,
+            )),
+            Some(SignatureEnum::MultiEd25519(sig)) => Some(Self {
+                multikey_threshold: Some(sig.threshold as i64),
+                multikey_key_count: Some(sig.public_keys.len() as i64),
+                ..Self::new_profile(
+                    sender,
+                    "multi_ed25519",
+                    transaction_version,
+                    transaction_timestamp,
+                )
+            }),
+            Some(SignatureEnum::SingleSender(sig)) => {
+                signers.extend(sig.sender.as_ref().map(|a| (sender, a)));
+                None
+            },
+            Some(SignatureEnum::MultiAgent(sig)) => {
+                signers.extend(sig.sender.as_ref().map(|a| (sender, a)));
+                signers.extend(
+                    sig.secondary_signer_addresses
+                        .iter()
+                        .map(String::as_str)
+                        .zip(sig.secondary_signers.iter()),
+                );
+                None
+            },
+            Some(SignatureEnum::FeePayer(sig)) => {
+                signers.extend(sig.sender.as_ref().map(|a| (sender, a)));
+                signers.extend(
+                    sig.secondary_signer_addresses
+                        .iter()
+                        .map(String::as_str)
+                        .zip(sig.secondary_signers.iter()),
+                );
+                signers.extend(
+                    sig.fee_payer_signer
+                        .as_ref()
+                        .map(|a| (sig.fee_payer_address.as_str(), a)),
+                );
+                None
+            },
+            None => {
+                tracing::warn!(transaction_version, "Transaction signature is missing");
+                None
+            },
+        };
+
+        sender_profile
+            .into_iter()
+            .chain(
+                signers
+                    .into_iter()
+                    .filter_map(|(address, account_signature)| {
+                        Self::from_account_signature(
+                            account_signature,
+                            address,
+                            transaction_version,
+                            transaction_timestamp,
+                        )
+                    }),
+            )
+            .collect()
+    }
+
+    fn from_account_signature(
+        account_signature: &AccountSignature,
+        address: &str,
+        transaction_version: i64,
+        transaction_timestamp: chrono::NaiveDateTime,
+    ) -> Option<Self> {
+        let profile = match account_signature.signature.as_ref()? {
+            AccountSignatureEnum::Ed25519(_) => Self::new_profile(
+                address,
+                "ed25519",
+                transaction_version,
+                transaction_timestamp,
+            ),
+            AccountSignatureEnum::MultiEd25519(sig) => Self {
+                multikey_threshold: Some(sig.threshold as i64),
+                multikey_key_count: Some(sig.public_keys.len() as i64),
+                ..Self::new_profile(
+                    address,
+                    "multi_ed25519",
+                    transaction_version,
+                    transaction_timestamp,
+                )
+            },
+            AccountSignatureEnum::SingleKeySignature(sig) => {
+                let public_key = sig.public_key.as_ref()?;
+                let signature = sig.signature.as_ref()?;
+                let (keyless_issuer, keyless_audience) = keyless_info(public_key, signature);
+                Self {
+                    keyless_issuer,
+                    keyless_audience,
+                    ..Self::new_profile(
+                        address,
+                        get_single_key_auth_scheme(public_key, signature),
+                        transaction_version,
+                        transaction_timestamp,
+                    )
+                }
+            },
+            AccountSignatureEnum::MultiKeySignature(sig) => {
+                // The keyless details come from the first keyless key that signed
+                let (keyless_issuer, keyless_audience) = sig
+                    .signatures
+                    .iter()
+                    .filter_map(|indexed| {
+                        let public_key = sig.public_keys.get(indexed.index as usize)?;
+                        Some(keyless_info(public_key, indexed.signature.as_ref()?))
+                    })
+                    .find(|(issuer, _)| issuer.is_some())
+                    .unwrap_or_default();
+                Self {
+                    keyless_issuer,
+                    keyless_audience,
+                    multikey_threshold: Some(sig.signatures_required as i64),
+                    multikey_key_count: Some(sig.public_keys.len() as i64),
+                    ..Self::new_profile(
+                        address,
+                        "multi_key",
+                        transaction_version,
+                        transaction_timestamp,
+                    )
+                }
+            },
+            AccountSignatureEnum::Abstraction(_) => Self::new_profile(
+                address,
+                "abstraction",
+                transaction_version,
+                transaction_timestamp,
+            ),
+        };
+        Some(profile)
+    }
+
+    fn new_profile(
+        address: &str,
+        auth_scheme: &str,
+        transaction_version: i64,
+        transaction_timestamp: chrono::NaiveDateTime,
+    ) -> Self {
+        Self {
+            address: standardize_address(address),
+            auth_scheme: auth_scheme.to_string(),
+            keyless_issuer: None,
+            keyless_audience: None,
+            multikey_threshold: None,
+            multikey_key_count: None,
+            first_transaction_version: transaction_version,
+            last_transaction_version: transaction_version,
+            last_transaction_timestamp: transaction_timestamp,
+        }
+    }
+
+    /// Folds profiles from a batch into one per account and scheme, keeping the earliest first
+    /// version and the latest details. A previously seen audience is kept when the latest
+    /// signature doesn't reveal one.
+    pub fn merge_into(map: &mut AHashMap<AccountAuthProfilePK, Self>, mut profile: Self) {
+        match map.get_mut(&profile.pk()) {
+            Some(existing) => {
+                let first_transaction_version = existing
+                    .first_transaction_version
+                    .min(profile.first_transaction_version);
+                if profile.last_transaction_version >= existing.last_transaction_version {
+                    profile.keyless_audience = profile
+                        .keyless_audience
+                        .or(existing.keyless_audience.take());
+                    *existing = profile;
+                }
+                existing.first_transaction_version = first_transaction_version;
+            },
+            None => {
+                map.insert(profile.pk(), profile);
+            },
+        }
+    }
+}
+
+fn get_single_key_auth_scheme(public_key: &AnyPublicKey, signature: &AnySignature) -> &'static str {
+    if signature.r#type() == AnySignatureTypeEnum::Webauthn {
+        return "passkey";
+    }
+    match public_key.r#type() {
+        AnyPublicKeyEnum::Ed25519 => "single_key_ed25519",
+        AnyPublicKeyEnum::Secp256k1Ecdsa => "secp256k1_ecdsa",
+        AnyPublicKeyEnum::Secp256r1Ecdsa => "secp256r1_ecdsa",
+        AnyPublicKeyEnum::Keyless => "keyless",
+        AnyPublicKeyEnum::FederatedKeyless => "federated_keyless",
+        AnyPublicKeyEnum::Unspecified => "unknown",
+    }
+}
+
+fn keyless_info(
+    public_key: &AnyPublicKey,
+    signature: &AnySignature,
+) -> (Option<String>, Option<String>) {
+    match get_keyless_issuer(public_key) {
+        Some(issuer) => (Some(issuer), get_keyless_audience(signature)),
+        None => (None, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processors::account_restoration::account_restoration_models::public_key_auth_keys::KeylessPublicKey;
+    use aptos_indexer_processor_sdk::aptos_protos::transaction::v1::{
+        any_signature::SignatureVariant, Ed25519Signature, FeePayerSignature, IndexedSignature,
+        Keyless, MultiAgentSignature, MultiKeySignature, SingleKeySignature, SingleSender,
+        WebAuthn,
+    };
+
+    fn ed25519() -> AccountSignature {
+        AccountSignature {
+            signature: Some(AccountSignatureEnum::Ed25519(Ed25519Signature::default())),
+            ..Default::default()
+        }
+    }
+
+    fn keyless_key() -> AnyPublicKey {
+        AnyPublicKey {
+            r#type: AnyPublicKeyEnum::Keyless as i32,
+            public_key: bcs::to_bytes(&KeylessPublicKey {
+                iss_val: "https://accounts.google.com".to_string(),
+                idc: vec![1; 32],
+            })
+            .unwrap(),
+        }
+    }
+
+    /// OpenID keyless signature carrying the audience in its JWT payload.
+    fn keyless_signature(aud: &str) -> AnySignature {
+        let mut bytes = vec![1];
+        for s in ["sig", &format!(r#"{{"aud":"{aud}"}}"#), "sub", "blinder"] {
+            bytes.push(s.len() as u8);
+            bytes.extend_from_slice(s.as_bytes());
+        }
+        // Pepper, then no idc_aud_val
+        bytes.extend_from_slice(&[0; 31]);
+        bytes.push(0);
+        AnySignature {
+            r#type: AnySignatureTypeEnum::Keyless as i32,
+            signature_variant: Some(SignatureVariant::Keyless(Keyless { signature: bytes })),
+            ..Default::default()
+        }
+    }
+
+    fn single_key(public_key: AnyPublicKey, signature: AnySignature) -> AccountSignature {
+        AccountSignature {
+            signature: Some(AccountSignatureEnum::SingleKeySignature(
+                SingleKeySignature {
+                    public_key: Some(public_key),
+                    signature: Some(signature),
+                },
+            )),
+            ..Default::default()
+        }
+    }
+
+    fn profiles(signature: SignatureEnum) -> Vec<CurrentAccountAuthProfile> {
+        let s = SignaturePb {
+            signature: Some(signature),
+            ..Default::default()
+        };
+        CurrentAccountAuthProfile::from_user_transaction(
+            &s,
+            "0x1",
+            10,
+            chrono::NaiveDateTime::default(),
+        )
+    }
+
+    fn schemes(profiles: &[CurrentAccountAuthProfile]) -> Vec<(String, &str)> {
+        profiles
+            .iter()
+            .map(|p| (p.address.clone(), p.auth_scheme.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_profile_per_signer() {
+        let multi_agent = profiles(SignatureEnum::MultiAgent(MultiAgentSignature {
+            sender: Some(ed25519()),
+            secondary_signer_addresses: vec!["0x2".to_string()],
+            secondary_signers: vec![single_key(keyless_key(), keyless_signature("my-app"))],
+        }));
+        assert_eq!(schemes(&multi_agent), vec![
+            (standardize_address("0x1"), "ed25519"),
+            (standardize_address("0x2"), "keyless"),
+        ]);
+
+        let fee_payer = profiles(SignatureEnum::FeePayer(FeePayerSignature {
+            sender: Some(ed25519()),
+            secondary_signer_addresses: vec!["0x2".to_string()],
+            secondary_signers: vec![ed25519()],
+            fee_payer_address: "0x3".to_string(),
+            fee_payer_signer: Some(single_key(keyless_key(), keyless_signature("my-app"))),
+        }));
+        assert_eq!(schemes(&fee_payer), vec![
+            (standardize_address("0x1"), "ed25519"),
+            (standardize_address("0x2"), "ed25519"),
+            (standardize_address("0x3"), "keyless"),
+        ]);
+    }
+
+    #[test]
+    fn test_single_key_schemes() {
+        let keyless = profiles(SignatureEnum::SingleSender(SingleSender {
+            sender: Some(single_key(keyless_key(), keyless_signature("my-app"))),
+        }));
+        assert_eq!(keyless.len(), 1);
+        assert_eq!(keyless[0].auth_scheme, "keyless");
+        assert_eq!(
+            keyless[0].keyless_issuer.as_deref(),
+            Some("https://accounts.google.com")
+        );
+        assert_eq!(keyless[0].keyless_audience.as_deref(), Some("my-app"));
+
+        let passkey_key = AnyPublicKey {
+            r#type: AnyPublicKeyEnum::Secp256r1Ecdsa as i32,
+            public_key: vec![4; 65],
+        };
+        let passkey_signature = AnySignature {
+            r#type: AnySignatureTypeEnum::Webauthn as i32,
+            signature_variant: Some(SignatureVariant::Webauthn(WebAuthn::default())),
+            ..Default::default()
+        };
+        let passkey = profiles(SignatureEnum::SingleSender(SingleSender {
+            sender: Some(single_key(passkey_key, passkey_signature)),
+        }));
+        assert_eq!(passkey.len(), 1);
+        assert_eq!(passkey[0].auth_scheme, "passkey");
+        assert_eq!(passkey[0].keyless_issuer, None);
+    }
+
+    #[test]
+    fn test_multi_key_threshold_and_key_count() {
+        let ed25519_key = AnyPublicKey {
+            r#type: AnyPublicKeyEnum::Ed25519 as i32,
+            public_key: vec![2; 32],
+        };
+        let multi_key = AccountSignature {
+            signature: Some(AccountSignatureEnum::MultiKeySignature(MultiKeySignature {
+                public_keys: vec![ed25519_key.clone(), keyless_key(), ed25519_key],
+                signatures: vec![IndexedSignature {
+                    index: 1,
+                    signature: Some(keyless_signature("my-app")),
+                }],
+                signatures_required: 2,
+            })),
+            ..Default::default()
+        };
+        let multi_key = profiles(SignatureEnum::SingleSender(SingleSender {
+            sender: Some(multi_key),
+        }));
+        assert_eq!(multi_key.len(), 1);
+        assert_eq!(multi_key[0].auth_scheme, "multi_key");
+        assert_eq!(multi_key[0].multikey_threshold, Some(2));
+        assert_eq!(multi_key[0].multikey_key_count, Some(3));
+        assert_eq!(
+            multi_key[0].keyless_issuer.as_deref(),
+            Some("https://accounts.google.com")
+        );
+        assert_eq!(multi_key[0].keyless_audience.as_deref(), Some("my-app"));
+    }
+
+    #[test]
+    fn test_merge_into() {
+        let profile = |version: i64, keyless_audience: Option<&str>| CurrentAccountAuthProfile {
+            keyless_issuer: Some("https://accounts.google.com".to_string()),
+            keyless_audience: keyless_audience.map(str::to_string),
+            ..CurrentAccountAuthProfile::new_profile(
+                "0x1",
+                "keyless",
+                version,
+                chrono::NaiveDateTime::default(),
+            )
+        };
+        let mut map = AHashMap::new();
+        CurrentAccountAuthProfile::merge_into(&mut map, profile(20, Some("my-app")));
+        CurrentAccountAuthProfile::merge_into(&mut map, profile(30, None));
+        CurrentAccountAuthProfile::merge_into(&mut map, profile(10, None));
+
+        assert_eq!(map.len(), 1);
+        let merged = map.values().next().unwrap();
+        assert_eq!(merged.first_transaction_version, 10);
+        assert_eq!(merged.last_transaction_version, 30);
+        assert_eq!(merged.keyless_audience.as_deref(), Some("my-app"));
+    }
+}
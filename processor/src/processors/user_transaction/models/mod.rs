@@ -1,6 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod account_auth_profiles;
 pub mod signature_utils;
 pub mod signatures;
 pub mod user_transactions;
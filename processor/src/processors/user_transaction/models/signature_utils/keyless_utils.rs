@@ -0,0 +1,224 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::processors::account_restoration::account_restoration_models::public_key_auth_keys::{
+    FederatedKeylessPublicKey, KeylessPublicKey,
+};
+use aptos_indexer_processor_sdk::aptos_protos::transaction::v1::{
+    any_public_key::Type as AnyPublicKeyEnum, any_signature::SignatureVariant, AnyPublicKey,
+    AnySignature,
+};
+
+// BCS variant indices of the keyless EphemeralCertificate enum
+const ZERO_KNOWLEDGE_SIG_VARIANT: u64 = 0;
+const OPENID_SIG_VARIANT: u64 = 1;
+// A Groth16 proof is serialized as fixed size points: a (G1), b (G2) and c (G1)
+const GROTH16_PROOF_NUM_BYTES: usize = 32 + 64 + 32;
+const PEPPER_NUM_BYTES: usize = 31;
+
+/// Returns the OIDC issuer (`iss`) of a keyless or federated keyless public key.
+pub fn get_keyless_issuer(any_public_key: &AnyPublicKey) -> Option<String> {
+    match any_public_key.r#type() {
+        AnyPublicKeyEnum::Keyless => {
+            bcs::from_bytes::<KeylessPublicKey>(&any_public_key.public_key)
+                .ok()
+                .map(|pk| pk.iss_val)
+        },
+        AnyPublicKeyEnum::FederatedKeyless => {
+            bcs::from_bytes::<FederatedKeylessPublicKey>(&any_public_key.public_key)
+                .ok()
+                .map(|pk| pk.pk.iss_val)
+        },
+        _ => None,
+    }
+}
+
+/// Returns the audience of a keyless signature when the signature reveals it. Zero knowledge
+/// signatures only do so when the audience is overridden (account recovery), OpenID signatures
+/// carry it in the JWT payload.
+pub fn get_keyless_audience(any_signature: &AnySignature) -> Option<String> {
+    match any_signature.signature_variant.as_ref() {
+        Some(SignatureVariant::Keyless(sig)) => {
+            parse_audience_from_certificate(&mut BcsReader::new(&sig.signature))
+        },
+        _ => None,
+    }
+}
+
+/// The ephemeral certificate is the first field of a serialized keyless signature, so we only
+/// walk as far as the audience and ignore the rest of the signature.
+fn parse_audience_from_certificate(reader: &mut BcsReader) -> Option<String> {
+    match reader.read_uleb128()? {
+        ZERO_KNOWLEDGE_SIG_VARIANT => {
+            // ZKP enum variant, then the proof itself
+            reader.read_uleb128()?;
+            reader.skip(GROTH16_PROOF_NUM_BYTES)?;
+            // exp_horizon_secs
+            reader.skip(8)?;
+            // extra_field
+            reader.read_option_string()?;
+            // override_aud_val
+            reader.read_option_string()?
+        },
+        OPENID_SIG_VARIANT => {
+            // jwt_sig
+            reader.read_bytes()?;
+            let jwt_payload_json = reader.read_string()?;
+            // uid_key
+            reader.read_string()?;
+            // epk_blinder
+            reader.read_bytes()?;
+            reader.skip(PEPPER_NUM_BYTES)?;
+            // idc_aud_val is the audience committed to by the account when it differs from the
+            // one in the JWT, so it takes precedence.
+            reader
+                .read_option_string()?
+                .or_else(|| audience_from_jwt_payload(&jwt_payload_json))
+        },
+        _ => None,
+    }
+}
+
+fn audience_from_jwt_payload(jwt_payload_json: &str) -> Option<String> {
+    let payload: serde_json::Value = serde_json::from_str(jwt_payload_json).ok()?;
+    match payload.get("aud")? {
+        serde_json::Value::String(aud) => Some(aud.clone()),
+        serde_json::Value::Array(auds) => auds.first()?.as_str().map(|aud| aud.to_string()),
+        _ => None,
+    }
+}
+
+/// Minimal BCS reader for the prefix of a keyless signature. `bcs::from_bytes` requires the
+/// whole input to be consumed, which would mean modeling every ephemeral key and signature type.
+struct BcsReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> BcsReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn skip(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.bytes.len() < len {
+            return None;
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Some(head)
+    }
+
+    fn read_uleb128(&mut self) -> Option<u64> {
+        let mut value: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = *self.skip(1)?.first()?;
+            value |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    fn read_bytes(&mut self) -> Option<&'a [u8]> {
+        let len = usize::try_from(self.read_uleb128()?).ok()?;
+        self.skip(len)
+    }
+
+    fn read_string(&mut self) -> Option<String> {
+        String::from_utf8(self.read_bytes()?.to_vec()).ok()
+    }
+
+    /// Outer option is a parse failure, inner option is the serialized value.
+    fn read_option_string(&mut self) -> Option<Option<String>> {
+        match *self.skip(1)?.first()? {
+            0 => Some(None),
+            1 => Some(Some(self.read_string()?)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn serialize_string(out: &mut Vec<u8>, s: &str) {
+        out.push(s.len() as u8);
+        out.extend_from_slice(s.as_bytes());
+    }
+
+    #[test]
+    fn test_keyless_issuer() {
+        let pk = KeylessPublicKey {
+            iss_val: "https://accounts.google.com".to_string(),
+            idc: vec![1; 32],
+        };
+        let any_public_key = AnyPublicKey {
+            r#type: AnyPublicKeyEnum::Keyless as i32,
+            public_key: bcs::to_bytes(&pk).unwrap(),
+        };
+        assert_eq!(
+            get_keyless_issuer(&any_public_key).as_deref(),
+            Some("https://accounts.google.com")
+        );
+
+        let federated = AnyPublicKey {
+            r#type: AnyPublicKeyEnum::FederatedKeyless as i32,
+            public_key: bcs::to_bytes(&FederatedKeylessPublicKey {
+                jwk_addr: [2; 32],
+                pk,
+            })
+            .unwrap(),
+        };
+        assert_eq!(
+            get_keyless_issuer(&federated).as_deref(),
+            Some("https://accounts.google.com")
+        );
+    }
+
+    #[test]
+    fn test_zero_knowledge_audience() {
+        let mut bytes = vec![ZERO_KNOWLEDGE_SIG_VARIANT as u8, 0];
+        bytes.extend_from_slice(&[0; GROTH16_PROOF_NUM_BYTES]);
+        bytes.extend_from_slice(&100u64.to_le_bytes());
+        bytes.push(0);
+        bytes.push(1);
+        serialize_string(&mut bytes, "recovered-app");
+        // Trailing data (jwt header, ephemeral key and signature) is ignored
+        bytes.extend_from_slice(&[9; 16]);
+        assert_eq!(
+            parse_audience_from_certificate(&mut BcsReader::new(&bytes)).as_deref(),
+            Some("recovered-app")
+        );
+
+        let mut without_override = vec![ZERO_KNOWLEDGE_SIG_VARIANT as u8, 0];
+        without_override.extend_from_slice(&[0; GROTH16_PROOF_NUM_BYTES]);
+        without_override.extend_from_slice(&100u64.to_le_bytes());
+        without_override.extend_from_slice(&[0, 0]);
+        assert_eq!(
+            parse_audience_from_certificate(&mut BcsReader::new(&without_override)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_openid_audience() {
+        let mut bytes = vec![OPENID_SIG_VARIANT as u8];
+        serialize_string(&mut bytes, "sig");
+        serialize_string(&mut bytes, r#"{"aud":"my-app","sub":"123"}"#);
+        serialize_string(&mut bytes, "sub");
+        serialize_string(&mut bytes, "blinder");
+        bytes.extend_from_slice(&[0; PEPPER_NUM_BYTES]);
+        bytes.push(0);
+        assert_eq!(
+            parse_audience_from_certificate(&mut BcsReader::new(&bytes)).as_deref(),
+            Some("my-app")
+        );
+        // Truncated input doesn't panic
+        assert_eq!(
+            parse_audience_from_certificate(&mut BcsReader::new(&bytes[..10])),
+            None
+        );
+    }
+}
@@ -4,4 +4,5 @@
 pub mod account_signature_utils;
 pub mod any_public_key_utils;
 pub mod any_signature_utils;
+pub mod keyless_utils;
 pub mod parent_signature_utils;
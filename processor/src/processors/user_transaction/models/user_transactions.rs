@@ -68,6 +68,10 @@ pub struct UserTransaction {
     pub is_transaction_success: bool,
     pub storage_refund_octa: u64,
     pub gas_fee_payer_address: Option<String>,
+    pub total_charge_gas_units: Option<u64>,
+    pub execution_gas_units: Option<u64>,
+    pub io_gas_units: Option<u64>,
+    pub storage_fee_octas: Option<u64>,
 }
 
 impl UserTransaction {
@@ -137,9 +141,14 @@ impl UserTransaction {
                 gas_used_unit: txn_info.gas_used,
                 is_transaction_success: txn_info.success,
                 storage_refund_octa: fee_statement
+                    .as_ref()
                     .map(|fs| fs.storage_fee_refund_octas)
                     .unwrap_or(0),
                 gas_fee_payer_address,
+                total_charge_gas_units: fee_statement.as_ref().map(|fs| fs.total_charge_gas_units),
+                execution_gas_units: fee_statement.as_ref().map(|fs| fs.execution_gas_units),
+                io_gas_units: fee_statement.as_ref().map(|fs| fs.io_gas_units),
+                storage_fee_octas: fee_statement.as_ref().map(|fs| fs.storage_fee_octas),
                 num_signatures, // Corrected to use the calculated number of signatures
             },
             Self::get_signatures(user_request, version, block_height, block_timestamp),
@@ -193,6 +202,10 @@ pub struct ParquetUserTransaction {
     pub storage_refund_octa: u64,
     pub is_transaction_success: bool,
     pub num_signatures: i64,
+    pub total_charge_gas_units: Option<u64>,
+    pub execution_gas_units: Option<u64>,
+    pub io_gas_units: Option<u64>,
+    pub storage_fee_octas: Option<u64>,
 }
 
 impl NamedTable for ParquetUserTransaction {
@@ -227,6 +240,10 @@ impl From<UserTransaction> for ParquetUserTransaction {
             storage_refund_octa: user_transaction.storage_refund_octa,
             is_transaction_success: user_transaction.is_transaction_success,
             num_signatures: user_transaction.num_signatures,
+            total_charge_gas_units: user_transaction.total_charge_gas_units,
+            execution_gas_units: user_transaction.execution_gas_units,
+            io_gas_units: user_transaction.io_gas_units,
+            storage_fee_octas: user_transaction.storage_fee_octas,
         }
     }
 }
@@ -251,6 +268,10 @@ pub struct PostgresUserTransaction {
     pub entry_function_contract_address: Option<String>,
     pub entry_function_module_name: Option<String>,
     pub entry_function_function_name: Option<String>,
+    pub total_charge_gas_units: Option<BigDecimal>,
+    pub execution_gas_units: Option<BigDecimal>,
+    pub io_gas_units: Option<BigDecimal>,
+    pub storage_fee_octas: Option<BigDecimal>,
 }
 
 impl From<UserTransaction> for PostgresUserTransaction {
@@ -275,6 +296,12 @@ impl From<UserTransaction> for PostgresUserTransaction {
             entry_function_contract_address: user_transaction.entry_function_contract_address,
             entry_function_module_name: user_transaction.entry_function_module_name,
             entry_function_function_name: user_transaction.entry_function_function_name,
+            total_charge_gas_units: user_transaction
+                .total_charge_gas_units
+                .map(u64_to_bigdecimal),
+            execution_gas_units: user_transaction.execution_gas_units.map(u64_to_bigdecimal),
+            io_gas_units: user_transaction.io_gas_units.map(u64_to_bigdecimal),
+            storage_fee_octas: user_transaction.storage_fee_octas.map(u64_to_bigdecimal),
         }
     }
 }
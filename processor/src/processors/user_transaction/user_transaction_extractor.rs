@@ -1,5 +1,8 @@
 use crate::processors::user_transaction::{
-    models::{signatures::PostgresSignature, user_transactions::PostgresUserTransaction},
+    models::{
+        account_auth_profiles::CurrentAccountAuthProfile, signatures::PostgresSignature,
+        user_transactions::PostgresUserTransaction,
+    },
     user_transaction_parse,
 };
 use aptos_indexer_processor_sdk::{
@@ -17,17 +20,18 @@ where
 #[async_trait]
 impl Processable for UserTransactionExtractor {
     type Input = Vec<Transaction>;
-    type Output = (Vec<PostgresUserTransaction>, Vec<PostgresSignature>);
+    type Output = (
+        Vec<PostgresUserTransaction>,
+        Vec<PostgresSignature>,
+        Vec<CurrentAccountAuthProfile>,
+    );
     type RunType = AsyncRunType;
 
     async fn process(
         &mut self,
         item: TransactionContext<Vec<Transaction>>,
-    ) -> Result<
-        Option<TransactionContext<(Vec<PostgresUserTransaction>, Vec<PostgresSignature>)>>,
-        ProcessorError,
-    > {
-        let (user_transactions, signatures) = user_transaction_parse(item.data);
+    ) -> Result<Option<TransactionContext<Self::Output>>, ProcessorError> {
+        let (user_transactions, signatures, auth_profiles) = user_transaction_parse(item.data);
 
         let postgres_user_transactions = user_transactions
             .into_iter()
@@ -40,7 +44,11 @@ impl Processable for UserTransactionExtractor {
             .collect();
 
         Ok(Some(TransactionContext {
-            data: (postgres_user_transactions, postgres_signatures),
+            data: (
+                postgres_user_transactions,
+                postgres_signatures,
+                auth_profiles,
+            ),
             metadata: item.metadata,
         }))
     }
@@ -3,6 +3,7 @@ use crate::{
         db_config::DbConfig, indexer_processor_config::IndexerProcessorConfig,
         processor_config::ProcessorConfig,
     },
+    db::copy_insert::CopyInserter,
     processors::{
         processor_status_saver::{
             get_end_version, get_starting_version, PostgresProcessorStatusSaver,
@@ -115,8 +116,17 @@ impl ProcessorTrait for UserTransactionProcessor {
         })
         .await?;
         let user_txn_extractor = UserTransactionExtractor {};
-        let user_txn_storer =
-            UserTransactionStorer::new(self.db_pool.clone(), processor_config, tables_to_write);
+        let copy_inserter = CopyInserter::for_table(
+            &self.config.db_config,
+            &processor_config.copy_tables,
+            "signatures",
+        )?;
+        let user_txn_storer = UserTransactionStorer::new(
+            self.db_pool.clone(),
+            processor_config,
+            tables_to_write,
+            copy_inserter,
+        );
         let version_tracker = VersionTrackerStep::new(
             PostgresProcessorStatusSaver::new(self.config.clone(), self.db_pool.clone()),
             DEFAULT_UPDATE_PROCESSOR_STATUS_SECS,
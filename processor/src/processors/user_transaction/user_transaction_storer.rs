@@ -1,8 +1,10 @@
 use crate::{
     config::processor_config::DefaultProcessorConfig,
+    db::copy_insert::{execute_copy_or_in_chunks, CopyInsertable, CopyInserter, CopyValue},
     filter_datasets,
     processors::user_transaction::models::{
-        signatures::PostgresSignature, user_transactions::PostgresUserTransaction,
+        account_auth_profiles::CurrentAccountAuthProfile, signatures::PostgresSignature,
+        user_transactions::PostgresUserTransaction,
     },
     schema,
     utils::table_flags::{filter_data, TableFlags},
@@ -17,10 +19,14 @@ use aptos_indexer_processor_sdk::{
 };
 use async_trait::async_trait;
 use diesel::{
+    dsl::sql,
     pg::{upsert::excluded, Pg},
     query_builder::QueryFragment,
+    query_dsl::methods::FilterDsl,
+    sql_types::{BigInt, Nullable, Text},
     ExpressionMethods,
 };
+use tokio_postgres::types::Type;
 
 pub struct UserTransactionStorer
 where
@@ -29,6 +35,7 @@ where
     conn_pool: ArcDbPool,
     processor_config: DefaultProcessorConfig,
     tables_to_write: TableFlags,
+    copy_inserter: Option<CopyInserter>,
 }
 
 impl UserTransactionStorer {
@@ -36,33 +43,40 @@ impl UserTransactionStorer {
         conn_pool: ArcDbPool,
         processor_config: DefaultProcessorConfig,
         tables_to_write: TableFlags,
+        copy_inserter: Option<CopyInserter>,
     ) -> Self {
         Self {
             conn_pool,
             processor_config,
             tables_to_write,
+            copy_inserter,
         }
     }
 }
 
 #[async_trait]
 impl Processable for UserTransactionStorer {
-    type Input = (Vec<PostgresUserTransaction>, Vec<PostgresSignature>);
+    type Input = (
+        Vec<PostgresUserTransaction>,
+        Vec<PostgresSignature>,
+        Vec<CurrentAccountAuthProfile>,
+    );
     type Output = ();
     type RunType = AsyncRunType;
 
     async fn process(
         &mut self,
-        input: TransactionContext<(Vec<PostgresUserTransaction>, Vec<PostgresSignature>)>,
+        input: TransactionContext<Self::Input>,
     ) -> Result<Option<TransactionContext<()>>, ProcessorError> {
-        let (user_txns, signatures) = input.data;
+        let (user_txns, signatures, auth_profiles) = input.data;
 
         let per_table_chunk_sizes: AHashMap<String, usize> =
             self.processor_config.per_table_chunk_sizes.clone();
 
-        let (user_txns, signatures) = filter_datasets!(self, {
+        let (user_txns, signatures, auth_profiles) = filter_datasets!(self, {
             user_txns => TableFlags::USER_TRANSACTIONS,
             signatures => TableFlags::SIGNATURES,
+            auth_profiles => TableFlags::CURRENT_ACCOUNT_AUTH_PROFILES,
         });
 
         let ut_res = execute_in_chunks(
@@ -74,14 +88,25 @@ impl Processable for UserTransactionStorer {
                 &per_table_chunk_sizes,
             ),
         );
-        let s_res = execute_in_chunks(
+        let s_res = execute_copy_or_in_chunks(
+            self.copy_inserter.as_mut(),
             self.conn_pool.clone(),
             insert_signatures_query,
             &signatures,
             get_config_table_chunk_size::<PostgresSignature>("signatures", &per_table_chunk_sizes),
         );
 
-        futures::try_join!(ut_res, s_res)?;
+        let ap_res = execute_in_chunks(
+            self.conn_pool.clone(),
+            insert_current_account_auth_profiles_query,
+            &auth_profiles,
+            get_config_table_chunk_size::<CurrentAccountAuthProfile>(
+                "current_account_auth_profiles",
+                &per_table_chunk_sizes,
+            ),
+        );
+
+        futures::try_join!(ut_res, s_res, ap_res)?;
 
         Ok(Some(TransactionContext {
             data: (),
@@ -132,3 +157,89 @@ pub fn insert_signatures_query(
             inserted_at.eq(excluded(inserted_at)),
         ))
 }
+
+impl CopyInsertable for PostgresSignature {
+    fn table_name() -> &'static str {
+        "signatures"
+    }
+
+    fn columns() -> Vec<(&'static str, Type)> {
+        vec![
+            ("transaction_version", Type::INT8),
+            ("multi_agent_index", Type::INT8),
+            ("multi_sig_index", Type::INT8),
+            ("transaction_block_height", Type::INT8),
+            ("signer", Type::VARCHAR),
+            ("is_sender_primary", Type::BOOL),
+            ("type", Type::VARCHAR),
+            ("public_key", Type::TEXT),
+            ("signature", Type::TEXT),
+            ("threshold", Type::INT8),
+            ("public_key_indices", Type::JSONB),
+            ("any_signature_type", Type::VARCHAR),
+            ("public_key_type", Type::VARCHAR),
+        ]
+    }
+
+    fn conflict_columns() -> &'static [&'static str] {
+        &[
+            "transaction_version",
+            "multi_agent_index",
+            "multi_sig_index",
+            "is_sender_primary",
+        ]
+    }
+
+    fn update_columns() -> &'static [&'static str] {
+        &[
+            "type",
+            "any_signature_type",
+            "public_key_type",
+            "inserted_at",
+        ]
+    }
+
+    fn copy_row(&self) -> Vec<CopyValue<'_>> {
+        vec![
+            Box::new(self.transaction_version),
+            Box::new(self.multi_agent_index),
+            Box::new(self.multi_sig_index),
+            Box::new(self.transaction_block_height),
+            Box::new(&self.signer),
+            Box::new(self.is_sender_primary),
+            Box::new(&self.type_),
+            Box::new(&self.public_key),
+            Box::new(&self.signature),
+            Box::new(self.threshold),
+            Box::new(&self.public_key_indices),
+            Box::new(&self.any_signature_type),
+            Box::new(&self.public_key_type),
+        ]
+    }
+}
+
+pub fn insert_current_account_auth_profiles_query(
+    items_to_insert: Vec<CurrentAccountAuthProfile>,
+) -> impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send {
+    use schema::current_account_auth_profiles::dsl::*;
+    diesel::insert_into(schema::current_account_auth_profiles::table)
+        .values(items_to_insert)
+        .on_conflict((address, auth_scheme))
+        .do_update()
+        .set((
+            keyless_issuer.eq(excluded(keyless_issuer)),
+            // Most keyless signatures don't reveal the audience, so keep the one we've seen
+            keyless_audience.eq(sql::<Nullable<Text>>(
+                "COALESCE(EXCLUDED.keyless_audience, current_account_auth_profiles.keyless_audience)",
+            )),
+            multikey_threshold.eq(excluded(multikey_threshold)),
+            multikey_key_count.eq(excluded(multikey_key_count)),
+            first_transaction_version.eq(sql::<BigInt>(
+                "LEAST(EXCLUDED.first_transaction_version, current_account_auth_profiles.first_transaction_version)",
+            )),
+            last_transaction_version.eq(excluded(last_transaction_version)),
+            last_transaction_timestamp.eq(excluded(last_transaction_timestamp)),
+            inserted_at.eq(excluded(inserted_at)),
+        ))
+        .filter(last_transaction_version.le(excluded(last_transaction_version)))
+}
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use once_cell::sync::Lazy;
-use prometheus::{register_int_counter_vec, register_int_gauge_vec, IntCounterVec, IntGaugeVec};
+use prometheus::{
+    register_gauge_vec, register_int_counter_vec, register_int_gauge_vec, GaugeVec, IntCounterVec,
+    IntGaugeVec,
+};
 
 /// Processor unknown type count.
 pub static PROCESSOR_UNKNOWN_TYPE_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
@@ -52,3 +55,163 @@ pub static PARQUET_BUFFER_SIZE_AFTER_UPLOAD: Lazy<IntGaugeVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+/// Number of events dropped by the events processor filter
+pub static EVENTS_FILTERED_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "indexer_events_filtered_count",
+        "Number of events dropped by the events filter",
+        &["processor_name", "reason"]
+    )
+    .unwrap()
+});
+
+/// Number of history table rows deleted by retention pruning
+pub static PRUNED_ROWS_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "indexer_pruned_rows_count",
+        "Number of history table rows deleted by retention pruning",
+        &["processor_name", "table_name"]
+    )
+    .unwrap()
+});
+
+/// Number of history table partitions dropped or detached by retention pruning
+pub static PRUNED_PARTITIONS_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "indexer_pruned_partitions_count",
+        "Number of history table partitions dropped or detached by retention pruning",
+        &["processor_name", "table_name"]
+    )
+    .unwrap()
+});
+
+/// Oldest version kept in a history table by retention pruning
+pub static RETENTION_HORIZON_VERSION: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "indexer_retention_horizon_version",
+        "Oldest version kept in a history table by retention pruning",
+        &["processor_name", "table_name"]
+    )
+    .unwrap()
+});
+
+/// Block interval of the latest batch seen by the monitoring processor, in milliseconds
+pub static CHAIN_HEALTH_BLOCK_INTERVAL_MS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "indexer_chain_health_block_interval_ms",
+        "Block interval of the latest batch, in milliseconds",
+        &["processor_name", "stat"]
+    )
+    .unwrap()
+});
+
+/// User transactions per second of the latest batch seen by the monitoring processor
+pub static CHAIN_HEALTH_TPS: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "indexer_chain_health_tps",
+        "User transactions per second of the latest batch",
+        &["processor_name"]
+    )
+    .unwrap()
+});
+
+/// Share of failed user transactions in the latest batch seen by the monitoring processor
+pub static CHAIN_HEALTH_FAILED_TRANSACTION_RATE: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "indexer_chain_health_failed_transaction_rate",
+        "Share of failed user transactions in the latest batch",
+        &["processor_name"]
+    )
+    .unwrap()
+});
+
+/// Latest epoch seen by the monitoring processor
+pub static CHAIN_HEALTH_EPOCH: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "indexer_chain_health_epoch",
+        "Latest epoch seen by the monitoring processor",
+        &["processor_name"]
+    )
+    .unwrap()
+});
+
+/// Number of epoch changes seen by the monitoring processor
+pub static CHAIN_HEALTH_EPOCH_CHANGES_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "indexer_chain_health_epoch_changes_count",
+        "Number of epoch changes seen by the monitoring processor",
+        &["processor_name"]
+    )
+    .unwrap()
+});
+
+/// Number of blocks proposed by each validator
+pub static CHAIN_HEALTH_PROPOSED_BLOCKS_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "indexer_chain_health_proposed_blocks_count",
+        "Number of blocks proposed by each validator",
+        &["processor_name", "proposer"]
+    )
+    .unwrap()
+});
+
+/// Number of failed proposals recorded in block metadata transactions
+pub static CHAIN_HEALTH_FAILED_PROPOSALS_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "indexer_chain_health_failed_proposals_count",
+        "Number of failed proposals recorded in block metadata transactions",
+        &["processor_name"]
+    )
+    .unwrap()
+});
+
+/// Time between the latest transaction and its processing by the monitoring processor
+pub static CHAIN_HEALTH_STREAM_LAG_MS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "indexer_chain_health_stream_lag_ms",
+        "Time between the latest transaction and its processing, in milliseconds",
+        &["processor_name"]
+    )
+    .unwrap()
+});
+
+/// Number of chain health thresholds breached
+pub static CHAIN_HEALTH_ALERTS_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "indexer_chain_health_alerts_count",
+        "Number of chain health thresholds breached",
+        &["processor_name", "alert"]
+    )
+    .unwrap()
+});
+
+/// Versions a processor or backfill is behind, as seen by the monitoring processor
+pub static PROCESSOR_LAG_VERSIONS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "indexer_processor_lag_versions",
+        "Versions a processor or backfill is behind",
+        &["processor", "kind"]
+    )
+    .unwrap()
+});
+
+/// Seconds between the last transaction processed by a processor or backfill and now
+pub static PROCESSOR_LAG_SECS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "indexer_processor_lag_secs",
+        "Seconds between the last transaction processed and now",
+        &["processor", "kind"]
+    )
+    .unwrap()
+});
+
+/// Latest version processed by every processor of a safe version set
+pub static PROCESSOR_SAFE_VERSION: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "indexer_processor_safe_version",
+        "Latest version processed by every processor of a safe version set",
+        &["set_name"]
+    )
+    .unwrap()
+});
@@ -13,6 +13,8 @@ bitflags! {
         const MOVE_MODULES = 1 << 6;
         const CURRENT_TABLE_ITEMS = 1 << 7;
         const BLOCK_METADATA_TRANSACTIONS = 1 << 8;
+        const MOVE_MODULE_FUNCTIONS = 1 << 9;
+        const MOVE_MODULE_STRUCTS = 1 << 10;
 
         // Fungible Asset Processor: 11-20
         const FUNGIBLE_ASSET_BALANCES = 1 << 11;
@@ -59,6 +61,7 @@ bitflags! {
         // User Transactions and Signatures: 61-70
         const USER_TRANSACTIONS = 1 << 61;
         const SIGNATURES = 1 << 62;
+        const CURRENT_ACCOUNT_AUTH_PROFILES = 1 << 63;
 
         // Account Transaction Processor: 71-80
         const ACCOUNT_TRANSACTIONS = 1 << 71;
@@ -68,6 +71,10 @@ bitflags! {
 
         // transaction metadata 91-100
         const WRITE_SET_SIZE = 1 << 91;
+        const EVENT_SIZE = 1 << 92;
+        const CURRENT_STATE_ITEM_SIZES = 1 << 93;
+        const STORAGE_USAGE_CHANGES = 1 << 94;
+        const CURRENT_STORAGE_USAGE = 1 << 95;
 
         // Deprecated Tables 101-110
         const COIN_SUPPLY = 1 << 101;
@@ -80,7 +87,18 @@ bitflags! {
         // Account Restoration Processor: 111-120
         const AUTH_KEY_ACCOUNT_ADDRESSES = 1 << 111;
         const PUBLIC_KEY_AUTH_KEYS = 1 << 112;
+        const ACCOUNT_KEY_ROTATIONS = 1 << 113;
+
+        // Default Processor (continued): 121-122, 127
+        const BLOCK_EPILOGUE_TRANSACTIONS = 1 << 121;
+        const VALIDATOR_TRANSACTIONS = 1 << 122;
+        const MOVE_MODULE_UPGRADES = 1 << 127;
+
+        // Gas Fee Processor: 123-126
         const GAS_FEES = 1 << 123;
+        const GAS_FEE_BLOCK_SUMMARIES = 1 << 124;
+        const GAS_FEE_HOURLY_SUMMARIES = 1 << 125;
+        const GAS_FEE_ENTRY_FUNCTION_HOURLY_SUMMARIES = 1 << 126;
     }
 }
 
@@ -109,6 +127,22 @@ pub fn filter_data<T>(tables_to_write: &TableFlags, flag: TableFlags, data: Vec<
     }
 }
 
+/**
+ * Like filter_data, for tables that are only written when the tables_to_write set contains the flag,
+ * e.g. history tables that aren't written by default.
+ */
+pub fn filter_opt_in_data<T>(
+    tables_to_write: &TableFlags,
+    flag: TableFlags,
+    data: Vec<T>,
+) -> Vec<T> {
+    if tables_to_write.contains(flag) {
+        data
+    } else {
+        vec![]
+    }
+}
+
 /// Macro to filter multiple data sets with their corresponding table flags in one go
 #[macro_export]
 macro_rules! filter_datasets {